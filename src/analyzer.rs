@@ -4,7 +4,10 @@ use std::path::{Path, PathBuf};
 use syn::parse_file;
 use walkdir::WalkDir;
 
-use crate::rules::RuleEngine;
+use crate::rules::code_smells;
+use crate::rules::todos::TodoInventory;
+use crate::rules::unsafe_audit::UnsafeInventory;
+use crate::rules::{RuleConfig, RuleEngine};
 
 #[derive(Debug, Clone)]
 pub struct CodeIssue {
@@ -16,6 +19,15 @@ pub struct CodeIssue {
     pub severity: Severity,
     #[allow(dead_code)]
     pub roast_level: RoastLevel,
+    pub context: CodeContext,
+}
+
+impl CodeIssue {
+    /// The same issue, attributed to another kind of code
+    pub fn with_context(mut self, context: CodeContext) -> Self {
+        self.context = context;
+        self
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -32,6 +44,58 @@ pub enum RoastLevel {
     Savage,    // Brutal honesty
 }
 
+/// Where a piece of code lives, rules can treat each context differently
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CodeContext {
+    Production,  // Library and binary code
+    Test,        // #[test] functions, #[cfg(test)] modules, tests/ directory
+    Example,     // examples/ directory
+    Bench,       // benches/ directory and #[bench] functions
+    BuildScript, // build.rs
+}
+
+impl CodeContext {
+    /// Guess the context of a whole file from its path
+    pub fn from_path(path: &Path) -> Self {
+        let in_dir = |name: &str| {
+            path.parent()
+                .is_some_and(|parent| parent.components().any(|c| c.as_os_str() == name))
+        };
+
+        if path.file_name().is_some_and(|name| name == "build.rs") {
+            CodeContext::BuildScript
+        } else if in_dir("tests") || path.file_stem().is_some_and(|stem| stem == "tests") {
+            CodeContext::Test
+        } else if in_dir("benches") {
+            CodeContext::Bench
+        } else if in_dir("examples") {
+            CodeContext::Example
+        } else {
+            CodeContext::Production
+        }
+    }
+
+    /// Test and bench code is scored separately from the rest
+    pub fn is_test_code(&self) -> bool {
+        matches!(self, CodeContext::Test | CodeContext::Bench)
+    }
+
+    pub fn description(&self, lang: &str) -> &'static str {
+        match (self, lang) {
+            (CodeContext::Production, "zh-CN") => "生产代码",
+            (CodeContext::Test, "zh-CN") => "测试代码",
+            (CodeContext::Example, "zh-CN") => "示例代码",
+            (CodeContext::Bench, "zh-CN") => "基准测试",
+            (CodeContext::BuildScript, "zh-CN") => "构建脚本",
+            (CodeContext::Production, _) => "production",
+            (CodeContext::Test, _) => "test",
+            (CodeContext::Example, _) => "example",
+            (CodeContext::Bench, _) => "bench",
+            (CodeContext::BuildScript, _) => "build script",
+        }
+    }
+}
+
 pub struct CodeAnalyzer {
     rule_engine: RuleEngine,
     exclude_patterns: Vec<Regex>,
@@ -59,6 +123,12 @@ impl CodeAnalyzer {
         }
     }
 
+    /// 用自定义的规则参数重建规则引擎
+    pub fn with_config(mut self, config: RuleConfig) -> Self {
+        self.rule_engine = RuleEngine::with_config(config);
+        self
+    }
+
     fn should_exclude(&self, path: &Path) -> bool {
         let path_str = path.to_string_lossy();
         self.exclude_patterns
@@ -68,12 +138,59 @@ impl CodeAnalyzer {
 
     pub fn analyze_path(&self, path: &Path) -> Vec<CodeIssue> {
         let mut issues = Vec::new();
+        let mut magic_numbers = Vec::new();
+        let mut dead_code = Vec::new();
+
+        for file in self.rust_files(path) {
+            let Some((content, syntax_tree)) = Self::parse(&file) else {
+                continue;
+            };
+            issues.extend(
+                self.rule_engine
+                    .check_file(&file, &syntax_tree, &content, &self.lang),
+            );
+            magic_numbers.extend(self.rule_engine.magic_numbers(&file, &syntax_tree));
+            dead_code.push(code_smells::DeadCodeCensus::collect(&file, &syntax_tree));
+        }
+
+        // 跨文件重复的魔法数字和跨文件引用的私有条目只有看完整个项目才知道
+        code_smells::flag_project_repeats(&mut issues, &magic_numbers, &self.lang);
+        code_smells::flag_unused_across_files(&mut issues, &dead_code, &self.lang);
+        issues
+    }
+
+    /// 每个文件的 unsafe 代码清单，没有 unsafe 的文件不出现在结果里
+    pub fn unsafe_inventory(&self, path: &Path) -> Vec<UnsafeInventory> {
+        self.rust_files(path)
+            .iter()
+            .filter_map(|file| {
+                let (content, syntax_tree) = Self::parse(file)?;
+                Some(UnsafeInventory::collect(file, &syntax_tree, &content))
+            })
+            .filter(|inventory| !inventory.is_empty())
+            .collect()
+    }
+
+    /// 每个文件的 TODO/FIXME/HACK 和 todo!() 清单，没有待办的文件不出现在结果里
+    pub fn todo_inventory(&self, path: &Path) -> Vec<TodoInventory> {
+        self.rust_files(path)
+            .iter()
+            .filter_map(|file| {
+                let (content, syntax_tree) = Self::parse(file)?;
+                Some(TodoInventory::collect(file, &syntax_tree, &content))
+            })
+            .filter(|inventory| !inventory.is_empty())
+            .collect()
+    }
+
+    fn rust_files(&self, path: &Path) -> Vec<PathBuf> {
+        let mut files = Vec::new();
 
         if path.is_file() {
             if !self.should_exclude(path) {
                 if let Some(ext) = path.extension() {
                     if ext == "rs" {
-                        issues.extend(self.analyze_file(path));
+                        files.push(path.to_path_buf());
                     }
                 }
             }
@@ -84,25 +201,27 @@ impl CodeAnalyzer {
                 .filter(|e| !self.should_exclude(e.path()))
                 .filter(|e| e.path().extension().is_some_and(|ext| ext == "rs"))
             {
-                issues.extend(self.analyze_file(entry.path()));
+                files.push(entry.path().to_path_buf());
             }
         }
 
-        issues
+        files
     }
 
     pub fn analyze_file(&self, file_path: &Path) -> Vec<CodeIssue> {
-        let content = match fs::read_to_string(file_path) {
-            Ok(content) => content,
-            Err(_) => return vec![],
-        };
-
-        let syntax_tree = match parse_file(&content) {
-            Ok(tree) => tree,
-            Err(_) => return vec![],
-        };
+        match Self::parse(file_path) {
+            Some((content, syntax_tree)) => {
+                self.rule_engine
+                    .check_file(file_path, &syntax_tree, &content, &self.lang)
+            }
+            None => vec![],
+        }
+    }
 
-        self.rule_engine
-            .check_file(file_path, &syntax_tree, &content, &self.lang)
+    /// 读不了或解析失败的文件直接跳过
+    fn parse(file_path: &Path) -> Option<(String, syn::File)> {
+        let content = fs::read_to_string(file_path).ok()?;
+        let syntax_tree = parse_file(&content).ok()?;
+        Some((content, syntax_tree))
     }
 }
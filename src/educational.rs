@@ -45,6 +45,9 @@ impl EducationalAdvisor {
             "abbreviation-abuse",
             self.create_abbreviation_abuse_advice(),
         );
+        self.add_advice("naming-convention", self.create_naming_convention_advice());
+        self.add_advice("pinyin-naming", self.create_pinyin_naming_advice());
+        self.add_advice("identifier-typo", self.create_identifier_typo_advice());
 
         // Complexity advice
         self.add_advice("deep-nesting", self.create_deep_nesting_advice());
@@ -59,8 +62,62 @@ impl EducationalAdvisor {
         // Rust-specific advice
         self.add_advice("unwrap-abuse", self.create_unwrap_abuse_advice());
         self.add_advice("string-abuse", self.create_string_abuse_advice());
+        self.add_advice("vec-abuse", self.create_vec_abuse_advice());
+        self.add_advice("lifetime-abuse", self.create_lifetime_abuse_advice());
+        self.add_advice("generic-abuse", self.create_generic_abuse_advice());
         self.add_advice("unnecessary-clone", self.create_unnecessary_clone_advice());
         self.add_advice("iterator-abuse", self.create_iterator_abuse_advice());
+        self.add_advice("match-abuse", self.create_match_abuse_advice());
+
+        // Error handling advice
+        self.add_advice("stringly-error", self.create_stringly_error_advice());
+        self.add_advice("boxed-error", self.create_boxed_error_advice());
+        self.add_advice("swallowed-error", self.create_swallowed_error_advice());
+        self.add_advice(
+            "incomplete-error-type",
+            self.create_incomplete_error_type_advice(),
+        );
+
+        // Concurrency advice
+        self.add_advice("async-abuse", self.create_async_abuse_advice());
+        self.add_advice("channel-abuse", self.create_channel_abuse_advice());
+        self.add_advice("lock-abuse", self.create_lock_abuse_advice());
+
+        // Unsafe audit advice
+        self.add_advice("unsafe-abuse", self.create_unsafe_abuse_advice());
+
+        // Public API hygiene advice
+        self.add_advice("owned-ref-param", self.create_owned_ref_param_advice());
+        self.add_advice("bool-param-soup", self.create_bool_param_soup_advice());
+        self.add_advice("leaky-struct", self.create_leaky_struct_advice());
+        self.add_advice(
+            "missing-non-exhaustive",
+            self.create_missing_non_exhaustive_advice(),
+        );
+        self.add_advice("missing-debug", self.create_missing_debug_advice());
+        self.add_advice("leaky-impl-trait", self.create_leaky_impl_trait_advice());
+
+        // Documentation advice
+        self.add_advice("missing-docs", self.create_missing_docs_advice());
+        self.add_advice("parrot-docs", self.create_parrot_docs_advice());
+        self.add_advice(
+            "broken-doc-example",
+            self.create_broken_doc_example_advice(),
+        );
+        self.add_advice(
+            "missing-doc-sections",
+            self.create_missing_doc_sections_advice(),
+        );
+
+        // Type design advice
+        self.add_advice("god-struct", self.create_god_struct_advice());
+        self.add_advice("option-soup", self.create_option_soup_advice());
+        self.add_advice("data-clump", self.create_data_clump_advice());
+        self.add_advice(
+            "primitive-obsession",
+            self.create_primitive_obsession_advice(),
+        );
+        self.add_advice("huge-impl", self.create_huge_impl_advice());
 
         // Student code advice
         self.add_advice("println-debugging", self.create_println_debugging_advice());
@@ -235,29 +292,87 @@ impl EducationalAdvisor {
     fn create_string_abuse_advice(&self) -> EducationalAdvice {
         if self.lang == "zh-CN" {
             EducationalAdvice {
-                why_bad:
-                    "不必要的 String 分配会增加内存使用和性能开销，特别是在只需要读取的场景中。"
-                        .to_string(),
-                how_to_fix: "在只需要读取字符串的地方使用 &str，只在需要拥有所有权时使用 String。"
-                    .to_string(),
-                example_bad: Some("fn process_name(name: String) -> String".to_string()),
-                example_good: Some("fn process_name(name: &str) -> String".to_string()),
-                rust_docs_link: Some(
-                    "https://doc.rust-lang.org/book/ch04-03-slices.html".to_string(),
-                ),
-                best_practice_tip: Some(
-                    "优先使用 &str 作为函数参数，这样可以接受 String 和 &str 两种类型。"
-                        .to_string(),
-                ),
+                why_bad: "拥有字符串本身没有错，结构体字段就该是 String。浪费的是多余的分配：只读却要 String 参数，对 String 再 to_string()，format!(\"{}\", x)，as_str().to_string() 来回转换，循环里每轮重新拼出整个字符串，push_str(&format!(..)) 造出用完即弃的临时 String。".to_string(),
+                how_to_fix: "只读的参数用 &str；要副本用 clone()，能移动就移动；单参数 format! 换成 to_string()；往已有的 String 里追加用 push_str 或 write!（use std::fmt::Write），循环前用 String::with_capacity 预留空间。".to_string(),
+                example_bad: Some("fn render(name: String, items: &[u32]) -> String {\n    let mut out = format!(\"{}\", name);\n    for item in items {\n        out = format!(\"{}{},\", out, item);\n    }\n    out\n}".to_string()),
+                example_good: Some("fn render(name: &str, items: &[u32]) -> String {\n    let mut out = name.to_string();\n    for item in items {\n        let _ = write!(out, \"{item},\");\n    }\n    out\n}".to_string()),
+                rust_docs_link: Some("https://doc.rust-lang.org/std/fmt/trait.Write.html".to_string()),
+                best_practice_tip: Some("参数用 &str 能同时接受 String 和字面量；只有要存起来的时候才拿走所有权。".to_string()),
+            }
+        } else {
+            EducationalAdvice {
+                why_bad: "Owning strings is fine, struct fields should be String. The waste is in needless allocations: taking a String only to read it, calling to_string() on a String, format!(\"{}\", x), as_str().to_string() round trips, rebuilding a whole string on every loop pass, and push_str(&format!(..)) creating a throwaway temporary.".to_string(),
+                how_to_fix: "Take &str for read-only params; clone() when you need a copy and move when you can; replace single-argument format! with to_string(); append to an existing String with push_str or write! (use std::fmt::Write) and reserve with String::with_capacity before the loop.".to_string(),
+                example_bad: Some("fn render(name: String, items: &[u32]) -> String {\n    let mut out = format!(\"{}\", name);\n    for item in items {\n        out = format!(\"{}{},\", out, item);\n    }\n    out\n}".to_string()),
+                example_good: Some("fn render(name: &str, items: &[u32]) -> String {\n    let mut out = name.to_string();\n    for item in items {\n        let _ = write!(out, \"{item},\");\n    }\n    out\n}".to_string()),
+                rust_docs_link: Some("https://doc.rust-lang.org/std/fmt/trait.Write.html".to_string()),
+                best_practice_tip: Some("&str parameters accept both String and literals; only take ownership when you store the value.".to_string()),
+            }
+        }
+    }
+
+    fn create_vec_abuse_advice(&self) -> EducationalAdvice {
+        if self.lang == "zh-CN" {
+            EducationalAdvice {
+                why_bad: "Vec 本身没有错，浪费的是多余的分配和扫描：元素已知还从 Vec::new() 一个个 push，循环次数已知却不预留容量，&Vec 参数拒绝了数组和切片，collect 成 Vec 马上又 iter() 或 len()，len() == 0 判空，尺寸固定的 Vec<Vec<T>> 每行一次分配，循环里对 Vec 反复 contains 是 O(n×m)。".to_string(),
+                how_to_fix: "已知元素直接写 vec![..]，已知个数用 Vec::with_capacity；只读参数用 &[T]；不需要中间结果就别 collect，数个数用 count()；判空用 is_empty()；固定尺寸的矩阵用 [[T; N]; M] 或扁平的 Vec；要反复查找的集合先收集成 HashSet。".to_string(),
+                example_bad: Some("fn dedup(items: &Vec<u32>, banned: Vec<u32>) -> usize {\n    let mut kept = Vec::new();\n    for item in items {\n        if !banned.contains(item) {\n            kept.push(*item);\n        }\n    }\n    kept.iter().collect::<Vec<_>>().len()\n}".to_string()),
+                example_good: Some("fn dedup(items: &[u32], banned: &[u32]) -> usize {\n    let banned: HashSet<_> = banned.iter().collect();\n    items.iter().filter(|item| !banned.contains(item)).count()\n}".to_string()),
+                rust_docs_link: Some("https://doc.rust-lang.org/std/vec/struct.Vec.html#capacity-and-reallocation".to_string()),
+                best_practice_tip: Some("参数用 &[T] 能同时接受 Vec、数组和切片；只有要存起来或者要改长度的时候才用 Vec。".to_string()),
+            }
+        } else {
+            EducationalAdvice {
+                why_bad: "Vec itself is fine, the waste is in extra allocations and scans: pushing known elements one by one onto Vec::new(), not reserving when the count is known, &Vec parameters that refuse arrays and slices, collecting into a Vec only to iter() or len() it, len() == 0 checks, fixed-size Vec<Vec<T>> allocating every row, and contains on a Vec inside a loop being O(n×m).".to_string(),
+                how_to_fix: "Write known elements as vec![..] and reserve known counts with Vec::with_capacity; take &[T] for read-only params; skip the collect when you don't need the intermediate result and count with count(); check emptiness with is_empty(); use [[T; N]; M] or a flat Vec for fixed matrices; collect repeatedly searched sets into a HashSet.".to_string(),
+                example_bad: Some("fn dedup(items: &Vec<u32>, banned: Vec<u32>) -> usize {\n    let mut kept = Vec::new();\n    for item in items {\n        if !banned.contains(item) {\n            kept.push(*item);\n        }\n    }\n    kept.iter().collect::<Vec<_>>().len()\n}".to_string()),
+                example_good: Some("fn dedup(items: &[u32], banned: &[u32]) -> usize {\n    let banned: HashSet<_> = banned.iter().collect();\n    items.iter().filter(|item| !banned.contains(item)).count()\n}".to_string()),
+                rust_docs_link: Some("https://doc.rust-lang.org/std/vec/struct.Vec.html#capacity-and-reallocation".to_string()),
+                best_practice_tip: Some("&[T] parameters accept Vecs, arrays and slices alike; only take a Vec when you store it or change its length.".to_string()),
+            }
+        }
+    }
+
+    fn create_lifetime_abuse_advice(&self) -> EducationalAdvice {
+        if self.lang == "zh-CN" {
+            EducationalAdvice {
+                why_bad: "生命周期标注应该表达真实的约束。省略规则能推出来的 'a、只出现一次的 'a 只是噪音；只出现在返回值里的 'a 其实就是 'static；为了让借用检查器闭嘴加的 T: 'static 会拒绝所有带引用的类型；结构体上多个互不相关的生命周期让每个使用者都多写标注；impl<'a> Foo<'a> 里的 &'a mut self 会把对象借用到它自己生命周期结束，调用一次就再也用不了。".to_string(),
+                how_to_fix: "能省略就省略，路径里的生命周期写 '_；返回值要么和参数关联，要么直接写 'static；去掉没有理由的 'static 约束，先弄清楚借用检查器到底在抱怨什么；只修饰共享引用的结构体用一个生命周期；方法接收者写 &mut self，不要复用类型的生命周期参数。".to_string(),
+                example_bad: Some("struct Parser<'a> {\n    input: &'a str,\n}\n\nimpl<'a> Parser<'a> {\n    fn next(&'a mut self) -> Option<&'a str> { None }\n}\n\nfn first<'a>(s: &'a str) -> &'a str {\n    &s[..1]\n}".to_string()),
+                example_good: Some("struct Parser<'a> {\n    input: &'a str,\n}\n\nimpl<'a> Parser<'a> {\n    fn next(&mut self) -> Option<&'a str> { None }\n}\n\nfn first(s: &str) -> &str {\n    &s[..1]\n}".to_string()),
+                rust_docs_link: Some("https://doc.rust-lang.org/reference/lifetime-elision.html".to_string()),
+                best_practice_tip: Some("先不写生命周期，让编译器告诉你哪里真的需要；需要时再加，并且只加一个。".to_string()),
+            }
+        } else {
+            EducationalAdvice {
+                why_bad: "Lifetime annotations should express real constraints. An 'a that elision would infer or that appears once is noise; an 'a that only appears in the return type is really 'static; a T: 'static added to quiet the borrow checker rejects every type holding a reference; several unrelated lifetimes on a struct make every user write more annotations; and &'a mut self in impl<'a> Foo<'a> borrows the value until its own lifetime ends, so it is unusable after one call.".to_string(),
+                how_to_fix: "Elide whatever can be elided and write '_ in paths; tie returned lifetimes to a parameter or just write 'static; drop 'static bounds that have no reason and find out what the borrow checker actually complained about; use one lifetime for structs that only hold shared references; take &mut self instead of reusing the type's lifetime parameter.".to_string(),
+                example_bad: Some("struct Parser<'a> {\n    input: &'a str,\n}\n\nimpl<'a> Parser<'a> {\n    fn next(&'a mut self) -> Option<&'a str> { None }\n}\n\nfn first<'a>(s: &'a str) -> &'a str {\n    &s[..1]\n}".to_string()),
+                example_good: Some("struct Parser<'a> {\n    input: &'a str,\n}\n\nimpl<'a> Parser<'a> {\n    fn next(&mut self) -> Option<&'a str> { None }\n}\n\nfn first(s: &str) -> &str {\n    &s[..1]\n}".to_string()),
+                rust_docs_link: Some("https://doc.rust-lang.org/reference/lifetime-elision.html".to_string()),
+                best_practice_tip: Some("Start without lifetimes and let the compiler tell you where one is really needed; then add it, and only one.".to_string()),
+            }
+        }
+    }
+
+    fn create_generic_abuse_advice(&self) -> EducationalAdvice {
+        if self.lang == "zh-CN" {
+            EducationalAdvice {
+                why_bad: "泛型是有代价的：没用上的类型参数逼调用者写 turbofish；只出现在一个参数里的类型参数多了一个要追踪的名字；同一串约束到处复制，改一次要改很多处；写在结构体定义上的约束会传染给每个 impl 和每个用到它的函数；大函数体会为每种实例化完整编译一份，拖慢编译、撑大二进制。".to_string(),
+                how_to_fix: "删掉没用的类型参数；只用一次的写成 impl Trait；反复出现的约束提取成带 blanket impl 的 trait；约束放到真正需要它的 impl 上；泛型只为转换参数时，把函数体挪进非泛型的 inner 函数，外层只做 .as_ref() / .into()。".to_string(),
+                example_bad: Some("struct Cache<K: Hash + Eq, V> {\n    items: HashMap<K, V>,\n}\n\npub fn load<P: AsRef<Path>>(path: P) -> Config {\n    let path = path.as_ref();\n    // ... 上百行解析逻辑\n}".to_string()),
+                example_good: Some("struct Cache<K, V> {\n    items: HashMap<K, V>,\n}\n\nimpl<K: Hash + Eq, V> Cache<K, V> { /* ... */ }\n\npub fn load<P: AsRef<Path>>(path: P) -> Config {\n    fn inner(path: &Path) -> Config {\n        // ... 上百行解析逻辑\n    }\n    inner(path.as_ref())\n}".to_string()),
+                rust_docs_link: Some("https://rust-lang.github.io/api-guidelines/future-proofing.html#data-structures-do-not-duplicate-derived-trait-bounds-c-struct-bounds".to_string()),
+                best_practice_tip: Some("标准库的 fs::read_to_string 就是这么写的：泛型外壳加非泛型的 inner 函数。".to_string()),
             }
         } else {
             EducationalAdvice {
-                why_bad: "Unnecessary String allocations increase memory usage and performance overhead, especially in read-only scenarios.".to_string(),
-                how_to_fix: "Use &str where you only need to read strings, use String only when you need ownership.".to_string(),
-                example_bad: Some("fn process_name(name: String) -> String".to_string()),
-                example_good: Some("fn process_name(name: &str) -> String".to_string()),
-                rust_docs_link: Some("https://doc.rust-lang.org/book/ch04-03-slices.html".to_string()),
-                best_practice_tip: Some("Prefer &str as function parameters, this way you can accept both String and &str types.".to_string()),
+                why_bad: "Generics have a cost: an unused type parameter forces callers into turbofish; a type parameter that appears in one argument is one more name to track; the same bound chain copied everywhere has to be edited everywhere; bounds on a struct definition spread to every impl and every function that uses it; and a large body is compiled again for every instantiation, slowing builds and bloating binaries.".to_string(),
+                how_to_fix: "Delete unused type parameters; write single-use ones as impl Trait; extract repeated bounds into a trait with a blanket impl; put bounds on the impls that need them; when the generic only converts an argument, move the body into a non-generic inner fn and keep the wrapper to .as_ref() / .into().".to_string(),
+                example_bad: Some("struct Cache<K: Hash + Eq, V> {\n    items: HashMap<K, V>,\n}\n\npub fn load<P: AsRef<Path>>(path: P) -> Config {\n    let path = path.as_ref();\n    // ... hundreds of lines of parsing\n}".to_string()),
+                example_good: Some("struct Cache<K, V> {\n    items: HashMap<K, V>,\n}\n\nimpl<K: Hash + Eq, V> Cache<K, V> { /* ... */ }\n\npub fn load<P: AsRef<Path>>(path: P) -> Config {\n    fn inner(path: &Path) -> Config {\n        // ... hundreds of lines of parsing\n    }\n    inner(path.as_ref())\n}".to_string()),
+                rust_docs_link: Some("https://rust-lang.github.io/api-guidelines/future-proofing.html#data-structures-do-not-duplicate-derived-trait-bounds-c-struct-bounds".to_string()),
+                best_practice_tip: Some("std's fs::read_to_string is written exactly like this: a generic shell around a non-generic inner fn.".to_string()),
             }
         }
     }
@@ -265,21 +380,21 @@ impl EducationalAdvisor {
     fn create_println_debugging_advice(&self) -> EducationalAdvice {
         if self.lang == "zh-CN" {
             EducationalAdvice {
-                why_bad: "遗留的 println! 调试语句会污染输出，在生产环境中可能泄露敏感信息。".to_string(),
-                how_to_fix: "使用 log 库进行日志记录，或者使用 dbg! 宏进行临时调试（记得删除）。".to_string(),
-                example_bad: Some("println!(\"Debug: {:?}\", sensitive_data);".to_string()),
-                example_good: Some("log::debug!(\"Processing data: {:?}\", data);\n// or for temporary debugging:\ndbg!(&data);".to_string()),
-                rust_docs_link: Some("https://docs.rs/log/latest/log/".to_string()),
-                best_practice_tip: Some("使用条件编译 #[cfg(debug_assertions)] 来确保调试代码不会进入生产环境。".to_string()),
+                why_bad: "库代码里的 println!、eprintln! 和直接写 stdout 会出现在每个调用者的终端上，而且关不掉；遗留的 dbg! 会把文件名、行号和值打到 stderr，还可能泄露敏感信息。二进制的正常输出没问题，但 {:?} 这样的调试打印不该留给用户看。".to_string(),
+                how_to_fix: "库里用 log 或 tracing 记录，让调用者决定输出到哪里：println! 换成 debug!，eprintln! 换成 warn! 或 error!；需要输出的内容返回给调用者，或者接收一个 W: Write 参数；提交前删掉所有 dbg!。".to_string(),
+                example_bad: Some("pub fn load(path: &Path) -> Config {\n    println!(\"loading {:?}\", path);\n    let config = dbg!(parse(path));\n    config\n}".to_string()),
+                example_good: Some("pub fn load(path: &Path) -> Config {\n    tracing::debug!(?path, \"loading config\");\n    parse(path)\n}".to_string()),
+                rust_docs_link: Some("https://docs.rs/tracing/latest/tracing/".to_string()),
+                best_practice_tip: Some("二进制里用 env_logger 或 tracing-subscriber 初始化日志，调试时用 RUST_LOG=debug 打开。".to_string()),
             }
         } else {
             EducationalAdvice {
-                why_bad: "Leftover println! debug statements pollute output and may leak sensitive information in production.".to_string(),
-                how_to_fix: "Use the log crate for logging, or use the dbg! macro for temporary debugging (remember to remove).".to_string(),
-                example_bad: Some("println!(\"Debug: {:?}\", sensitive_data);".to_string()),
-                example_good: Some("log::debug!(\"Processing data: {:?}\", data);\n// or for temporary debugging:\ndbg!(&data);".to_string()),
-                rust_docs_link: Some("https://docs.rs/log/latest/log/".to_string()),
-                best_practice_tip: Some("Use conditional compilation #[cfg(debug_assertions)] to ensure debug code doesn't reach production.".to_string()),
+                why_bad: "println!, eprintln! and direct stdout writes in library code end up on every caller's terminal with no way to turn them off; a leftover dbg! dumps file, line and value to stderr and may leak sensitive data. Regular output in a binary is fine, but {:?} debug prints shouldn't reach users.".to_string(),
+                how_to_fix: "Log through log or tracing in libraries so the caller decides where output goes: println! becomes debug!, eprintln! becomes warn! or error!; return data to the caller or take a W: Write parameter instead of printing; remove every dbg! before committing.".to_string(),
+                example_bad: Some("pub fn load(path: &Path) -> Config {\n    println!(\"loading {:?}\", path);\n    let config = dbg!(parse(path));\n    config\n}".to_string()),
+                example_good: Some("pub fn load(path: &Path) -> Config {\n    tracing::debug!(?path, \"loading config\");\n    parse(path)\n}".to_string()),
+                rust_docs_link: Some("https://docs.rs/tracing/latest/tracing/".to_string()),
+                best_practice_tip: Some("Initialize env_logger or tracing-subscriber in the binary and turn debug output on with RUST_LOG=debug.".to_string()),
             }
         }
     }
@@ -370,8 +485,8 @@ impl EducationalAdvisor {
             why_bad: "Dead code increases maintenance burden and can confuse developers."
                 .to_string(),
             how_to_fix: "Remove unused functions, variables, and imports regularly.".to_string(),
-            example_bad: None,
-            example_good: None,
+            example_bad: Some("return total;\nlog_total(total); // never runs".to_string()),
+            example_good: Some("log_total(total);\nreturn total;".to_string()),
             rust_docs_link: None,
             best_practice_tip: Some(
                 "Use #[allow(dead_code)] only temporarily during development.".to_string(),
@@ -419,13 +534,24 @@ impl EducationalAdvisor {
     }
 
     fn create_todo_comment_advice(&self) -> EducationalAdvice {
-        EducationalAdvice {
-            why_bad: "Too many TODO comments indicate incomplete or poorly planned code.".to_string(),
-            how_to_fix: "Either implement the TODOs or create proper issue tracking for future work.".to_string(),
-            example_bad: Some("// TODO: implement this\n// TODO: fix this bug\n// TODO: optimize this".to_string()),
-            example_good: Some("// Create GitHub issues for planned improvements\n// Implement critical functionality before committing".to_string()),
-            rust_docs_link: None,
-            best_practice_tip: Some("Use TODO sparingly and always with a specific plan for resolution.".to_string()),
+        if self.lang == "zh-CN" {
+            EducationalAdvice {
+                why_bad: "没有工单、没有负责人的 TODO 不会被排期，只会越积越多；写了期限却过期的 TODO 说明计划早就失效了；todo!() 和 unimplemented!() 一旦执行就会 panic。".to_string(),
+                how_to_fix: "给每个 TODO 写上负责人和工单，比如 TODO(alice): #123，需要期限就加上日期；做不完的功能返回错误而不是留着 todo!()；过期的 TODO 要么做掉，要么重新排期。".to_string(),
+                example_bad: Some("// TODO: fix this later\nfn parse(input: &str) -> Config {\n    todo!()\n}".to_string()),
+                example_good: Some("// TODO(alice, 2025-03-01): support nested tables, see #123\nfn parse(input: &str) -> Result<Config, ParseError> {\n    Err(ParseError::Unsupported(\"nested tables\"))\n}".to_string()),
+                rust_docs_link: Some("https://doc.rust-lang.org/std/macro.todo.html".to_string()),
+                best_practice_tip: Some("用 --todos 导出全部待办清单，按负责人和工单逐条清理。".to_string()),
+            }
+        } else {
+            EducationalAdvice {
+                why_bad: "A TODO with no ticket and no owner never gets scheduled, it just piles up; a TODO past its date means the plan is long dead; todo!() and unimplemented!() panic the moment they run.".to_string(),
+                how_to_fix: "Give every TODO an owner and a ticket, e.g. TODO(alice): #123, and a date if it has a deadline; return an error for unfinished features instead of leaving todo!() behind; either do overdue TODOs or reschedule them.".to_string(),
+                example_bad: Some("// TODO: fix this later\nfn parse(input: &str) -> Config {\n    todo!()\n}".to_string()),
+                example_good: Some("// TODO(alice, 2025-03-01): support nested tables, see #123\nfn parse(input: &str) -> Result<Config, ParseError> {\n    Err(ParseError::Unsupported(\"nested tables\"))\n}".to_string()),
+                rust_docs_link: Some("https://doc.rust-lang.org/std/macro.todo.html".to_string()),
+                best_practice_tip: Some("Run with --todos to export every TODO with its owner and ticket, then work through them.".to_string()),
+            }
         }
     }
 
@@ -450,4 +576,608 @@ impl EducationalAdvisor {
             best_practice_tip: Some("Keep module nesting to 2-3 levels maximum, use re-exports for convenience.".to_string()),
         }
     }
+
+    fn create_stringly_error_advice(&self) -> EducationalAdvice {
+        if self.lang == "zh-CN" {
+            EducationalAdvice {
+                why_bad: "Result<T, String> 让调用者只能靠比较字符串来区分错误，错误文案一改调用方就悄悄坏掉。".to_string(),
+                how_to_fix: "为公开 API 定义一个错误枚举，每种失败原因一个变体，并实现 Display 和 std::error::Error。".to_string(),
+                example_bad: Some("pub fn parse(input: &str) -> Result<Config, String> {\n    Err(format!(\"bad line: {}\", input))\n}".to_string()),
+                example_good: Some("#[derive(Debug, thiserror::Error)]\npub enum ParseError {\n    #[error(\"bad line: {0}\")]\n    BadLine(String),\n}\n\npub fn parse(input: &str) -> Result<Config, ParseError>".to_string()),
+                rust_docs_link: Some("https://doc.rust-lang.org/std/error/trait.Error.html".to_string()),
+                best_practice_tip: Some("错误类型也是 API 的一部分，它应该能被 match，而不是被 contains。".to_string()),
+            }
+        } else {
+            EducationalAdvice {
+                why_bad: "Result<T, String> forces callers to compare strings to tell errors apart, and any wording change silently breaks them.".to_string(),
+                how_to_fix: "Define an error enum for public APIs with one variant per failure, implementing Display and std::error::Error.".to_string(),
+                example_bad: Some("pub fn parse(input: &str) -> Result<Config, String> {\n    Err(format!(\"bad line: {}\", input))\n}".to_string()),
+                example_good: Some("#[derive(Debug, thiserror::Error)]\npub enum ParseError {\n    #[error(\"bad line: {0}\")]\n    BadLine(String),\n}\n\npub fn parse(input: &str) -> Result<Config, ParseError>".to_string()),
+                rust_docs_link: Some("https://doc.rust-lang.org/std/error/trait.Error.html".to_string()),
+                best_practice_tip: Some("Error types are part of your API: callers should be able to match on them, not grep them.".to_string()),
+            }
+        }
+    }
+
+    fn create_boxed_error_advice(&self) -> EducationalAdvice {
+        if self.lang == "zh-CN" {
+            EducationalAdvice {
+                why_bad: "库返回 Box<dyn Error> 时，调用者无法得知可能有哪些错误，只能 downcast 猜测具体类型。".to_string(),
+                how_to_fix: "库里用具体的错误枚举（thiserror），把 Box<dyn Error> 或 anyhow 留给应用程序的 main。".to_string(),
+                example_bad: Some("pub fn load(path: &Path) -> Result<Data, Box<dyn Error>>".to_string()),
+                example_good: Some("#[derive(Debug, thiserror::Error)]\npub enum LoadError {\n    #[error(\"io error\")]\n    Io(#[from] std::io::Error),\n    #[error(\"invalid format\")]\n    Format,\n}\n\npub fn load(path: &Path) -> Result<Data, LoadError>".to_string()),
+                rust_docs_link: Some("https://doc.rust-lang.org/book/ch09-02-recoverable-errors-with-result.html".to_string()),
+                best_practice_tip: Some("经验法则：库用 thiserror，应用用 anyhow。".to_string()),
+            }
+        } else {
+            EducationalAdvice {
+                why_bad: "When a library returns Box<dyn Error>, callers can't see which errors are possible and have to downcast to guess.".to_string(),
+                how_to_fix: "Use a concrete error enum in libraries (thiserror) and keep Box<dyn Error> or anyhow for the application's main.".to_string(),
+                example_bad: Some("pub fn load(path: &Path) -> Result<Data, Box<dyn Error>>".to_string()),
+                example_good: Some("#[derive(Debug, thiserror::Error)]\npub enum LoadError {\n    #[error(\"io error\")]\n    Io(#[from] std::io::Error),\n    #[error(\"invalid format\")]\n    Format,\n}\n\npub fn load(path: &Path) -> Result<Data, LoadError>".to_string()),
+                rust_docs_link: Some("https://doc.rust-lang.org/book/ch09-02-recoverable-errors-with-result.html".to_string()),
+                best_practice_tip: Some("Rule of thumb: thiserror for libraries, anyhow for applications.".to_string()),
+            }
+        }
+    }
+
+    fn create_swallowed_error_advice(&self) -> EducationalAdvice {
+        if self.lang == "zh-CN" {
+            EducationalAdvice {
+                why_bad: "let _ = ...、.ok(); 和 map_err(|_| ...) 会让错误或错误原因消失，线上出问题时没有任何线索。".to_string(),
+                how_to_fix: "用 ? 把错误传上去；确实可以忽略时记一条日志并写注释；map_err 时把原错误保留为 source。".to_string(),
+                example_bad: Some("let _ = fs::remove_file(&tmp);\nsender.send(msg).ok();\nlet n = s.parse::<u32>().map_err(|_| MyError::Parse)?;".to_string()),
+                example_good: Some("if let Err(e) = fs::remove_file(&tmp) {\n    log::warn!(\"failed to clean up {}: {}\", tmp.display(), e);\n}\nlet n = s.parse::<u32>().map_err(MyError::Parse)?;".to_string()),
+                rust_docs_link: Some("https://doc.rust-lang.org/std/result/index.html".to_string()),
+                best_practice_tip: Some("每一个被忽略的错误都应该有一行注释解释为什么可以忽略。".to_string()),
+            }
+        } else {
+            EducationalAdvice {
+                why_bad: "let _ = ..., .ok(); and map_err(|_| ...) make errors or their causes vanish, leaving no trace when production breaks.".to_string(),
+                how_to_fix: "Propagate with ?; when ignoring is really fine, log it and leave a comment; keep the original error as the source in map_err.".to_string(),
+                example_bad: Some("let _ = fs::remove_file(&tmp);\nsender.send(msg).ok();\nlet n = s.parse::<u32>().map_err(|_| MyError::Parse)?;".to_string()),
+                example_good: Some("if let Err(e) = fs::remove_file(&tmp) {\n    log::warn!(\"failed to clean up {}: {}\", tmp.display(), e);\n}\nlet n = s.parse::<u32>().map_err(MyError::Parse)?;".to_string()),
+                rust_docs_link: Some("https://doc.rust-lang.org/std/result/index.html".to_string()),
+                best_practice_tip: Some("Every ignored error deserves a comment explaining why ignoring it is safe.".to_string()),
+            }
+        }
+    }
+
+    fn create_incomplete_error_type_advice(&self) -> EducationalAdvice {
+        if self.lang == "zh-CN" {
+            EducationalAdvice {
+                why_bad: "没有实现 Display 和 std::error::Error 的错误类型无法打印成人类可读的信息，也不能被 ? 转换成 Box<dyn Error> 或 anyhow::Error。".to_string(),
+                how_to_fix: "为错误枚举实现 Display 和 std::error::Error，或者使用 #[derive(thiserror::Error)]。".to_string(),
+                example_bad: Some("#[derive(Debug)]\npub enum ConfigError {\n    Missing,\n    Invalid(String),\n}".to_string()),
+                example_good: Some("#[derive(Debug, thiserror::Error)]\npub enum ConfigError {\n    #[error(\"config file is missing\")]\n    Missing,\n    #[error(\"invalid value: {0}\")]\n    Invalid(String),\n}".to_string()),
+                rust_docs_link: Some("https://doc.rust-lang.org/std/error/trait.Error.html".to_string()),
+                best_practice_tip: Some("实现 Error::source，让错误链能一路追溯到根因。".to_string()),
+            }
+        } else {
+            EducationalAdvice {
+                why_bad: "Error types without Display and std::error::Error can't be printed for humans and can't be converted by ? into Box<dyn Error> or anyhow::Error.".to_string(),
+                how_to_fix: "Implement Display and std::error::Error for the error enum, or use #[derive(thiserror::Error)].".to_string(),
+                example_bad: Some("#[derive(Debug)]\npub enum ConfigError {\n    Missing,\n    Invalid(String),\n}".to_string()),
+                example_good: Some("#[derive(Debug, thiserror::Error)]\npub enum ConfigError {\n    #[error(\"config file is missing\")]\n    Missing,\n    #[error(\"invalid value: {0}\")]\n    Invalid(String),\n}".to_string()),
+                rust_docs_link: Some("https://doc.rust-lang.org/std/error/trait.Error.html".to_string()),
+                best_practice_tip: Some("Implement Error::source so the error chain leads all the way to the root cause.".to_string()),
+            }
+        }
+    }
+
+    fn create_async_abuse_advice(&self) -> EducationalAdvice {
+        if self.lang == "zh-CN" {
+            EducationalAdvice {
+                why_bad: "async 运行时用少量线程轮流执行大量任务。thread::sleep、std::fs、std::net 这类阻塞调用会占住执行器线程；跨 .await 持有 std::sync::Mutex 的守卫会让 future 不再是 Send，还可能死锁。".to_string(),
+                how_to_fix: "用 tokio::time::sleep、tokio::fs、tokio::net 等异步版本，或者把阻塞代码放进 spawn_blocking；在 .await 之前 drop 掉锁守卫，必须跨 await 持锁时用 tokio::sync::Mutex。".to_string(),
+                example_bad: Some("async fn refresh(state: Arc<Mutex<State>>) {\n    let mut guard = state.lock().unwrap();\n    std::thread::sleep(Duration::from_secs(1));\n    guard.data = fetch().await;\n}".to_string()),
+                example_good: Some("async fn refresh(state: Arc<Mutex<State>>) {\n    tokio::time::sleep(Duration::from_secs(1)).await;\n    let data = fetch().await;\n    state.lock().unwrap().data = data;\n}".to_string()),
+                rust_docs_link: Some("https://docs.rs/tokio/latest/tokio/task/fn.spawn_blocking.html".to_string()),
+                best_practice_tip: Some("两次 .await 之间的代码最好在几十微秒内跑完。".to_string()),
+            }
+        } else {
+            EducationalAdvice {
+                why_bad: "Async runtimes multiplex many tasks over a few threads. Blocking calls like thread::sleep, std::fs and std::net hog an executor thread; holding a std::sync::Mutex guard across .await makes the future !Send and can deadlock.".to_string(),
+                how_to_fix: "Use the async versions (tokio::time::sleep, tokio::fs, tokio::net) or move blocking work into spawn_blocking; drop lock guards before awaiting, and use tokio::sync::Mutex when the lock really must span an await.".to_string(),
+                example_bad: Some("async fn refresh(state: Arc<Mutex<State>>) {\n    let mut guard = state.lock().unwrap();\n    std::thread::sleep(Duration::from_secs(1));\n    guard.data = fetch().await;\n}".to_string()),
+                example_good: Some("async fn refresh(state: Arc<Mutex<State>>) {\n    tokio::time::sleep(Duration::from_secs(1)).await;\n    let data = fetch().await;\n    state.lock().unwrap().data = data;\n}".to_string()),
+                rust_docs_link: Some("https://docs.rs/tokio/latest/tokio/task/fn.spawn_blocking.html".to_string()),
+                best_practice_tip: Some("Code between two .await points should finish within tens of microseconds.".to_string()),
+            }
+        }
+    }
+
+    fn create_channel_abuse_advice(&self) -> EducationalAdvice {
+        if self.lang == "zh-CN" {
+            EducationalAdvice {
+                why_bad: "无界 channel 没有背压：生产者比消费者快时队列会无限增长。在循环里反复创建 channel 通常说明任务的生命周期没有设计好。".to_string(),
+                how_to_fix: "在循环外创建一次 channel 并克隆 Sender；优先使用带容量的 channel，让发送方在队列满时等待。".to_string(),
+                example_bad: Some("for job in jobs {\n    let (tx, rx) = mpsc::channel();\n    spawn_worker(job, tx);\n    results.push(rx.recv()?);\n}".to_string()),
+                example_good: Some("let (tx, rx) = mpsc::sync_channel(64);\nfor job in jobs {\n    spawn_worker(job, tx.clone());\n}\ndrop(tx);\nlet results: Vec<_> = rx.iter().collect();".to_string()),
+                rust_docs_link: Some("https://doc.rust-lang.org/std/sync/mpsc/fn.sync_channel.html".to_string()),
+                best_practice_tip: Some("给每个 channel 的容量一个明确的理由。".to_string()),
+            }
+        } else {
+            EducationalAdvice {
+                why_bad: "Unbounded channels have no backpressure: when producers outrun consumers the queue grows without limit. Creating channels over and over in a loop usually means task lifetimes weren't designed.".to_string(),
+                how_to_fix: "Create the channel once outside the loop and clone the Sender; prefer bounded channels so senders wait when the queue is full.".to_string(),
+                example_bad: Some("for job in jobs {\n    let (tx, rx) = mpsc::channel();\n    spawn_worker(job, tx);\n    results.push(rx.recv()?);\n}".to_string()),
+                example_good: Some("let (tx, rx) = mpsc::sync_channel(64);\nfor job in jobs {\n    spawn_worker(job, tx.clone());\n}\ndrop(tx);\nlet results: Vec<_> = rx.iter().collect();".to_string()),
+                rust_docs_link: Some("https://doc.rust-lang.org/std/sync/mpsc/fn.sync_channel.html".to_string()),
+                best_practice_tip: Some("Every channel capacity should have a reason behind it.".to_string()),
+            }
+        }
+    }
+
+    fn create_lock_abuse_advice(&self) -> EducationalAdvice {
+        if self.lang == "zh-CN" {
+            EducationalAdvice {
+                why_bad: "Arc<Mutex<Vec<T>>> 让所有线程争抢同一把锁，并发退化成串行；.lock().unwrap() 会在锁中毒后把一个线程的 panic 传染给所有线程；一个表达式里连拿两把锁容易死锁。".to_string(),
+                how_to_fix: "让一个任务独占数据并通过 channel 通信，或者使用 DashMap 等并发容器；处理 PoisonError 或使用不会中毒的 parking_lot；需要多把锁时固定加锁顺序。".to_string(),
+                example_bad: Some("let results = Arc::new(Mutex::new(Vec::new()));\nresults.lock().unwrap().push(value);".to_string()),
+                example_good: Some("let (tx, rx) = mpsc::channel();\ntx.send(value)?;\nlet results: Vec<_> = rx.iter().collect();".to_string()),
+                rust_docs_link: Some("https://doc.rust-lang.org/std/sync/struct.Mutex.html#poisoning".to_string()),
+                best_practice_tip: Some("不要通过共享内存来通信，而要通过通信来共享内存。".to_string()),
+            }
+        } else {
+            EducationalAdvice {
+                why_bad: "Arc<Mutex<Vec<T>>> makes every thread fight over one lock, turning concurrency into a queue; .lock().unwrap() spreads one thread's panic to all of them once the lock is poisoned; taking two locks in one expression invites deadlock.".to_string(),
+                how_to_fix: "Let one task own the data and talk to it over a channel, or use a concurrent container like DashMap; handle PoisonError or use parking_lot, which doesn't poison; fix a lock order when several locks are needed.".to_string(),
+                example_bad: Some("let results = Arc::new(Mutex::new(Vec::new()));\nresults.lock().unwrap().push(value);".to_string()),
+                example_good: Some("let (tx, rx) = mpsc::channel();\ntx.send(value)?;\nlet results: Vec<_> = rx.iter().collect();".to_string()),
+                rust_docs_link: Some("https://doc.rust-lang.org/std/sync/struct.Mutex.html#poisoning".to_string()),
+                best_practice_tip: Some("Don't communicate by sharing memory; share memory by communicating.".to_string()),
+            }
+        }
+    }
+
+    fn create_unsafe_abuse_advice(&self) -> EducationalAdvice {
+        if self.lang == "zh-CN" {
+            EducationalAdvice {
+                why_bad: "unsafe 代码的正确性完全依赖作者的推理。没有写下来的推理无法被审查，也会在后续修改中悄悄失效；transmute、from_raw_parts 和 static mut 是最容易引入未定义行为的地方。".to_string(),
+                how_to_fix: "每个 unsafe 块和 unsafe impl 前写 // SAFETY: 注释说明为什么满足前提；每个 unsafe fn 写 # Safety 文档说明调用者的义务；尽量用安全的替代品：from_ne_bytes / bytemuck 代替 transmute，Atomic* / OnceLock 代替 static mut。".to_string(),
+                example_bad: Some("static mut COUNTER: u32 = 0;\n\npub unsafe fn view(ptr: *const u8, len: usize) -> &'static [u8] {\n    std::slice::from_raw_parts(ptr, len)\n}".to_string()),
+                example_good: Some("static COUNTER: AtomicU32 = AtomicU32::new(0);\n\n/// # Safety\n///\n/// `ptr` must point to `len` initialized bytes that live for `'a`.\npub unsafe fn view<'a>(ptr: *const u8, len: usize) -> &'a [u8] {\n    // SAFETY: guaranteed by the caller, see # Safety above\n    unsafe { std::slice::from_raw_parts(ptr, len) }\n}".to_string()),
+                rust_docs_link: Some("https://doc.rust-lang.org/nomicon/".to_string()),
+                best_practice_tip: Some("用 --unsafe-audit 导出全部 unsafe 代码清单，交给安全审查。".to_string()),
+            }
+        } else {
+            EducationalAdvice {
+                why_bad: "The soundness of unsafe code rests entirely on the author's reasoning. Reasoning that isn't written down can't be reviewed and silently rots as the code changes; transmute, from_raw_parts and static mut are where undefined behavior most often sneaks in.".to_string(),
+                how_to_fix: "Put a // SAFETY: comment before every unsafe block and unsafe impl explaining why the preconditions hold; give every unsafe fn a # Safety doc section listing the caller's obligations; prefer safe alternatives: from_ne_bytes / bytemuck over transmute, Atomic* / OnceLock over static mut.".to_string(),
+                example_bad: Some("static mut COUNTER: u32 = 0;\n\npub unsafe fn view(ptr: *const u8, len: usize) -> &'static [u8] {\n    std::slice::from_raw_parts(ptr, len)\n}".to_string()),
+                example_good: Some("static COUNTER: AtomicU32 = AtomicU32::new(0);\n\n/// # Safety\n///\n/// `ptr` must point to `len` initialized bytes that live for `'a`.\npub unsafe fn view<'a>(ptr: *const u8, len: usize) -> &'a [u8] {\n    // SAFETY: guaranteed by the caller, see # Safety above\n    unsafe { std::slice::from_raw_parts(ptr, len) }\n}".to_string()),
+                rust_docs_link: Some("https://doc.rust-lang.org/nomicon/".to_string()),
+                best_practice_tip: Some("Run with --unsafe-audit to export the full unsafe inventory for security review.".to_string()),
+            }
+        }
+    }
+
+    fn create_owned_ref_param_advice(&self) -> EducationalAdvice {
+        if self.lang == "zh-CN" {
+            EducationalAdvice {
+                why_bad: "&String、&Vec<T>、&Box<T> 参数要求调用者手里恰好有这个拥有所有权的类型，而 &str、&[T]、&T 能接受的东西更多，性能也一样。".to_string(),
+                how_to_fix: "把 &String 改成 &str，&Vec<T> 改成 &[T]，&Box<T> 改成 &T；Deref 强制转换让现有调用点不用修改。".to_string(),
+                example_bad: Some("pub fn greet(name: &String, scores: &Vec<u32>) -> String".to_string()),
+                example_good: Some("pub fn greet(name: &str, scores: &[u32]) -> String".to_string()),
+                rust_docs_link: Some("https://rust-lang.github.io/api-guidelines/flexibility.html".to_string()),
+                best_practice_tip: Some("使用 clippy::ptr_arg 在 CI 里自动发现这类参数。".to_string()),
+            }
+        } else {
+            EducationalAdvice {
+                why_bad: "&String, &Vec<T> and &Box<T> parameters demand that callers hold exactly that owned type, while &str, &[T] and &T accept more and cost the same.".to_string(),
+                how_to_fix: "Take &str instead of &String, &[T] instead of &Vec<T>, &T instead of &Box<T>; deref coercion keeps existing call sites compiling.".to_string(),
+                example_bad: Some("pub fn greet(name: &String, scores: &Vec<u32>) -> String".to_string()),
+                example_good: Some("pub fn greet(name: &str, scores: &[u32]) -> String".to_string()),
+                rust_docs_link: Some("https://rust-lang.github.io/api-guidelines/flexibility.html".to_string()),
+                best_practice_tip: Some("clippy::ptr_arg catches these parameters in CI.".to_string()),
+            }
+        }
+    }
+
+    fn create_bool_param_soup_advice(&self) -> EducationalAdvice {
+        if self.lang == "zh-CN" {
+            EducationalAdvice {
+                why_bad: "多个 bool 参数让调用点变成 f(true, false, true)，读代码的人无法知道每个值的含义，传错顺序编译器也不会报错。".to_string(),
+                how_to_fix: "为每个开关定义一个两值枚举，或者把选项收进一个结构体（可以配合 Default 和构建器）。".to_string(),
+                example_bad: Some("pub fn export(path: &Path, compress: bool, overwrite: bool, verbose: bool)\n\nexport(path, true, false, true);".to_string()),
+                example_good: Some("pub struct ExportOptions {\n    pub compression: Compression,\n    pub overwrite: bool,\n    pub verbose: bool,\n}\n\nexport(path, &ExportOptions { compression: Compression::Gzip, ..Default::default() });".to_string()),
+                rust_docs_link: Some("https://rust-lang.github.io/api-guidelines/type-safety.html#c-custom-type".to_string()),
+                best_practice_tip: Some("一个 bool 参数还能接受，第三个出现时就该重构了。".to_string()),
+            }
+        } else {
+            EducationalAdvice {
+                why_bad: "Several bool parameters turn call sites into f(true, false, true): readers can't tell what each value means and the compiler won't catch swapped arguments.".to_string(),
+                how_to_fix: "Give each switch its own two-variant enum, or gather the options into a struct (with Default or a builder).".to_string(),
+                example_bad: Some("pub fn export(path: &Path, compress: bool, overwrite: bool, verbose: bool)\n\nexport(path, true, false, true);".to_string()),
+                example_good: Some("pub struct ExportOptions {\n    pub compression: Compression,\n    pub overwrite: bool,\n    pub verbose: bool,\n}\n\nexport(path, &ExportOptions { compression: Compression::Gzip, ..Default::default() });".to_string()),
+                rust_docs_link: Some("https://rust-lang.github.io/api-guidelines/type-safety.html#c-custom-type".to_string()),
+                best_practice_tip: Some("One bool parameter is fine; by the third it's time to refactor.".to_string()),
+            }
+        }
+    }
+
+    fn create_leaky_struct_advice(&self) -> EducationalAdvice {
+        if self.lang == "zh-CN" {
+            EducationalAdvice {
+                why_bad: "构造函数里的校验只有在它是唯一的构造途径时才有意义。字段全部 pub 时，任何人都可以用结构体字面量绕过校验，造出不合法的值。".to_string(),
+                how_to_fix: "把需要维护不变量的字段改成私有，提供只读的 getter；修改也通过会重新校验的方法进行。".to_string(),
+                example_bad: Some("pub struct Percent {\n    pub value: u8,\n}\n\nimpl Percent {\n    pub fn new(value: u8) -> Option<Self> {\n        (value <= 100).then_some(Self { value })\n    }\n}".to_string()),
+                example_good: Some("pub struct Percent {\n    value: u8,\n}\n\nimpl Percent {\n    pub fn new(value: u8) -> Option<Self> {\n        (value <= 100).then_some(Self { value })\n    }\n\n    pub fn value(&self) -> u8 {\n        self.value\n    }\n}".to_string()),
+                rust_docs_link: Some("https://rust-lang.github.io/api-guidelines/future-proofing.html#c-struct-private".to_string()),
+                best_practice_tip: Some("没有不变量的纯数据结构可以放心地公开字段。".to_string()),
+            }
+        } else {
+            EducationalAdvice {
+                why_bad: "Validation in a constructor only matters if the constructor is the only way in. With every field pub, anyone can use a struct literal to skip it and build an invalid value.".to_string(),
+                how_to_fix: "Make the fields that carry invariants private, expose read-only getters, and route mutation through methods that re-validate.".to_string(),
+                example_bad: Some("pub struct Percent {\n    pub value: u8,\n}\n\nimpl Percent {\n    pub fn new(value: u8) -> Option<Self> {\n        (value <= 100).then_some(Self { value })\n    }\n}".to_string()),
+                example_good: Some("pub struct Percent {\n    value: u8,\n}\n\nimpl Percent {\n    pub fn new(value: u8) -> Option<Self> {\n        (value <= 100).then_some(Self { value })\n    }\n\n    pub fn value(&self) -> u8 {\n        self.value\n    }\n}".to_string()),
+                rust_docs_link: Some("https://rust-lang.github.io/api-guidelines/future-proofing.html#c-struct-private".to_string()),
+                best_practice_tip: Some("Plain data without invariants can keep public fields.".to_string()),
+            }
+        }
+    }
+
+    fn create_missing_non_exhaustive_advice(&self) -> EducationalAdvice {
+        if self.lang == "zh-CN" {
+            EducationalAdvice {
+                why_bad: "下游代码对公开枚举做穷尽 match 时，新增一个变体就会让它们编译失败，于是每次加变体都成了破坏性更新。".to_string(),
+                how_to_fix: "给会持续增长的公开枚举（错误类型、事件、命令等）加上 #[non_exhaustive]，下游必须写通配分支。".to_string(),
+                example_bad: Some("pub enum ConfigError {\n    Missing,\n    Invalid,\n}".to_string()),
+                example_good: Some("#[non_exhaustive]\npub enum ConfigError {\n    Missing,\n    Invalid,\n}".to_string()),
+                rust_docs_link: Some("https://doc.rust-lang.org/reference/attributes/type_system.html#the-non_exhaustive-attribute".to_string()),
+                best_practice_tip: Some("#[non_exhaustive] 最好在 1.0 之前加上，之后再加本身就是破坏性更新。".to_string()),
+            }
+        } else {
+            EducationalAdvice {
+                why_bad: "When downstream code matches exhaustively on a public enum, adding a variant breaks their build, so every new variant becomes a breaking change.".to_string(),
+                how_to_fix: "Mark public enums that keep growing (errors, events, commands...) #[non_exhaustive] so downstream code must include a wildcard arm.".to_string(),
+                example_bad: Some("pub enum ConfigError {\n    Missing,\n    Invalid,\n}".to_string()),
+                example_good: Some("#[non_exhaustive]\npub enum ConfigError {\n    Missing,\n    Invalid,\n}".to_string()),
+                rust_docs_link: Some("https://doc.rust-lang.org/reference/attributes/type_system.html#the-non_exhaustive-attribute".to_string()),
+                best_practice_tip: Some("Add #[non_exhaustive] before 1.0 - adding it later is itself a breaking change.".to_string()),
+            }
+        }
+    }
+
+    fn create_missing_debug_advice(&self) -> EducationalAdvice {
+        if self.lang == "zh-CN" {
+            EducationalAdvice {
+                why_bad: "没有 Debug 的公开类型无法用 {:?} 或 dbg! 打印，而且包含它的类型也没法 #[derive(Debug)]，问题会一路传染给库的用户。".to_string(),
+                how_to_fix: "给所有公开类型加上 #[derive(Debug)]；包含敏感数据时手写 Debug 实现并隐藏敏感字段。".to_string(),
+                example_bad: Some("pub struct Client {\n    endpoint: String,\n}".to_string()),
+                example_good: Some("#[derive(Debug)]\npub struct Client {\n    endpoint: String,\n}".to_string()),
+                rust_docs_link: Some("https://rust-lang.github.io/api-guidelines/debuggability.html#c-debug".to_string()),
+                best_practice_tip: Some("在 lib.rs 加上 #![warn(missing_debug_implementations)]。".to_string()),
+            }
+        } else {
+            EducationalAdvice {
+                why_bad: "Public types without Debug can't be printed with {:?} or dbg!, and any type containing them can't #[derive(Debug)] either - the problem spreads to every user of the library.".to_string(),
+                how_to_fix: "Add #[derive(Debug)] to every public type; write a manual Debug impl that redacts sensitive fields when needed.".to_string(),
+                example_bad: Some("pub struct Client {\n    endpoint: String,\n}".to_string()),
+                example_good: Some("#[derive(Debug)]\npub struct Client {\n    endpoint: String,\n}".to_string()),
+                rust_docs_link: Some("https://rust-lang.github.io/api-guidelines/debuggability.html#c-debug".to_string()),
+                best_practice_tip: Some("Add #![warn(missing_debug_implementations)] to lib.rs.".to_string()),
+            }
+        }
+    }
+
+    fn create_leaky_impl_trait_advice(&self) -> EducationalAdvice {
+        if self.lang == "zh-CN" {
+            EducationalAdvice {
+                why_bad: "返回 impl Trait 的公开函数让调用者无法命名返回类型，不能把它存进结构体字段；具体类型是否 Send/Sync 也会隐式成为 API 的一部分，改实现就可能破坏下游。".to_string(),
+                how_to_fix: "返回一个具名的包装类型，或者 Box<dyn Trait>；迭代器、闭包和 Future 这类惯用场景除外。".to_string(),
+                example_bad: Some("pub fn connection() -> impl Read + Write".to_string()),
+                example_good: Some("pub struct Connection { /* ... */ }\n\npub fn connection() -> Connection".to_string()),
+                rust_docs_link: Some("https://rust-lang.github.io/api-guidelines/future-proofing.html".to_string()),
+                best_practice_tip: Some("公开 API 的返回类型应该是你愿意长期承诺的类型。".to_string()),
+            }
+        } else {
+            EducationalAdvice {
+                why_bad: "Public functions returning impl Trait give callers a type they can't name or store in a struct field, and whether the concrete type is Send/Sync silently becomes part of your API - changing the body can break downstream.".to_string(),
+                how_to_fix: "Return a named wrapper type or Box<dyn Trait>; iterators, closures and futures are the idiomatic exceptions.".to_string(),
+                example_bad: Some("pub fn connection() -> impl Read + Write".to_string()),
+                example_good: Some("pub struct Connection { /* ... */ }\n\npub fn connection() -> Connection".to_string()),
+                rust_docs_link: Some("https://rust-lang.github.io/api-guidelines/future-proofing.html".to_string()),
+                best_practice_tip: Some("A public return type should be a type you're willing to commit to long-term.".to_string()),
+            }
+        }
+    }
+
+    fn create_missing_docs_advice(&self) -> EducationalAdvice {
+        if self.lang == "zh-CN" {
+            EducationalAdvice {
+                why_bad: "公开条目就是库的用户界面，没有文档，用户只能读源码猜用法，cargo doc 生成的页面也是空的。".to_string(),
+                how_to_fix: "给每个公开条目写一句说明它做什么的 /// 摘要，必要时补上示例；可以在 lib.rs 里开启 #![warn(missing_docs)]。".to_string(),
+                example_bad: Some("pub fn retry(times: u32, op: impl FnMut() -> bool) -> bool".to_string()),
+                example_good: Some("/// 最多执行 `op` `times` 次，直到它返回 true。\n///\n/// 全部失败时返回 false。\npub fn retry(times: u32, op: impl FnMut() -> bool) -> bool".to_string()),
+                rust_docs_link: Some("https://doc.rust-lang.org/rustdoc/how-to-write-documentation.html".to_string()),
+                best_practice_tip: Some("#![warn(missing_docs)] 能让编译器帮你盯着文档。".to_string()),
+            }
+        } else {
+            EducationalAdvice {
+                why_bad: "Public items are your library's user interface. Without docs, users read the source to guess how to use them and cargo doc renders an empty page.".to_string(),
+                how_to_fix: "Give every public item a one-line /// summary of what it does, plus an example where useful; turn on #![warn(missing_docs)] in lib.rs.".to_string(),
+                example_bad: Some("pub fn retry(times: u32, op: impl FnMut() -> bool) -> bool".to_string()),
+                example_good: Some("/// Runs `op` up to `times` times until it returns true.\n///\n/// Returns false if every attempt failed.\npub fn retry(times: u32, op: impl FnMut() -> bool) -> bool".to_string()),
+                rust_docs_link: Some("https://doc.rust-lang.org/rustdoc/how-to-write-documentation.html".to_string()),
+                best_practice_tip: Some("#![warn(missing_docs)] lets the compiler keep an eye on your docs.".to_string()),
+            }
+        }
+    }
+
+    fn create_parrot_docs_advice(&self) -> EducationalAdvice {
+        if self.lang == "zh-CN" {
+            EducationalAdvice {
+                why_bad: "只复述名字的文档没有提供任何新信息，还会让人以为这里已经写过文档了。"
+                    .to_string(),
+                how_to_fix:
+                    "写名字里看不出来的东西：单位、取值范围、什么时候返回 None、有什么副作用。"
+                        .to_string(),
+                example_bad: Some(
+                    "/// Gets the timeout.\npub fn get_timeout(&self) -> u64".to_string(),
+                ),
+                example_good: Some(
+                    "/// 请求超时时间（毫秒），0 表示永不超时。\npub fn timeout(&self) -> u64"
+                        .to_string(),
+                ),
+                rust_docs_link: Some(
+                    "https://rust-lang.github.io/api-guidelines/documentation.html".to_string(),
+                ),
+                best_practice_tip: Some("好的文档回答的是名字回答不了的问题。".to_string()),
+            }
+        } else {
+            EducationalAdvice {
+                why_bad: "Docs that only restate the name add no information, and they make the item look documented when it isn't.".to_string(),
+                how_to_fix: "Write what the name can't say: units, valid ranges, when it returns None, what side effects it has.".to_string(),
+                example_bad: Some("/// Gets the timeout.\npub fn get_timeout(&self) -> u64".to_string()),
+                example_good: Some("/// Request timeout in milliseconds; 0 means never time out.\npub fn timeout(&self) -> u64".to_string()),
+                rust_docs_link: Some("https://rust-lang.github.io/api-guidelines/documentation.html".to_string()),
+                best_practice_tip: Some("Good docs answer the questions the name can't.".to_string()),
+            }
+        }
+    }
+
+    fn create_broken_doc_example_advice(&self) -> EducationalAdvice {
+        if self.lang == "zh-CN" {
+            EducationalAdvice {
+                why_bad: "代码块默认会被当作 doctest 编译运行，解析不了的示例会让 cargo test 失败；就算被 ignore 了，用户照着抄也会出错。".to_string(),
+                how_to_fix: "把示例改成能编译的代码，可以用 `# ` 开头的隐藏行补上样板；不是 Rust 的代码块标上 text 或对应的语言。".to_string(),
+                example_bad: Some("/// ```\n/// let cfg = Config::new(\n/// ```".to_string()),
+                example_good: Some("/// ```\n/// # use mycrate::Config;\n/// let cfg = Config::new();\n/// ```".to_string()),
+                rust_docs_link: Some("https://doc.rust-lang.org/rustdoc/write-documentation/documentation-tests.html".to_string()),
+                best_practice_tip: Some("让 cargo test 跑 doctest，示例就不会悄悄过期。".to_string()),
+            }
+        } else {
+            EducationalAdvice {
+                why_bad: "Code blocks are compiled and run as doctests by default, so an example that doesn't parse fails cargo test - and even if it's ignored, users copying it get broken code.".to_string(),
+                how_to_fix: "Make the example compile, using `# `-prefixed hidden lines for boilerplate; tag blocks that aren't Rust as text or their real language.".to_string(),
+                example_bad: Some("/// ```\n/// let cfg = Config::new(\n/// ```".to_string()),
+                example_good: Some("/// ```\n/// # use mycrate::Config;\n/// let cfg = Config::new();\n/// ```".to_string()),
+                rust_docs_link: Some("https://doc.rust-lang.org/rustdoc/write-documentation/documentation-tests.html".to_string()),
+                best_practice_tip: Some("Let cargo test run your doctests and examples can't silently go stale.".to_string()),
+            }
+        }
+    }
+
+    fn create_missing_doc_sections_advice(&self) -> EducationalAdvice {
+        if self.lang == "zh-CN" {
+            EducationalAdvice {
+                why_bad: "调用者需要知道函数什么时候返回 Err、什么时候会 panic，才能决定怎么处理；这些信息不写在文档里，就只能去读实现。".to_string(),
+                how_to_fix: "返回 Result 的公开函数加 # Errors 章节，列出各种错误情况；可能 panic 的加 # Panics 章节写清楚触发条件。".to_string(),
+                example_bad: Some("/// 读取配置文件。\npub fn load(path: &Path) -> Result<Config, Error>".to_string()),
+                example_good: Some("/// 读取配置文件。\n///\n/// # Errors\n///\n/// 文件不存在或者不是合法的 TOML 时返回错误。\npub fn load(path: &Path) -> Result<Config, Error>".to_string()),
+                rust_docs_link: Some("https://rust-lang.github.io/api-guidelines/documentation.html#function-docs-include-error-panic-and-safety-considerations-c-failure".to_string()),
+                best_practice_tip: Some("clippy::missing_errors_doc 和 clippy::missing_panics_doc 能自动检查这两个章节。".to_string()),
+            }
+        } else {
+            EducationalAdvice {
+                why_bad: "Callers need to know when a function returns Err or panics to decide how to handle it; if the docs don't say, they have to read the implementation.".to_string(),
+                how_to_fix: "Add an # Errors section listing the failure cases to public functions returning Result, and a # Panics section with the conditions to functions that can panic.".to_string(),
+                example_bad: Some("/// Loads the config file.\npub fn load(path: &Path) -> Result<Config, Error>".to_string()),
+                example_good: Some("/// Loads the config file.\n///\n/// # Errors\n///\n/// Fails if the file is missing or isn't valid TOML.\npub fn load(path: &Path) -> Result<Config, Error>".to_string()),
+                rust_docs_link: Some("https://rust-lang.github.io/api-guidelines/documentation.html#function-docs-include-error-panic-and-safety-considerations-c-failure".to_string()),
+                best_practice_tip: Some("clippy::missing_errors_doc and clippy::missing_panics_doc check both sections automatically.".to_string()),
+            }
+        }
+    }
+
+    fn create_god_struct_advice(&self) -> EducationalAdvice {
+        if self.lang == "zh-CN" {
+            EducationalAdvice {
+                why_bad: "字段太多的结构体通常承担了好几种职责，每个方法都能碰到所有状态，改动的影响范围难以判断，测试时也得构造一大堆无关字段。".to_string(),
+                how_to_fix: "找出总是一起读写的字段，把它们提取成独立的结构体，原结构体只持有这些子结构。".to_string(),
+                example_bad: Some("struct Server {\n    host: String, port: u16, tls_cert: PathBuf, tls_key: PathBuf,\n    max_conns: usize, idle_timeout: Duration, log_level: Level, log_file: PathBuf,\n    // ...还有十几个\n}".to_string()),
+                example_good: Some("struct Server {\n    listen: ListenConfig,\n    tls: TlsConfig,\n    limits: Limits,\n    logging: LogConfig,\n}".to_string()),
+                rust_docs_link: Some("https://doc.rust-lang.org/book/ch05-00-structs.html".to_string()),
+                best_practice_tip: Some("一个结构体的字段应该能用一句话概括它们的共同点。".to_string()),
+            }
+        } else {
+            EducationalAdvice {
+                why_bad: "A struct with too many fields usually carries several responsibilities: every method can touch all of the state, the blast radius of a change is hard to judge, and tests have to build piles of unrelated fields.".to_string(),
+                how_to_fix: "Find the fields that are always read and written together, extract them into their own structs, and have the original hold those instead.".to_string(),
+                example_bad: Some("struct Server {\n    host: String, port: u16, tls_cert: PathBuf, tls_key: PathBuf,\n    max_conns: usize, idle_timeout: Duration, log_level: Level, log_file: PathBuf,\n    // ...a dozen more\n}".to_string()),
+                example_good: Some("struct Server {\n    listen: ListenConfig,\n    tls: TlsConfig,\n    limits: Limits,\n    logging: LogConfig,\n}".to_string()),
+                rust_docs_link: Some("https://doc.rust-lang.org/book/ch05-00-structs.html".to_string()),
+                best_practice_tip: Some("You should be able to say in one sentence what a struct's fields have in common.".to_string()),
+            }
+        }
+    }
+
+    fn create_option_soup_advice(&self) -> EducationalAdvice {
+        if self.lang == "zh-CN" {
+            EducationalAdvice {
+                why_bad: "n 个 Option 字段有 2^n 种组合，通常只有少数几种是合法的；编译器没法阻止非法组合，每次使用都要检查一遍。".to_string(),
+                how_to_fix: "把合法的状态列成枚举的变体，每个变体只带它需要的数据。".to_string(),
+                example_bad: Some("struct Job {\n    started_at: Option<Instant>,\n    finished_at: Option<Instant>,\n    error: Option<String>,\n    output: Option<Vec<u8>>,\n}".to_string()),
+                example_good: Some("enum Job {\n    Queued,\n    Running { started_at: Instant },\n    Failed { started_at: Instant, error: String },\n    Done { started_at: Instant, output: Vec<u8> },\n}".to_string()),
+                rust_docs_link: Some("https://doc.rust-lang.org/book/ch06-01-defining-an-enum.html".to_string()),
+                best_practice_tip: Some("让非法状态无法表示（make invalid states unrepresentable）。".to_string()),
+            }
+        } else {
+            EducationalAdvice {
+                why_bad: "n Option fields give 2^n combinations and usually only a handful are valid; the compiler can't rule out the others, so every use has to check again.".to_string(),
+                how_to_fix: "List the valid states as enum variants, each carrying only the data it needs.".to_string(),
+                example_bad: Some("struct Job {\n    started_at: Option<Instant>,\n    finished_at: Option<Instant>,\n    error: Option<String>,\n    output: Option<Vec<u8>>,\n}".to_string()),
+                example_good: Some("enum Job {\n    Queued,\n    Running { started_at: Instant },\n    Failed { started_at: Instant, error: String },\n    Done { started_at: Instant, output: Vec<u8> },\n}".to_string()),
+                rust_docs_link: Some("https://doc.rust-lang.org/book/ch06-01-defining-an-enum.html".to_string()),
+                best_practice_tip: Some("Make invalid states unrepresentable.".to_string()),
+            }
+        }
+    }
+
+    fn create_data_clump_advice(&self) -> EducationalAdvice {
+        if self.lang == "zh-CN" {
+            EducationalAdvice {
+                why_bad: "同一组字段在多个结构体里重复出现，说明它们是一个没有名字的概念；相关的逻辑也会跟着到处复制。".to_string(),
+                how_to_fix: "把这组字段提取成一个结构体，给它起个名字，相关方法也挪过去。".to_string(),
+                example_bad: Some("struct Order { street: String, city: String, zip: String, /* ... */ }\nstruct Customer { street: String, city: String, zip: String, /* ... */ }".to_string()),
+                example_good: Some("struct Address { street: String, city: String, zip: String }\n\nstruct Order { shipping: Address, /* ... */ }\nstruct Customer { address: Address, /* ... */ }".to_string()),
+                rust_docs_link: Some("https://doc.rust-lang.org/book/ch05-01-defining-structs.html".to_string()),
+                best_practice_tip: Some("总是一起出现的数据，值得拥有一个名字。".to_string()),
+            }
+        } else {
+            EducationalAdvice {
+                why_bad: "The same group of fields in several structs is a concept without a name, and the logic around it gets copied along with it.".to_string(),
+                how_to_fix: "Extract the group into a named struct and move the related methods onto it.".to_string(),
+                example_bad: Some("struct Order { street: String, city: String, zip: String, /* ... */ }\nstruct Customer { street: String, city: String, zip: String, /* ... */ }".to_string()),
+                example_good: Some("struct Address { street: String, city: String, zip: String }\n\nstruct Order { shipping: Address, /* ... */ }\nstruct Customer { address: Address, /* ... */ }".to_string()),
+                rust_docs_link: Some("https://doc.rust-lang.org/book/ch05-01-defining-structs.html".to_string()),
+                best_practice_tip: Some("Data that always travels together deserves a name.".to_string()),
+            }
+        }
+    }
+
+    fn create_primitive_obsession_advice(&self) -> EducationalAdvice {
+        if self.lang == "zh-CN" {
+            EducationalAdvice {
+                why_bad: "用 String 表示 ID、邮箱和 URL，编译器就分不清它们：参数传反了照样编译通过，格式校验也得在每个使用点重复。".to_string(),
+                how_to_fix: "为每种领域值定义 newtype，在构造时校验一次；URL 可以直接用 url::Url 这类现成类型。".to_string(),
+                example_bad: Some("struct User {\n    user_id: String,\n    email: String,\n    avatar_url: String,\n}".to_string()),
+                example_good: Some("struct UserId(String);\nstruct Email(String);\n\nstruct User {\n    user_id: UserId,\n    email: Email,\n    avatar_url: Url,\n}".to_string()),
+                rust_docs_link: Some("https://doc.rust-lang.org/rust-by-example/generics/new_types.html".to_string()),
+                best_practice_tip: Some("newtype 在运行时是零成本的，却能在编译期挡住一整类错误。".to_string()),
+            }
+        } else {
+            EducationalAdvice {
+                why_bad: "With IDs, emails and URLs all stored as String the compiler can't tell them apart: swapped arguments compile fine and format checks get repeated at every use.".to_string(),
+                how_to_fix: "Define a newtype per domain value and validate once in its constructor; for URLs use an existing type such as url::Url.".to_string(),
+                example_bad: Some("struct User {\n    user_id: String,\n    email: String,\n    avatar_url: String,\n}".to_string()),
+                example_good: Some("struct UserId(String);\nstruct Email(String);\n\nstruct User {\n    user_id: UserId,\n    email: Email,\n    avatar_url: Url,\n}".to_string()),
+                rust_docs_link: Some("https://doc.rust-lang.org/rust-by-example/generics/new_types.html".to_string()),
+                best_practice_tip: Some("Newtypes cost nothing at runtime and rule out a whole class of bugs at compile time.".to_string()),
+            }
+        }
+    }
+
+    fn create_huge_impl_advice(&self) -> EducationalAdvice {
+        if self.lang == "zh-CN" {
+            EducationalAdvice {
+                why_bad: "一个 impl 块里有几十个方法，说明这个类型承担了太多职责，读者很难找到想要的方法，也看不出哪些方法是一组的。".to_string(),
+                how_to_fix: "按职责把方法分组：拆成多个带注释的 impl 块、提取 trait，或者把一部分状态和方法挪到新类型里。".to_string(),
+                example_bad: Some("impl Editor {\n    fn open(..) {}\n    fn save(..) {}\n    fn render(..) {}\n    fn highlight(..) {}\n    // ...还有 40 个\n}".to_string()),
+                example_good: Some("impl Editor { /* 文件操作 */ }\n\nstruct Renderer { /* ... */ }\nimpl Renderer { /* 渲染 */ }".to_string()),
+                rust_docs_link: Some("https://doc.rust-lang.org/book/ch05-03-method-syntax.html".to_string()),
+                best_practice_tip: Some("一个类型越小，越容易测试和复用。".to_string()),
+            }
+        } else {
+            EducationalAdvice {
+                why_bad: "Dozens of methods in one impl block mean the type carries too many responsibilities; readers struggle to find the method they want or see which ones belong together.".to_string(),
+                how_to_fix: "Group methods by responsibility: split into several commented impl blocks, extract traits, or move part of the state and its methods into a new type.".to_string(),
+                example_bad: Some("impl Editor {\n    fn open(..) {}\n    fn save(..) {}\n    fn render(..) {}\n    fn highlight(..) {}\n    // ...40 more\n}".to_string()),
+                example_good: Some("impl Editor { /* file operations */ }\n\nstruct Renderer { /* ... */ }\nimpl Renderer { /* rendering */ }".to_string()),
+                rust_docs_link: Some("https://doc.rust-lang.org/book/ch05-03-method-syntax.html".to_string()),
+                best_practice_tip: Some("The smaller a type, the easier it is to test and reuse.".to_string()),
+            }
+        }
+    }
+
+    fn create_match_abuse_advice(&self) -> EducationalAdvice {
+        if self.lang == "zh-CN" {
+            EducationalAdvice {
+                why_bad: "很多 match 只是 if let、let else、Option 组合子或 ? 的啰嗦写法，多出来的分支和缩进让真正的逻辑更难看清。".to_string(),
+                how_to_fix: "只关心一个分支用 if let；取值失败就返回用 let else；Some 映射成 Some 用 map；给默认值用 unwrap_or；原样传播 None/Err 用 ?；match bool 就写 if。".to_string(),
+                example_bad: Some("let port = match config.port {\n    Some(p) => p,\n    None => 8080,\n};".to_string()),
+                example_good: Some("let port = config.port.unwrap_or(8080);".to_string()),
+                rust_docs_link: Some("https://doc.rust-lang.org/book/ch06-03-if-let.html".to_string()),
+                best_practice_tip: Some("match 留给真正有多个分支要处理的地方。".to_string()),
+            }
+        } else {
+            EducationalAdvice {
+                why_bad: "Many matches are just a long way of writing if let, let else, an Option combinator or ?; the extra arms and indentation bury the actual logic.".to_string(),
+                how_to_fix: "Use if let when only one arm matters, let else to bind or bail out, map for Some-to-Some, unwrap_or for defaults, ? to pass None/Err through, and a plain if for bools.".to_string(),
+                example_bad: Some("let port = match config.port {\n    Some(p) => p,\n    None => 8080,\n};".to_string()),
+                example_good: Some("let port = config.port.unwrap_or(8080);".to_string()),
+                rust_docs_link: Some("https://doc.rust-lang.org/book/ch06-03-if-let.html".to_string()),
+                best_practice_tip: Some("Save match for when there really are several cases to handle.".to_string()),
+            }
+        }
+    }
+
+    fn create_naming_convention_advice(&self) -> EducationalAdvice {
+        if self.lang == "zh-CN" {
+            EducationalAdvice {
+                why_bad: "Rust 社区的命名惯例是所有 crate 共享的语言：大小写风格告诉读者这是类型、函数还是常量，as_/to_/into_ 告诉调用者转换的代价和所有权。".to_string(),
+                how_to_fix: "函数、变量、字段、模块用 snake_case，类型和枚举变体用 UpperCamelCase，常量和静态变量用 SCREAMING_SNAKE_CASE；缩写词当作一个单词；getter 去掉 get_ 前缀；as_ 借用、to_ 分配、into_ 消耗 self。".to_string(),
+                example_bad: Some("struct HTTPServer;\nfn get_port(&self) -> u16;\nfn as_string(&self) -> String;".to_string()),
+                example_good: Some("struct HttpServer;\nfn port(&self) -> u16;\nfn to_string(&self) -> String;".to_string()),
+                rust_docs_link: Some("https://rust-lang.github.io/api-guidelines/naming.html".to_string()),
+                best_practice_tip: Some("名字是 API 的一部分，遵守惯例能让使用者不看文档就猜对行为。".to_string()),
+            }
+        } else {
+            EducationalAdvice {
+                why_bad: "Rust naming conventions are a language shared by every crate: the case tells readers whether a name is a type, function or constant, and as_/to_/into_ tell callers the cost and ownership of a conversion.".to_string(),
+                how_to_fix: "Use snake_case for functions, variables, fields and modules, UpperCamelCase for types and variants, SCREAMING_SNAKE_CASE for consts and statics. Treat acronyms as one word, drop the get_ prefix on getters, and make as_ borrow, to_ allocate and into_ consume self.".to_string(),
+                example_bad: Some("struct HTTPServer;\nfn get_port(&self) -> u16;\nfn as_string(&self) -> String;".to_string()),
+                example_good: Some("struct HttpServer;\nfn port(&self) -> u16;\nfn to_string(&self) -> String;".to_string()),
+                rust_docs_link: Some("https://rust-lang.github.io/api-guidelines/naming.html".to_string()),
+                best_practice_tip: Some("Names are part of your API - following the conventions lets users guess behavior without reading docs.".to_string()),
+            }
+        }
+    }
+
+    fn create_pinyin_naming_advice(&self) -> EducationalAdvice {
+        if self.lang == "zh-CN" {
+            EducationalAdvice {
+                why_bad: "拼音没有声调，同一串字母对应好几个词；中英混杂的名字两种语言的读者都看不懂；汉字标识符需要输入法，grep、补全和代码审查工具也不友好。".to_string(),
+                how_to_fix: "统一用英文命名，拿不准的业务词可以查团队词汇表；确实需要中文说明的地方写在文档注释里。".to_string(),
+                example_bad: Some("fn denglu(yonghu: &Yonghu, mima: &str) -> bool\nlet 用户名 = input();".to_string()),
+                example_good: Some("/// 用户登录\nfn login(user: &User, password: &str) -> bool\nlet username = input();".to_string()),
+                rust_docs_link: Some("https://rust-lang.github.io/api-guidelines/naming.html".to_string()),
+                best_practice_tip: Some("维护一份中英业务术语表，新人起名时直接查表。".to_string()),
+            }
+        } else {
+            EducationalAdvice {
+                why_bad: "Pinyin has no tones, so one spelling maps to several words; mixed pinyin/English names are unreadable in both languages; Chinese-character identifiers need an IME and confuse grep, autocomplete and review tools.".to_string(),
+                how_to_fix: "Name things in English, using a team glossary for domain terms. Put Chinese explanations in doc comments where they are really needed.".to_string(),
+                example_bad: Some("fn denglu(yonghu: &Yonghu, mima: &str) -> bool\nlet 用户名 = input();".to_string()),
+                example_good: Some("/// 用户登录\nfn login(user: &User, password: &str) -> bool\nlet username = input();".to_string()),
+                rust_docs_link: Some("https://rust-lang.github.io/api-guidelines/naming.html".to_string()),
+                best_practice_tip: Some("Keep a Chinese-English glossary of domain terms so naming is a lookup, not a guess.".to_string()),
+            }
+        }
+    }
+
+    fn create_identifier_typo_advice(&self) -> EducationalAdvice {
+        if self.lang == "zh-CN" {
+            EducationalAdvice {
+                why_bad: "拼错的标识符搜不到、记不住，公开 API 里的错别字要等到下一个大版本才能改，还会被下游代码原样抄走。".to_string(),
+                how_to_fix: "按提示改成正确拼写；产品名、领域术语这类词典不认识的词，用 --allow-word 或 --word-list 加进项目词表。".to_string(),
+                example_bad: Some("pub struct Packet {\n    lenght: usize,\n}\n\npub fn recieve(adress: &str) -> Packet".to_string()),
+                example_good: Some("pub struct Packet {\n    length: usize,\n}\n\npub fn receive(address: &str) -> Packet".to_string()),
+                rust_docs_link: Some("https://rust-lang.github.io/api-guidelines/naming.html".to_string()),
+                best_practice_tip: Some("在 CI 里跑拼写检查，把项目词表和代码一起提交。".to_string()),
+            }
+        } else {
+            EducationalAdvice {
+                why_bad: "Misspelled identifiers are hard to search for and easy to get wrong again. A typo in a public API can only be fixed in the next major version, and downstream code copies it meanwhile.".to_string(),
+                how_to_fix: "Rename to the suggested spelling. Add product names and domain terms the dictionary doesn't know with --allow-word or a --word-list file.".to_string(),
+                example_bad: Some("pub struct Packet {\n    lenght: usize,\n}\n\npub fn recieve(adress: &str) -> Packet".to_string()),
+                example_good: Some("pub struct Packet {\n    length: usize,\n}\n\npub fn receive(address: &str) -> Packet".to_string()),
+                rust_docs_link: Some("https://rust-lang.github.io/api-guidelines/naming.html".to_string()),
+                best_practice_tip: Some("Run a spell check in CI and commit the project word list next to the code.".to_string()),
+            }
+        }
+    }
 }
@@ -131,7 +131,7 @@ impl HallOfShame {
         // Sort patterns by frequency
         let mut most_common_patterns: Vec<PatternStats> =
             self.pattern_stats.values().cloned().collect();
-        most_common_patterns.sort_by(|a, b| b.count.cmp(&a.count));
+        most_common_patterns.sort_by_key(|b| std::cmp::Reverse(b.count));
 
         // Calculate garbage density (issues per 1000 lines)
         let total_issues: usize = self.entries.iter().map(|e| e.total_issues).sum();
@@ -168,12 +168,26 @@ impl HallOfShame {
                 "Complexity Issues".to_string()
             }
             name if name.contains("unwrap")
+                || name.contains("error")
                 || name.contains("panic")
                 || name.contains("string")
                 || name.contains("clone") =>
             {
                 "Rust-specific Issues".to_string()
             }
+            name if name.contains("async") || name.contains("lock") || name.contains("channel") => {
+                "Concurrency Issues".to_string()
+            }
+            name if name.contains("doc") => "Documentation Issues".to_string(),
+            name if name.contains("param")
+                || name.contains("leaky")
+                || name.starts_with("missing-") =>
+            {
+                "API Design Issues".to_string()
+            }
+            "god-struct" | "option-soup" | "data-clump" | "primitive-obsession" | "huge-impl" => {
+                "Type Design Issues".to_string()
+            }
             name if name.contains("println") || name.contains("todo") => {
                 "Student Code Issues".to_string()
             }
@@ -197,7 +211,7 @@ impl HallOfShame {
     #[allow(dead_code)]
     pub fn get_most_common_patterns(&self, limit: usize) -> Vec<&PatternStats> {
         let mut patterns: Vec<&PatternStats> = self.pattern_stats.values().collect();
-        patterns.sort_by(|a, b| b.count.cmp(&a.count));
+        patterns.sort_by_key(|b| std::cmp::Reverse(b.count));
         patterns.into_iter().take(limit).collect()
     }
 
@@ -167,6 +167,24 @@ impl I18n {
                 "这变量名的信息量约等于一个句号".to_string(),
                 "你这是在节约字符还是在为难后来的维护者？".to_string(),
             ],
+            ("zh-CN", "naming-convention") => vec![
+                "snake_case、UpperCamelCase、SCREAMING_SNAKE_CASE，Rust 只有这三种，你偏偏选了第四种".to_string(),
+                "HTTPServer？缩写词在 Rust 里也只是一个普通单词，别大喊大叫".to_string(),
+                "get_name？Rust 的 getter 不需要 get_ 前缀，这不是 Java".to_string(),
+                "as_ 是免费借用，to_ 是昂贵转换，into_ 会吃掉 self，名字可别骗人".to_string(),
+            ],
+            ("zh-CN", "pinyin-naming") => vec![
+                "拼音命名？yonghu 是用户还是永胡，全靠读者的想象力".to_string(),
+                "中英混搭的变量名，就像火锅里放了芝士".to_string(),
+                "用汉字当标识符，换台电脑连名字都打不出来".to_string(),
+                "拼音没有声调，shijian 到底是时间、事件还是实践？".to_string(),
+            ],
+            ("zh-CN", "identifier-typo") => vec![
+                "标识符里有错别字，编译器不管，可读代码的人都看见了".to_string(),
+                "recieve、lenght、adress……拼写检查一下又不要钱".to_string(),
+                "API 名字里的错别字会跟着版本号一起永垂不朽".to_string(),
+                "拼错的单词让 grep 都搜不到，这是在和同事捉迷藏吗？".to_string(),
+            ],
             ("zh-CN", "deep-nesting") => vec![
                 "这嵌套层数比俄罗斯套娃还要深，你是在挑战人类的理解极限吗？".to_string(),
                 "嵌套这么深，是想挖到地心还是想让读代码的人迷路？".to_string(),
@@ -224,10 +242,10 @@ impl I18n {
                 "闭包参数过多，你确定不是在写函数？".to_string(),
             ],
             ("zh-CN", "lifetime-abuse") => vec![
-                "生命周期标注比我的生命还复杂".to_string(),
-                "这么多生命周期，你是在写哲学论文吗？".to_string(),
-                "生命周期滥用，建议重新设计数据结构".to_string(),
-                "生命周期多到让人怀疑人生".to_string(),
+                "生命周期标注是写给编译器的，也是写给人看的".to_string(),
+                "签名里的生命周期越多，读起来越累".to_string(),
+                "生命周期是用来讲清借用关系的，不是装饰".to_string(),
+                "多写一个生命周期，读代码的人就要多记一个".to_string(),
             ],
             ("zh-CN", "trait-complexity") => vec![
                 "这个 trait 的方法比我的借口还多".to_string(),
@@ -238,27 +256,101 @@ impl I18n {
                 "这么多泛型，你是在写数学公式吗？".to_string(),
             ],
             ("zh-CN", "generic-abuse") => vec![
-                "泛型参数比我的购物清单还长".to_string(),
-                "这么多泛型，编译器都要哭了".to_string(),
-                "泛型滥用，建议重新设计架构".to_string(),
-                "泛型多到让人怀疑这还是 Rust 吗".to_string(),
-                "泛型参数的命名创意约等于零".to_string(),
-                "泛型名字比我的耐心还短".to_string(),
+                "没用上的类型参数只会让调用者多写 turbofish".to_string(),
+                "只出现一次的泛型，impl Trait 更直白".to_string(),
+                "约束写在结构体上，每个 impl 都得跟着抄一遍".to_string(),
+                "泛型大函数每种类型都要编译一份".to_string(),
             ],
             ("zh-CN", "channel-abuse") => vec![
-                "Channel 用得比我发微信还频繁，你确定不是在写聊天软件？".to_string(),
-                "这么多 Channel，你是想开通讯公司吗？".to_string(),
-                "Channel 滥用！你的程序比电话交换机还复杂".to_string(),
-                "Channel 数量超标，建议重新设计架构".to_string(),
-                "这么多 Channel，我怀疑你在写分布式系统".to_string(),
+                "循环里创建无界 channel，每一圈都新开一条没有背压的管道".to_string(),
+                "无界 channel 会替你记住每一条没人消费的消息，直到内存耗尽".to_string(),
+                "把 channel 提到循环外面，或者给它一个容量上限".to_string(),
             ],
             ("zh-CN", "async-abuse") => vec![
-                "Async 函数比我的异步人生还要复杂".to_string(),
-                "这么多 async，你确定不是在写 JavaScript？".to_string(),
-                "Async 滥用！建议学习一下同步编程的美好".to_string(),
-                "异步函数过多，小心把自己绕晕了".to_string(),
-                "Await 用得比我等外卖还频繁".to_string(),
-                "这么多 await，你的程序是在等什么？世界末日吗？".to_string(),
+                "async 函数里的阻塞调用让整个执行器线程陪你一起睡".to_string(),
+                "thread::sleep 出现在 async 里，其他任务全都饿着".to_string(),
+                "std 的锁守卫跨过了 .await，死锁已经在路上了".to_string(),
+                "std::fs 是同步 API，async 里请用 tokio::fs 或 spawn_blocking".to_string(),
+            ],
+            ("zh-CN", "lock-abuse") => vec![
+                "Arc<Mutex<Vec<..>>>：整个集合一把大锁，并发程序被写成了串行程序".to_string(),
+                ".lock().unwrap() 把锁中毒变成了连锁崩溃".to_string(),
+                "一条链里连拿两把锁，加锁顺序一乱就是死锁".to_string(),
+            ],
+            ("zh-CN", "owned-ref-param") => vec![
+                "公开函数收 &String，调用者只好先造一个 String".to_string(),
+                "&Vec<T> 参数？&[T] 能接受的东西更多".to_string(),
+                "&Box<T> 多了一层没用的间接".to_string(),
+            ],
+            ("zh-CN", "bool-param-soup") => vec![
+                "f(true, false, true)，这是 API 还是摩斯电码？".to_string(),
+                "bool 参数太多，换成枚举或者选项结构体吧".to_string(),
+                "bool 参数顺序传错了，编译器也不会告诉你".to_string(),
+            ],
+            ("zh-CN", "leaky-struct") => vec![
+                "构造函数做了校验，字段却全是 pub，校验形同虚设".to_string(),
+                "字段全公开的结构体守不住任何不变量".to_string(),
+                "把字段改成私有，再提供 getter".to_string(),
+            ],
+            ("zh-CN", "missing-non-exhaustive") => vec![
+                "公开枚举没有 #[non_exhaustive]，加个变体就是破坏性更新".to_string(),
+                "给会增长的枚举加上 #[non_exhaustive]".to_string(),
+                "你的下一个小版本会变成大版本".to_string(),
+            ],
+            ("zh-CN", "missing-debug") => vec![
+                "公开类型不能 {:?}，用户调试时只能干瞪眼".to_string(),
+                "#[derive(Debug)] 就一行，加上吧".to_string(),
+                "没有 Debug 的类型会传染：包含它的类型也没法 derive".to_string(),
+            ],
+            ("zh-CN", "leaky-impl-trait") => vec![
+                "返回 impl Trait，调用者没法给类型起名字".to_string(),
+                "impl Trait 把自动 trait 悄悄变成了 API 的一部分".to_string(),
+                "考虑返回具名类型".to_string(),
+            ],
+            ("zh-CN", "missing-docs") => vec![
+                "公开 API 没有文档，用户只能读源码".to_string(),
+                "一行 /// 都没有，cargo doc 页面一片空白".to_string(),
+                "写文档的时间比用户猜用法的时间少多了".to_string(),
+            ],
+            ("zh-CN", "parrot-docs") => vec![
+                "文档只是把名字念了一遍".to_string(),
+                "\"Gets the foo\"——谢谢，名字已经告诉我了".to_string(),
+                "写点名字里看不出来的东西".to_string(),
+            ],
+            ("zh-CN", "broken-doc-example") => vec![
+                "文档示例连解析都过不了".to_string(),
+                "过期的示例比没有示例更糟糕".to_string(),
+                "doctest 一跑就挂".to_string(),
+            ],
+            ("zh-CN", "missing-doc-sections") => vec![
+                "返回 Result 却不写 # Errors".to_string(),
+                "会 panic 却不写 # Panics".to_string(),
+                "文档只讲了顺利的情况".to_string(),
+            ],
+            ("zh-CN", "god-struct") => vec![
+                "字段多到这个结构体什么都管".to_string(),
+                "上帝结构体里藏着好几个该独立的类型".to_string(),
+                "改一个字段之前得先看懂全部字段".to_string(),
+            ],
+            ("zh-CN", "option-soup") => vec![
+                "一堆 Option 字段，合法状态全靠猜".to_string(),
+                "这些 Option 其实是枚举的变体".to_string(),
+                "让非法状态无法表示".to_string(),
+            ],
+            ("zh-CN", "data-clump") => vec![
+                "同一组字段到处复制".to_string(),
+                "总是一起出现的字段该有自己的类型".to_string(),
+                "数据泥团，提取成结构体吧".to_string(),
+            ],
+            ("zh-CN", "primitive-obsession") => vec![
+                "ID、邮箱、URL 全是 String，传反了也能编译".to_string(),
+                "newtype 是零成本的，用起来".to_string(),
+                "让类型系统帮你区分这些字符串".to_string(),
+            ],
+            ("zh-CN", "huge-impl") => vec![
+                "impl 块长得翻不到底".to_string(),
+                "这个类型的职责太多了".to_string(),
+                "按职责拆分方法".to_string(),
             ],
             ("zh-CN", "dyn-trait-abuse") => vec![
                 "Dyn trait 用得比我换工作还频繁".to_string(),
@@ -267,6 +359,21 @@ impl I18n {
                 "动态 trait 过多，编译器优化都哭了".to_string(),
                 "这么多 dyn，你的程序比变色龙还善变".to_string(),
             ],
+            ("zh-CN", "println-debugging") => vec![
+                "没人要看的输出，就别打印了".to_string(),
+                "调试输出还留在代码里".to_string(),
+                "换成 log 或 tracing 吧".to_string(),
+            ],
+            ("zh-CN", "vec-abuse") => vec![
+                "Vec 用得没问题，浪费的是多余的分配".to_string(),
+                "每一次分配都该有它的理由".to_string(),
+                "分配一次，多用几次".to_string(),
+            ],
+            ("zh-CN", "todo-comment") => vec![
+                "待办事项只会越攒越多，不会自己消失".to_string(),
+                "留给以后的活，最后都落到别人头上".to_string(),
+                "写着“以后再改”的代码，往往没有以后".to_string(),
+            ],
             ("zh-CN", "unsafe-abuse") => vec![
                 "Unsafe 代码！你这是在玩火还是在挑战 Rust 的底线？".to_string(),
                 "又见 unsafe！安全性是什么？能吃吗？".to_string(),
@@ -295,10 +402,9 @@ impl I18n {
                 "这么深的模块，找个函数比找宝藏还难".to_string(),
             ],
             ("zh-CN", "pattern-matching-abuse") => vec![
-                "模式匹配比我的感情生活还复杂".to_string(),
-                "这么多模式，你是在写解谜游戏吗？".to_string(),
-                "模式过多，建议简化逻辑".to_string(),
-                "复杂的模式让代码可读性直线下降".to_string(),
+                "`_` 分支把新变体都吞了，编译器想提醒你都没机会".to_string(),
+                "通配分支藏住了枚举变体".to_string(),
+                "把剩下的变体列出来，让穷尽检查帮你干活".to_string(),
                 "Match 分支比我的人生选择还多".to_string(),
                 "这么多 match 分支，你确定不是在写状态机？".to_string(),
             ],
@@ -333,6 +439,27 @@ impl I18n {
                 "这函数的复杂度已经超越人类理解范围".to_string(),
                 "建议拆分函数，或者直接重写".to_string(),
             ],
+            ("zh-CN", "stringly-error") => vec![
+                "错误类型是 String？调用者只能靠猜".to_string(),
+                "字符串错误：看得懂，match 不了".to_string(),
+                "定义个错误枚举很难吗？".to_string(),
+            ],
+            ("zh-CN", "boxed-error") => vec![
+                "库里的 Box<dyn Error>，开盲盒一样的错误处理".to_string(),
+                "错误装箱发货，收件人不知道里面是什么".to_string(),
+                "库该有自己的错误类型".to_string(),
+            ],
+            ("zh-CN", "swallowed-error") => vec![
+                "错误被悄悄吞掉了，连个响都没有".to_string(),
+                "眼不见为净的错误处理".to_string(),
+                "这个错误去哪了？没人知道".to_string(),
+                "吞错误一时爽，排查火葬场".to_string(),
+            ],
+            ("zh-CN", "incomplete-error-type") => vec![
+                "名字叫 Error，却不实现 Error".to_string(),
+                "这个错误类型连 Display 都没有，打印出来给谁看？".to_string(),
+                "半成品错误类型，? 都用不了".to_string(),
+            ],
             // 英文版本
             ("en-US", "terrible-naming") => vec![
                 "This variable name is more abstract than my programming skills, and I can't even write Hello World correctly".to_string(),
@@ -357,6 +484,24 @@ impl I18n {
                 "This variable name has the information content of a period".to_string(),
                 "Are you saving characters or just torturing future maintainers?".to_string(),
             ],
+            ("en-US", "naming-convention") => vec![
+                "snake_case, UpperCamelCase, SCREAMING_SNAKE_CASE - Rust has three styles and you picked a fourth".to_string(),
+                "HTTPServer? In Rust an acronym is just a word, stop shouting".to_string(),
+                "get_name? Rust getters don't need a get_ prefix, this isn't Java".to_string(),
+                "as_ borrows for free, to_ converts at a cost, into_ consumes self - don't let the name lie".to_string(),
+            ],
+            ("en-US", "pinyin-naming") => vec![
+                "Pinyin names? Is 'yonghu' a user or a mystery, only the author knows".to_string(),
+                "Half pinyin, half English - a name that needs two dictionaries".to_string(),
+                "Chinese characters as identifiers - good luck typing that on another machine".to_string(),
+                "Pinyin has no tones: is 'shijian' time, event or practice?".to_string(),
+            ],
+            ("en-US", "identifier-typo") => vec![
+                "The compiler doesn't care about typos, but every reader notices".to_string(),
+                "recieve, lenght, adress... spell checkers are free, you know".to_string(),
+                "A typo in a public API is forever, semver won't let you take it back".to_string(),
+                "Misspelled names hide from grep - nobody will find your 'adress' field".to_string(),
+            ],
             ("en-US", "deep-nesting") => vec![
                 "This nesting is deeper than Russian dolls, are you challenging the limits of human comprehension?".to_string(),
                 "Nesting this deep, are you trying to dig to Earth's core or just make code readers get lost?".to_string(),
@@ -414,10 +559,10 @@ impl I18n {
                 "Too many closure parameters, are you sure you're not writing a function?".to_string(),
             ],
             ("en-US", "lifetime-abuse") => vec![
-                "Lifetime annotations are more complex than my actual life".to_string(),
-                "So many lifetimes, are you writing a philosophy paper?".to_string(),
-                "Lifetime abuse, suggest redesigning data structures".to_string(),
-                "So many lifetimes it makes me question existence".to_string(),
+                "Lifetime annotations are for the reader as much as the compiler".to_string(),
+                "The more lifetimes in a signature, the harder it is to read".to_string(),
+                "Lifetimes should explain borrows, not decorate them".to_string(),
+                "Every extra lifetime is one more thing the reader has to track".to_string(),
             ],
             ("en-US", "trait-complexity") => vec![
                 "This trait has more methods than my excuses".to_string(),
@@ -428,27 +573,101 @@ impl I18n {
                 "So many generics, are you writing mathematical formulas?".to_string(),
             ],
             ("en-US", "generic-abuse") => vec![
-                "Generic parameters longer than my shopping list".to_string(),
-                "So many generics, even the compiler is crying".to_string(),
-                "Generic abuse, suggest redesigning architecture".to_string(),
-                "So many generics, makes me wonder if this is still Rust".to_string(),
-                "Generic parameter naming creativity equals zero".to_string(),
-                "Generic name shorter than my patience".to_string(),
+                "An unused type parameter only makes callers write turbofish".to_string(),
+                "A generic used once reads better as impl Trait".to_string(),
+                "Bounds on the struct get copied onto every impl".to_string(),
+                "A big generic fn is compiled once per type".to_string(),
             ],
             ("en-US", "channel-abuse") => vec![
-                "Using channels more frequently than I text, are you writing a chat app?".to_string(),
-                "So many channels, are you starting a telecom company?".to_string(),
-                "Channel abuse! Your program is more complex than a phone exchange".to_string(),
-                "Channel count exceeded, suggest redesigning architecture".to_string(),
-                "So many channels, I suspect you're writing a distributed system".to_string(),
+                "Unbounded channel created in a loop - a new pipe with no backpressure every iteration".to_string(),
+                "Unbounded channels remember every unconsumed message, right up until memory runs out".to_string(),
+                "Hoist the channel out of the loop, or give it a capacity".to_string(),
             ],
             ("en-US", "async-abuse") => vec![
-                "Async functions more complex than my asynchronous life".to_string(),
-                "So many async, are you sure you're not writing JavaScript?".to_string(),
-                "Async abuse! Suggest learning the beauty of synchronous programming".to_string(),
-                "Too many async functions, careful not to confuse yourself".to_string(),
-                "Using await more frequently than I wait for food delivery".to_string(),
-                "So many awaits, what is your program waiting for? The apocalypse?".to_string(),
+                "Blocking calls in async code put the whole executor thread to sleep".to_string(),
+                "thread::sleep inside async, every other task starves".to_string(),
+                "A std lock guard held across .await, a deadlock is on its way".to_string(),
+                "std::fs is synchronous, use tokio::fs or spawn_blocking in async code".to_string(),
+            ],
+            ("en-US", "lock-abuse") => vec![
+                "Arc<Mutex<Vec<..>>>: one big lock around the collection, your concurrent code runs single file".to_string(),
+                ".lock().unwrap() turns lock poisoning into a cascade of panics".to_string(),
+                "Two locks in one chain - inconsistent lock order means deadlock".to_string(),
+            ],
+            ("en-US", "owned-ref-param") => vec![
+                "A public fn taking &String makes callers build a String first".to_string(),
+                "&Vec<T> parameter? &[T] accepts strictly more".to_string(),
+                "&Box<T> is just an extra useless indirection".to_string(),
+            ],
+            ("en-US", "bool-param-soup") => vec![
+                "f(true, false, true) - is this an API or Morse code?".to_string(),
+                "Too many bool params, use enums or an options struct".to_string(),
+                "Swap two bool arguments and the compiler won't say a word".to_string(),
+            ],
+            ("en-US", "leaky-struct") => vec![
+                "The constructor validates, but every field is pub - the checks are optional".to_string(),
+                "A struct with all-public fields can't keep any invariant".to_string(),
+                "Make the fields private and add getters".to_string(),
+            ],
+            ("en-US", "missing-non-exhaustive") => vec![
+                "Public enum without #[non_exhaustive], one new variant is a breaking change".to_string(),
+                "Add #[non_exhaustive] to enums that keep growing".to_string(),
+                "Your next minor release just became a major one".to_string(),
+            ],
+            ("en-US", "missing-debug") => vec![
+                "Public type can't be {:?}-printed, users debugging it are stuck".to_string(),
+                "#[derive(Debug)] is one line, add it".to_string(),
+                "Missing Debug is contagious: nothing containing it can derive Debug".to_string(),
+            ],
+            ("en-US", "leaky-impl-trait") => vec![
+                "Returning impl Trait means callers can't name the type".to_string(),
+                "impl Trait quietly makes auto traits part of your API".to_string(),
+                "Consider returning a named type".to_string(),
+            ],
+            ("en-US", "missing-docs") => vec![
+                "Public API without docs, users get to read the source".to_string(),
+                "Not a single /// line, cargo doc renders a blank page".to_string(),
+                "Writing docs takes less time than users spend guessing".to_string(),
+            ],
+            ("en-US", "parrot-docs") => vec![
+                "The docs just read the name back".to_string(),
+                "\"Gets the foo\" - thanks, the name told me already".to_string(),
+                "Write down what the name can't tell".to_string(),
+            ],
+            ("en-US", "broken-doc-example") => vec![
+                "The doc example doesn't even parse".to_string(),
+                "A stale example is worse than no example".to_string(),
+                "The doctest fails before it starts".to_string(),
+            ],
+            ("en-US", "missing-doc-sections") => vec![
+                "Returns Result but has no # Errors section".to_string(),
+                "Can panic but has no # Panics section".to_string(),
+                "The docs only cover the happy path".to_string(),
+            ],
+            ("en-US", "god-struct") => vec![
+                "So many fields this struct is in charge of everything".to_string(),
+                "Several types are hiding inside this god struct".to_string(),
+                "You need to understand every field before changing one".to_string(),
+            ],
+            ("en-US", "option-soup") => vec![
+                "A pile of Option fields, valid states are anyone's guess".to_string(),
+                "These Options are really enum variants".to_string(),
+                "Make invalid states unrepresentable".to_string(),
+            ],
+            ("en-US", "data-clump") => vec![
+                "The same group of fields copied everywhere".to_string(),
+                "Fields that travel together deserve their own type".to_string(),
+                "Data clump, extract a struct".to_string(),
+            ],
+            ("en-US", "primitive-obsession") => vec![
+                "IDs, emails and URLs are all String - swap them and it still compiles".to_string(),
+                "Newtypes are zero-cost, use them".to_string(),
+                "Let the type system tell these strings apart".to_string(),
+            ],
+            ("en-US", "huge-impl") => vec![
+                "An impl block you can't scroll to the end of".to_string(),
+                "This type has too many responsibilities".to_string(),
+                "Split the methods by responsibility".to_string(),
             ],
             ("en-US", "dyn-trait-abuse") => vec![
                 "Using dyn traits more frequently than I change jobs".to_string(),
@@ -457,6 +676,21 @@ impl I18n {
                 "Too many dynamic traits, even compiler optimizations are crying".to_string(),
                 "So many dyns, your program is more changeable than a chameleon".to_string(),
             ],
+            ("en-US", "println-debugging") => vec![
+                "Output nobody asked to see".to_string(),
+                "Debug output left behind".to_string(),
+                "Switch to log or tracing".to_string(),
+            ],
+            ("en-US", "vec-abuse") => vec![
+                "Vecs are fine, the extra allocations aren't".to_string(),
+                "Every allocation should earn its keep".to_string(),
+                "Allocate once, reuse often".to_string(),
+            ],
+            ("en-US", "todo-comment") => vec![
+                "Unfinished work piles up, it never leaves on its own".to_string(),
+                "Work left for later ends up on someone else's plate".to_string(),
+                "Code marked \"later\" rarely gets a later".to_string(),
+            ],
             ("en-US", "unsafe-abuse") => vec![
                 "Unsafe code! Are you playing with fire or challenging Rust's bottom line?".to_string(),
                 "Another unsafe! What is safety? Can you eat it?".to_string(),
@@ -485,10 +719,9 @@ impl I18n {
                 "Such deep modules, finding a function is harder than finding treasure".to_string(),
             ],
             ("en-US", "pattern-matching-abuse") => vec![
-                "Pattern matching more complex than my love life".to_string(),
-                "So many patterns, are you writing a puzzle game?".to_string(),
-                "Too many patterns, suggest simplifying logic".to_string(),
-                "Complex patterns make code readability plummet".to_string(),
+                "The `_` arm swallows new variants before the compiler can warn you".to_string(),
+                "A wildcard arm is hiding enum variants".to_string(),
+                "List the remaining variants and let exhaustiveness checking work for you".to_string(),
                 "More match branches than my life choices".to_string(),
                 "So many match branches, are you sure you're not writing a state machine?".to_string(),
             ],
@@ -518,6 +751,27 @@ impl I18n {
                 "Duplicate code alert! Even my copy machine is jealous of your efficiency".to_string(),
                 "Your code has more clones than a sci-fi movie, time for some refactoring!".to_string(),
             ],
+            ("en-US", "stringly-error") => vec![
+                "String as an error type? Callers will have to guess".to_string(),
+                "Stringly-typed errors: readable, but not matchable".to_string(),
+                "Is defining an error enum really that hard?".to_string(),
+            ],
+            ("en-US", "boxed-error") => vec![
+                "Box<dyn Error> in a library - error handling as a mystery box".to_string(),
+                "Errors shipped in a box, the recipient has no idea what's inside".to_string(),
+                "A library deserves its own error type".to_string(),
+            ],
+            ("en-US", "swallowed-error") => vec![
+                "An error got swallowed without a sound".to_string(),
+                "Out of sight, out of mind error handling".to_string(),
+                "Where did this error go? Nobody knows".to_string(),
+                "Swallowing errors today, debugging nightmares tomorrow".to_string(),
+            ],
+            ("en-US", "incomplete-error-type") => vec![
+                "Called an Error, doesn't implement Error".to_string(),
+                "This error type has no Display - who is it supposed to talk to?".to_string(),
+                "A half-finished error type, ? can't even use it".to_string(),
+            ],
             _ => vec!["Unknown issue detected".to_string()],
         }
     }
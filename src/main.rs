@@ -3,19 +3,11 @@ use std::fs;
 use std::path::PathBuf;
 use walkdir::WalkDir;
 
-mod analyzer;
-mod educational;
-mod hall_of_shame;
-mod i18n;
-mod reporter;
-mod rules;
-mod scoring;
-mod utils;
-
-use analyzer::CodeAnalyzer;
-use educational::EducationalAdvisor;
-use hall_of_shame::HallOfShame;
-use reporter::Reporter;
+use garbage_code_hunter::rules::context::ContextMap;
+use garbage_code_hunter::rules::RuleConfig;
+use garbage_code_hunter::{
+    CodeAnalyzer, CodeContext, CodeIssue, EducationalAdvisor, HallOfShame, Reporter,
+};
 
 #[derive(Parser)]
 #[command(name = "garbage-code-hunter")]
@@ -73,16 +65,83 @@ struct Args {
     /// Show improvement suggestions based on analysis
     #[arg(long)]
     suggestions: bool,
+
+    /// Print a per-file inventory of unsafe code for security review
+    #[arg(long)]
+    unsafe_audit: bool,
+
+    /// List every TODO/FIXME/HACK comment and todo!() with owner, ticket and due date
+    #[arg(long)]
+    todos: bool,
+
+    /// Numbers that are not magic in this project (comma separated or repeated)
+    #[arg(long, value_delimiter = ',', allow_negative_numbers = true)]
+    allow_number: Vec<i64>,
+
+    /// Words the spell checker should accept (comma separated or repeated)
+    #[arg(long, value_delimiter = ',')]
+    allow_word: Vec<String>,
+
+    /// File with project-specific words for the spell checker, one per line
+    #[arg(long)]
+    word_list: Option<PathBuf>,
 }
 
 fn main() {
     let args = Args::parse();
 
-    let analyzer = CodeAnalyzer::new(&args.exclude, &args.lang);
+    let mut custom_words = args.allow_word.clone();
+    if let Some(word_list) = &args.word_list {
+        match fs::read_to_string(word_list) {
+            Ok(content) => custom_words.extend(parse_word_list(&content)),
+            Err(err) => {
+                eprintln!("Cannot read word list {}: {}", word_list.display(), err);
+                std::process::exit(1);
+            }
+        }
+    }
+
+    let analyzer = CodeAnalyzer::new(&args.exclude, &args.lang).with_config(RuleConfig {
+        allowed_numbers: args.allow_number.clone(),
+        custom_words,
+    });
+
+    if args.unsafe_audit {
+        let inventories = analyzer.unsafe_inventory(&args.path);
+        let reporter = Reporter::new(
+            false,
+            false,
+            args.verbose,
+            args.top,
+            args.issues,
+            false,
+            args.markdown,
+            &args.lang,
+        );
+        reporter.report_unsafe_inventory(&inventories);
+        return;
+    }
+
+    if args.todos {
+        let inventories = analyzer.todo_inventory(&args.path);
+        let reporter = Reporter::new(
+            false,
+            false,
+            args.verbose,
+            args.top,
+            args.issues,
+            false,
+            args.markdown,
+            &args.lang,
+        );
+        reporter.report_todo_inventory(&inventories);
+        return;
+    }
+
     let issues = analyzer.analyze_path(&args.path);
 
     // Calculate metrics for scoring
-    let (file_count, total_lines) = calculate_metrics(&args.path, &args.exclude);
+    let (file_count, total_lines, test_lines) = calculate_metrics(&args.path, &args.exclude);
 
     // Initialize educational advisor if needed
     let educational_advisor = if args.educational {
@@ -123,18 +182,31 @@ fn main() {
             issues,
             file_count,
             total_lines,
+            test_lines,
             educational_advisor.as_ref(),
             hall_of_shame.as_ref(),
             args.suggestions,
         );
     } else {
-        reporter.report_with_metrics(issues, file_count, total_lines);
+        reporter.report_with_metrics(issues, file_count, total_lines, test_lines);
     }
 }
 
-fn calculate_metrics(path: &PathBuf, exclude_patterns: &[String]) -> (usize, usize) {
+/// One or more words per line; everything after `#` is a comment
+fn parse_word_list(content: &str) -> Vec<String> {
+    content
+        .lines()
+        .map(|line| line.split('#').next().unwrap_or_default())
+        .flat_map(str::split_whitespace)
+        .map(str::to_string)
+        .collect()
+}
+
+/// File count, total lines and how many of those lines are test or bench code
+fn calculate_metrics(path: &PathBuf, exclude_patterns: &[String]) -> (usize, usize, usize) {
     let mut file_count = 0;
     let mut total_lines = 0;
+    let mut test_lines = 0;
 
     // Convert exclude patterns to regex patterns
     let exclude_regexes: Vec<regex::Regex> = exclude_patterns
@@ -162,6 +234,7 @@ fn calculate_metrics(path: &PathBuf, exclude_patterns: &[String]) -> (usize, usi
                     file_count = 1;
                     if let Ok(content) = fs::read_to_string(path) {
                         total_lines = content.lines().count();
+                        test_lines = count_test_lines(path, &content);
                     }
                 }
             }
@@ -176,16 +249,30 @@ fn calculate_metrics(path: &PathBuf, exclude_patterns: &[String]) -> (usize, usi
             file_count += 1;
             if let Ok(content) = fs::read_to_string(entry.path()) {
                 total_lines += content.lines().count();
+                test_lines += count_test_lines(entry.path(), &content);
             }
         }
     }
 
-    (file_count, total_lines)
+    (file_count, total_lines, test_lines)
+}
+
+fn count_test_lines(path: &std::path::Path, content: &str) -> usize {
+    match syn::parse_file(content) {
+        Ok(syntax_tree) => {
+            let context_map = ContextMap::new(path, &syntax_tree);
+            (1..=content.lines().count())
+                .filter(|line| context_map.context_at(*line).is_test_code())
+                .count()
+        }
+        Err(_) if CodeContext::from_path(path).is_test_code() => content.lines().count(),
+        Err(_) => 0,
+    }
 }
 
 fn group_issues_by_file(
-    issues: &[analyzer::CodeIssue],
-) -> std::collections::HashMap<std::path::PathBuf, Vec<analyzer::CodeIssue>> {
+    issues: &[CodeIssue],
+) -> std::collections::HashMap<std::path::PathBuf, Vec<CodeIssue>> {
     let mut grouped = std::collections::HashMap::new();
     for issue in issues {
         grouped
@@ -5,11 +5,13 @@ use std::collections::{BTreeMap, HashMap};
 use std::hash::{Hash, Hasher};
 use std::time::{SystemTime, UNIX_EPOCH};
 
-use crate::analyzer::{CodeIssue, Severity};
+use crate::analyzer::{CodeContext, CodeIssue, Severity};
 use crate::educational::EducationalAdvisor;
 use crate::hall_of_shame::HallOfShame;
 use crate::i18n::I18n;
-use crate::scoring::{CodeQualityScore, CodeScorer};
+use crate::rules::todos::{TodoDate, TodoInventory, TodoItem};
+use crate::rules::unsafe_audit::{UnsafeInventory, UnsafeKind};
+use crate::scoring::{CodeQualityScore, CodeScorer, QualityLevel};
 
 pub struct Reporter {
     harsh_mode: bool,
@@ -196,6 +198,24 @@ impl Reporter {
                         vec!["Rust 特性使用得不错 🦀", "代码很 Rust 化 ⚡"]
                     }
                 }
+                "文档" => {
+                    if score >= 80.0 {
+                        vec![
+                            "公开 API 的文档比沙漠里的水还稀缺 🏜️",
+                            "cargo doc 生成出来的页面干净得像一张白纸 📄",
+                            "文档写的都是函数名的复读 🦜",
+                            "示例代码连编译器都看不懂 🤯",
+                        ]
+                    } else if score >= 60.0 {
+                        vec![
+                            "文档还有不少欠账 📚",
+                            "补几行 /// 吧，未来的你会感谢现在的你 🙏",
+                            "# Errors 和 # Panics 章节别忘了 📝",
+                        ]
+                    } else {
+                        vec!["文档写得挺用心 📚", "公开 API 都有说明 ✅"]
+                    }
+                }
                 _ => vec!["代码需要改进 🔧"],
             }
         } else {
@@ -349,6 +369,27 @@ impl Reporter {
                         ]
                     }
                 }
+                "Documentation" => {
+                    if score >= 80.0 {
+                        vec![
+                            "Public API docs are scarcer than water in a desert 🏜️",
+                            "cargo doc renders a page as blank as a fresh notebook 📄",
+                            "The docs just parrot the function names 🦜",
+                            "Even the compiler can't read these examples 🤯",
+                        ]
+                    } else if score >= 60.0 {
+                        vec![
+                            "There's some documentation debt to pay off 📚",
+                            "Add a few /// lines, future you will be grateful 🙏",
+                            "Don't forget the # Errors and # Panics sections 📝",
+                        ]
+                    } else {
+                        vec![
+                            "Documentation is in good shape 📚",
+                            "Public API is well documented ✅",
+                        ]
+                    }
+                }
                 _ => vec!["Code needs improvement 🔧"],
             }
         }
@@ -356,21 +397,24 @@ impl Reporter {
 
     #[allow(dead_code)]
     pub fn report(&self, issues: Vec<CodeIssue>) {
-        self.report_with_metrics(issues, 1, 100);
+        self.report_with_metrics(issues, 1, 100, 0);
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub fn report_with_enhanced_features(
         &self,
         mut issues: Vec<CodeIssue>,
         file_count: usize,
         total_lines: usize,
+        test_lines: usize,
         educational_advisor: Option<&EducationalAdvisor>,
         hall_of_shame: Option<&HallOfShame>,
         show_suggestions: bool,
     ) {
         // calculate quality score
         let scorer = CodeScorer::new();
-        let quality_score = scorer.calculate_score(&issues, file_count, total_lines);
+        let quality_score =
+            scorer.calculate_score_with_test_lines(&issues, file_count, total_lines, test_lines);
 
         if issues.is_empty() {
             self.print_clean_code_message_with_score(&quality_score);
@@ -437,10 +481,12 @@ impl Reporter {
         mut issues: Vec<CodeIssue>,
         file_count: usize,
         total_lines: usize,
+        test_lines: usize,
     ) {
         // calculate quality score
         let scorer = CodeScorer::new();
-        let quality_score = scorer.calculate_score(&issues, file_count, total_lines);
+        let quality_score =
+            scorer.calculate_score_with_test_lines(&issues, file_count, total_lines, test_lines);
 
         if issues.is_empty() {
             self.print_clean_code_message_with_score(&quality_score);
@@ -481,6 +527,264 @@ impl Reporter {
         }
     }
 
+    /// --unsafe-audit：按文件列出所有 unsafe 代码，给安全审查用
+    pub fn report_unsafe_inventory(&self, inventories: &[UnsafeInventory]) {
+        if self.markdown {
+            self.print_markdown_unsafe_inventory(inventories);
+            return;
+        }
+
+        let zh = self.i18n.lang == "zh-CN";
+        let title = if zh {
+            "🔐 Unsafe 代码清单"
+        } else {
+            "🔐 Unsafe Inventory"
+        };
+        println!("{}", title.bright_red().bold());
+        println!("{}", "─".repeat(50).bright_black());
+
+        if inventories.is_empty() {
+            if zh {
+                println!("{}", "没有发现 unsafe 代码 🎉".bright_green());
+            } else {
+                println!("{}", "No unsafe code found 🎉".bright_green());
+            }
+            return;
+        }
+
+        for inventory in inventories {
+            println!();
+            println!(
+                "📁 {} ({})",
+                inventory
+                    .file_path
+                    .display()
+                    .to_string()
+                    .bright_blue()
+                    .bold(),
+                self.unsafe_kind_counts(inventory)
+            );
+            for site in &inventory.sites {
+                let status = match site.documented {
+                    Some(true) => "✓ documented".green(),
+                    Some(false) => "✗ undocumented".red(),
+                    None => "".normal(),
+                };
+                println!(
+                    "   {:>5}  {:<15} {:<30} {}",
+                    format!("L{}", site.line).bright_black(),
+                    site.kind.label().yellow(),
+                    site.item.as_deref().unwrap_or("-"),
+                    status
+                );
+            }
+        }
+
+        let sites: usize = inventories.iter().map(|i| i.sites.len()).sum();
+        let undocumented: usize = inventories.iter().map(|i| i.undocumented()).sum();
+        println!();
+        if zh {
+            println!(
+                "共 {} 个文件，{} 处 unsafe，其中 {} 处缺少 SAFETY 说明",
+                inventories.len(),
+                sites,
+                undocumented.to_string().red()
+            );
+        } else {
+            println!(
+                "{} files, {} unsafe sites, {} without a SAFETY justification",
+                inventories.len(),
+                sites,
+                undocumented.to_string().red()
+            );
+        }
+    }
+
+    fn print_markdown_unsafe_inventory(&self, inventories: &[UnsafeInventory]) {
+        println!("# Unsafe Inventory");
+        println!();
+
+        for inventory in inventories {
+            println!("## {}", inventory.file_path.display());
+            println!();
+            println!("{}", self.unsafe_kind_counts(inventory));
+            println!();
+            println!("| Line | Kind | Item | Documented |");
+            println!("| --- | --- | --- | --- |");
+            for site in &inventory.sites {
+                let documented = match site.documented {
+                    Some(true) => "✅",
+                    Some(false) => "❌",
+                    None => "-",
+                };
+                println!(
+                    "| {} | {} | {} | {} |",
+                    site.line,
+                    site.kind.label(),
+                    site.item.as_deref().unwrap_or("-"),
+                    documented
+                );
+            }
+            println!();
+        }
+
+        let sites: usize = inventories.iter().map(|i| i.sites.len()).sum();
+        let undocumented: usize = inventories.iter().map(|i| i.undocumented()).sum();
+        println!(
+            "**Total**: {} files, {} unsafe sites, {} undocumented",
+            inventories.len(),
+            sites,
+            undocumented
+        );
+    }
+
+    fn unsafe_kind_counts(&self, inventory: &UnsafeInventory) -> String {
+        [
+            UnsafeKind::Block,
+            UnsafeKind::Fn,
+            UnsafeKind::Impl,
+            UnsafeKind::Trait,
+            UnsafeKind::StaticMut,
+            UnsafeKind::Transmute,
+            UnsafeKind::FromRawParts,
+        ]
+        .iter()
+        .filter_map(|&kind| match inventory.count(kind) {
+            0 => None,
+            count => Some(format!("{}: {}", kind.label(), count)),
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+    }
+
+    /// --todos：按文件列出所有待办事项和负责人、工单、期限
+    pub fn report_todo_inventory(&self, inventories: &[TodoInventory]) {
+        let today = TodoDate::today();
+        if self.markdown {
+            self.print_markdown_todo_inventory(inventories, today);
+            return;
+        }
+
+        let zh = self.i18n.lang == "zh-CN";
+        let title = if zh {
+            "📋 待办清单"
+        } else {
+            "📋 TODO Inventory"
+        };
+        println!("{}", title.bright_yellow().bold());
+        println!("{}", "─".repeat(50).bright_black());
+
+        if inventories.is_empty() {
+            if zh {
+                println!("{}", "没有发现 TODO，干得漂亮 🎉".bright_green());
+            } else {
+                println!("{}", "No TODOs found 🎉".bright_green());
+            }
+            return;
+        }
+
+        for inventory in inventories {
+            println!();
+            println!(
+                "📁 {} ({})",
+                inventory
+                    .file_path
+                    .display()
+                    .to_string()
+                    .bright_blue()
+                    .bold(),
+                inventory.items.len()
+            );
+            for item in &inventory.items {
+                let status = if item.is_overdue(today) {
+                    "✗ overdue".red()
+                } else if !item.kind.is_macro() && !item.is_tracked() {
+                    "✗ no ticket".yellow()
+                } else {
+                    "".normal()
+                };
+                println!(
+                    "   {:>5}  {:<16} {:<12} {:<16} {:<10}  {} {}",
+                    format!("L{}", item.line).bright_black(),
+                    item.kind.label().yellow(),
+                    item.owner.as_deref().unwrap_or("-"),
+                    item.ticket.as_deref().unwrap_or("-"),
+                    Self::todo_due(item),
+                    item.text,
+                    status
+                );
+            }
+        }
+
+        let items: usize = inventories.iter().map(|i| i.items.len()).sum();
+        let untracked: usize = inventories.iter().map(|i| i.untracked()).sum();
+        let overdue: usize = inventories.iter().map(|i| i.overdue(today)).sum();
+        println!();
+        if zh {
+            println!(
+                "共 {} 个文件，{} 条待办，{} 条没有工单，{} 条已过期",
+                inventories.len(),
+                items,
+                untracked.to_string().yellow(),
+                overdue.to_string().red()
+            );
+        } else {
+            println!(
+                "{} files, {} items, {} without a ticket, {} overdue",
+                inventories.len(),
+                items,
+                untracked.to_string().yellow(),
+                overdue.to_string().red()
+            );
+        }
+    }
+
+    fn print_markdown_todo_inventory(&self, inventories: &[TodoInventory], today: TodoDate) {
+        println!("# TODO Inventory");
+        println!();
+
+        for inventory in inventories {
+            println!("## {}", inventory.file_path.display());
+            println!();
+            println!("| Line | Kind | Owner | Ticket | Due | Text |");
+            println!("| --- | --- | --- | --- | --- | --- |");
+            for item in &inventory.items {
+                let due = if item.is_overdue(today) {
+                    format!("⚠️ {}", Self::todo_due(item))
+                } else {
+                    Self::todo_due(item)
+                };
+                println!(
+                    "| {} | {} | {} | {} | {} | {} |",
+                    item.line,
+                    item.kind.label(),
+                    item.owner.as_deref().unwrap_or("-"),
+                    item.ticket.as_deref().unwrap_or("-"),
+                    due,
+                    item.text.replace('|', "\\|")
+                );
+            }
+            println!();
+        }
+
+        let items: usize = inventories.iter().map(|i| i.items.len()).sum();
+        let untracked: usize = inventories.iter().map(|i| i.untracked()).sum();
+        let overdue: usize = inventories.iter().map(|i| i.overdue(today)).sum();
+        println!(
+            "**Total**: {} files, {} items, {} without a ticket, {} overdue",
+            inventories.len(),
+            items,
+            untracked,
+            overdue
+        );
+    }
+
+    fn todo_due(item: &TodoItem) -> String {
+        item.due
+            .map(|due| due.to_string())
+            .unwrap_or_else(|| "-".to_string())
+    }
+
     #[allow(dead_code)]
     fn print_clean_code_message(&self) {
         if self.markdown {
@@ -577,6 +881,19 @@ impl Reporter {
                 .bold()
         );
 
+        if let Some(test_score) = quality_score.test_score {
+            let test_label = match self.i18n.lang.as_str() {
+                "zh-CN" => "🧪 测试代码",
+                _ => "🧪 Test Code",
+            };
+            println!(
+                "   {}: {:.1}/100 {}",
+                test_label,
+                test_score,
+                QualityLevel::from_score(test_score).emoji()
+            );
+        }
+
         if quality_score.total_lines > 0 {
             let (lines_label, files_label, density_label) = match self.i18n.lang.as_str() {
                 "zh-CN" => ("📏 代码行数", "📁 文件数量", "🔍 问题密度"),
@@ -673,6 +990,7 @@ impl Reporter {
                     ("zh-CN", "rust-features") => "Rust功能",
                     ("zh-CN", "structure") => "代码结构",
                     ("zh-CN", "duplication") => "重复代码",
+                    ("zh-CN", "documentation") => "文档",
                     (_, "naming") => "Naming",
                     (_, "complexity") => "Complexity",
                     (_, "rust-basics") => "Rust Basics",
@@ -680,6 +998,7 @@ impl Reporter {
                     (_, "rust-features") => "Rust Features",
                     (_, "structure") => "Code Structure",
                     (_, "duplication") => "Code Duplication",
+                    (_, "documentation") => "Documentation",
                     _ => category,
                 };
                 println!(
@@ -950,6 +1269,8 @@ impl Reporter {
                         ("zh-CN", "match-abuse") => "Match 滥用",
                         ("zh-CN", "hungarian-notation") => "匈牙利命名法",
                         ("zh-CN", "abbreviation-abuse") => "过度缩写",
+                        ("zh-CN", "pinyin-naming") => "拼音命名",
+                        ("zh-CN", "identifier-typo") => "拼写错误",
                         ("zh-CN", "meaningless-naming") => "无意义命名",
                         ("zh-CN", "commented-code") => "被注释代码",
                         ("zh-CN", "dead-code") => "死代码",
@@ -1017,7 +1338,16 @@ impl Reporter {
                 Severity::Mild => "😐",
             };
 
-            let line_info = format!("{}:{}", issue.line, issue.column);
+            let line_info = if issue.context == CodeContext::Production {
+                format!("{}:{}", issue.line, issue.column)
+            } else {
+                format!(
+                    "{}:{} [{}]",
+                    issue.line,
+                    issue.column,
+                    issue.context.description(&self.i18n.lang)
+                )
+            };
             let colored_message = match issue.severity {
                 Severity::Nuclear => issue.message.red().bold(),
                 Severity::Spicy => issue.message.yellow(),
@@ -1184,6 +1514,7 @@ impl Reporter {
             ("advanced-rust", "高级特性", "Advanced Rust", "⚡"),
             ("rust-features", "Rust功能", "Rust Features", "🚀"),
             ("structure", "代码结构", "Code Structure", "🏗️"),
+            ("documentation", "文档", "Documentation", "📚"),
         ];
 
         for (category_key, zh_name, en_name, icon) in &categories {
@@ -1276,10 +1607,12 @@ impl Reporter {
                     ("zh-CN", "complexity") => "🧩 将复杂函数分解为更小、更专注的函数",
                     ("zh-CN", "duplication") => "🔄 消除重复代码，提取公共函数和模块",
                     ("zh-CN", "rust-features") => "🦀 学习和应用 Rust 最佳实践，减少不必要的分配",
+                    ("zh-CN", "documentation") => "📚 给公开 API 补上文档，返回 Result 的函数写清楚 # Errors",
                     (_, "naming") => "🏷️ Focus on improving variable and function naming - clear names make code self-documenting",
                     (_, "complexity") => "🧩 Break down complex functions into smaller, focused functions",
                     (_, "duplication") => "🔄 Eliminate code duplication, extract common functions and modules",
                     (_, "rust-features") => "🦀 Learn and apply Rust best practices, reduce unnecessary allocations",
+                    (_, "documentation") => "📚 Document the public API and spell out # Errors for functions returning Result",
                     _ => continue,
                 };
                 suggestions.push(suggestion.to_string());
@@ -1539,6 +1872,7 @@ impl Reporter {
             ("advanced-rust", "高级特性", "Advanced Rust", "⚡"),
             ("rust-features", "Rust功能", "Rust Features", "🚀"),
             ("structure", "代码结构", "Code Structure", "🏗️"),
+            ("documentation", "文档", "Documentation", "📚"),
         ];
 
         for (category_key, zh_name, en_name, icon) in &categories {
@@ -1629,6 +1963,7 @@ impl Reporter {
                 "complexity" => "复杂度",
                 "duplication" => "代码重复",
                 "rust-features" => "Rust功能",
+                "documentation" => "文档",
                 _ => category,
             }
         } else {
@@ -1637,6 +1972,7 @@ impl Reporter {
                 "complexity" => "Complexity",
                 "duplication" => "Duplication",
                 "rust-features" => "Rust Features",
+                "documentation" => "Documentation",
                 _ => category,
             }
         };
@@ -1671,13 +2007,14 @@ impl Reporter {
 
         // Show the calculation formula
         let weights = [
-            ("naming", 0.25, "命名规范", "Naming"),
+            ("naming", 0.20, "命名规范", "Naming"),
             ("complexity", 0.20, "复杂度", "Complexity"),
             ("duplication", 0.15, "代码重复", "Duplication"),
             ("rust-basics", 0.15, "Rust基础", "Rust Basics"),
             ("advanced-rust", 0.10, "高级特性", "Advanced Rust"),
             ("rust-features", 0.10, "Rust功能", "Rust Features"),
             ("structure", 0.05, "代码结构", "Code Structure"),
+            ("documentation", 0.05, "文档", "Documentation"),
         ];
 
         let mut calculation_parts = Vec::new();
@@ -1817,7 +2154,7 @@ impl Reporter {
         }
 
         let mut sorted_files: Vec<_> = file_issue_counts.into_iter().collect();
-        sorted_files.sort_by(|a, b| b.1.cmp(&a.1));
+        sorted_files.sort_by_key(|b| std::cmp::Reverse(b.1));
 
         if !sorted_files.is_empty() {
             println!("{}", self.i18n.get("top_files").bright_yellow().bold());
@@ -1881,6 +2218,9 @@ impl Reporter {
                 match rule_name_str {
                     "terrible-naming" => "糟糕的变量命名",
                     "single-letter-variable" => "单字母变量",
+                    "naming-convention" => "命名惯例",
+                    "pinyin-naming" => "拼音命名",
+                    "identifier-typo" => "拼写错误",
                     "deep-nesting" => "过度嵌套",
                     "long-function" => "超长函数",
                     "unwrap-abuse" => "unwrap() 滥用",
@@ -2075,7 +2415,7 @@ impl Reporter {
 
             // Sort rule groups by count (most frequent first)
             let mut sorted_rules: Vec<_> = rule_groups.into_iter().collect();
-            sorted_rules.sort_by(|a, b| b.1.len().cmp(&a.1.len()));
+            sorted_rules.sort_by_key(|b| std::cmp::Reverse(b.1.len()));
 
             // Display grouped issues with counts and examples
             for (rule_name, rule_issues) in sorted_rules {
@@ -2168,6 +2508,14 @@ impl Reporter {
             name if name.contains("duplication") => "🔄",
             name if name.contains("function") => "⚠️",
             name if name.contains("unwrap") => "🛡️",
+            name if name.contains("error") => "🧯",
+            name if name.contains("doc") => "📚",
+            "god-struct" | "option-soup" | "data-clump" | "primitive-obsession" | "huge-impl" => {
+                "🧱"
+            }
+            name if name.contains("async") || name.contains("lock") || name.contains("channel") => {
+                "🔒"
+            }
             name if name.contains("string") => "📝",
             name if name.contains("println") => "🔍",
             name if name.contains("magic") => "🔢",
@@ -2210,12 +2558,35 @@ impl Reporter {
             "unnecessary-clone" => "不必要克隆".to_string(),
             "channel-abuse" => "通道滥用".to_string(),
             "async-abuse" => "异步滥用".to_string(),
+            "lock-abuse" => "锁滥用".to_string(),
+            "owned-ref-param" => "&String/&Vec参数".to_string(),
+            "bool-param-soup" => "bool参数汤".to_string(),
+            "leaky-struct" => "字段全公开的结构体".to_string(),
+            "missing-non-exhaustive" => "缺少non_exhaustive".to_string(),
+            "missing-debug" => "缺少Debug".to_string(),
+            "leaky-impl-trait" => "泄露的impl Trait".to_string(),
+            "missing-docs" => "缺少文档".to_string(),
+            "parrot-docs" => "复读机文档".to_string(),
+            "broken-doc-example" => "失效的文档示例".to_string(),
+            "missing-doc-sections" => "缺少文档章节".to_string(),
+            "naming-convention" => "命名惯例".to_string(),
+            "pinyin-naming" => "拼音命名".to_string(),
+            "identifier-typo" => "拼写错误".to_string(),
+            "god-struct" => "上帝结构体".to_string(),
+            "option-soup" => "Option汤".to_string(),
+            "data-clump" => "数据泥团".to_string(),
+            "primitive-obsession" => "基本类型偏执".to_string(),
+            "huge-impl" => "巨型impl".to_string(),
             "dyn-trait-abuse" => "动态trait滥用".to_string(),
             "unsafe-abuse" => "unsafe滥用".to_string(),
             "ffi-abuse" => "FFI滥用".to_string(),
             "pattern-matching-abuse" => "模式匹配滥用".to_string(),
             "reference-abuse" => "引用滥用".to_string(),
             "box-abuse" => "Box滥用".to_string(),
+            "stringly-error" => "字符串错误类型".to_string(),
+            "boxed-error" => "Box<dyn Error>滥用".to_string(),
+            "swallowed-error" => "吞掉的错误".to_string(),
+            "incomplete-error-type" => "不完整的错误类型".to_string(),
             "slice-abuse" => "切片滥用".to_string(),
             "module-complexity" => "模块复杂度".to_string(),
             _ => rule_name.replace("-", " "),
@@ -2385,12 +2756,38 @@ impl Reporter {
             "deep-nesting" => "深层嵌套".to_string(),
             "unwrap-abuse" => "unwrap滥用".to_string(),
             "string-abuse" => "字符串滥用".to_string(),
+            "vec-abuse" => "Vec滥用".to_string(),
             "println-debugging" => "println调试".to_string(),
             "long-function" => "过长函数".to_string(),
             "god-function" => "上帝函数".to_string(),
             "file-too-long" => "文件过长".to_string(),
             "unordered-imports" => "导入混乱".to_string(),
             "deep-module-nesting" => "模块嵌套过深".to_string(),
+            "stringly-error" => "字符串错误类型".to_string(),
+            "boxed-error" => "Box<dyn Error>滥用".to_string(),
+            "swallowed-error" => "吞掉的错误".to_string(),
+            "incomplete-error-type" => "不完整的错误类型".to_string(),
+            "channel-abuse" => "通道滥用".to_string(),
+            "async-abuse" => "异步滥用".to_string(),
+            "lock-abuse" => "锁滥用".to_string(),
+            "owned-ref-param" => "&String/&Vec参数".to_string(),
+            "bool-param-soup" => "bool参数汤".to_string(),
+            "leaky-struct" => "字段全公开的结构体".to_string(),
+            "missing-non-exhaustive" => "缺少non_exhaustive".to_string(),
+            "missing-debug" => "缺少Debug".to_string(),
+            "leaky-impl-trait" => "泄露的impl Trait".to_string(),
+            "missing-docs" => "缺少文档".to_string(),
+            "parrot-docs" => "复读机文档".to_string(),
+            "broken-doc-example" => "失效的文档示例".to_string(),
+            "missing-doc-sections" => "缺少文档章节".to_string(),
+            "naming-convention" => "命名惯例".to_string(),
+            "pinyin-naming" => "拼音命名".to_string(),
+            "identifier-typo" => "拼写错误".to_string(),
+            "god-struct" => "上帝结构体".to_string(),
+            "option-soup" => "Option汤".to_string(),
+            "data-clump" => "数据泥团".to_string(),
+            "primitive-obsession" => "基本类型偏执".to_string(),
+            "huge-impl" => "巨型impl".to_string(),
             _ => rule_name.to_string(),
         }
     }
@@ -1,8 +1,19 @@
+use proc_macro2::{TokenStream, TokenTree};
+use quote::ToTokens;
+use std::collections::HashSet;
 use std::path::Path;
-use syn::{visit::Visit, ExprClosure, File, GenericParam, ItemImpl, ItemTrait, Lifetime};
+use syn::{
+    punctuated::Punctuated, spanned::Spanned, visit::Visit, Block, ExprClosure, File, FnArg,
+    GenericArgument, GenericParam, Generics, ImplItemFn, ItemFn, ItemImpl, ItemStruct, ItemTrait,
+    Lifetime, Macro, Pat, PatType, PathArguments, ReturnType, Signature, Stmt, Token, TraitItemFn,
+    Type, TypeParamBound, TypePath, TypeReference, WherePredicate,
+};
 
-use crate::analyzer::{CodeIssue, RoastLevel, Severity};
+use crate::analyzer::{CodeContext, CodeIssue, RoastLevel, Severity};
+use crate::rules::common::{is_public, rule_issue};
+use crate::rules::macro_args::visit_macro_args;
 use crate::rules::Rule;
+use crate::utils::{get_position, get_source_text};
 
 pub struct ComplexClosureRule;
 
@@ -24,6 +35,8 @@ impl Rule for ComplexClosureRule {
     }
 }
 
+/// 检测多余和过度约束的生命周期：省略规则能推出来的、只出现一次的、只出现在返回值里的、
+/// 为了让借用检查器闭嘴加的 'static、能合并的结构体生命周期参数、impl 里的 &'a mut self
 pub struct LifetimeAbuseRule;
 
 impl Rule for LifetimeAbuseRule {
@@ -35,10 +48,10 @@ impl Rule for LifetimeAbuseRule {
         &self,
         file_path: &Path,
         syntax_tree: &File,
-        _content: &str,
-        _lang: &str,
+        content: &str,
+        lang: &str,
     ) -> Vec<CodeIssue> {
-        let mut visitor = LifetimeVisitor::new(file_path.to_path_buf());
+        let mut visitor = LifetimeVisitor::new(file_path.to_path_buf(), content, lang);
         visitor.visit_file(syntax_tree);
         visitor.issues
     }
@@ -64,6 +77,8 @@ impl Rule for TraitComplexityRule {
     }
 }
 
+/// 检测没用上的和约束放错地方的泛型：没用到的类型参数、只出现在一个参数里的类型参数（用 impl Trait 更清楚）、
+/// 反复出现的约束组合、结构体定义上的 trait 约束、只为一次转换而泛型化的大函数
 pub struct GenericAbuseRule;
 
 impl Rule for GenericAbuseRule {
@@ -75,10 +90,12 @@ impl Rule for GenericAbuseRule {
         &self,
         file_path: &Path,
         syntax_tree: &File,
-        _content: &str,
-        _lang: &str,
+        content: &str,
+        lang: &str,
     ) -> Vec<CodeIssue> {
-        let mut visitor = GenericVisitor::new(file_path.to_path_buf());
+        let mut drop_impls = DropImpls::default();
+        drop_impls.visit_file(syntax_tree);
+        let mut visitor = GenericVisitor::new(file_path.to_path_buf(), content, lang, drop_impls.0);
         visitor.visit_file(syntax_tree);
         visitor.issues
     }
@@ -117,6 +134,7 @@ impl ClosureVisitor {
                 message: messages[self.issues.len() % messages.len()].to_string(),
                 severity: Severity::Spicy,
                 roast_level: RoastLevel::Sarcastic,
+                context: CodeContext::Production,
             });
         }
 
@@ -136,12 +154,17 @@ impl ClosureVisitor {
                 message: messages[self.issues.len() % messages.len()].to_string(),
                 severity: Severity::Mild,
                 roast_level: RoastLevel::Gentle,
+                context: CodeContext::Production,
             });
         }
     }
 }
 
 impl<'ast> Visit<'ast> for ClosureVisitor {
+    fn visit_macro(&mut self, mac: &'ast Macro) {
+        visit_macro_args(self, mac);
+    }
+
     fn visit_expr_closure(&mut self, closure: &'ast ExprClosure) {
         self.closure_depth += 1;
         self.check_closure_complexity(closure);
@@ -150,47 +173,789 @@ impl<'ast> Visit<'ast> for ClosureVisitor {
     }
 }
 
-struct LifetimeVisitor {
+struct LifetimeVisitor<'a> {
     file_path: std::path::PathBuf,
+    content: &'a str,
     issues: Vec<CodeIssue>,
-    lifetime_count: usize,
+    lang: String,
+    /// 当前 impl 块声明、并且出现在 self 类型里的生命周期
+    self_lifetimes: Vec<String>,
+    in_trait_impl: bool,
 }
 
-impl LifetimeVisitor {
-    fn new(file_path: std::path::PathBuf) -> Self {
+/// 签名里一个生命周期出现的位置
+struct LifetimeSlot {
+    /// None 表示省略了生命周期的引用
+    name: Option<String>,
+    /// `&'a T` 的引用生命周期，不在 &mut 后面也不在泛型参数里，协变，可以随便缩短
+    plain_ref: bool,
+    /// `&'a T` 的引用生命周期，省略时什么都不用写；其他位置要写 '_
+    is_ref: bool,
+    /// 参数位置的 impl Trait 里不能写 '_
+    in_impl_trait: bool,
+}
+
+impl LifetimeSlot {
+    fn is(&self, name: &str) -> bool {
+        self.name.as_deref() == Some(name)
+    }
+}
+
+/// 收集一个类型里所有的生命周期位置，fn 指针和 Fn(..) 里的省略引用属于它们自己的作用域，不计入
+#[derive(Default)]
+struct SlotCollector {
+    slots: Vec<LifetimeSlot>,
+    mut_depth: usize,
+    path_depth: usize,
+    fn_depth: usize,
+    impl_depth: usize,
+}
+
+impl<'ast> Visit<'ast> for SlotCollector {
+    fn visit_type_reference(&mut self, reference: &'ast TypeReference) {
+        let name = reference.lifetime.as_ref().map(|l| l.ident.to_string());
+        if self.fn_depth == 0 || name.is_some() {
+            self.slots.push(LifetimeSlot {
+                name,
+                plain_ref: self.fn_depth == 0 && self.mut_depth == 0 && self.path_depth == 0,
+                is_ref: self.fn_depth == 0,
+                in_impl_trait: self.impl_depth > 0,
+            });
+        }
+        let mutable = reference.mutability.is_some();
+        self.mut_depth += usize::from(mutable);
+        self.visit_type(&reference.elem);
+        self.mut_depth -= usize::from(mutable);
+    }
+
+    fn visit_lifetime(&mut self, lifetime: &'ast Lifetime) {
+        self.slots.push(LifetimeSlot {
+            name: Some(lifetime.ident.to_string()),
+            plain_ref: false,
+            is_ref: false,
+            in_impl_trait: self.impl_depth > 0,
+        });
+    }
+
+    fn visit_angle_bracketed_generic_arguments(
+        &mut self,
+        args: &'ast syn::AngleBracketedGenericArguments,
+    ) {
+        self.path_depth += 1;
+        syn::visit::visit_angle_bracketed_generic_arguments(self, args);
+        self.path_depth -= 1;
+    }
+
+    fn visit_type_impl_trait(&mut self, impl_trait: &'ast syn::TypeImplTrait) {
+        self.impl_depth += 1;
+        syn::visit::visit_type_impl_trait(self, impl_trait);
+        self.impl_depth -= 1;
+    }
+
+    fn visit_type_bare_fn(&mut self, bare_fn: &'ast syn::TypeBareFn) {
+        self.fn_depth += 1;
+        syn::visit::visit_type_bare_fn(self, bare_fn);
+        self.fn_depth -= 1;
+    }
+
+    fn visit_parenthesized_generic_arguments(
+        &mut self,
+        args: &'ast syn::ParenthesizedGenericArguments,
+    ) {
+        self.fn_depth += 1;
+        syn::visit::visit_parenthesized_generic_arguments(self, args);
+        self.fn_depth -= 1;
+    }
+}
+
+/// 一段语法里出现过的所有生命周期名字
+#[derive(Default)]
+struct LifetimeNames(HashSet<String>);
+
+impl<'ast> Visit<'ast> for LifetimeNames {
+    fn visit_lifetime(&mut self, lifetime: &'ast Lifetime) {
+        self.0.insert(lifetime.ident.to_string());
+    }
+
+    fn visit_macro(&mut self, mac: &'ast Macro) {
+        visit_macro_args(self, mac);
+    }
+}
+
+/// 类型里出现过的单段路径名，`Vec<T>` 得到 `Vec` 和 `T`
+#[derive(Default)]
+struct TypeNames(HashSet<String>);
+
+impl<'ast> Visit<'ast> for TypeNames {
+    fn visit_path(&mut self, path: &'ast syn::Path) {
+        if let Some(ident) = path.get_ident() {
+            self.0.insert(ident.to_string());
+        }
+        syn::visit::visit_path(self, path);
+    }
+}
+
+fn type_slots(ty: &Type) -> Vec<LifetimeSlot> {
+    let mut collector = SlotCollector::default();
+    collector.visit_type(ty);
+    collector.slots
+}
+
+/// 泛型参数的约束和 where 子句里提到的生命周期：被约束的生命周期不能随便省略
+fn bounded_lifetimes(generics: &Generics) -> HashSet<String> {
+    let mut names = LifetimeNames::default();
+    for param in &generics.params {
+        match param {
+            GenericParam::Lifetime(def) if !def.bounds.is_empty() => {
+                names.0.insert(def.lifetime.ident.to_string());
+                for bound in &def.bounds {
+                    names.visit_lifetime(bound);
+                }
+            }
+            GenericParam::Type(type_param) => {
+                for bound in &type_param.bounds {
+                    names.visit_type_param_bound(bound);
+                }
+            }
+            _ => {}
+        }
+    }
+    if let Some(where_clause) = &generics.where_clause {
+        names.visit_where_clause(where_clause);
+    }
+    names.0
+}
+
+/// 只有放进 'static 容器才需要 'static 的方法：spawn 出去、downcast、leak
+const NEEDS_STATIC_METHODS: &[&str] = &[
+    "spawn",
+    "spawn_local",
+    "spawn_blocking",
+    "type_id",
+    "downcast",
+    "downcast_ref",
+    "downcast_mut",
+    "leak",
+];
+
+/// 在函数体（和返回类型）里找真正需要 'static 的东西：spawn、Any/TypeId、dyn Trait、
+/// 'static 生命周期、unsafe，以及把带约束类型的值交给别的函数——对方可能就要求 'static
+struct StaticUse {
+    /// 类型里带有 'static 约束类型参数的函数参数
+    values: HashSet<String>,
+    needed: bool,
+}
+
+impl StaticUse {
+    fn mentions_value(&self, expr: &syn::Expr) -> bool {
+        let mut mentions = ValueMentions {
+            values: &self.values,
+            found: false,
+        };
+        mentions.visit_expr(expr);
+        mentions.found
+    }
+}
+
+/// 表达式里有没有直接用到这些变量
+struct ValueMentions<'v> {
+    values: &'v HashSet<String>,
+    found: bool,
+}
+
+impl<'ast> Visit<'ast> for ValueMentions<'_> {
+    fn visit_expr_path(&mut self, path: &'ast syn::ExprPath) {
+        if path
+            .path
+            .get_ident()
+            .is_some_and(|ident| self.values.contains(&ident.to_string()))
+        {
+            self.found = true;
+        }
+    }
+
+    fn visit_macro(&mut self, mac: &'ast Macro) {
+        visit_macro_args(self, mac);
+    }
+}
+
+impl<'ast> Visit<'ast> for StaticUse {
+    fn visit_expr_call(&mut self, call: &'ast syn::ExprCall) {
+        if let syn::Expr::Path(func) = &*call.func {
+            if func.path.segments.last().is_some_and(|segment| {
+                NEEDS_STATIC_METHODS.contains(&segment.ident.to_string().as_str())
+            }) {
+                self.needed = true;
+            }
+        }
+        if call.args.iter().any(|arg| self.mentions_value(arg)) {
+            self.needed = true;
+        }
+        syn::visit::visit_expr_call(self, call);
+    }
+
+    fn visit_expr_method_call(&mut self, call: &'ast syn::ExprMethodCall) {
+        if NEEDS_STATIC_METHODS.contains(&call.method.to_string().as_str())
+            || call.args.iter().any(|arg| self.mentions_value(arg))
+        {
+            self.needed = true;
+        }
+        syn::visit::visit_expr_method_call(self, call);
+    }
+
+    fn visit_expr_struct(&mut self, expr: &'ast syn::ExprStruct) {
+        if expr
+            .fields
+            .iter()
+            .any(|field| self.mentions_value(&field.expr))
+        {
+            self.needed = true;
+        }
+        syn::visit::visit_expr_struct(self, expr);
+    }
+
+    fn visit_path(&mut self, path: &'ast syn::Path) {
+        if path
+            .segments
+            .iter()
+            .any(|segment| segment.ident == "Any" || segment.ident == "TypeId")
+        {
+            self.needed = true;
+        }
+        syn::visit::visit_path(self, path);
+    }
+
+    fn visit_type_trait_object(&mut self, _: &'ast syn::TypeTraitObject) {
+        self.needed = true;
+    }
+
+    fn visit_type_impl_trait(&mut self, _: &'ast syn::TypeImplTrait) {
+        self.needed = true;
+    }
+
+    fn visit_lifetime(&mut self, lifetime: &'ast Lifetime) {
+        if lifetime.ident == "static" {
+            self.needed = true;
+        }
+    }
+
+    fn visit_expr_unsafe(&mut self, _: &'ast syn::ExprUnsafe) {
+        self.needed = true;
+    }
+
+    fn visit_item_static(&mut self, _: &'ast syn::ItemStatic) {
+        self.needed = true;
+    }
+
+    fn visit_macro(&mut self, mac: &'ast Macro) {
+        visit_macro_args(self, mac);
+    }
+}
+
+/// 需要 'static 才有意义的约束
+fn has_static_companion(bounds: &Punctuated<TypeParamBound, Token![+]>) -> bool {
+    bounds.iter().any(|bound| match bound {
+        TypeParamBound::Trait(bound) => bound.path.segments.last().is_some_and(|segment| {
+            matches!(
+                segment.ident.to_string().as_str(),
+                "Any" | "Send" | "Sync" | "Error" | "Future"
+            )
+        }),
+        _ => false,
+    })
+}
+
+impl<'a> LifetimeVisitor<'a> {
+    fn new(file_path: std::path::PathBuf, content: &'a str, lang: &str) -> Self {
         Self {
             file_path,
+            content,
             issues: Vec::new(),
-            lifetime_count: 0,
+            lang: lang.to_string(),
+            self_lifetimes: Vec::new(),
+            in_trait_impl: false,
+        }
+    }
+
+    fn text<T: Spanned>(&self, node: &T) -> String {
+        get_source_text(self.content, node).unwrap_or_else(|| "..".to_string())
+    }
+
+    /// 检查函数自己声明的生命周期：能省略的、只出现一次的、只出现在返回值里的
+    fn check_fn_lifetimes(&mut self, sig: &Signature, block: Option<&Block>) {
+        let mut receiver_lifetime = None;
+        let mut inputs = Vec::new();
+        for arg in &sig.inputs {
+            match arg {
+                FnArg::Receiver(receiver) => match &receiver.reference {
+                    Some((_, lifetime)) => {
+                        let name = lifetime.as_ref().map(|l| l.ident.to_string());
+                        receiver_lifetime.clone_from(&name);
+                        inputs.push(LifetimeSlot {
+                            name,
+                            plain_ref: true,
+                            is_ref: true,
+                            in_impl_trait: false,
+                        });
+                    }
+                    None if receiver.colon_token.is_some() => {
+                        inputs.extend(type_slots(&receiver.ty))
+                    }
+                    None => {}
+                },
+                FnArg::Typed(pat_type) => inputs.extend(type_slots(&pat_type.ty)),
+            }
+        }
+        let outputs = match &sig.output {
+            ReturnType::Type(_, ty) => type_slots(ty),
+            ReturnType::Default => Vec::new(),
+        };
+
+        let bounded = bounded_lifetimes(&sig.generics);
+        let mut in_body = LifetimeNames::default();
+        if let Some(block) = block {
+            in_body.visit_block(block);
+        }
+        let fn_name = &sig.ident;
+
+        for def in sig.generics.lifetimes() {
+            let name = def.lifetime.ident.to_string();
+            if bounded.contains(&name) || in_body.0.contains(&name) {
+                continue;
+            }
+            let uses = |slots: &[LifetimeSlot]| slots.iter().filter(|slot| slot.is(&name)).count();
+            let (ins, outs) = (uses(&inputs), uses(&outputs));
+            if inputs
+                .iter()
+                .any(|slot| slot.in_impl_trait && slot.is(&name))
+            {
+                continue;
+            }
+            let elision_infers = inputs.len() == 1 || receiver_lifetime.as_deref() == Some(&name);
+            let only_refs = inputs
+                .iter()
+                .filter(|slot| slot.is(&name))
+                .all(|slot| slot.plain_ref);
+            let placeholder = inputs
+                .iter()
+                .chain(&outputs)
+                .any(|slot| !slot.is_ref && slot.is(&name));
+
+            let lt = format!("'{name}");
+            let (messages, severity) = match (ins, outs, self.lang.as_str()) {
+                (0, 0, "zh-CN") => (vec![
+                    format!("{fn_name}() 声明了 {lt} 却一次也没用上，删掉"),
+                    format!("{lt} 挂在 {fn_name}() 上当摆设，删掉吧"),
+                ], Severity::Mild),
+                (0, 0, _) => (vec![
+                    format!("{fn_name}() declares {lt} and never uses it - delete it"),
+                    format!("{lt} is just decoration on {fn_name}(), remove it"),
+                ], Severity::Mild),
+                (0, _, "zh-CN") => (vec![
+                    format!("{lt} 只出现在 {fn_name}() 的返回类型里，没有和任何参数关联，调用者要多长就给多长，其实就是 'static；要么直接返回 'static，要么把它绑到参数上"),
+                    format!("{fn_name}() 返回的 {lt} 凭空而来，没有哪个参数能保证它活那么久"),
+                ], Severity::Spicy),
+                (0, _, _) => (vec![
+                    format!("{lt} only appears in {fn_name}()'s return type, tied to no parameter - the caller picks any lifetime, so it's really 'static; return 'static or tie it to an argument"),
+                    format!("the {lt} returned by {fn_name}() comes from nowhere, no parameter guarantees it lives that long"),
+                ], Severity::Spicy),
+                (1, 0, "zh-CN") => {
+                    let fix = if placeholder { "写成 '_" } else { "直接省略" };
+                    (vec![
+                        format!("{lt} 在 {fn_name}() 的签名里只出现一次，什么也没关联，{fix}"),
+                        format!("只用一次的 {lt} 不表达任何约束，{fn_name}() 里{fix}就行"),
+                    ], Severity::Mild)
+                }
+                (1, 0, _) => {
+                    let fix = if placeholder { "write '_" } else { "just elide it" };
+                    (vec![
+                        format!("{lt} appears once in {fn_name}()'s signature and relates nothing - {fix}"),
+                        format!("a {lt} used once expresses no constraint, in {fn_name}() {fix}"),
+                    ], Severity::Mild)
+                }
+                (1, _, "zh-CN") if elision_infers => {
+                    let fix = if placeholder { format!("去掉 <{lt}>，其余位置写 '_") } else { format!("去掉 <{lt}>") };
+                    (vec![
+                        format!("{fn_name}() 上的 {lt} 正是省略规则会推出来的生命周期，{fix}，签名一样成立"),
+                        format!("{fn_name}() 的 {lt} 编译器自己就能推断，手写出来只是噪音，{fix}"),
+                    ], Severity::Mild)
+                }
+                (1, _, _) if elision_infers => {
+                    let fix = if placeholder { format!("drop <{lt}> and write '_ elsewhere") } else { format!("drop <{lt}>") };
+                    (vec![
+                        format!("{lt} on {fn_name}() is exactly what lifetime elision infers - {fix}, the signature means the same"),
+                        format!("the compiler infers {fn_name}()'s {lt} by itself, spelling it out is noise - {fix}"),
+                    ], Severity::Mild)
+                }
+                (_, 0, "zh-CN") if only_refs => (vec![
+                    format!("{lt} 把 {fn_name}() 的 {ins} 个引用参数绑成同一个生命周期，可返回值里用不到它；共享引用本来就能缩短，全部省略即可"),
+                    format!("{fn_name}() 的参数共用 {lt} 没有任何作用，去掉它"),
+                ], Severity::Mild),
+                (_, 0, _) if only_refs => (vec![
+                    format!("{lt} ties {ins} reference params of {fn_name}() together, yet the return type never uses it - references shrink on their own, elide them all"),
+                    format!("sharing {lt} across {fn_name}()'s params achieves nothing, drop it"),
+                ], Severity::Mild),
+                _ => continue,
+            };
+            self.issues.push(rule_issue(
+                &self.file_path,
+                get_position(&def.lifetime),
+                "lifetime-abuse",
+                messages[self.issues.len() % messages.len()].clone(),
+                severity,
+            ));
+        }
+    }
+
+    /// T: 'static 约束，函数体里却没有任何真正需要 'static 的东西
+    fn check_static_bounds(&mut self, sig: &Signature, block: &Block) {
+        let mut bounds: Vec<(&syn::Ident, &Lifetime)> = Vec::new();
+        for type_param in sig.generics.type_params() {
+            bounds.extend(type_param.bounds.iter().filter_map(|bound| match bound {
+                TypeParamBound::Lifetime(lifetime) if lifetime.ident == "static" => {
+                    Some((&type_param.ident, lifetime))
+                }
+                _ => None,
+            }));
+        }
+        // Any、Send 这些约束和 'static 一起出现，说明是有意为之
+        let deliberate: HashSet<String> = sig
+            .generics
+            .type_params()
+            .filter(|param| has_static_companion(&param.bounds))
+            .map(|param| param.ident.to_string())
+            .chain(
+                sig.generics
+                    .where_clause
+                    .iter()
+                    .flat_map(|clause| &clause.predicates)
+                    .filter_map(|predicate| match predicate {
+                        WherePredicate::Type(predicate)
+                            if has_static_companion(&predicate.bounds) =>
+                        {
+                            let bounded = &predicate.bounded_ty;
+                            Some(quote::quote!(#bounded).to_string())
+                        }
+                        _ => None,
+                    }),
+            )
+            .collect();
+        if let Some(where_clause) = &sig.generics.where_clause {
+            for predicate in &where_clause.predicates {
+                let WherePredicate::Type(predicate) = predicate else {
+                    continue;
+                };
+                let Type::Path(path) = &predicate.bounded_ty else {
+                    continue;
+                };
+                // impl 块的类型参数在方法上加约束是在限制这个方法，不算
+                let Some(ident) = path
+                    .path
+                    .get_ident()
+                    .filter(|ident| sig.generics.type_params().any(|p| p.ident == **ident))
+                else {
+                    continue;
+                };
+                bounds.extend(predicate.bounds.iter().filter_map(|bound| match bound {
+                    TypeParamBound::Lifetime(lifetime) if lifetime.ident == "static" => {
+                        Some((ident, lifetime))
+                    }
+                    _ => None,
+                }));
+            }
+        }
+
+        if bounds.is_empty() {
+            return;
+        }
+
+        let bounded: HashSet<String> = bounds.iter().map(|(param, _)| param.to_string()).collect();
+        let values = sig
+            .inputs
+            .iter()
+            .filter_map(|input| match input {
+                FnArg::Typed(PatType { pat, ty, .. }) => Some((pat, ty)),
+                FnArg::Receiver(_) => None,
+            })
+            .filter(|(_, ty)| {
+                let mut names = TypeNames::default();
+                names.visit_type(ty);
+                !names.0.is_disjoint(&bounded)
+            })
+            .filter_map(|(pat, _)| match &**pat {
+                Pat::Ident(pat_ident) => Some(pat_ident.ident.to_string()),
+                _ => None,
+            })
+            .collect();
+        let mut uses = StaticUse {
+            values,
+            needed: false,
+        };
+        uses.visit_block(block);
+        if let ReturnType::Type(_, ty) = &sig.output {
+            uses.visit_type(ty);
+        }
+        if uses.needed {
+            return;
+        }
+
+        let fn_name = &sig.ident;
+        for (param, lifetime) in bounds {
+            if deliberate.contains(&param.to_string()) {
+                continue;
+            }
+            let messages = if self.lang == "zh-CN" {
+                vec![
+                    format!("{fn_name}() 要求 {param}: 'static，可函数体里没有 spawn、Any、Box<dyn ..> 这类真正需要 'static 的地方；是为了让借用检查器闭嘴才加的吗？它会拒绝所有带引用的类型"),
+                    format!("{param}: 'static 在 {fn_name}() 里看不出用处，多半是绕过借用检查器的补丁，去掉试试"),
+                ]
+            } else {
+                vec![
+                    format!("{fn_name}() requires {param}: 'static, but nothing in the body needs it (no spawn, Any or Box<dyn ..>) - was it added to silence the borrow checker? It rejects every type holding a reference"),
+                    format!("{param}: 'static has no visible purpose in {fn_name}(), likely a borrow checker workaround - try removing it"),
+                ]
+            };
+            self.issues.push(rule_issue(
+                &self.file_path,
+                get_position(lifetime),
+                "lifetime-abuse",
+                messages[self.issues.len() % messages.len()].clone(),
+                Severity::Mild,
+            ));
+        }
+    }
+
+    /// impl 块里的 &'a mut self：把 self 借到它自己整个生命周期结束
+    fn check_mut_self(&mut self, sig: &Signature) {
+        let Some(FnArg::Receiver(receiver)) = sig.inputs.first() else {
+            return;
+        };
+        let Some((_, Some(lifetime))) = &receiver.reference else {
+            return;
+        };
+        let name = lifetime.ident.to_string();
+        if receiver.mutability.is_none() || !self.self_lifetimes.contains(&name) {
+            return;
+        }
+
+        let fn_name = &sig.ident;
+        let messages = if self.lang == "zh-CN" {
+            vec![
+                format!("{fn_name}(&'{name} mut self) 把 self 可变借用到 '{name} 结束，也就是这个值自己的整个生命周期，调用一次之后它就再也不能用了；去掉 '{name}，写 &mut self"),
+                format!("&'{name} mut self 里的 '{name} 和类型参数是同一个，{fn_name}() 调用一次就把对象永久借走了"),
+            ]
+        } else {
+            vec![
+                format!("{fn_name}(&'{name} mut self) borrows self mutably until '{name} ends, which is the value's entire lifetime - after one call it is unusable; drop '{name} and take &mut self"),
+                format!("the '{name} in &'{name} mut self is the type's own lifetime parameter, one call to {fn_name}() borrows the value forever"),
+            ]
+        };
+        self.issues.push(rule_issue(
+            &self.file_path,
+            get_position(receiver),
+            "lifetime-abuse",
+            messages[self.issues.len() % messages.len()].clone(),
+            Severity::Spicy,
+        ));
+    }
+
+    /// impl<'a> Foo<'a> 里 'a 只在头部出现一次，写成 impl Foo<'_>
+    fn check_impl_lifetimes(&mut self, item_impl: &ItemImpl) {
+        let bounded = bounded_lifetimes(&item_impl.generics);
+        let mut header = SlotCollector::default();
+        header.visit_type(&item_impl.self_ty);
+        if let Some((_, path, _)) = &item_impl.trait_ {
+            header.visit_path(path);
+        }
+        let mut in_items = LifetimeNames::default();
+        for item in &item_impl.items {
+            in_items.visit_impl_item(item);
         }
+
+        for def in item_impl.generics.lifetimes() {
+            let name = def.lifetime.ident.to_string();
+            let uses = header.slots.iter().filter(|slot| slot.is(&name)).count();
+            if uses != 1 || bounded.contains(&name) || in_items.0.contains(&name) {
+                continue;
+            }
+            let header = match &item_impl.trait_ {
+                Some((_, path, _)) => {
+                    format!("{} for {}", self.text(path), self.text(&item_impl.self_ty))
+                }
+                None => self.text(&item_impl.self_ty),
+            };
+            let fixed = header.replace(&format!("'{name}"), "'_");
+            let messages = if self.lang == "zh-CN" {
+                vec![
+                    format!(
+                        "impl 上的 '{name} 只出现了一次，方法里也没用到，写成 impl {fixed} 就行"
+                    ),
+                    format!("'{name} 在这个 impl 里什么也没关联，用 '_ 代替，少声明一个生命周期"),
+                ]
+            } else {
+                vec![
+                    format!("'{name} on this impl appears once and no method uses it - write impl {fixed}"),
+                    format!("'{name} relates nothing in this impl, use '_ and skip the declaration"),
+                ]
+            };
+            self.issues.push(rule_issue(
+                &self.file_path,
+                get_position(&def.lifetime),
+                "lifetime-abuse",
+                messages[self.issues.len() % messages.len()].clone(),
+                Severity::Mild,
+            ));
+        }
+    }
+
+    /// 多个生命周期参数都只用在共享引用上，一个就够了
+    fn check_struct_lifetimes(&mut self, item_struct: &ItemStruct) {
+        let names: Vec<String> = item_struct
+            .generics
+            .lifetimes()
+            .map(|def| def.lifetime.ident.to_string())
+            .collect();
+        if names.len() < 2 || !bounded_lifetimes(&item_struct.generics).is_empty() {
+            return;
+        }
+        let slots: Vec<LifetimeSlot> = item_struct
+            .fields
+            .iter()
+            .flat_map(|field| type_slots(&field.ty))
+            .filter(|slot| slot.name.as_ref().is_some_and(|name| names.contains(name)))
+            .collect();
+        if slots.is_empty() || !slots.iter().all(|slot| slot.plain_ref) {
+            return;
+        }
+
+        let struct_name = &item_struct.ident;
+        let count = names.len();
+        let list = names
+            .iter()
+            .map(|name| format!("'{name}"))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let first = &names[0];
+        let messages = if self.lang == "zh-CN" {
+            vec![
+                format!("{struct_name} 有 {count} 个生命周期参数（{list}），但它们都只用在共享引用上，合并成一个 '{first} 就够了，用到它的地方能少写一堆标注"),
+                format!("{struct_name} 的 {list} 互不约束又都只修饰 &T，一个 '{first} 就能表达同样的意思"),
+            ]
+        } else {
+            vec![
+                format!("{struct_name} has {count} lifetime params ({list}) but they only annotate shared references - one '{first} does the job and saves annotations everywhere it's used"),
+                format!("{list} on {struct_name} are unrelated and only mark &T fields, a single '{first} says the same"),
+            ]
+        };
+        self.issues.push(rule_issue(
+            &self.file_path,
+            get_position(&item_struct.ident),
+            "lifetime-abuse",
+            messages[self.issues.len() % messages.len()].clone(),
+            Severity::Mild,
+        ));
+    }
+
+    /// Box<dyn Trait + 'static> 里的 'static 就是默认值
+    fn check_redundant_static(&mut self, type_path: &TypePath) {
+        let Some(segment) = type_path.path.segments.last() else {
+            return;
+        };
+        if !matches!(segment.ident.to_string().as_str(), "Box" | "Rc" | "Arc") {
+            return;
+        }
+        let PathArguments::AngleBracketed(args) = &segment.arguments else {
+            return;
+        };
+        let Some(GenericArgument::Type(Type::TraitObject(object))) = args.args.first() else {
+            return;
+        };
+        let Some(lifetime) = object.bounds.iter().find_map(|bound| match bound {
+            TypeParamBound::Lifetime(lifetime) if lifetime.ident == "static" => Some(lifetime),
+            _ => None,
+        }) else {
+            return;
+        };
+
+        let ty = self.text(type_path);
+        let pointer = &segment.ident;
+        let messages = if self.lang == "zh-CN" {
+            vec![
+                format!("{ty} 里的 + 'static 是多余的，{pointer}<dyn Trait> 默认就是 'static"),
+                format!("{pointer}<dyn ..> 的默认生命周期本来就是 'static，这个标注可以删掉"),
+            ]
+        } else {
+            vec![
+                format!("the + 'static in {ty} is redundant, {pointer}<dyn Trait> is 'static by default"),
+                format!("{pointer}<dyn ..> already defaults to 'static, this bound can go"),
+            ]
+        };
+        self.issues.push(rule_issue(
+            &self.file_path,
+            get_position(lifetime),
+            "lifetime-abuse",
+            messages[self.issues.len() % messages.len()].clone(),
+            Severity::Mild,
+        ));
     }
 }
 
-impl<'ast> Visit<'ast> for LifetimeVisitor {
-    fn visit_lifetime(&mut self, lifetime: &'ast Lifetime) {
-        self.lifetime_count += 1;
+impl<'ast> Visit<'ast> for LifetimeVisitor<'_> {
+    fn visit_macro(&mut self, mac: &'ast Macro) {
+        visit_macro_args(self, mac);
+    }
 
-        // Check for excessive lifetime usage
-        if self.lifetime_count > 5 {
-            let messages = [
-                "生命周期标注比我的生命还复杂",
-                "这么多生命周期，你是在写哲学论文吗？",
-                "生命周期滥用，建议重新设计数据结构",
-                "生命周期多到让人怀疑人生",
-            ];
+    fn visit_item_fn(&mut self, item_fn: &'ast ItemFn) {
+        self.check_fn_lifetimes(&item_fn.sig, Some(&item_fn.block));
+        self.check_static_bounds(&item_fn.sig, &item_fn.block);
+        // 嵌套函数不属于外层 impl
+        let outer = std::mem::take(&mut self.self_lifetimes);
+        syn::visit::visit_item_fn(self, item_fn);
+        self.self_lifetimes = outer;
+    }
 
-            self.issues.push(CodeIssue {
-                file_path: self.file_path.clone(),
-                line: 1,
-                column: 1,
-                rule_name: "lifetime-abuse".to_string(),
-                message: messages[self.issues.len() % messages.len()].to_string(),
-                severity: Severity::Spicy,
-                roast_level: RoastLevel::Sarcastic,
-            });
+    fn visit_item_impl(&mut self, item_impl: &'ast ItemImpl) {
+        self.check_impl_lifetimes(item_impl);
+        let mut in_self = LifetimeNames::default();
+        in_self.visit_type(&item_impl.self_ty);
+        let self_lifetimes = item_impl
+            .generics
+            .lifetimes()
+            .map(|def| def.lifetime.ident.to_string())
+            .filter(|name| in_self.0.contains(name))
+            .collect();
+
+        let outer_lifetimes = std::mem::replace(&mut self.self_lifetimes, self_lifetimes);
+        let outer_trait_impl =
+            std::mem::replace(&mut self.in_trait_impl, item_impl.trait_.is_some());
+        syn::visit::visit_item_impl(self, item_impl);
+        self.self_lifetimes = outer_lifetimes;
+        self.in_trait_impl = outer_trait_impl;
+    }
+
+    fn visit_impl_item_fn(&mut self, impl_fn: &'ast ImplItemFn) {
+        // trait 实现的签名由 trait 决定，改不了
+        if !self.in_trait_impl {
+            self.check_fn_lifetimes(&impl_fn.sig, Some(&impl_fn.block));
+            self.check_static_bounds(&impl_fn.sig, &impl_fn.block);
+            self.check_mut_self(&impl_fn.sig);
         }
+        syn::visit::visit_impl_item_fn(self, impl_fn);
+    }
+
+    fn visit_trait_item_fn(&mut self, trait_fn: &'ast TraitItemFn) {
+        self.check_fn_lifetimes(&trait_fn.sig, trait_fn.default.as_ref());
+        syn::visit::visit_trait_item_fn(self, trait_fn);
+    }
+
+    fn visit_item_struct(&mut self, item_struct: &'ast ItemStruct) {
+        self.check_struct_lifetimes(item_struct);
+        syn::visit::visit_item_struct(self, item_struct);
+    }
 
-        syn::visit::visit_lifetime(self, lifetime);
+    fn visit_type_path(&mut self, type_path: &'ast TypePath) {
+        self.check_redundant_static(type_path);
+        syn::visit::visit_type_path(self, type_path);
     }
 }
 
@@ -225,6 +990,7 @@ impl TraitVisitor {
                 message: messages[self.issues.len() % messages.len()].to_string(),
                 severity: Severity::Spicy,
                 roast_level: RoastLevel::Sarcastic,
+                context: CodeContext::Production,
             });
         }
 
@@ -244,6 +1010,7 @@ impl TraitVisitor {
                 message: messages[self.issues.len() % messages.len()].to_string(),
                 severity: Severity::Mild,
                 roast_level: RoastLevel::Gentle,
+                context: CodeContext::Production,
             });
         }
     }
@@ -256,83 +1023,563 @@ impl<'ast> Visit<'ast> for TraitVisitor {
     }
 }
 
-struct GenericVisitor {
+struct GenericVisitor<'a> {
     file_path: std::path::PathBuf,
+    content: &'a str,
     issues: Vec<CodeIssue>,
+    lang: String,
+    /// 文件里实现了 Drop 的类型：Drop 实现的约束必须和结构体定义一致
+    drop_types: HashSet<String>,
+    /// 每种约束组合出现的位置，按第一次出现的顺序
+    bound_chains: Vec<(String, Vec<(usize, usize)>)>,
+    in_trait_impl: bool,
+    /// 当前 impl 块有自己的类型参数，方法体离不开它们
+    in_generic_impl: bool,
 }
 
-impl GenericVisitor {
-    fn new(file_path: std::path::PathBuf) -> Self {
+/// 同一组约束出现这么多次，就值得起个 trait 别名
+const REPEATED_BOUND_CHAIN: usize = 3;
+
+/// 函数体超过这么多行，每种实例化都复制一份就很可观了
+const LARGE_GENERIC_BODY_LINES: usize = 30;
+
+/// 只用来在入口处做一次转换的 trait，以及对应的转换方法
+const CONVERSION_TRAITS: &[(&str, &str)] = &[
+    ("AsRef", "as_ref()"),
+    ("AsMut", "as_mut()"),
+    ("Into", "into()"),
+    ("Borrow", "borrow()"),
+    ("BorrowMut", "borrow_mut()"),
+    ("ToString", "to_string()"),
+];
+
+/// 函数自己的一个类型参数
+struct FnTypeParam<'p> {
+    ident: &'p syn::Ident,
+    /// 行内约束和 where 子句里以它为主语的约束
+    bounds: Vec<&'p TypeParamBound>,
+}
+
+impl<'a> GenericVisitor<'a> {
+    fn new(
+        file_path: std::path::PathBuf,
+        content: &'a str,
+        lang: &str,
+        drop_types: HashSet<String>,
+    ) -> Self {
         Self {
             file_path,
+            content,
             issues: Vec::new(),
+            lang: lang.to_string(),
+            drop_types,
+            bound_chains: Vec::new(),
+            in_trait_impl: false,
+            in_generic_impl: false,
         }
     }
 
-    fn check_generic_abuse(&mut self, generics: &syn::Generics) {
-        if generics.params.len() > 5 {
-            let messages = [
-                "泛型参数比我的购物清单还长",
-                "这么多泛型，编译器都要哭了",
-                "泛型滥用，建议重新设计架构",
-                "泛型多到让人怀疑这还是 Rust 吗",
-            ];
+    fn text<T: Spanned>(&self, node: &T) -> String {
+        get_source_text(self.content, node).unwrap_or_else(|| "..".to_string())
+    }
 
-            self.issues.push(CodeIssue {
-                file_path: self.file_path.clone(),
-                line: 1,
-                column: 1,
-                rule_name: "generic-abuse".to_string(),
-                message: messages[self.issues.len() % messages.len()].to_string(),
-                severity: Severity::Spicy,
-                roast_level: RoastLevel::Sarcastic,
-            });
+    fn bounds_text(&self, bounds: &[&TypeParamBound]) -> String {
+        bounds
+            .iter()
+            .map(|bound| self.text(*bound))
+            .collect::<Vec<_>>()
+            .join(" + ")
+    }
+
+    /// 记下一组约束；至少两个 trait 才算“一串”
+    fn record_bound_chain<'b>(&mut self, bounds: impl IntoIterator<Item = &'b TypeParamBound>) {
+        let bounds: Vec<&TypeParamBound> = bounds.into_iter().collect();
+        let traits = bounds
+            .iter()
+            .filter(|bound| matches!(bound, TypeParamBound::Trait(_)))
+            .count();
+        let Some(first) = bounds.first() else {
+            return;
+        };
+        if traits < 2 {
+            return;
+        }
+        let mut parts: Vec<String> = bounds.iter().map(|bound| self.text(*bound)).collect();
+        parts.sort();
+        let chain = parts.join(" + ");
+        let position = get_position(*first);
+        match self.bound_chains.iter_mut().find(|(key, _)| *key == chain) {
+            Some((_, positions)) => positions.push(position),
+            None => self.bound_chains.push((chain, vec![position])),
         }
+    }
 
-        // Check for single-letter generic names (except T, U, V)
-        for param in &generics.params {
-            if let GenericParam::Type(type_param) = param {
-                let name = type_param.ident.to_string();
-                if name.len() == 1 && !matches!(name.as_str(), "T" | "U" | "V" | "E" | "K") {
-                    let messages = [
-                        format!("泛型参数 '{name}' 的命名创意约等于零"),
-                        format!("泛型 '{name}' 的名字比我的耐心还短"),
-                        format!("用 '{name}' 做泛型名？建议用更有意义的名字"),
-                    ];
-
-                    self.issues.push(CodeIssue {
-                        file_path: self.file_path.clone(),
-                        line: 1,
-                        column: 1,
-                        rule_name: "generic-abuse".to_string(),
-                        message: messages[self.issues.len() % messages.len()].clone(),
-                        severity: Severity::Mild,
-                        roast_level: RoastLevel::Gentle,
-                    });
+    fn record_generics(&mut self, generics: &Generics) {
+        for param in generics.type_params() {
+            self.record_bound_chain(&param.bounds);
+        }
+        if let Some(where_clause) = &generics.where_clause {
+            for predicate in &where_clause.predicates {
+                if let WherePredicate::Type(predicate) = predicate {
+                    self.record_bound_chain(&predicate.bounds);
                 }
             }
         }
     }
+
+    /// 泛型函数：没用上的类型参数、只出现在一个参数里的类型参数、大函数体的单态化膨胀
+    fn check_fn(&mut self, sig: &Signature, block: &Block, public: bool) {
+        let params = fn_type_params(&sig.generics);
+        let body = block.to_token_stream();
+        let bloated = self.check_monomorphization(sig, block, &params);
+
+        for param in &params {
+            let name = param.ident.to_string();
+            // 其他类型参数的约束里提到它，说明它参与了类型之间的关系
+            let in_other_bounds = params
+                .iter()
+                .filter(|other| other.ident != param.ident)
+                .flat_map(|other| &other.bounds)
+                .chain(&param.bounds)
+                .any(|bound| mentions_ident(bound.to_token_stream(), &name));
+            let in_output = match &sig.output {
+                ReturnType::Type(_, ty) => mentions_ident(ty.to_token_stream(), &name),
+                ReturnType::Default => false,
+            };
+            if in_other_bounds || in_output || mentions_ident(body.clone(), &name) {
+                continue;
+            }
+
+            let args: Vec<&PatType> = sig
+                .inputs
+                .iter()
+                .filter_map(|arg| match arg {
+                    FnArg::Typed(pat_type)
+                        if mentions_ident(pat_type.ty.to_token_stream(), &name) =>
+                    {
+                        Some(pat_type)
+                    }
+                    _ => None,
+                })
+                .collect();
+
+            // fn assert_send<T: Send>() {} 这种空函数是编译期断言
+            if args.is_empty() && block.stmts.is_empty() {
+                continue;
+            }
+            if args.iter().all(|arg| !arg_is_used(arg, &body)) {
+                self.report_unused_param(sig, param, &args);
+            } else if !public
+                && !bloated
+                && !param.bounds.is_empty()
+                && args.len() == 1
+                && count_ident(args[0].ty.to_token_stream(), &name) == 1
+                && !param.bounds.iter().any(|bound| is_maybe_sized(bound))
+            {
+                self.report_single_position(sig, param, args[0]);
+            }
+        }
+    }
+
+    fn report_unused_param(&mut self, sig: &Signature, param: &FnTypeParam, args: &[&PatType]) {
+        let fn_name = &sig.ident;
+        let name = param.ident;
+        let (messages, severity) = match (args.first(), self.lang.as_str()) {
+            (None, "zh-CN") => (vec![
+                format!("{fn_name}() 声明了类型参数 {name}，参数、返回值、函数体里都没用到它，调用者却得用 turbofish 替它选一个类型；删掉"),
+                format!("{name} 在 {fn_name}() 里毫无用处，只会逼调用者写 ::<..>"),
+            ], Severity::Spicy),
+            (None, _) => (vec![
+                format!("{fn_name}() declares type parameter {name} but no argument, return type or body uses it - callers have to pick one with turbofish for nothing; delete it"),
+                format!("{name} does nothing in {fn_name}() except force callers to write ::<..>"),
+            ], Severity::Spicy),
+            (Some(arg), "zh-CN") => {
+                let arg = arg_name(arg, self.content);
+                (vec![
+                    format!("{fn_name}() 的 {name} 只用在参数 {arg} 上，而函数体根本没用这个参数；每换一种类型都要多编译一份什么也不干的代码"),
+                    format!("{fn_name}() 为了一个被忽略的参数 {arg} 引入泛型 {name}，删掉参数和泛型"),
+                ], Severity::Mild)
+            }
+            (Some(arg), _) => {
+                let arg = arg_name(arg, self.content);
+                (vec![
+                    format!("{fn_name}()'s {name} is only used by argument {arg}, which the body never touches - every new type compiles another copy that does nothing with it"),
+                    format!("{fn_name}() introduces generic {name} for an ignored argument {arg}, drop both"),
+                ], Severity::Mild)
+            }
+        };
+        self.issues.push(rule_issue(
+            &self.file_path,
+            get_position(param.ident),
+            "generic-abuse",
+            messages[self.issues.len() % messages.len()].clone(),
+            severity,
+        ));
+    }
+
+    /// 公开函数不提：换成 impl Trait 之后调用者就不能用 turbofish 了
+    fn report_single_position(&mut self, sig: &Signature, param: &FnTypeParam, arg: &PatType) {
+        let fn_name = &sig.ident;
+        let name = param.ident;
+        let arg_name = arg_name(arg, self.content);
+        let bounds = self.bounds_text(&param.bounds);
+        // 参数类型就是 T 本身时可以给出完整的写法
+        let whole = matches!(arg.ty.as_ref(), Type::Path(path) if path.path.is_ident(name));
+        let messages = match (whole, self.lang.as_str()) {
+            (true, "zh-CN") => vec![
+                format!("{fn_name}() 的类型参数 {name} 只出现在参数 {arg_name} 里一次，写成 {arg_name}: impl {bounds} 更直白，签名上少一个要追踪的名字"),
+                format!("{name} 只服务于 {arg_name} 一个参数，{arg_name}: impl {bounds} 就能表达同样的意思"),
+            ],
+            (false, "zh-CN") => vec![
+                format!("{fn_name}() 的类型参数 {name} 只出现在参数 {arg_name} 里一次，把 {arg_name} 类型里的 {name} 换成 impl {bounds} 更直白"),
+                format!("{name} 只服务于 {arg_name} 一个参数，在那里写 impl {bounds} 就能表达同样的意思"),
+            ],
+            (true, _) => vec![
+                format!("{fn_name}()'s type parameter {name} appears once, in argument {arg_name} - {arg_name}: impl {bounds} says the same with one less name to track"),
+                format!("{name} only serves {arg_name}, {arg_name}: impl {bounds} expresses the same thing"),
+            ],
+            (false, _) => vec![
+                format!("{fn_name}()'s type parameter {name} appears once, in argument {arg_name} - replace {name} with impl {bounds} in that type"),
+                format!("{name} only serves {arg_name}, writing impl {bounds} there expresses the same thing"),
+            ],
+        };
+        self.issues.push(rule_issue(
+            &self.file_path,
+            get_position(param.ident),
+            "generic-abuse",
+            messages[self.issues.len() % messages.len()].clone(),
+            Severity::Mild,
+        ));
+    }
+
+    /// 泛型只用来做一次 AsRef / Into 转换，函数体却很大：每种类型都复制一份
+    fn check_monomorphization(
+        &mut self,
+        sig: &Signature,
+        block: &Block,
+        params: &[FnTypeParam],
+    ) -> bool {
+        if self.in_generic_impl {
+            return false;
+        }
+        let start = block.brace_token.span.open().start().line;
+        let end = block.brace_token.span.close().start().line;
+        let lines = end.saturating_sub(start);
+        if lines < LARGE_GENERIC_BODY_LINES {
+            return false;
+        }
+        // 已经拆出 inner 函数了
+        if block
+            .stmts
+            .iter()
+            .any(|stmt| matches!(stmt, Stmt::Item(syn::Item::Fn(_))))
+        {
+            return false;
+        }
+
+        let mut conversions: Vec<(String, &'static str)> = Vec::new();
+        for param in params {
+            let Some(method) = conversion_method(&param.bounds) else {
+                return false;
+            };
+            conversions.push((param.ident.to_string(), method));
+        }
+        for arg in &sig.inputs {
+            if let FnArg::Typed(pat_type) = arg {
+                if let Type::ImplTrait(impl_trait) = pat_type.ty.as_ref() {
+                    let bounds: Vec<&TypeParamBound> = impl_trait.bounds.iter().collect();
+                    let Some(method) = conversion_method(&bounds) else {
+                        return false;
+                    };
+                    conversions.push((self.text(&pat_type.ty), method));
+                }
+            }
+        }
+        let Some((generic, method)) = conversions.first() else {
+            return false;
+        };
+
+        let fn_name = &sig.ident;
+        let messages = if self.lang == "zh-CN" {
+            vec![
+                format!("{fn_name}() 的函数体有 {lines} 行，每换一种 {generic} 就要完整编译一份；泛型只是为了调一次 .{method}，把函数体挪进一个非泛型的 inner 函数，外层只做转换再调用它"),
+                format!("{fn_name}() 大部分代码和 {generic} 无关，却随着它一起单态化；拆成 fn inner(..) {{ .. }} 加一层 .{method} 的薄壳"),
+            ]
+        } else {
+            vec![
+                format!("{fn_name}() has a {lines}-line body that gets compiled again for every {generic}; the generic only exists for one .{method} call, move the body into a non-generic inner fn and keep a thin converting wrapper"),
+                format!("most of {fn_name}() doesn't care about {generic} but is monomorphized with it - split it into fn inner(..) {{ .. }} behind a .{method} shim"),
+            ]
+        };
+        self.issues.push(rule_issue(
+            &self.file_path,
+            get_position(&sig.ident),
+            "generic-abuse",
+            messages[self.issues.len() % messages.len()].clone(),
+            Severity::Mild,
+        ));
+        true
+    }
+
+    /// 结构体定义上的 trait 约束：每个 impl 和每个用到它的地方都得跟着重复
+    fn check_struct_bounds(&mut self, item_struct: &ItemStruct) {
+        if self.drop_types.contains(&item_struct.ident.to_string()) {
+            return;
+        }
+        let fields = item_struct.fields.to_token_stream();
+        let struct_name = &item_struct.ident;
+
+        for param in fn_type_params(&item_struct.generics) {
+            let bounds: Vec<&TypeParamBound> = param
+                .bounds
+                .iter()
+                .copied()
+                .filter(|bound| matches!(bound, TypeParamBound::Trait(_)) && !is_maybe_sized(bound))
+                .collect();
+            let Some(first) = bounds.first() else {
+                continue;
+            };
+            // 字段用到了关联类型（T::Output），约束是必须的
+            if uses_associated_type(fields.clone(), &param.ident.to_string()) {
+                continue;
+            }
+
+            let name = param.ident;
+            let bounds = self.bounds_text(&bounds);
+            let messages = if self.lang == "zh-CN" {
+                vec![
+                    format!("{struct_name} 定义上的 {name}: {bounds} 会传染给每一个 impl 和每一个用到 {struct_name} 的泛型函数；约束放到真正需要它的 impl 上"),
+                    format!("结构体字段并不需要 {name}: {bounds}，把它挪到用到这些能力的 impl 块上，{struct_name} 本身保持无约束"),
+                ]
+            } else {
+                vec![
+                    format!("{name}: {bounds} on the {struct_name} definition spreads to every impl and every generic fn that mentions {struct_name} - put the bound on the impls that need it"),
+                    format!("the fields of {struct_name} don't need {name}: {bounds}, move it to the impl blocks that use those capabilities and keep the struct unbounded"),
+                ]
+            };
+            self.issues.push(rule_issue(
+                &self.file_path,
+                get_position(*first),
+                "generic-abuse",
+                messages[self.issues.len() % messages.len()].clone(),
+                Severity::Mild,
+            ));
+        }
+    }
+
+    /// 文件看完之后报告反复出现的约束组合
+    fn report_bound_chains(&mut self) {
+        for (chain, positions) in std::mem::take(&mut self.bound_chains) {
+            if positions.len() < REPEATED_BOUND_CHAIN {
+                continue;
+            }
+            let count = positions.len();
+            let lines = positions
+                .iter()
+                .map(|(line, _)| line.to_string())
+                .collect::<Vec<_>>()
+                .join(", ");
+            let messages = if self.lang == "zh-CN" {
+                vec![
+                    format!("{chain} 这串约束在第 {lines} 行重复了 {count} 次，定义一个 trait 别名：trait Name: {chain} {{}} 加上 blanket impl，以后只改一处"),
+                    format!("同样的 {chain} 写了 {count} 遍（第 {lines} 行），提取成一个带 blanket impl 的 trait"),
+                ]
+            } else {
+                vec![
+                    format!("the bound chain {chain} is repeated {count} times (lines {lines}) - define an alias trait Name: {chain} {{}} with a blanket impl and change it in one place"),
+                    format!("{chain} is spelled out {count} times (lines {lines}), extract it into a trait with a blanket impl"),
+                ]
+            };
+            self.issues.push(rule_issue(
+                &self.file_path,
+                positions[0],
+                "generic-abuse",
+                messages[self.issues.len() % messages.len()].clone(),
+                Severity::Mild,
+            ));
+        }
+    }
 }
 
-impl<'ast> Visit<'ast> for GenericVisitor {
-    fn visit_item_fn(&mut self, func: &'ast syn::ItemFn) {
-        self.check_generic_abuse(&func.sig.generics);
+impl<'ast> Visit<'ast> for GenericVisitor<'_> {
+    fn visit_file(&mut self, file: &'ast File) {
+        syn::visit::visit_file(self, file);
+        self.report_bound_chains();
+    }
+
+    fn visit_item_fn(&mut self, func: &'ast ItemFn) {
+        self.record_generics(&func.sig.generics);
+        self.check_fn(&func.sig, &func.block, is_public(&func.vis));
+        let outer = std::mem::replace(&mut self.in_generic_impl, false);
         syn::visit::visit_item_fn(self, func);
+        self.in_generic_impl = outer;
     }
 
-    fn visit_item_struct(&mut self, struct_item: &'ast syn::ItemStruct) {
-        self.check_generic_abuse(&struct_item.generics);
+    fn visit_item_struct(&mut self, struct_item: &'ast ItemStruct) {
+        self.record_generics(&struct_item.generics);
+        self.check_struct_bounds(struct_item);
         syn::visit::visit_item_struct(self, struct_item);
     }
 
     fn visit_item_enum(&mut self, enum_item: &'ast syn::ItemEnum) {
-        self.check_generic_abuse(&enum_item.generics);
+        self.record_generics(&enum_item.generics);
         syn::visit::visit_item_enum(self, enum_item);
     }
 
+    fn visit_item_trait(&mut self, trait_item: &'ast ItemTrait) {
+        self.record_generics(&trait_item.generics);
+        syn::visit::visit_item_trait(self, trait_item);
+    }
+
     fn visit_item_impl(&mut self, impl_item: &'ast ItemImpl) {
-        self.check_generic_abuse(&impl_item.generics);
+        self.record_generics(&impl_item.generics);
+        let outer_trait_impl =
+            std::mem::replace(&mut self.in_trait_impl, impl_item.trait_.is_some());
+        let outer_generic_impl = std::mem::replace(
+            &mut self.in_generic_impl,
+            impl_item.generics.type_params().next().is_some(),
+        );
         syn::visit::visit_item_impl(self, impl_item);
+        self.in_trait_impl = outer_trait_impl;
+        self.in_generic_impl = outer_generic_impl;
+    }
+
+    fn visit_impl_item_fn(&mut self, impl_fn: &'ast ImplItemFn) {
+        // trait 实现的签名由 trait 决定，改不了
+        if !self.in_trait_impl {
+            self.record_generics(&impl_fn.sig.generics);
+            self.check_fn(&impl_fn.sig, &impl_fn.block, is_public(&impl_fn.vis));
+        }
+        syn::visit::visit_impl_item_fn(self, impl_fn);
+    }
+
+    fn visit_trait_item_fn(&mut self, trait_fn: &'ast TraitItemFn) {
+        self.record_generics(&trait_fn.sig.generics);
+        syn::visit::visit_trait_item_fn(self, trait_fn);
+    }
+}
+
+/// 收集文件里所有 `impl Drop for X` 的 X
+#[derive(Default)]
+struct DropImpls(HashSet<String>);
+
+impl<'ast> Visit<'ast> for DropImpls {
+    fn visit_item_impl(&mut self, item_impl: &'ast ItemImpl) {
+        let is_drop = item_impl
+            .trait_
+            .as_ref()
+            .and_then(|(_, path, _)| path.segments.last())
+            .is_some_and(|segment| segment.ident == "Drop");
+        if let (true, Type::Path(self_ty)) = (is_drop, item_impl.self_ty.as_ref()) {
+            if let Some(segment) = self_ty.path.segments.last() {
+                self.0.insert(segment.ident.to_string());
+            }
+        }
+        syn::visit::visit_item_impl(self, item_impl);
+    }
+}
+
+/// 类型参数连同 where 子句里以它为主语的约束
+fn fn_type_params(generics: &Generics) -> Vec<FnTypeParam<'_>> {
+    generics
+        .type_params()
+        .map(|param| {
+            let mut bounds: Vec<&TypeParamBound> = param.bounds.iter().collect();
+            if let Some(where_clause) = &generics.where_clause {
+                for predicate in &where_clause.predicates {
+                    if let WherePredicate::Type(predicate) = predicate {
+                        if matches!(&predicate.bounded_ty, Type::Path(path) if path.path.is_ident(&param.ident))
+                        {
+                            bounds.extend(&predicate.bounds);
+                        }
+                    }
+                }
+            }
+            FnTypeParam {
+                ident: &param.ident,
+                bounds,
+            }
+        })
+        .collect()
+}
+
+/// 所有约束都是同一种转换 trait 时返回对应的转换方法
+fn conversion_method(bounds: &[&TypeParamBound]) -> Option<&'static str> {
+    let mut method = None;
+    for bound in bounds {
+        match bound {
+            TypeParamBound::Trait(bound) => {
+                let ident = bound.path.segments.last()?.ident.to_string();
+                let (_, found) = CONVERSION_TRAITS.iter().find(|(name, _)| *name == ident)?;
+                method = Some(*found);
+            }
+            TypeParamBound::Lifetime(_) => {}
+            _ => return None,
+        }
     }
+    method
+}
+
+fn is_maybe_sized(bound: &TypeParamBound) -> bool {
+    matches!(bound, TypeParamBound::Trait(bound) if matches!(bound.modifier, syn::TraitBoundModifier::Maybe(_)))
+}
+
+/// 参数的名字，`mut x` 只取 x
+fn arg_name(arg: &PatType, content: &str) -> String {
+    match arg.pat.as_ref() {
+        Pat::Ident(ident) => ident.ident.to_string(),
+        pat => get_source_text(content, pat).unwrap_or_else(|| "..".to_string()),
+    }
+}
+
+/// 参数在函数体里被用到了；`_` 和 `_name` 的参数算没用
+fn arg_is_used(arg: &PatType, body: &TokenStream) -> bool {
+    match arg.pat.as_ref() {
+        Pat::Wild(_) => false,
+        Pat::Ident(ident) => {
+            let name = ident.ident.to_string();
+            !name.starts_with('_') && mentions_ident(body.clone(), &name)
+        }
+        _ => true,
+    }
+}
+
+fn mentions_ident(tokens: TokenStream, name: &str) -> bool {
+    count_ident(tokens, name) > 0
+}
+
+/// 统计标识符出现的次数，format 字符串里内联的 {name} 也算
+fn count_ident(tokens: TokenStream, name: &str) -> usize {
+    tokens
+        .into_iter()
+        .map(|token| match token {
+            TokenTree::Ident(ident) => usize::from(ident == name),
+            TokenTree::Group(group) => count_ident(group.stream(), name),
+            TokenTree::Literal(literal) => {
+                let literal = literal.to_string();
+                usize::from(
+                    literal.starts_with('"')
+                        && (literal.contains(&format!("{{{name}}}"))
+                            || literal.contains(&format!("{{{name}:"))),
+                )
+            }
+            TokenTree::Punct(_) => 0,
+        })
+        .sum()
+}
+
+/// 字段里出现了 T::Assoc 或者 <T as Trait>::Assoc
+fn uses_associated_type(tokens: TokenStream, name: &str) -> bool {
+    let tokens: Vec<TokenTree> = tokens.into_iter().collect();
+    tokens.iter().enumerate().any(|(index, token)| match token {
+        TokenTree::Ident(ident) if ident == name => {
+            let next_is = |offset: usize, ch: char| {
+                matches!(tokens.get(index + offset), Some(TokenTree::Punct(p)) if p.as_char() == ch)
+            };
+            (next_is(1, ':') && next_is(2, ':'))
+                || matches!(tokens.get(index + 1), Some(TokenTree::Ident(next)) if next == "as")
+        }
+        TokenTree::Group(group) => uses_associated_type(group.stream(), name),
+        _ => false,
+    })
 }
@@ -0,0 +1,649 @@
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use syn::{
+    visit::Visit, Attribute, Block, Expr, Fields, File, FnArg, GenericArgument, ImplItem, ItemEnum,
+    ItemFn, ItemImpl, ItemStruct, ItemTrait, Macro, PathArguments, ReturnType, Signature,
+    TraitItem, Type, TypeParamBound,
+};
+
+use crate::analyzer::{CodeContext, CodeIssue, Severity};
+use crate::rules::common::{
+    derived_traits, is_library_file, is_public, rule_issue, type_name, type_to_string,
+};
+use crate::rules::{ContextPolicy, Rule};
+use crate::utils::get_position;
+
+/// 公开函数参数用 &String / &Vec<T> / &Box<T>
+pub struct OwnedRefParamRule;
+
+/// 公开函数有超过 2 个 bool 参数
+pub struct BoolParamSoupRule;
+
+/// 字段全部公开、却有带校验的构造函数的公开结构体
+pub struct LeakyStructRule;
+
+/// 会持续增长的公开枚举没有 #[non_exhaustive]
+pub struct MissingNonExhaustiveRule;
+
+/// 公开类型没有实现 Debug
+pub struct MissingDebugRule;
+
+/// 公开函数返回 impl Trait，把具体类型的自动 trait 泄露给调用者
+pub struct LeakyImplTraitRule;
+
+/// API 设计只对库的正式代码有意义
+fn api_context_policy(context: CodeContext) -> ContextPolicy {
+    match context {
+        CodeContext::Production => ContextPolicy::Report,
+        _ => ContextPolicy::Ignore,
+    }
+}
+
+impl Rule for OwnedRefParamRule {
+    fn name(&self) -> &'static str {
+        "owned-ref-param"
+    }
+
+    fn context_policy(&self, context: CodeContext) -> ContextPolicy {
+        api_context_policy(context)
+    }
+
+    fn check(
+        &self,
+        file_path: &Path,
+        syntax_tree: &File,
+        _content: &str,
+        lang: &str,
+    ) -> Vec<CodeIssue> {
+        if !is_library_file(file_path) {
+            return Vec::new();
+        }
+        ApiSurface::collect(syntax_tree).owned_ref_params(file_path, lang)
+    }
+}
+
+impl Rule for BoolParamSoupRule {
+    fn name(&self) -> &'static str {
+        "bool-param-soup"
+    }
+
+    fn context_policy(&self, context: CodeContext) -> ContextPolicy {
+        api_context_policy(context)
+    }
+
+    fn check(
+        &self,
+        file_path: &Path,
+        syntax_tree: &File,
+        _content: &str,
+        lang: &str,
+    ) -> Vec<CodeIssue> {
+        if !is_library_file(file_path) {
+            return Vec::new();
+        }
+        ApiSurface::collect(syntax_tree).bool_param_soup(file_path, lang)
+    }
+}
+
+impl Rule for LeakyStructRule {
+    fn name(&self) -> &'static str {
+        "leaky-struct"
+    }
+
+    fn context_policy(&self, context: CodeContext) -> ContextPolicy {
+        api_context_policy(context)
+    }
+
+    fn check(
+        &self,
+        file_path: &Path,
+        syntax_tree: &File,
+        _content: &str,
+        lang: &str,
+    ) -> Vec<CodeIssue> {
+        if !is_library_file(file_path) {
+            return Vec::new();
+        }
+        ApiSurface::collect(syntax_tree).leaky_structs(file_path, lang)
+    }
+}
+
+impl Rule for MissingNonExhaustiveRule {
+    fn name(&self) -> &'static str {
+        "missing-non-exhaustive"
+    }
+
+    fn context_policy(&self, context: CodeContext) -> ContextPolicy {
+        api_context_policy(context)
+    }
+
+    fn check(
+        &self,
+        file_path: &Path,
+        syntax_tree: &File,
+        _content: &str,
+        lang: &str,
+    ) -> Vec<CodeIssue> {
+        if !is_library_file(file_path) {
+            return Vec::new();
+        }
+        ApiSurface::collect(syntax_tree).missing_non_exhaustive(file_path, lang)
+    }
+}
+
+impl Rule for MissingDebugRule {
+    fn name(&self) -> &'static str {
+        "missing-debug"
+    }
+
+    fn context_policy(&self, context: CodeContext) -> ContextPolicy {
+        api_context_policy(context)
+    }
+
+    fn check(
+        &self,
+        file_path: &Path,
+        syntax_tree: &File,
+        _content: &str,
+        lang: &str,
+    ) -> Vec<CodeIssue> {
+        if !is_library_file(file_path) {
+            return Vec::new();
+        }
+        ApiSurface::collect(syntax_tree).missing_debug(file_path, lang)
+    }
+}
+
+impl Rule for LeakyImplTraitRule {
+    fn name(&self) -> &'static str {
+        "leaky-impl-trait"
+    }
+
+    fn context_policy(&self, context: CodeContext) -> ContextPolicy {
+        api_context_policy(context)
+    }
+
+    fn check(
+        &self,
+        file_path: &Path,
+        syntax_tree: &File,
+        _content: &str,
+        lang: &str,
+    ) -> Vec<CodeIssue> {
+        if !is_library_file(file_path) {
+            return Vec::new();
+        }
+        ApiSurface::collect(syntax_tree).leaky_impl_traits(file_path, lang)
+    }
+}
+
+// ============================================================================
+// 收集文件里的公开 API
+// ============================================================================
+
+/// 名字暗示会不断加新变体的枚举
+const GROWING_ENUM_SUFFIXES: &[&str] = &[
+    "Error", "Kind", "Event", "Message", "Command", "Request", "Response",
+];
+
+/// 变体数量达到这个值，说明这个枚举一直在长
+const GROWING_ENUM_VARIANTS: usize = 5;
+
+/// 返回 impl Trait 的惯用写法：这些类型本来就没法命名或不值得命名
+const IDIOMATIC_IMPL_TRAITS: &[&str] = &[
+    "Iterator",
+    "DoubleEndedIterator",
+    "ExactSizeIterator",
+    "IntoIterator",
+    "Fn",
+    "FnMut",
+    "FnOnce",
+    "Future",
+    "Stream",
+    "Display",
+];
+
+#[derive(Default)]
+struct ApiSurface<'ast> {
+    /// 公开的函数、方法和公开 trait 的方法
+    pub_fns: Vec<&'ast Signature>,
+    pub_structs: Vec<&'ast ItemStruct>,
+    pub_enums: Vec<&'ast ItemEnum>,
+    /// 手写了 impl Debug 的类型
+    debug_impls: HashSet<String>,
+    /// 类型名 -> 带校验逻辑的构造函数名
+    validating_ctors: HashMap<String, String>,
+}
+
+impl<'ast> ApiSurface<'ast> {
+    fn collect(syntax_tree: &'ast File) -> Self {
+        let mut surface = Self::default();
+        surface.visit_file(syntax_tree);
+        surface
+    }
+
+    fn owned_ref_params(&self, file_path: &Path, lang: &str) -> Vec<CodeIssue> {
+        let mut issues = Vec::new();
+
+        for sig in &self.pub_fns {
+            for ty in typed_params(sig) {
+                let Type::Reference(reference) = ty else {
+                    continue;
+                };
+                if reference.mutability.is_some() {
+                    continue;
+                }
+                let Some((owned, borrowed)) = borrowed_form(&reference.elem) else {
+                    continue;
+                };
+                let name = &sig.ident;
+
+                let messages = if lang == "zh-CN" {
+                    vec![
+                        format!("公开函数 {name}() 的参数是 &{owned}，改成 {borrowed}，调用者就不用先造一个 {owned} 出来"),
+                        format!("{name}() 要 &{owned}？{borrowed} 能接受的东西更多，而且不会更慢"),
+                        format!("&{owned} 参数把调用者绑死在 {owned} 上，{name}() 用 {borrowed} 就够了"),
+                    ]
+                } else {
+                    vec![
+                        format!("pub fn {name}() takes &{owned} - take {borrowed} so callers don't have to build a {owned} first"),
+                        format!("{name}() wants &{owned}? {borrowed} accepts strictly more and costs nothing extra"),
+                        format!("a &{owned} parameter ties callers to {owned}, {borrowed} is all {name}() needs"),
+                    ]
+                };
+
+                issues.push(rule_issue(
+                    file_path,
+                    get_position(ty),
+                    "owned-ref-param",
+                    messages[issues.len() % messages.len()].clone(),
+                    Severity::Spicy,
+                ));
+            }
+        }
+
+        issues
+    }
+
+    fn bool_param_soup(&self, file_path: &Path, lang: &str) -> Vec<CodeIssue> {
+        let mut issues = Vec::new();
+
+        for sig in &self.pub_fns {
+            let bools = typed_params(sig)
+                .filter(|ty| matches!(ty, Type::Path(p) if p.path.is_ident("bool")))
+                .count();
+            if bools <= 2 {
+                continue;
+            }
+            let name = &sig.ident;
+
+            let messages = if lang == "zh-CN" {
+                vec![
+                    format!("{name}() 有 {bools} 个 bool 参数，调用处 {name}(true, false, true) 谁看得懂？换成枚举或者选项结构体"),
+                    format!("bool 参数汤：{name}() 吃了 {bools} 个 bool，传错顺序编译器也不会提醒你"),
+                    format!("{name}() 的 {bools} 个 bool 参数让每个调用点都变成了猜谜游戏"),
+                ]
+            } else {
+                vec![
+                    format!("{name}() takes {bools} bool params - who can read {name}(true, false, true)? Use enums or an options struct"),
+                    format!("bool parameter soup: {name}() eats {bools} bools and the compiler won't notice if you swap them"),
+                    format!("{bools} bool params turn every call to {name}() into a guessing game"),
+                ]
+            };
+
+            issues.push(rule_issue(
+                file_path,
+                get_position(&sig.ident),
+                "bool-param-soup",
+                messages[issues.len() % messages.len()].clone(),
+                Severity::Spicy,
+            ));
+        }
+
+        issues
+    }
+
+    fn leaky_structs(&self, file_path: &Path, lang: &str) -> Vec<CodeIssue> {
+        let mut issues = Vec::new();
+
+        for item_struct in &self.pub_structs {
+            let Fields::Named(fields) = &item_struct.fields else {
+                continue;
+            };
+            if fields.named.is_empty() || !fields.named.iter().all(|f| is_public(&f.vis)) {
+                continue;
+            }
+            let name = item_struct.ident.to_string();
+            let Some(ctor) = self.validating_ctors.get(&name) else {
+                continue;
+            };
+
+            let messages = if lang == "zh-CN" {
+                vec![
+                    format!("{name}::{ctor}() 辛辛苦苦做了校验，可字段全是 pub，谁都能直接造一个不合法的 {name}"),
+                    format!("{name} 的字段全部公开，{ctor}() 里的检查形同虚设，把字段改成私有再提供 getter"),
+                    format!("{name} 有不变量却把字段全亮出来了，{ctor}() 守的门旁边就是一堵不存在的墙"),
+                ]
+            } else {
+                vec![
+                    format!("{name}::{ctor}() validates its input, but every field is pub - anyone can build an invalid {name}"),
+                    format!("all fields of {name} are public, so the checks in {ctor}() are optional - make them private and add getters"),
+                    format!("{name} has invariants but exposes every field, {ctor}() guards a door next to a missing wall"),
+                ]
+            };
+
+            issues.push(rule_issue(
+                file_path,
+                get_position(&item_struct.ident),
+                "leaky-struct",
+                messages[issues.len() % messages.len()].clone(),
+                Severity::Spicy,
+            ));
+        }
+
+        issues
+    }
+
+    fn missing_non_exhaustive(&self, file_path: &Path, lang: &str) -> Vec<CodeIssue> {
+        let mut issues = Vec::new();
+
+        for item_enum in &self.pub_enums {
+            if has_attr(&item_enum.attrs, "non_exhaustive") {
+                continue;
+            }
+            let name = item_enum.ident.to_string();
+            let growing = GROWING_ENUM_SUFFIXES
+                .iter()
+                .any(|suffix| name.ends_with(suffix))
+                || item_enum.variants.len() >= GROWING_ENUM_VARIANTS;
+            if !growing {
+                continue;
+            }
+
+            let messages = if lang == "zh-CN" {
+                vec![
+                    format!("公开枚举 {name} 没有 #[non_exhaustive]，下次加变体就是一次破坏性更新"),
+                    format!("{name} 看起来还会继续长，加上 #[non_exhaustive]，别让下游的 match 一升级就编译失败"),
+                    format!("{name} 少了 #[non_exhaustive]，你的下一个小版本会变成大版本"),
+                ]
+            } else {
+                vec![
+                    format!("pub enum {name} lacks #[non_exhaustive], adding a variant will be a breaking change"),
+                    format!("{name} looks like it will keep growing - add #[non_exhaustive] before downstream matches break"),
+                    format!("no #[non_exhaustive] on {name}, your next minor release just became a major one"),
+                ]
+            };
+
+            issues.push(rule_issue(
+                file_path,
+                get_position(&item_enum.ident),
+                "missing-non-exhaustive",
+                messages[issues.len() % messages.len()].clone(),
+                Severity::Mild,
+            ));
+        }
+
+        issues
+    }
+
+    fn missing_debug(&self, file_path: &Path, lang: &str) -> Vec<CodeIssue> {
+        let mut issues = Vec::new();
+
+        let types = self
+            .pub_structs
+            .iter()
+            .map(|s| (&s.ident, &s.attrs, "struct"))
+            .chain(self.pub_enums.iter().map(|e| (&e.ident, &e.attrs, "enum")));
+
+        for (ident, attrs, kind) in types {
+            let name = ident.to_string();
+            if derived_traits(attrs).contains("Debug") || self.debug_impls.contains(&name) {
+                continue;
+            }
+
+            let messages = if lang == "zh-CN" {
+                vec![
+                    format!("公开 {kind} {name} 没有实现 Debug，用户想 {{:?}} 打印一下都不行"),
+                    format!("{name} 缺少 #[derive(Debug)]，包含它的类型也都没法 derive Debug 了"),
+                    format!("{name} 不能 Debug，调试你的库的人会在 dbg!() 面前碰壁"),
+                ]
+            } else {
+                vec![
+                    format!("pub {kind} {name} doesn't implement Debug, users can't even {{:?}} it"),
+                    format!("{name} is missing #[derive(Debug)], so nothing that contains it can derive Debug either"),
+                    format!("{name} can't be Debug-printed, people debugging with your library will hit a wall at dbg!()"),
+                ]
+            };
+
+            issues.push(rule_issue(
+                file_path,
+                get_position(ident),
+                "missing-debug",
+                messages[issues.len() % messages.len()].clone(),
+                Severity::Mild,
+            ));
+        }
+
+        issues
+    }
+
+    fn leaky_impl_traits(&self, file_path: &Path, lang: &str) -> Vec<CodeIssue> {
+        let mut issues = Vec::new();
+
+        for sig in &self.pub_fns {
+            let ReturnType::Type(_, ty) = &sig.output else {
+                continue;
+            };
+            let Type::ImplTrait(impl_trait) = ty.as_ref() else {
+                continue;
+            };
+            let traits: Vec<String> = impl_trait
+                .bounds
+                .iter()
+                .filter_map(|bound| match bound {
+                    TypeParamBound::Trait(t) => t.path.segments.last().map(|s| s.ident.to_string()),
+                    _ => None,
+                })
+                .collect();
+            if traits
+                .iter()
+                .any(|t| IDIOMATIC_IMPL_TRAITS.contains(&t.as_str()))
+            {
+                continue;
+            }
+            let name = &sig.ident;
+            let shown = traits.join(" + ");
+
+            let messages = if lang == "zh-CN" {
+                vec![
+                    format!("{name}() 返回 impl {shown}：调用者没法给这个类型起名字、存进结构体，它是不是 Send/Sync 还会悄悄成为 API 的一部分"),
+                    format!("impl {shown} 出现在公开返回值里，具体类型的自动 trait 会泄露出去，改实现就可能破坏下游"),
+                    format!("{name}() 的返回类型无法命名，考虑返回一个具名类型或 Box<dyn {shown}>"),
+                ]
+            } else {
+                vec![
+                    format!("{name}() returns impl {shown}: callers can't name it or store it in a struct, and its Send/Sync-ness silently becomes API"),
+                    format!("impl {shown} in a public return type leaks the concrete type's auto traits, changing the body can break downstream"),
+                    format!("the return type of {name}() can't be named, return a named type or Box<dyn {shown}>"),
+                ]
+            };
+
+            issues.push(rule_issue(
+                file_path,
+                get_position(ty.as_ref()),
+                "leaky-impl-trait",
+                messages[issues.len() % messages.len()].clone(),
+                Severity::Mild,
+            ));
+        }
+
+        issues
+    }
+}
+
+impl<'ast> Visit<'ast> for ApiSurface<'ast> {
+    fn visit_item_fn(&mut self, item_fn: &'ast ItemFn) {
+        if is_public(&item_fn.vis) {
+            self.pub_fns.push(&item_fn.sig);
+        }
+        syn::visit::visit_item_fn(self, item_fn);
+    }
+
+    fn visit_item_impl(&mut self, item_impl: &'ast ItemImpl) {
+        let self_name = type_name(&item_impl.self_ty);
+
+        match (&item_impl.trait_, self_name) {
+            (Some((_, trait_path, _)), Some(self_name))
+                if trait_path
+                    .segments
+                    .last()
+                    .is_some_and(|s| s.ident == "Debug") =>
+            {
+                self.debug_impls.insert(self_name);
+            }
+            // trait 实现的方法签名由 trait 决定，只看固有 impl
+            (None, self_name) => {
+                for item in &item_impl.items {
+                    let ImplItem::Fn(impl_fn) = item else {
+                        continue;
+                    };
+                    if is_public(&impl_fn.vis) {
+                        self.pub_fns.push(&impl_fn.sig);
+                    }
+                    if let Some(self_name) = &self_name {
+                        if returns_self(&impl_fn.sig, self_name) && validates(&impl_fn.block) {
+                            self.validating_ctors
+                                .entry(self_name.clone())
+                                .or_insert_with(|| impl_fn.sig.ident.to_string());
+                        }
+                    }
+                }
+            }
+            _ => {}
+        }
+
+        syn::visit::visit_item_impl(self, item_impl);
+    }
+
+    fn visit_item_trait(&mut self, item_trait: &'ast ItemTrait) {
+        if is_public(&item_trait.vis) {
+            for item in &item_trait.items {
+                if let TraitItem::Fn(trait_fn) = item {
+                    self.pub_fns.push(&trait_fn.sig);
+                }
+            }
+        }
+        syn::visit::visit_item_trait(self, item_trait);
+    }
+
+    fn visit_item_struct(&mut self, item_struct: &'ast ItemStruct) {
+        if is_public(&item_struct.vis) {
+            self.pub_structs.push(item_struct);
+        }
+        syn::visit::visit_item_struct(self, item_struct);
+    }
+
+    fn visit_item_enum(&mut self, item_enum: &'ast ItemEnum) {
+        if is_public(&item_enum.vis) {
+            self.pub_enums.push(item_enum);
+        }
+        syn::visit::visit_item_enum(self, item_enum);
+    }
+}
+
+/// 除 self 以外的参数类型
+fn typed_params(sig: &Signature) -> impl Iterator<Item = &Type> {
+    sig.inputs.iter().filter_map(|arg| match arg {
+        FnArg::Typed(pat_type) => Some(pat_type.ty.as_ref()),
+        FnArg::Receiver(_) => None,
+    })
+}
+
+/// String -> (String, &str)，Vec<T> -> (Vec<T>, &[T])，Box<T> -> (Box<T>, &T)
+fn borrowed_form(ty: &Type) -> Option<(String, String)> {
+    let Type::Path(type_path) = ty else {
+        return None;
+    };
+    let segment = type_path.path.segments.last()?;
+    let inner = match &segment.arguments {
+        PathArguments::AngleBracketed(args) => args.args.iter().find_map(|arg| match arg {
+            GenericArgument::Type(inner) => Some(type_to_string(inner)),
+            _ => None,
+        }),
+        _ => None,
+    };
+
+    match (segment.ident.to_string().as_str(), inner) {
+        ("String", None) => Some(("String".to_string(), "&str".to_string())),
+        ("Vec", Some(inner)) => Some((format!("Vec<{inner}>"), format!("&[{inner}]"))),
+        // &Box<dyn Trait> 改成 &dyn Trait
+        ("Box", Some(inner)) => Some((format!("Box<{inner}>"), format!("&{inner}"))),
+        _ => None,
+    }
+}
+
+/// 返回 Self、类型本身，或者包在 Result / Option 里的它们
+fn returns_self(sig: &Signature, self_name: &str) -> bool {
+    let ReturnType::Type(_, ty) = &sig.output else {
+        return false;
+    };
+    let Type::Path(type_path) = ty.as_ref() else {
+        return false;
+    };
+    let Some(segment) = type_path.path.segments.last() else {
+        return false;
+    };
+    if segment.ident == "Self" || segment.ident == self_name {
+        return true;
+    }
+    if segment.ident != "Result" && segment.ident != "Option" {
+        return false;
+    }
+    let PathArguments::AngleBracketed(args) = &segment.arguments else {
+        return false;
+    };
+    matches!(args.args.first(), Some(GenericArgument::Type(inner))
+        if type_name(inner).is_some_and(|name| name == "Self" || name == self_name))
+}
+
+/// 构造函数里有没有校验：if、match、?、assert!/ensure!/panic! 或者 Err(..)
+fn validates(block: &Block) -> bool {
+    let mut finder = ValidationFinder::default();
+    finder.visit_block(block);
+    finder.found
+}
+
+#[derive(Default)]
+struct ValidationFinder {
+    found: bool,
+}
+
+impl<'ast> Visit<'ast> for ValidationFinder {
+    fn visit_expr(&mut self, expr: &'ast Expr) {
+        match expr {
+            Expr::If(_) | Expr::Match(_) | Expr::Try(_) => self.found = true,
+            Expr::Call(call) => {
+                if let Expr::Path(func) = call.func.as_ref() {
+                    if func.path.is_ident("Err") {
+                        self.found = true;
+                    }
+                }
+            }
+            _ => {}
+        }
+        syn::visit::visit_expr(self, expr);
+    }
+
+    fn visit_macro(&mut self, mac: &'ast Macro) {
+        let name = mac.path.segments.last().map(|s| s.ident.to_string());
+        if name.is_some_and(|name| {
+            name.starts_with("assert") || matches!(name.as_str(), "ensure" | "bail" | "panic")
+        }) {
+            self.found = true;
+        }
+    }
+}
+
+fn has_attr(attrs: &[Attribute], name: &str) -> bool {
+    attrs.iter().any(|attr| attr.path().is_ident(name))
+}
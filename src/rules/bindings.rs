@@ -0,0 +1,558 @@
+use proc_macro2::{Spacing, TokenStream, TokenTree};
+use quote::ToTokens;
+use std::collections::HashMap;
+use syn::{
+    visit::Visit, Arm, Block, ConstParam, ExprClosure, ExprForLoop, ExprIf, ExprLet, ExprPath,
+    ExprStruct, ExprWhile, Fields, File, FnArg, Ident, ImplItem, ImplItemFn, ItemConst, ItemEnum,
+    ItemFn, ItemForeignMod, ItemImpl, ItemMod, ItemStatic, ItemStruct, ItemTrait, ItemType,
+    ItemUnion, Local, Macro, Member, Pat, PatStruct, Signature, TraitItem, TraitItemFn, TypeParam,
+};
+
+use crate::utils::get_position;
+
+/// 命名类规则只看"起名字"的地方：let 绑定、参数、字段、条目、泛型和闭包参数。
+/// 使用处不重复报告，第三方类型的字段名、trait 规定的方法名也不算在我们头上。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum BindingKind {
+    Variable,
+    Parameter,
+    ClosureParameter,
+    Field,
+    Function,
+    Method,
+    Type,
+    Variant,
+    Constant,
+    Module,
+    Generic,
+}
+
+impl BindingKind {
+    pub(crate) fn label(self, lang: &str) -> &'static str {
+        let zh = lang == "zh-CN";
+        match self {
+            BindingKind::Variable if zh => "变量",
+            BindingKind::Variable => "Variable",
+            BindingKind::Parameter if zh => "参数",
+            BindingKind::Parameter => "Parameter",
+            BindingKind::ClosureParameter if zh => "闭包参数",
+            BindingKind::ClosureParameter => "Closure parameter",
+            BindingKind::Field if zh => "字段",
+            BindingKind::Field => "Field",
+            BindingKind::Function if zh => "函数",
+            BindingKind::Function => "Function",
+            BindingKind::Method if zh => "方法",
+            BindingKind::Method => "Method",
+            BindingKind::Type if zh => "类型",
+            BindingKind::Type => "Type",
+            BindingKind::Variant if zh => "枚举变体",
+            BindingKind::Variant => "Variant",
+            BindingKind::Constant if zh => "常量",
+            BindingKind::Constant => "Constant",
+            BindingKind::Module if zh => "模块",
+            BindingKind::Module => "Module",
+            BindingKind::Generic if zh => "泛型参数",
+            BindingKind::Generic => "Generic",
+        }
+    }
+
+    /// 函数体内的局部绑定
+    pub(crate) fn is_local(self) -> bool {
+        matches!(
+            self,
+            BindingKind::Variable | BindingKind::Parameter | BindingKind::ClosureParameter
+        )
+    }
+}
+
+/// 一次声明，`uses` 是它自己被使用的次数（不含声明本身）：局部绑定按最近的声明解析，
+/// 被遮蔽的同名绑定各算各的；字段只算 `.field` 和结构体字面量、模式里的字段名
+#[derive(Debug, Clone)]
+pub(crate) struct Binding {
+    pub name: String,
+    pub kind: BindingKind,
+    pub line: usize,
+    pub column: usize,
+    pub uses: usize,
+}
+
+impl Binding {
+    /// 附在问题描述后面的使用次数
+    pub(crate) fn uses_note(&self, lang: &str) -> String {
+        match (lang == "zh-CN", self.uses) {
+            (true, uses) => format!("（声明后用了 {} 次）", uses),
+            (false, 1) => " (used once)".to_string(),
+            (false, uses) => format!(" (used {} times)", uses),
+        }
+    }
+}
+
+pub(crate) fn collect_bindings(syntax_tree: &File) -> Vec<Binding> {
+    let mut field_names = FieldNames::default();
+    field_names.visit_file(syntax_tree);
+    let mut collector = BindingCollector {
+        bindings: Vec::new(),
+        binding_scopes: Vec::new(),
+        scopes: vec![count_idents(syntax_tree)],
+        current: 0,
+        locals: Vec::new(),
+        field_names: field_names.0,
+    };
+    collector.visit_file(syntax_tree);
+    collector.finish()
+}
+
+/// 作用域里的标识符出现次数，`.` 后面的字段和方法名单独计数
+#[derive(Default)]
+struct IdentCounts {
+    plain: HashMap<String, usize>,
+    members: HashMap<String, usize>,
+}
+
+impl IdentCounts {
+    fn get(&self, name: &str, kind: BindingKind) -> usize {
+        let plain = self.plain.get(name).copied().unwrap_or(0);
+        // `x.len()` 用的是方法，`Self::len` 这样的路径也算
+        match kind {
+            BindingKind::Method => plain + self.members.get(name).copied().unwrap_or(0),
+            _ => plain,
+        }
+    }
+}
+
+/// 结构体字面量和结构体模式里写出来的字段名：`Foo { data: 1 }`、`let Foo { data, .. } = foo`
+#[derive(Default)]
+struct FieldNames(HashMap<String, usize>);
+
+impl FieldNames {
+    fn add(&mut self, member: &Member) {
+        if let Member::Named(ident) = member {
+            *self.0.entry(unraw(ident)).or_default() += 1;
+        }
+    }
+}
+
+impl<'ast> Visit<'ast> for FieldNames {
+    fn visit_expr_struct(&mut self, expr: &'ast ExprStruct) {
+        expr.fields.iter().for_each(|field| self.add(&field.member));
+        syn::visit::visit_expr_struct(self, expr);
+    }
+
+    fn visit_pat_struct(&mut self, pat: &'ast PatStruct) {
+        pat.fields.iter().for_each(|field| self.add(&field.member));
+        syn::visit::visit_pat_struct(self, pat);
+    }
+}
+
+fn count_idents<T: ToTokens>(node: &T) -> IdentCounts {
+    fn walk(tokens: TokenStream, counts: &mut IdentCounts) {
+        let mut after_dot = false;
+        for tree in tokens {
+            match &tree {
+                TokenTree::Ident(ident) => {
+                    let map = if after_dot {
+                        &mut counts.members
+                    } else {
+                        &mut counts.plain
+                    };
+                    *map.entry(unraw(ident)).or_default() += 1;
+                }
+                TokenTree::Group(group) => walk(group.stream(), counts),
+                TokenTree::Punct(_) | TokenTree::Literal(_) => {}
+            }
+            // `..` 是区间，不是成员访问
+            after_dot = matches!(&tree, TokenTree::Punct(punct)
+                if punct.as_char() == '.' && punct.spacing() == Spacing::Alone);
+        }
+    }
+
+    let mut counts = IdentCounts::default();
+    walk(node.to_token_stream(), &mut counts);
+    counts
+}
+
+fn unraw(ident: &Ident) -> String {
+    let name = ident.to_string();
+    name.strip_prefix("r#").map(str::to_string).unwrap_or(name)
+}
+
+struct BindingCollector {
+    bindings: Vec<Binding>,
+    /// 每个绑定所属作用域在 `scopes` 里的下标
+    binding_scopes: Vec<usize>,
+    /// 作用域内的标识符计数：0 是整个文件，其余是各个函数
+    scopes: Vec<IdentCounts>,
+    current: usize,
+    /// 当前函数里的块作用域，每层是按顺序声明的局部名字和对应的绑定下标；
+    /// match 分支、if let 之类只遮蔽名字、不报告的绑定没有下标
+    locals: Vec<Vec<(String, Option<usize>)>>,
+    field_names: HashMap<String, usize>,
+}
+
+impl BindingCollector {
+    fn push(&mut self, ident: &Ident, kind: BindingKind) {
+        let (line, column) = get_position(ident);
+        self.bindings.push(Binding {
+            name: unraw(ident),
+            kind,
+            line,
+            column,
+            uses: 0,
+        });
+        self.binding_scopes.push(self.current);
+        if kind.is_local() {
+            self.declare(unraw(ident), Some(self.bindings.len() - 1));
+        }
+    }
+
+    fn declare(&mut self, name: String, binding: Option<usize>) {
+        if let Some(scope) = self.locals.last_mut() {
+            scope.push((name, binding));
+        }
+    }
+
+    /// 模式里的名字只遮蔽外层同名绑定，不作为新绑定报告
+    fn shadow_pat(&mut self, pat: &Pat) {
+        match pat {
+            Pat::Ident(pat_ident) => {
+                self.declare(unraw(&pat_ident.ident), None);
+                if let Some((_, subpat)) = &pat_ident.subpat {
+                    self.shadow_pat(subpat);
+                }
+            }
+            Pat::Tuple(tuple) => tuple.elems.iter().for_each(|p| self.shadow_pat(p)),
+            Pat::TupleStruct(tuple) => tuple.elems.iter().for_each(|p| self.shadow_pat(p)),
+            Pat::Slice(slice) => slice.elems.iter().for_each(|p| self.shadow_pat(p)),
+            Pat::Struct(pat_struct) => pat_struct
+                .fields
+                .iter()
+                .for_each(|field| self.shadow_pat(&field.pat)),
+            Pat::Or(or) => or.cases.iter().for_each(|p| self.shadow_pat(p)),
+            Pat::Reference(reference) => self.shadow_pat(&reference.pat),
+            Pat::Type(pat_type) => self.shadow_pat(&pat_type.pat),
+            Pat::Paren(paren) => self.shadow_pat(&paren.pat),
+            _ => {}
+        }
+    }
+
+    /// 名字解析到最近的一次声明，是要报告的局部绑定就记一次使用
+    fn use_local(&mut self, name: &str) {
+        let declared = self
+            .locals
+            .iter()
+            .rev()
+            .flat_map(|scope| scope.iter().rev())
+            .find(|(declared, _)| declared == name);
+        if let Some(&(_, Some(index))) = declared {
+            self.bindings[index].uses += 1;
+        }
+    }
+
+    fn in_local_scope(&mut self, visit: impl FnOnce(&mut Self)) {
+        self.locals.push(Vec::new());
+        visit(self);
+        self.locals.pop();
+    }
+
+    fn push_pat(&mut self, pat: &Pat, kind: BindingKind) {
+        match pat {
+            Pat::Ident(pat_ident) => {
+                // 大写开头的是单元结构体或常量模式，不是新名字
+                let is_path_like = pat_ident
+                    .ident
+                    .to_string()
+                    .starts_with(|c: char| c.is_uppercase());
+                if pat_ident.ident != "self" && !is_path_like {
+                    self.push(&pat_ident.ident, kind);
+                }
+                if let Some((_, subpat)) = &pat_ident.subpat {
+                    self.push_pat(subpat, kind);
+                }
+            }
+            Pat::Tuple(tuple) => tuple.elems.iter().for_each(|p| self.push_pat(p, kind)),
+            Pat::TupleStruct(tuple) => tuple.elems.iter().for_each(|p| self.push_pat(p, kind)),
+            Pat::Slice(slice) => slice.elems.iter().for_each(|p| self.push_pat(p, kind)),
+            // `Foo { data, .. }` 的简写字段名由类型决定，只看改过名的绑定，简写的只遮蔽
+            Pat::Struct(pat_struct) => {
+                for field in &pat_struct.fields {
+                    if field.colon_token.is_some() {
+                        self.push_pat(&field.pat, kind);
+                    } else {
+                        self.shadow_pat(&field.pat);
+                    }
+                }
+            }
+            // 每个分支绑定的是同一批名字
+            Pat::Or(or) => {
+                if let Some(first) = or.cases.first() {
+                    self.push_pat(first, kind);
+                }
+            }
+            Pat::Reference(reference) => self.push_pat(&reference.pat, kind),
+            Pat::Type(pat_type) => self.push_pat(&pat_type.pat, kind),
+            Pat::Paren(paren) => self.push_pat(&paren.pat, kind),
+            _ => {}
+        }
+    }
+
+    fn push_fields(&mut self, fields: &Fields) {
+        for field in fields {
+            if let Some(ident) = &field.ident {
+                self.push(ident, BindingKind::Field);
+            }
+        }
+    }
+
+    fn push_params(&mut self, sig: &Signature) {
+        for input in &sig.inputs {
+            if let FnArg::Typed(pat_type) = input {
+                self.push_pat(&pat_type.pat, BindingKind::Parameter);
+            }
+        }
+    }
+
+    /// 在函数自己的作用域里访问签名和函数体
+    fn in_fn_scope<T: ToTokens>(&mut self, node: &T, visit: impl FnOnce(&mut Self)) {
+        let outer = self.current;
+        self.scopes.push(count_idents(node));
+        self.current = self.scopes.len() - 1;
+        // 嵌套的函数看不到外层函数的局部变量
+        let outer_locals = std::mem::replace(&mut self.locals, vec![Vec::new()]);
+        visit(self);
+        self.locals = outer_locals;
+        self.current = outer;
+    }
+
+    fn finish(mut self) -> Vec<Binding> {
+        let mut declarations: HashMap<(usize, String), usize> = HashMap::new();
+        for (binding, &scope) in self.bindings.iter().zip(&self.binding_scopes) {
+            *declarations
+                .entry((scope, binding.name.clone()))
+                .or_default() += 1;
+        }
+
+        for (binding, &scope) in self.bindings.iter_mut().zip(&self.binding_scopes) {
+            if binding.kind.is_local() {
+                // 局部绑定在访问时已经逐个解析计数
+                continue;
+            }
+            if binding.kind == BindingKind::Field {
+                let members = self.scopes[0].members.get(&binding.name).copied();
+                let literals = self.field_names.get(&binding.name).copied();
+                binding.uses = members.unwrap_or(0) + literals.unwrap_or(0);
+                continue;
+            }
+            let seen = self.scopes[scope].get(&binding.name, binding.kind);
+            let declared = declarations[&(scope, binding.name.clone())];
+            binding.uses = seen.saturating_sub(declared);
+        }
+
+        self.bindings
+    }
+}
+
+impl<'ast> Visit<'ast> for BindingCollector {
+    fn visit_local(&mut self, local: &'ast Local) {
+        // `let x = x + 1;` 右边的 x 还是上一个 x
+        if let Some(init) = &local.init {
+            self.visit_expr(&init.expr);
+            if let Some((_, diverge)) = &init.diverge {
+                self.visit_expr(diverge);
+            }
+        }
+        self.push_pat(&local.pat, BindingKind::Variable);
+    }
+
+    fn visit_expr_closure(&mut self, closure: &'ast ExprClosure) {
+        self.in_local_scope(|this| {
+            for input in &closure.inputs {
+                this.push_pat(input, BindingKind::ClosureParameter);
+            }
+            this.visit_expr(&closure.body);
+        });
+    }
+
+    fn visit_block(&mut self, block: &'ast Block) {
+        self.in_local_scope(|this| syn::visit::visit_block(this, block));
+    }
+
+    fn visit_arm(&mut self, arm: &'ast Arm) {
+        self.in_local_scope(|this| {
+            this.shadow_pat(&arm.pat);
+            if let Some((_, guard)) = &arm.guard {
+                this.visit_expr(guard);
+            }
+            this.visit_expr(&arm.body);
+        });
+    }
+
+    fn visit_expr_let(&mut self, expr: &'ast ExprLet) {
+        self.visit_expr(&expr.expr);
+        self.shadow_pat(&expr.pat);
+    }
+
+    fn visit_expr_if(&mut self, expr: &'ast ExprIf) {
+        // if let 绑定的名字只在 then 分支里可见
+        self.in_local_scope(|this| {
+            this.visit_expr(&expr.cond);
+            this.visit_block(&expr.then_branch);
+        });
+        if let Some((_, else_branch)) = &expr.else_branch {
+            self.visit_expr(else_branch);
+        }
+    }
+
+    fn visit_expr_while(&mut self, expr: &'ast ExprWhile) {
+        self.in_local_scope(|this| {
+            this.visit_expr(&expr.cond);
+            this.visit_block(&expr.body);
+        });
+    }
+
+    fn visit_expr_for_loop(&mut self, expr: &'ast ExprForLoop) {
+        self.visit_expr(&expr.expr);
+        self.in_local_scope(|this| {
+            this.shadow_pat(&expr.pat);
+            this.visit_block(&expr.body);
+        });
+    }
+
+    fn visit_expr_path(&mut self, expr: &'ast ExprPath) {
+        if expr.qself.is_none() {
+            if let Some(ident) = expr.path.get_ident() {
+                self.use_local(&unraw(ident));
+            }
+        }
+        syn::visit::visit_expr_path(self, expr);
+    }
+
+    /// 宏参数按标识符逐个解析，`.` 后面的是成员不是变量
+    fn visit_macro(&mut self, mac: &'ast Macro) {
+        fn walk(collector: &mut BindingCollector, tokens: TokenStream) {
+            let mut after_dot = false;
+            for tree in tokens {
+                match &tree {
+                    TokenTree::Ident(ident) if !after_dot => collector.use_local(&unraw(ident)),
+                    TokenTree::Group(group) => walk(collector, group.stream()),
+                    _ => {}
+                }
+                after_dot = matches!(&tree, TokenTree::Punct(punct)
+                    if punct.as_char() == '.' && punct.spacing() == Spacing::Alone);
+            }
+        }
+        walk(self, mac.tokens.clone());
+    }
+
+    fn visit_type_param(&mut self, param: &'ast TypeParam) {
+        self.push(&param.ident, BindingKind::Generic);
+        syn::visit::visit_type_param(self, param);
+    }
+
+    fn visit_const_param(&mut self, param: &'ast ConstParam) {
+        self.push(&param.ident, BindingKind::Generic);
+        syn::visit::visit_const_param(self, param);
+    }
+
+    fn visit_item_fn(&mut self, func: &'ast ItemFn) {
+        self.push(&func.sig.ident, BindingKind::Function);
+        self.in_fn_scope(func, |this| {
+            this.push_params(&func.sig);
+            syn::visit::visit_item_fn(this, func);
+        });
+    }
+
+    fn visit_item_impl(&mut self, item: &'ast ItemImpl) {
+        // trait 实现里的方法名和关联项名字由 trait 决定
+        let names_are_ours = item.trait_.is_none();
+        for impl_item in &item.items {
+            match impl_item {
+                ImplItem::Const(constant) if names_are_ours => {
+                    self.push(&constant.ident, BindingKind::Constant)
+                }
+                ImplItem::Type(assoc) if names_are_ours => {
+                    self.push(&assoc.ident, BindingKind::Type)
+                }
+                ImplItem::Fn(method) if names_are_ours => {
+                    self.push(&method.sig.ident, BindingKind::Method)
+                }
+                _ => {}
+            }
+        }
+        syn::visit::visit_item_impl(self, item);
+    }
+
+    fn visit_impl_item_fn(&mut self, method: &'ast ImplItemFn) {
+        self.in_fn_scope(method, |this| {
+            this.push_params(&method.sig);
+            syn::visit::visit_impl_item_fn(this, method);
+        });
+    }
+
+    fn visit_item_trait(&mut self, item: &'ast ItemTrait) {
+        self.push(&item.ident, BindingKind::Type);
+        for trait_item in &item.items {
+            match trait_item {
+                TraitItem::Const(constant) => self.push(&constant.ident, BindingKind::Constant),
+                TraitItem::Type(assoc) => self.push(&assoc.ident, BindingKind::Type),
+                TraitItem::Fn(method) => self.push(&method.sig.ident, BindingKind::Method),
+                _ => {}
+            }
+        }
+        syn::visit::visit_item_trait(self, item);
+    }
+
+    fn visit_trait_item_fn(&mut self, method: &'ast TraitItemFn) {
+        self.in_fn_scope(method, |this| {
+            this.push_params(&method.sig);
+            syn::visit::visit_trait_item_fn(this, method);
+        });
+    }
+
+    fn visit_item_struct(&mut self, item: &'ast ItemStruct) {
+        self.push(&item.ident, BindingKind::Type);
+        self.push_fields(&item.fields);
+        syn::visit::visit_item_struct(self, item);
+    }
+
+    fn visit_item_enum(&mut self, item: &'ast ItemEnum) {
+        self.push(&item.ident, BindingKind::Type);
+        for variant in &item.variants {
+            self.push(&variant.ident, BindingKind::Variant);
+            self.push_fields(&variant.fields);
+        }
+        syn::visit::visit_item_enum(self, item);
+    }
+
+    fn visit_item_union(&mut self, item: &'ast ItemUnion) {
+        self.push(&item.ident, BindingKind::Type);
+        for field in &item.fields.named {
+            if let Some(ident) = &field.ident {
+                self.push(ident, BindingKind::Field);
+            }
+        }
+        syn::visit::visit_item_union(self, item);
+    }
+
+    fn visit_item_type(&mut self, item: &'ast ItemType) {
+        self.push(&item.ident, BindingKind::Type);
+        syn::visit::visit_item_type(self, item);
+    }
+
+    fn visit_item_const(&mut self, item: &'ast ItemConst) {
+        self.push(&item.ident, BindingKind::Constant);
+        syn::visit::visit_item_const(self, item);
+    }
+
+    fn visit_item_static(&mut self, item: &'ast ItemStatic) {
+        self.push(&item.ident, BindingKind::Constant);
+        syn::visit::visit_item_static(self, item);
+    }
+
+    fn visit_item_mod(&mut self, item: &'ast ItemMod) {
+        self.push(&item.ident, BindingKind::Module);
+        syn::visit::visit_item_mod(self, item);
+    }
+
+    // extern 块里的名字由 C 那边决定
+    fn visit_item_foreign_mod(&mut self, _item: &'ast ItemForeignMod) {}
+}
@@ -1,18 +1,50 @@
-use std::path::Path;
-use syn::{visit::Visit, ExprLit, File, ItemFn, Lit};
+use proc_macro2::{Ident, TokenStream, TokenTree};
+use quote::ToTokens;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use syn::{
+    spanned::Spanned, visit::Visit, AttrStyle, Attribute, BinOp, Block, Expr, ExprAssign,
+    ExprBinary, ExprBreak, ExprClosure, ExprForLoop, ExprIf, ExprLit, ExprLoop, ExprRange,
+    ExprRepeat, ExprWhile, FieldValue, File, ImplItemConst, Item, ItemConst, ItemEnum, ItemFn,
+    ItemMod, ItemStatic, ItemStruct, Lit, Local, Macro, Member, Pat, Stmt, TraitItemConst,
+    TypeArray, Variant, Visibility,
+};
 
-use crate::analyzer::{CodeIssue, RoastLevel, Severity};
-use crate::rules::Rule;
+use crate::analyzer::{CodeContext, CodeIssue, RoastLevel, Severity};
+use crate::rules::common::rule_issue;
+use crate::rules::context::{is_cfg_test_attr, is_test_attr};
+use crate::rules::macro_args::visit_macro_args;
+use crate::rules::{ContextPolicy, Rule};
 use crate::utils::get_position;
 
 /// 检测魔法数字（硬编码的数字常量）
-pub struct MagicNumberRule;
+pub struct MagicNumberRule {
+    allowed: Vec<i64>,
+}
+
+impl MagicNumberRule {
+    /// `allowed` 是用户额外放行的数字，内置的常见值不需要重复配置
+    pub fn new(allowed: &[i64]) -> Self {
+        Self {
+            allowed: allowed.to_vec(),
+        }
+    }
+}
 
 impl Rule for MagicNumberRule {
     fn name(&self) -> &'static str {
         "magic-number"
     }
 
+    fn context_policy(&self, context: CodeContext) -> ContextPolicy {
+        match context {
+            // 测试里的期望值写成字面量反而更直观
+            CodeContext::Test => ContextPolicy::Ignore,
+            CodeContext::Example | CodeContext::Bench => ContextPolicy::Downgrade,
+            CodeContext::Production | CodeContext::BuildScript => ContextPolicy::Report,
+        }
+    }
+
     fn check(
         &self,
         file_path: &Path,
@@ -20,9 +52,8 @@ impl Rule for MagicNumberRule {
         _content: &str,
         lang: &str,
     ) -> Vec<CodeIssue> {
-        let mut visitor = MagicNumberVisitor::new(file_path.to_path_buf(), lang);
-        visitor.visit_file(syntax_tree);
-        visitor.issues
+        let occurrences = collect_magic_numbers(file_path, syntax_tree, &self.allowed);
+        magic_number_issues(&occurrences, lang)
     }
 }
 
@@ -62,65 +93,17 @@ impl Rule for CommentedCodeRule {
         content: &str,
         lang: &str,
     ) -> Vec<CodeIssue> {
-        let mut issues = Vec::new();
-        let lines: Vec<&str> = content.lines().collect();
-
-        let mut _commented_code_blocks = 0;
-        let mut current_block_size = 0;
-
-        for (line_num, line) in lines.iter().enumerate() {
-            let trimmed = line.trim();
-
-            // 检测被注释的代码行
-            if trimmed.starts_with("//") {
-                let comment_content = trimmed.trim_start_matches("//").trim();
-
-                // 检测是否像代码（包含常见的代码模式）
-                if is_likely_code(comment_content) {
-                    current_block_size += 1;
-                } else if current_block_size > 0 {
-                    // 结束一个代码块
-                    if current_block_size >= 3 {
-                        _commented_code_blocks += 1;
-                        issues.push(create_commented_code_issue(
-                            file_path,
-                            line_num + 1 - current_block_size,
-                            current_block_size,
-                            lang,
-                        ));
-                    }
-                    current_block_size = 0;
-                }
-            } else if current_block_size > 0 {
-                // 非注释行，结束当前块
-                if current_block_size >= 3 {
-                    _commented_code_blocks += 1;
-                    issues.push(create_commented_code_issue(
-                        file_path,
-                        line_num - current_block_size,
-                        current_block_size,
-                        lang,
-                    ));
-                }
-                current_block_size = 0;
-            }
-        }
-
-        // 处理文件末尾的代码块
-        if current_block_size >= 3 {
-            issues.push(create_commented_code_issue(
-                file_path,
-                lines.len() - current_block_size,
-                current_block_size,
-                lang,
-            ));
-        }
-
-        issues
+        comment_blocks(content)
+            .iter()
+            .flat_map(|block| commented_code_runs(block))
+            .map(|(start, end, column)| {
+                create_commented_code_issue(file_path, start, end, column, lang)
+            })
+            .collect()
     }
 }
 
-/// 检测明显的死代码
+/// 检测死代码：永远到不了的语句、恒真恒假的条件、没人用的私有条目
 pub struct DeadCodeRule;
 
 impl Rule for DeadCodeRule {
@@ -131,49 +114,24 @@ impl Rule for DeadCodeRule {
     fn check(
         &self,
         file_path: &Path,
-        _syntax_tree: &File,
-        content: &str,
+        syntax_tree: &File,
+        _content: &str,
         lang: &str,
     ) -> Vec<CodeIssue> {
-        let mut issues = Vec::new();
-        let lines: Vec<&str> = content.lines().collect();
-
-        for (line_num, line) in lines.iter().enumerate() {
-            let trimmed = line.trim();
-
-            // 检测明显的死代码模式
-            if is_dead_code_pattern(trimmed) {
-                let messages = if lang == "zh-CN" {
-                    vec![
-                        "发现死代码，这行永远不会执行",
-                        "这行代码比我的社交生活还死",
-                        "死代码警告：这里是代码的坟墓",
-                        "这行代码已经去世了，建议删除",
-                        "发现僵尸代码，需要清理",
-                    ]
-                } else {
-                    vec![
-                        "Dead code detected - this line will never execute",
-                        "This code is deader than my social life",
-                        "Dead code alert: code graveyard found here",
-                        "This line of code has passed away, consider removal",
-                        "Zombie code detected, cleanup needed",
-                    ]
-                };
-
-                issues.push(CodeIssue {
-                    file_path: file_path.to_path_buf(),
-                    line: line_num + 1,
-                    column: 1,
-                    rule_name: "dead-code".to_string(),
-                    message: messages[line_num % messages.len()].to_string(),
-                    severity: Severity::Mild,
-                    roast_level: RoastLevel::Sarcastic,
-                });
+        let mut visitor = DeadCodeVisitor::new(file_path.to_path_buf(), lang);
+        visitor.visit_file(syntax_tree);
+
+        // 有外部子模块（`mod foo;`）的文件，私有条目可能在子模块里被用到，留给项目级检查
+        let census = DeadCodeCensus::collect(file_path, syntax_tree);
+        if !census.has_child_files {
+            for item in census.unused_items(|_| false) {
+                visitor
+                    .issues
+                    .push(create_unused_item_issue(file_path, item, lang));
             }
         }
 
-        issues
+        visitor.issues
     }
 }
 
@@ -181,39 +139,165 @@ impl Rule for DeadCodeRule {
 // 辅助函数
 // ============================================================================
 
-fn is_likely_code(content: &str) -> bool {
-    // 检测是否像代码的模式
-    let code_patterns = [
-        "let ", "fn ", "if ", "else", "for ", "while ", "match ", "struct ", "enum ", "impl ",
-        "use ", "mod ", "return ", "break", "continue", "{", "}", "(", ")", "[", "]", ";", "=",
-        "==", "!=", "&&", "||", "->", "::",
-    ];
+/// 至少这么多行能解析成代码，才算被注释掉的代码块
+const MIN_COMMENTED_CODE_LINES: usize = 2;
 
-    let rust_keywords = [
-        "pub", "const", "static", "mut", "ref", "move", "async", "await", "unsafe", "extern",
-        "crate",
-    ];
+/// 一次最多往后看这么多行，更长的代码块分段找出来再合并
+const MAX_COMMENTED_CODE_LINES: usize = 50;
 
-    // 如果包含多个代码模式，很可能是代码
-    let pattern_count = code_patterns
-        .iter()
-        .filter(|&&pattern| content.contains(pattern))
-        .count();
+/// 一行普通 `//` 注释：行号（从 1 开始）、`//` 所在列、去掉 `//` 之后的内容
+struct CommentLine<'a> {
+    line: usize,
+    column: usize,
+    text: &'a str,
+}
+
+/// 把连续的 `//` 行注释分成块，文档注释（`///`、`//!`）里的示例不算
+fn comment_blocks(content: &str) -> Vec<Vec<CommentLine<'_>>> {
+    let mut blocks = Vec::new();
+    let mut current = Vec::new();
 
-    let keyword_count = rust_keywords
-        .iter()
-        .filter(|&&keyword| content.contains(keyword))
-        .count();
+    for (index, line) in content.lines().enumerate() {
+        let trimmed = line.trim_start();
+        let is_doc = (trimmed.starts_with("///") && !trimmed.starts_with("////"))
+            || trimmed.starts_with("//!");
+        match trimmed.strip_prefix("//") {
+            Some(text) if !is_doc => current.push(CommentLine {
+                line: index + 1,
+                column: line.len() - trimmed.len() + 1,
+                text,
+            }),
+            _ if !current.is_empty() => blocks.push(std::mem::take(&mut current)),
+            _ => {}
+        }
+    }
+    if !current.is_empty() {
+        blocks.push(current);
+    }
+
+    blocks
+}
+
+/// 在一个注释块里找出能被 syn 解析成代码的最长连续片段，返回 (起始行, 结束行, 列)
+fn commented_code_runs(block: &[CommentLine]) -> Vec<(usize, usize, usize)> {
+    let deltas: Vec<i32> = block.iter().map(|l| delimiter_delta(l.text)).collect();
+    let mut runs: Vec<(usize, usize, usize)> = Vec::new();
+    let mut start = 0;
+
+    while start < block.len() {
+        if block[start].text.trim().is_empty() {
+            start += 1;
+            continue;
+        }
+
+        match longest_code_run(block, &deltas, start) {
+            Some(end) => {
+                let (first, last) = (block[start].line, block[end - 1].line);
+                match runs.last_mut() {
+                    // 超过长度上限被切开的代码块接回去
+                    Some(previous) if previous.1 + 1 == first => previous.1 = last,
+                    _ => runs.push((first, last, block[start].column)),
+                }
+                start = end;
+            }
+            None => start += 1,
+        }
+    }
 
-    pattern_count >= 2 || keyword_count >= 1
+    runs
+}
+
+/// 从 start 开始一行行往后扩展，只在括号配平的位置交给 syn 解析，解析失败就停。
+/// 散文通常第一行就解析不过，每个起点最多解析 MAX_COMMENTED_CODE_LINES 次
+fn longest_code_run(block: &[CommentLine], deltas: &[i32], start: usize) -> Option<usize> {
+    let mut depth = 0;
+    let mut code_lines = 0;
+    let mut longest = None;
+
+    for end in start + 1..=block.len().min(start + MAX_COMMENTED_CODE_LINES) {
+        depth += deltas[end - 1];
+        if depth < 0 {
+            break;
+        }
+        if block[end - 1].text.trim().is_empty() {
+            continue;
+        }
+        code_lines += 1;
+        if depth > 0 {
+            continue;
+        }
+        if !parses_as_code(&block[start..end]) {
+            break;
+        }
+        if code_lines >= MIN_COMMENTED_CODE_LINES {
+            longest = Some(end);
+        }
+    }
+
+    longest
+}
+
+/// 一行里开括号减去闭括号的数量，字符串里的不算
+fn delimiter_delta(text: &str) -> i32 {
+    let mut delta = 0;
+    let mut in_string = false;
+    let mut escaped = false;
+    for c in text.chars() {
+        if in_string {
+            match c {
+                _ if escaped => escaped = false,
+                '\\' => escaped = true,
+                '"' => in_string = false,
+                _ => {}
+            }
+            continue;
+        }
+        match c {
+            '"' => in_string = true,
+            '(' | '[' | '{' => delta += 1,
+            ')' | ']' | '}' => delta -= 1,
+            _ => {}
+        }
+    }
+    delta
+}
+
+/// 试着把注释内容当作语句或条目解析，散文和 ASCII 图解析不过去
+fn parses_as_code(lines: &[CommentLine]) -> bool {
+    let source: Vec<&str> = lines.iter().map(|l| l.text).collect();
+    let wrapped = format!("{{\n{}\n}}", source.join("\n"));
+    match syn::parse_str::<Block>(&wrapped) {
+        Ok(block) => block.stmts.iter().any(is_code_stmt),
+        Err(_) => false,
+    }
+}
+
+/// "Note (see above)" 也能解析成函数调用，所以只认真正像代码的语句
+fn is_code_stmt(stmt: &Stmt) -> bool {
+    match stmt {
+        Stmt::Local(_) | Stmt::Item(_) | Stmt::Macro(_) => true,
+        Stmt::Expr(expr, Some(_)) => !matches!(expr, Expr::Path(_) | Expr::Lit(_)),
+        Stmt::Expr(expr, None) => matches!(
+            expr,
+            Expr::If(_)
+                | Expr::ForLoop(_)
+                | Expr::While(_)
+                | Expr::Loop(_)
+                | Expr::Match(_)
+                | Expr::Block(_)
+                | Expr::Unsafe(_)
+        ),
+    }
 }
 
 fn create_commented_code_issue(
     file_path: &Path,
-    line: usize,
-    block_size: usize,
+    start: usize,
+    end: usize,
+    column: usize,
     lang: &str,
 ) -> CodeIssue {
+    let block_size = end - start + 1;
     let messages = if lang == "zh-CN" {
         vec![
             format!("发现 {} 行被注释的代码，是舍不得删除吗？", block_size),
@@ -253,44 +337,640 @@ fn create_commented_code_issue(
         Severity::Mild
     };
 
+    let span = if lang == "zh-CN" {
+        format!("（第 {}-{} 行）", start, end)
+    } else {
+        format!(" (lines {}-{})", start, end)
+    };
+
     CodeIssue {
         file_path: file_path.to_path_buf(),
-        line,
-        column: 1,
+        line: start,
+        column,
         rule_name: "commented-code".to_string(),
-        message: messages[block_size % messages.len()].clone(),
+        message: format!("{}{}", messages[block_size % messages.len()], span),
+        severity,
+        roast_level: RoastLevel::Sarcastic,
+        context: CodeContext::Production,
+    }
+}
+
+// ============================================================================
+// Visitor 实现
+// ============================================================================
+
+/// 同一个数字在一个文件里出现这么多次，就合并成一条更严重的问题
+const FILE_REPEAT_THRESHOLD: usize = 3;
+
+/// 常见的非魔法数字：计数、位宽、时间和角度换算，谁看都知道是什么意思
+const WELL_KNOWN_NUMBERS: &[i64] = &[
+    -1, 0, 1, 2, 10, 100, 1000, // 计数与十进制
+    8, 16, 32, 64, 128, 255, 256, 512, 1024, // 位宽与 2 的幂
+    24, 60, 365, 3600, 86400, // 时间换算
+    90, 180, 360, // 角度
+];
+
+/// 一次魔法数字出现的位置，文件级和项目级的重复统计都基于它
+#[derive(Debug, Clone)]
+pub(crate) struct MagicNumber {
+    pub file_path: PathBuf,
+    pub value: i64,
+    pub line: usize,
+    pub column: usize,
+    /// 从周围代码推断出的名字（变量、字段、比较对象），用来建议常量名
+    pub name_hint: Option<String>,
+}
+
+pub(crate) fn collect_magic_numbers(
+    file_path: &Path,
+    syntax_tree: &File,
+    allowed: &[i64],
+) -> Vec<MagicNumber> {
+    let mut visitor = MagicNumberVisitor::new(file_path.to_path_buf(), allowed);
+    visitor.visit_file(syntax_tree);
+    visitor.occurrences
+}
+
+/// 按数值分组，保持第一次出现的顺序
+fn group_by_value(occurrences: &[MagicNumber]) -> Vec<(i64, Vec<&MagicNumber>)> {
+    let mut groups: Vec<(i64, Vec<&MagicNumber>)> = Vec::new();
+    for occurrence in occurrences {
+        match groups
+            .iter_mut()
+            .find(|(value, _)| *value == occurrence.value)
+        {
+            Some((_, group)) => group.push(occurrence),
+            None => groups.push((occurrence.value, vec![occurrence])),
+        }
+    }
+    groups
+}
+
+fn magic_number_issues(occurrences: &[MagicNumber], lang: &str) -> Vec<CodeIssue> {
+    let mut issues = Vec::new();
+
+    for (value, group) in group_by_value(occurrences) {
+        if group.len() < FILE_REPEAT_THRESHOLD {
+            for occurrence in group {
+                issues.push(create_magic_number_issue(occurrence, issues.len(), lang));
+            }
+            continue;
+        }
+
+        let mut lines: Vec<String> = group.iter().map(|o| o.line.to_string()).collect();
+        lines.dedup();
+        let const_name = suggested_const_name(value, &group);
+        let message = if lang == "zh-CN" {
+            format!(
+                "魔法数字 {} 在这个文件里出现了 {} 次（第 {} 行），改一处漏一处，提取成 const {} 吧",
+                value,
+                group.len(),
+                lines.join(", "),
+                const_name
+            )
+        } else {
+            format!(
+                "Magic number {} appears {} times in this file (lines {}) - change one, miss the rest. Extract `const {}`",
+                value,
+                group.len(),
+                lines.join(", "),
+                const_name
+            )
+        };
+        issues.push(create_repeated_issue(group[0], message));
+    }
+
+    issues
+}
+
+/// 同一个数字散落在多个文件里：把这些文件里的逐条问题换成一条跨文件的问题
+pub(crate) fn flag_project_repeats(
+    issues: &mut Vec<CodeIssue>,
+    occurrences: &[MagicNumber],
+    lang: &str,
+) {
+    for (value, group) in group_by_value(occurrences) {
+        let mut firsts: Vec<&MagicNumber> = Vec::new();
+        for occurrence in &group {
+            if !firsts.iter().any(|o| o.file_path == occurrence.file_path) {
+                firsts.push(occurrence);
+            }
+        }
+        if firsts.len() < 2 {
+            continue;
+        }
+
+        issues.retain(|issue| {
+            issue.rule_name != "magic-number"
+                || !group.iter().any(|o| {
+                    o.file_path == issue.file_path
+                        && o.line == issue.line
+                        && o.column == issue.column
+                })
+        });
+
+        let const_name = suggested_const_name(value, &group);
+        for first in firsts.iter().copied() {
+            let message = if lang == "zh-CN" {
+                format!(
+                    "魔法数字 {} 在 {} 个文件里一共出现了 {} 次，把 const {} 定义在一个公共模块里",
+                    value,
+                    firsts.len(),
+                    group.len(),
+                    const_name
+                )
+            } else {
+                format!(
+                    "Magic number {} shows up {} times across {} files - define `const {}` once in a shared module",
+                    value,
+                    group.len(),
+                    firsts.len(),
+                    const_name
+                )
+            };
+            issues.push(create_repeated_issue(first, message));
+        }
+    }
+}
+
+/// 用出现次数最多的名字提示生成常量名，没有提示就退回 MAGIC_<值>
+fn suggested_const_name(value: i64, group: &[&MagicNumber]) -> String {
+    let mut counts: Vec<(&str, usize)> = Vec::new();
+    for hint in group.iter().filter_map(|o| o.name_hint.as_deref()) {
+        match counts.iter_mut().find(|(name, _)| *name == hint) {
+            Some((_, count)) => *count += 1,
+            None => counts.push((hint, 1)),
+        }
+    }
+
+    let best = counts.iter().fold(
+        None,
+        |best: Option<(&str, usize)>, &(name, count)| match best {
+            Some((_, best_count)) if best_count >= count => best,
+            _ => Some((name, count)),
+        },
+    );
+    match best {
+        Some((name, _)) => to_screaming_snake(name),
+        None => format!("MAGIC_{}", value.unsigned_abs()),
+    }
+}
+
+fn to_screaming_snake(name: &str) -> String {
+    let mut result = String::new();
+    let mut prev_lower = false;
+    for ch in name.trim_matches('_').chars() {
+        if ch.is_uppercase() && prev_lower {
+            result.push('_');
+        }
+        prev_lower = ch.is_lowercase() || ch.is_ascii_digit();
+        result.extend(ch.to_uppercase());
+    }
+    result
+}
+
+fn create_magic_number_issue(occurrence: &MagicNumber, index: usize, lang: &str) -> CodeIssue {
+    let value = occurrence.value;
+    let messages = if lang == "zh-CN" {
+        vec![
+            format!("魔法数字 {}？这是什么咒语？", value),
+            format!("硬编码数字 {}，维护性-1", value),
+            format!("数字 {} 从天而降，没人知道它的含义", value),
+            format!("魔法数字 {}，建议定义为常量", value),
+            format!("看到数字 {}，我陷入了沉思", value),
+        ]
+    } else {
+        vec![
+            format!("Magic number {}? What spell is this?", value),
+            format!("Hardcoded number {} - maintainability -1", value),
+            format!(
+                "Number {} fell from the sky, nobody knows its meaning",
+                value
+            ),
+            format!("Magic number {} - consider defining as a constant", value),
+            format!("Seeing number {}, I'm lost in thought", value),
+        ]
+    };
+
+    let severity = if !(-100..=1000).contains(&value) {
+        Severity::Spicy
+    } else {
+        Severity::Mild
+    };
+
+    CodeIssue {
+        file_path: occurrence.file_path.clone(),
+        line: occurrence.line,
+        column: occurrence.column,
+        rule_name: "magic-number".to_string(),
+        message: messages[index % messages.len()].clone(),
         severity,
+        roast_level: RoastLevel::Gentle,
+        context: CodeContext::Production,
+    }
+}
+
+fn create_repeated_issue(first: &MagicNumber, message: String) -> CodeIssue {
+    CodeIssue {
+        file_path: first.file_path.clone(),
+        line: first.line,
+        column: first.column,
+        rule_name: "magic-number".to_string(),
+        message,
+        severity: Severity::Spicy,
         roast_level: RoastLevel::Sarcastic,
+        context: CodeContext::Production,
     }
 }
 
-fn is_dead_code_pattern(line: &str) -> bool {
-    // 检测明显的死代码模式
-    let dead_patterns = [
-        "return;",
-        "return ", // return 后的代码
-        "break;",
-        "continue;", // break/continue 后的代码
-        "panic!(",
-        "unreachable!(", // panic 后的代码
-        "std::process::exit(",
-    ];
+/// 表达式背后的名字：变量、字段或方法名，用作常量名提示
+fn expr_name(expr: &Expr) -> Option<String> {
+    let name = match expr {
+        Expr::Path(path) => path.path.segments.last()?.ident.to_string(),
+        Expr::Field(field) => match &field.member {
+            Member::Named(ident) => ident.to_string(),
+            Member::Unnamed(_) => return None,
+        },
+        Expr::MethodCall(call) => call.method.to_string(),
+        Expr::Reference(reference) => return expr_name(&reference.expr),
+        Expr::Paren(paren) => return expr_name(&paren.expr),
+        Expr::Unary(unary) => return expr_name(&unary.expr),
+        _ => return None,
+    };
+    // 单字母和 self 当不了常量名
+    (name.len() > 1 && name != "self").then_some(name)
+}
 
-    dead_patterns.iter().any(|&pattern| line.contains(pattern))
+fn pat_name(pat: &Pat) -> Option<String> {
+    match pat {
+        Pat::Ident(pat_ident) => Some(pat_ident.ident.to_string()),
+        Pat::Type(pat_type) => pat_name(&pat_type.pat),
+        _ => None,
+    }
+    .filter(|name| name.len() > 1)
+}
+
+fn is_int_lit(expr: &Expr) -> bool {
+    matches!(
+        expr,
+        Expr::Lit(ExprLit {
+            lit: Lit::Int(_),
+            ..
+        })
+    )
+}
+
+fn is_bit_op(op: &BinOp) -> bool {
+    matches!(
+        op,
+        BinOp::BitAnd(_)
+            | BinOp::BitOr(_)
+            | BinOp::BitXor(_)
+            | BinOp::Shl(_)
+            | BinOp::Shr(_)
+            | BinOp::BitAndAssign(_)
+            | BinOp::BitOrAssign(_)
+            | BinOp::BitXorAssign(_)
+            | BinOp::ShlAssign(_)
+            | BinOp::ShrAssign(_)
+    )
+}
+
+struct MagicNumberVisitor<'a> {
+    file_path: PathBuf,
+    allowed: &'a [i64],
+    occurrences: Vec<MagicNumber>,
+    name_hint: Option<String>,
+}
+
+impl<'a> MagicNumberVisitor<'a> {
+    fn new(file_path: PathBuf, allowed: &'a [i64]) -> Self {
+        Self {
+            file_path,
+            allowed,
+            occurrences: Vec::new(),
+            name_hint: None,
+        }
+    }
+
+    fn is_magic_number(&self, value: i64) -> bool {
+        !WELL_KNOWN_NUMBERS.contains(&value) && !self.allowed.contains(&value)
+    }
+
+    /// 带着名字提示访问表达式，没有新提示时沿用外层的
+    fn visit_named(&mut self, hint: Option<String>, expr: &Expr) {
+        let hint = hint.or_else(|| self.name_hint.clone());
+        let saved = std::mem::replace(&mut self.name_hint, hint);
+        self.visit_expr(expr);
+        self.name_hint = saved;
+    }
+}
+
+impl<'ast> Visit<'ast> for MagicNumberVisitor<'_> {
+    fn visit_macro(&mut self, mac: &'ast Macro) {
+        // 断言里的期望值本身就是文档
+        let is_assert = mac.path.segments.last().is_some_and(|segment| {
+            let name = segment.ident.to_string();
+            name.starts_with("assert") || name.starts_with("debug_assert")
+        });
+        if !is_assert {
+            visit_macro_args(self, mac);
+        }
+    }
+
+    // 具名常量的定义就是魔法数字的正确归宿
+    fn visit_item_const(&mut self, _item: &'ast ItemConst) {}
+
+    fn visit_item_static(&mut self, _item: &'ast ItemStatic) {}
+
+    fn visit_impl_item_const(&mut self, _item: &'ast ImplItemConst) {}
+
+    fn visit_trait_item_const(&mut self, _item: &'ast TraitItemConst) {}
+
+    // 枚举判别值和数组长度都是定义的一部分
+    fn visit_variant(&mut self, _variant: &'ast Variant) {}
+
+    fn visit_type_array(&mut self, array: &'ast TypeArray) {
+        self.visit_type(&array.elem);
+    }
+
+    fn visit_expr_repeat(&mut self, repeat: &'ast ExprRepeat) {
+        self.visit_expr(&repeat.expr);
+    }
+
+    fn visit_item_fn(&mut self, func: &'ast ItemFn) {
+        if !func.attrs.iter().any(is_test_attr) {
+            syn::visit::visit_item_fn(self, func);
+        }
+    }
+
+    fn visit_item_mod(&mut self, module: &'ast ItemMod) {
+        if !module.attrs.iter().any(is_cfg_test_attr) {
+            syn::visit::visit_item_mod(self, module);
+        }
+    }
+
+    fn visit_expr_range(&mut self, range: &'ast ExprRange) {
+        // 0..8、&buf[..4] 这类区间边界一眼就懂
+        for bound in [&range.start, &range.end].into_iter().flatten() {
+            if !is_int_lit(bound) {
+                self.visit_expr(bound);
+            }
+        }
+    }
+
+    fn visit_expr_binary(&mut self, binary: &'ast ExprBinary) {
+        if is_bit_op(&binary.op) {
+            // 位掩码和移位量按位读才有意义，不算魔法数字
+            for operand in [&binary.left, &binary.right] {
+                if !is_int_lit(operand) {
+                    self.visit_expr(operand);
+                }
+            }
+            return;
+        }
+        self.visit_named(expr_name(&binary.right), &binary.left);
+        self.visit_named(expr_name(&binary.left), &binary.right);
+    }
+
+    fn visit_local(&mut self, local: &'ast Local) {
+        if let Some(init) = &local.init {
+            self.visit_named(pat_name(&local.pat), &init.expr);
+            if let Some((_, diverge)) = &init.diverge {
+                self.visit_expr(diverge);
+            }
+        }
+    }
+
+    fn visit_field_value(&mut self, field: &'ast FieldValue) {
+        let hint = match &field.member {
+            Member::Named(ident) => Some(ident.to_string()),
+            Member::Unnamed(_) => None,
+        };
+        self.visit_named(hint, &field.expr);
+    }
+
+    fn visit_expr_assign(&mut self, assign: &'ast ExprAssign) {
+        self.visit_expr(&assign.left);
+        self.visit_named(expr_name(&assign.left), &assign.right);
+    }
+
+    fn visit_expr_lit(&mut self, expr_lit: &'ast ExprLit) {
+        if let Lit::Int(lit_int) = &expr_lit.lit {
+            let digits = lit_int.to_string();
+            let is_radix = ["0x", "0b", "0o"].iter().any(|p| digits.starts_with(p));
+            if let Ok(value) = lit_int.base10_parse::<i64>() {
+                if !is_radix && self.is_magic_number(value) {
+                    let (line, column) = get_position(expr_lit);
+                    self.occurrences.push(MagicNumber {
+                        file_path: self.file_path.clone(),
+                        value,
+                        line,
+                        column,
+                        name_hint: self.name_hint.clone(),
+                    });
+                }
+            }
+        }
+    }
 }
 
 // ============================================================================
-// Visitor 实现
+// 死代码检测
 // ============================================================================
 
-struct MagicNumberVisitor {
-    file_path: std::path::PathBuf,
+/// 一个没有可见性修饰的私有条目
+#[derive(Debug, Clone)]
+pub(crate) struct PrivateItem {
+    name: String,
+    kind: &'static str,
+    line: usize,
+    column: usize,
+}
+
+/// 一个文件里的私有条目和所有标识符出现次数，用来判断条目有没有被引用
+pub(crate) struct DeadCodeCensus {
+    file_path: PathBuf,
+    items: Vec<PrivateItem>,
+    idents: HashMap<String, usize>,
+    has_child_files: bool,
+}
+
+impl DeadCodeCensus {
+    pub(crate) fn collect(file_path: &Path, syntax_tree: &File) -> Self {
+        let mut collector = PrivateItemCollector::default();
+        collector.visit_file(syntax_tree);
+
+        let mut idents = HashMap::new();
+        count_idents(syntax_tree.to_token_stream(), &mut idents);
+
+        Self {
+            file_path: file_path.to_path_buf(),
+            items: collector.items,
+            idents,
+            has_child_files: collector.has_child_files,
+        }
+    }
+
+    /// 名字只在声明处出现过一次，且 `used_elsewhere` 也找不到的条目
+    fn unused_items(&self, used_elsewhere: impl Fn(&str) -> bool) -> Vec<&PrivateItem> {
+        self.items
+            .iter()
+            .filter(|item| self.idents.get(&item.name).copied().unwrap_or(0) <= 1)
+            .filter(|item| !used_elsewhere(&item.name))
+            .collect()
+    }
+}
+
+/// 有外部子模块的文件：私有条目在整个项目里都找不到引用才算死代码
+pub(crate) fn flag_unused_across_files(
+    issues: &mut Vec<CodeIssue>,
+    censuses: &[DeadCodeCensus],
+    lang: &str,
+) {
+    for census in censuses.iter().filter(|census| census.has_child_files) {
+        let used_elsewhere = |name: &str| {
+            censuses
+                .iter()
+                .any(|other| other.file_path != census.file_path && other.idents.contains_key(name))
+        };
+        for item in census.unused_items(used_elsewhere) {
+            issues.push(create_unused_item_issue(&census.file_path, item, lang));
+        }
+    }
+}
+
+fn create_unused_item_issue(file_path: &Path, item: &PrivateItem, lang: &str) -> CodeIssue {
+    let message = if lang == "zh-CN" {
+        format!(
+            "私有 {} `{}` 从来没被用过，它在这里只是占地方，删了吧",
+            item.kind, item.name
+        )
+    } else {
+        format!(
+            "Private {} `{}` is never used anywhere - it's just squatting here, delete it",
+            item.kind, item.name
+        )
+    };
+
+    CodeIssue {
+        file_path: file_path.to_path_buf(),
+        line: item.line,
+        column: item.column,
+        rule_name: "dead-code".to_string(),
+        message,
+        severity: Severity::Mild,
+        roast_level: RoastLevel::Sarcastic,
+        context: CodeContext::Production,
+    }
+}
+
+/// 统计所有标识符，宏参数和格式化字符串里的 `{name}` 也算引用
+fn count_idents(tokens: TokenStream, counts: &mut HashMap<String, usize>) {
+    for tree in tokens {
+        match tree {
+            TokenTree::Ident(ident) => *counts.entry(ident.to_string()).or_default() += 1,
+            TokenTree::Group(group) => count_idents(group.stream(), counts),
+            TokenTree::Literal(literal) => {
+                let text = literal.to_string();
+                for piece in text.split('{').skip(1) {
+                    let name: String = piece
+                        .chars()
+                        .take_while(|c| c.is_alphanumeric() || *c == '_')
+                        .collect();
+                    if !name.is_empty() {
+                        *counts.entry(name).or_default() += 1;
+                    }
+                }
+            }
+            TokenTree::Punct(_) => {}
+        }
+    }
+}
+
+/// 收集私有的函数、常量、静态变量、结构体、枚举和内联模块
+#[derive(Default)]
+struct PrivateItemCollector {
+    items: Vec<PrivateItem>,
+    has_child_files: bool,
+}
+
+impl PrivateItemCollector {
+    fn push(&mut self, kind: &'static str, vis: &Visibility, attrs: &[Attribute], ident: &Ident) {
+        let name = ident.to_string();
+        // 入口、测试、导出给外部的符号和 `_` 开头的名字都不算
+        let exempt = name == "main"
+            || name.starts_with('_')
+            || attrs.iter().any(|attr| {
+                is_test_attr(attr)
+                    || attr.path().is_ident("bench")
+                    || attr.path().is_ident("no_mangle")
+                    || attr.path().is_ident("export_name")
+            });
+        if matches!(vis, Visibility::Inherited) && !exempt {
+            let (line, column) = get_position(ident);
+            self.items.push(PrivateItem {
+                name,
+                kind,
+                line,
+                column,
+            });
+        }
+    }
+}
+
+impl<'ast> Visit<'ast> for PrivateItemCollector {
+    fn visit_item_fn(&mut self, item: &'ast ItemFn) {
+        self.push("fn", &item.vis, &item.attrs, &item.sig.ident);
+        syn::visit::visit_item_fn(self, item);
+    }
+
+    fn visit_item_const(&mut self, item: &'ast ItemConst) {
+        self.push("const", &item.vis, &item.attrs, &item.ident);
+        syn::visit::visit_item_const(self, item);
+    }
+
+    fn visit_item_static(&mut self, item: &'ast ItemStatic) {
+        self.push("static", &item.vis, &item.attrs, &item.ident);
+        syn::visit::visit_item_static(self, item);
+    }
+
+    fn visit_item_struct(&mut self, item: &'ast ItemStruct) {
+        self.push("struct", &item.vis, &item.attrs, &item.ident);
+        syn::visit::visit_item_struct(self, item);
+    }
+
+    fn visit_item_enum(&mut self, item: &'ast ItemEnum) {
+        self.push("enum", &item.vis, &item.attrs, &item.ident);
+        syn::visit::visit_item_enum(self, item);
+    }
+
+    fn visit_item_mod(&mut self, item: &'ast ItemMod) {
+        match &item.content {
+            None => self.has_child_files = true,
+            Some((_, items)) => {
+                // 只装着 impl 或测试的模块靠自身生效，不需要被引用
+                let only_side_effects = item.attrs.iter().any(is_cfg_test_attr)
+                    || items.iter().any(|inner| {
+                        matches!(inner, Item::Impl(_) | Item::Macro(_) | Item::ForeignMod(_))
+                    });
+                if !only_side_effects {
+                    self.push("mod", &item.vis, &item.attrs, &item.ident);
+                }
+            }
+        }
+        syn::visit::visit_item_mod(self, item);
+    }
+}
+
+struct DeadCodeVisitor {
+    file_path: PathBuf,
     issues: Vec<CodeIssue>,
     lang: String,
 }
 
-impl MagicNumberVisitor {
-    fn new(file_path: std::path::PathBuf, lang: &str) -> Self {
+impl DeadCodeVisitor {
+    fn new(file_path: PathBuf, lang: &str) -> Self {
         Self {
             file_path,
             issues: Vec::new(),
@@ -298,64 +978,224 @@ impl MagicNumberVisitor {
         }
     }
 
-    fn is_magic_number(&self, value: i64) -> bool {
-        // 常见的非魔法数字
-        !matches!(value, -1 | 0 | 1 | 2 | 10 | 100 | 1000)
+    fn push_issue<T: Spanned>(&mut self, node: &T, message: String, severity: Severity) {
+        self.issues.push(rule_issue(
+            &self.file_path,
+            get_position(node),
+            "dead-code",
+            message,
+            severity,
+        ));
     }
 
-    fn create_magic_number_issue(&self, value: i64, line: usize, column: usize) -> CodeIssue {
+    fn check_unreachable(&mut self, block: &Block) {
+        let Some((index, cause)) = block
+            .stmts
+            .iter()
+            .enumerate()
+            .find_map(|(index, stmt)| diverging_cause(stmt).map(|cause| (index, cause)))
+        else {
+            return;
+        };
+
+        // 条目声明会被提升，写在后面也不算死代码
+        let rest: Vec<&Stmt> = block.stmts[index + 1..]
+            .iter()
+            .filter(|stmt| !matches!(stmt, Stmt::Item(_)))
+            .collect();
+        let Some(first) = rest.first() else {
+            return;
+        };
+
         let messages = if self.lang == "zh-CN" {
             vec![
-                format!("魔法数字 {}？这是什么咒语？", value),
-                format!("硬编码数字 {}，维护性-1", value),
-                format!("数字 {} 从天而降，没人知道它的含义", value),
-                format!("魔法数字 {}，建议定义为常量", value),
-                format!("看到数字 {}，我陷入了沉思", value),
+                format!(
+                    "`{}` 之后还有 {} 条语句，它们永远不会执行",
+                    cause,
+                    rest.len()
+                ),
+                format!(
+                    "{} 条语句排在 `{}` 后面，这里是代码的坟墓",
+                    rest.len(),
+                    cause
+                ),
             ]
         } else {
             vec![
-                format!("Magic number {}? What spell is this?", value),
-                format!("Hardcoded number {} - maintainability -1", value),
                 format!(
-                    "Number {} fell from the sky, nobody knows its meaning",
-                    value
+                    "{} statement(s) after `{}` will never execute",
+                    rest.len(),
+                    cause
+                ),
+                format!(
+                    "Code after `{}` is deader than my social life ({} statement(s))",
+                    cause,
+                    rest.len()
                 ),
-                format!("Magic number {} - consider defining as a constant", value),
-                format!("Seeing number {}, I'm lost in thought", value),
             ]
         };
+        let message = messages[self.issues.len() % messages.len()].clone();
+        self.push_issue(*first, message, Severity::Spicy);
+    }
+}
+
+impl<'ast> Visit<'ast> for DeadCodeVisitor {
+    fn visit_block(&mut self, block: &'ast Block) {
+        self.check_unreachable(block);
+        syn::visit::visit_block(self, block);
+    }
 
-        let severity = if !(-100..=1000).contains(&value) {
+    fn visit_expr_if(&mut self, expr_if: &'ast ExprIf) {
+        if let Expr::Lit(ExprLit {
+            lit: Lit::Bool(value),
+            ..
+        }) = &*expr_if.cond
+        {
+            let message = match (value.value, self.lang == "zh-CN") {
+                (false, true) => "`if false` 分支永远不会执行，这是给代码办的葬礼".to_string(),
+                (false, false) => {
+                    "`if false` branch never runs - this is a funeral, not a feature flag"
+                        .to_string()
+                }
+                (true, true) => "`if true` 的条件毫无意义，else 分支永远到不了".to_string(),
+                (true, false) => {
+                    "`if true` always runs - the condition and any else branch are dead".to_string()
+                }
+            };
+            self.push_issue(&expr_if.cond, message, Severity::Mild);
+        }
+        syn::visit::visit_expr_if(self, expr_if);
+    }
+
+    fn visit_attribute(&mut self, attr: &'ast Attribute) {
+        if !allows_dead_code(attr) {
+            return;
+        }
+        let is_inner = matches!(attr.style, AttrStyle::Inner(_));
+        let message = match (is_inner, self.lang == "zh-CN") {
+            (true, true) => "`#![allow(dead_code)]` 把整个模块的死代码都藏进了地毯下".to_string(),
+            (true, false) => {
+                "`#![allow(dead_code)]` sweeps the whole module's dead code under the rug"
+                    .to_string()
+            }
+            (false, true) => "`#[allow(dead_code)]` 不是清理，只是让编译器闭嘴".to_string(),
+            (false, false) => {
+                "`#[allow(dead_code)]` doesn't clean anything up, it just gags the compiler"
+                    .to_string()
+            }
+        };
+        let severity = if is_inner {
             Severity::Spicy
         } else {
             Severity::Mild
         };
+        self.push_issue(attr, message, severity);
+    }
+}
 
-        CodeIssue {
-            file_path: self.file_path.clone(),
-            line,
-            column,
-            rule_name: "magic-number".to_string(),
-            message: messages[self.issues.len() % messages.len()].clone(),
-            severity,
-            roast_level: RoastLevel::Gentle,
-        }
+/// `#[allow(dead_code)]` 或 `#[allow(unused)]`
+fn allows_dead_code(attr: &Attribute) -> bool {
+    if !attr.path().is_ident("allow") {
+        return false;
     }
+    let mut found = false;
+    let _ = attr.parse_nested_meta(|meta| {
+        found |= meta.path.is_ident("dead_code") || meta.path.is_ident("unused");
+        Ok(())
+    });
+    found
 }
 
-impl<'ast> Visit<'ast> for MagicNumberVisitor {
-    fn visit_expr_lit(&mut self, expr_lit: &'ast ExprLit) {
-        if let Lit::Int(lit_int) = &expr_lit.lit {
-            if let Ok(value) = lit_int.base10_parse::<i64>() {
-                if self.is_magic_number(value) {
-                    let (line, column) = get_position(expr_lit);
-                    self.issues
-                        .push(self.create_magic_number_issue(value, line, column));
-                }
+/// 语句执行后控制流不会继续往下走时，返回原因
+fn diverging_cause(stmt: &Stmt) -> Option<String> {
+    let expr = match stmt {
+        Stmt::Expr(expr, _) => expr,
+        Stmt::Macro(stmt_macro) => return diverging_macro(&stmt_macro.mac),
+        _ => return None,
+    };
+
+    match expr {
+        Expr::Return(_) => Some("return".to_string()),
+        Expr::Break(_) => Some("break".to_string()),
+        Expr::Continue(_) => Some("continue".to_string()),
+        Expr::Macro(expr_macro) => diverging_macro(&expr_macro.mac),
+        Expr::Loop(expr_loop) if !contains_break(&expr_loop.body) => Some("loop {}".to_string()),
+        Expr::Call(call) => match &*call.func {
+            Expr::Path(path)
+                if path.path.segments.len() >= 2
+                    && path
+                        .path
+                        .segments
+                        .iter()
+                        .rev()
+                        .take(2)
+                        .map(|s| s.ident.to_string())
+                        .eq(["exit", "process"]) =>
+            {
+                Some("process::exit".to_string())
             }
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+fn diverging_macro(mac: &Macro) -> Option<String> {
+    let name = mac.path.segments.last()?.ident.to_string();
+    matches!(
+        name.as_str(),
+        "panic" | "unreachable" | "todo" | "unimplemented"
+    )
+    .then(|| format!("{}!", name))
+}
+
+/// 循环体里有没有能跳出这个循环的 break（嵌套循环里的无标签 break 不算）
+fn contains_break(body: &Block) -> bool {
+    struct BreakFinder {
+        found: bool,
+    }
+
+    impl<'ast> Visit<'ast> for BreakFinder {
+        fn visit_expr_break(&mut self, _expr: &'ast ExprBreak) {
+            self.found = true;
+        }
+        fn visit_expr_loop(&mut self, expr: &'ast ExprLoop) {
+            self.visit_labeled_only(&expr.body);
+        }
+        fn visit_expr_while(&mut self, expr: &'ast ExprWhile) {
+            self.visit_expr(&expr.cond);
+            self.visit_labeled_only(&expr.body);
         }
-        syn::visit::visit_expr_lit(self, expr_lit);
+        fn visit_expr_for_loop(&mut self, expr: &'ast ExprForLoop) {
+            self.visit_expr(&expr.expr);
+            self.visit_labeled_only(&expr.body);
+        }
+        fn visit_expr_closure(&mut self, _expr: &'ast ExprClosure) {}
+    }
+
+    impl BreakFinder {
+        /// 嵌套循环里只有带标签的 break 可能跳出外层循环
+        fn visit_labeled_only(&mut self, body: &Block) {
+            let mut labeled = LabeledBreakFinder { found: false };
+            labeled.visit_block(body);
+            self.found |= labeled.found;
+        }
+    }
+
+    struct LabeledBreakFinder {
+        found: bool,
     }
+
+    impl<'ast> Visit<'ast> for LabeledBreakFinder {
+        fn visit_expr_break(&mut self, expr: &'ast ExprBreak) {
+            self.found |= expr.label.is_some();
+        }
+        fn visit_expr_closure(&mut self, _expr: &'ast ExprClosure) {}
+    }
+
+    let mut finder = BreakFinder { found: false };
+    finder.visit_block(body);
+    finder.found
 }
 
 // ============================================================================
@@ -448,6 +1288,7 @@ impl GodFunctionVisitor {
                 message: messages[self.issues.len() % messages.len()].clone(),
                 severity,
                 roast_level: RoastLevel::Sarcastic,
+                context: CodeContext::Production,
             });
         }
     }
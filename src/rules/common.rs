@@ -0,0 +1,154 @@
+use std::collections::HashSet;
+use std::path::Path;
+use syn::{punctuated::Punctuated, Attribute, ExprMethodCall, Token, Type, Visibility};
+
+use crate::analyzer::{CodeContext, CodeIssue, RoastLevel, Severity};
+
+/// 通常返回 Result 的方法或函数
+pub(crate) const RESULT_METHODS: &[&str] = &[
+    "parse",
+    "lock",
+    "try_into",
+    "try_from",
+    "from_str",
+    "from_utf8",
+    "read_to_string",
+    "read_line",
+    "read_dir",
+    "write_all",
+    "flush",
+    "open",
+    "create",
+    "create_dir_all",
+    "remove_file",
+    "metadata",
+    "canonicalize",
+    "join",
+    "recv",
+    "send",
+    "connect",
+    "bind",
+    "var",
+];
+
+/// 不带参数的 .lock()、.read()、.write()，Mutex 和 RwLock 的加锁调用
+pub(crate) fn is_lock_call(call: &ExprMethodCall) -> bool {
+    call.args.is_empty() && matches!(call.method.to_string().as_str(), "lock" | "read" | "write")
+}
+
+pub(crate) fn is_public(vis: &Visibility) -> bool {
+    matches!(vis, Visibility::Public(_))
+}
+
+/// 库代码：按所在的 crate 判断。找得到 Cargo.toml 时，src/main.rs 和 src/bin/ 下的是二进制；
+/// src/ 下的其余模块要 crate 有 src/lib.rs，并且不是 main.rs 自己用 `mod` 声明的才算库，
+/// 纯二进制 crate 的模块不是库；src/ 以外的 tests、examples 也不算。找不到 Cargo.toml 就只看路径
+pub(crate) fn is_library_file(path: &Path) -> bool {
+    let is_main = path.file_name().is_some_and(|name| name == "main.rs");
+    if is_main {
+        return false;
+    }
+
+    let crate_root = path
+        .ancestors()
+        .skip(1)
+        .find(|dir| dir.join("Cargo.toml").is_file());
+    let Some(root) = crate_root else {
+        let components: Vec<_> = path.components().map(|c| c.as_os_str()).collect();
+        return !components
+            .windows(2)
+            .any(|pair| pair[0] == "src" && pair[1] == "bin");
+    };
+
+    let src = root.join("src");
+    let Ok(relative) = path.strip_prefix(&src) else {
+        return false;
+    };
+    if relative.starts_with("bin") || !src.join("lib.rs").is_file() {
+        return false;
+    }
+    // lib.rs 和 main.rs 并存时，main.rs 里 `mod xxx;` 引进来的模块属于二进制
+    let module = relative
+        .components()
+        .next()
+        .and_then(|first| Path::new(first.as_os_str()).file_stem())
+        .and_then(|stem| stem.to_str());
+    module.is_none_or(|module| module == "lib" || !declares_module(&src.join("main.rs"), module))
+}
+
+/// 文件顶层有没有 `mod name;` 声明
+fn declares_module(root_file: &Path, name: &str) -> bool {
+    std::fs::read_to_string(root_file)
+        .ok()
+        .and_then(|content| syn::parse_file(&content).ok())
+        .is_some_and(|file| {
+            file.items.iter().any(|item| {
+                matches!(item, syn::Item::Mod(item_mod)
+                    if item_mod.content.is_none() && item_mod.ident == name)
+            })
+        })
+}
+
+/// #[derive(...)] 里列出的 trait 名
+pub(crate) fn derived_traits(attrs: &[Attribute]) -> HashSet<String> {
+    let mut traits = HashSet::new();
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("derive")) {
+        if let Ok(paths) =
+            attr.parse_args_with(Punctuated::<syn::Path, Token![,]>::parse_terminated)
+        {
+            traits.extend(
+                paths
+                    .iter()
+                    .filter_map(|path| path.segments.last())
+                    .map(|segment| segment.ident.to_string()),
+            );
+        }
+    }
+    traits
+}
+
+/// quote 生成的 `std :: fmt :: Debug` 压缩成 `std::fmt::Debug`
+pub(crate) fn type_to_string(ty: &Type) -> String {
+    quote::quote!(#ty)
+        .to_string()
+        .replace(" :: ", "::")
+        .replace(" < ", "<")
+        .replace(" <", "<")
+        .replace(" > ", ">")
+        .replace(" >", ">")
+        .replace("& ", "&")
+}
+
+/// 路径类型的最后一段，`std::io::Result<T>` 得到 `Result`
+pub(crate) fn type_name(ty: &Type) -> Option<String> {
+    match ty {
+        Type::Path(type_path) => type_path.path.segments.last().map(|s| s.ident.to_string()),
+        _ => None,
+    }
+}
+
+/// 按严重程度配好吐槽力度的问题，Mild 温和，Nuclear 毒舌，其余讽刺
+pub(crate) fn rule_issue(
+    file_path: &Path,
+    (line, column): (usize, usize),
+    rule_name: &str,
+    message: String,
+    severity: Severity,
+) -> CodeIssue {
+    let roast_level = match severity {
+        Severity::Mild => RoastLevel::Gentle,
+        Severity::Nuclear => RoastLevel::Savage,
+        _ => RoastLevel::Sarcastic,
+    };
+
+    CodeIssue {
+        file_path: file_path.to_path_buf(),
+        line,
+        column,
+        rule_name: rule_name.to_string(),
+        message,
+        severity,
+        roast_level,
+        context: CodeContext::Production,
+    }
+}
@@ -1,7 +1,7 @@
 use std::path::Path;
 use syn::{visit::Visit, Block, File, ItemFn};
 
-use crate::analyzer::{CodeIssue, RoastLevel, Severity};
+use crate::analyzer::{CodeContext, CodeIssue, RoastLevel, Severity};
 use crate::rules::Rule;
 use crate::utils::get_position;
 
@@ -115,6 +115,7 @@ impl NestingVisitor {
                 ),
                 severity,
                 roast_level,
+                context: CodeContext::Production,
             });
         }
     }
@@ -270,6 +271,7 @@ impl<'ast> Visit<'ast> for FunctionLengthVisitor {
                 message: messages[self.issues.len() % messages.len()].clone(),
                 severity,
                 roast_level,
+                context: CodeContext::Production,
             });
         }
 
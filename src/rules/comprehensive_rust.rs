@@ -1,17 +1,16 @@
+use proc_macro2::{Group, TokenStream, TokenTree};
+use std::collections::{HashMap, HashSet};
 use std::path::Path;
 use syn::{
-    visit::Visit, ExprAsync, ExprAwait, ExprMatch, ExprUnsafe, File, ForeignItem, ItemFn,
-    ItemForeignMod, ItemMod, Macro, PatSlice, PatTuple, TypePath, TypeReference, TypeSlice,
-    TypeTraitObject,
+    spanned::Spanned, visit::Visit, ExprMatch, File, ForeignItem, ItemEnum, ItemFn, ItemForeignMod,
+    ItemImpl, ItemMacro, ItemMod, Macro, Pat, Type, TypeReference, TypeSlice, TypeTraitObject,
 };
 
-use crate::analyzer::{CodeIssue, RoastLevel, Severity};
+use crate::analyzer::{CodeContext, CodeIssue, RoastLevel, Severity};
 use crate::rules::Rule;
+use crate::utils::get_position;
 
-pub struct ChannelAbuseRule;
-pub struct AsyncAbuseRule;
 pub struct DynTraitAbuseRule;
-pub struct UnsafeAbuseRule;
 pub struct FFIAbuseRule;
 pub struct MacroAbuseRule;
 pub struct ModuleComplexityRule;
@@ -20,51 +19,6 @@ pub struct ReferenceAbuseRule;
 pub struct BoxAbuseRule;
 pub struct SliceAbuseRule;
 
-impl Rule for ChannelAbuseRule {
-    fn name(&self) -> &'static str {
-        "channel-abuse"
-    }
-
-    fn check(
-        &self,
-        file_path: &Path,
-        syntax_tree: &File,
-        content: &str,
-        _lang: &str,
-    ) -> Vec<CodeIssue> {
-        let mut visitor = ChannelVisitor::new(file_path.to_path_buf());
-        visitor.visit_file(syntax_tree);
-
-        // Also check for channel-related imports and usage in content
-        if content.contains("std::sync::mpsc") || content.contains("tokio::sync") {
-            visitor.channel_count += content.matches("channel").count();
-            visitor.channel_count += content.matches("Sender").count();
-            visitor.channel_count += content.matches("Receiver").count();
-        }
-
-        visitor.check_channel_overuse();
-        visitor.issues
-    }
-}
-
-impl Rule for AsyncAbuseRule {
-    fn name(&self) -> &'static str {
-        "async-abuse"
-    }
-
-    fn check(
-        &self,
-        file_path: &Path,
-        syntax_tree: &File,
-        _content: &str,
-        _lang: &str,
-    ) -> Vec<CodeIssue> {
-        let mut visitor = AsyncVisitor::new(file_path.to_path_buf());
-        visitor.visit_file(syntax_tree);
-        visitor.issues
-    }
-}
-
 impl Rule for DynTraitAbuseRule {
     fn name(&self) -> &'static str {
         "dyn-trait-abuse"
@@ -83,27 +37,6 @@ impl Rule for DynTraitAbuseRule {
     }
 }
 
-impl Rule for UnsafeAbuseRule {
-    fn name(&self) -> &'static str {
-        "unsafe-abuse"
-    }
-
-    fn check(
-        &self,
-        file_path: &Path,
-        syntax_tree: &File,
-        content: &str,
-        _lang: &str,
-    ) -> Vec<CodeIssue> {
-        let mut visitor = UnsafeVisitor::new(file_path.to_path_buf());
-        visitor.visit_file(syntax_tree);
-
-        // 检查内容中的 unsafe 关键字使用
-        visitor.check_unsafe_in_content(content);
-        visitor.issues
-    }
-}
-
 impl Rule for FFIAbuseRule {
     fn name(&self) -> &'static str {
         "ffi-abuse"
@@ -135,9 +68,9 @@ impl Rule for MacroAbuseRule {
         file_path: &Path,
         syntax_tree: &File,
         _content: &str,
-        _lang: &str,
+        lang: &str,
     ) -> Vec<CodeIssue> {
-        let mut visitor = MacroVisitor::new(file_path.to_path_buf());
+        let mut visitor = MacroVisitor::new(file_path.to_path_buf(), lang);
         visitor.visit_file(syntax_tree);
         visitor.issues
     }
@@ -171,9 +104,9 @@ impl Rule for PatternMatchingAbuseRule {
         file_path: &Path,
         syntax_tree: &File,
         _content: &str,
-        _lang: &str,
+        lang: &str,
     ) -> Vec<CodeIssue> {
-        let mut visitor = PatternVisitor::new(file_path.to_path_buf());
+        let mut visitor = PatternVisitor::new(file_path.to_path_buf(), syntax_tree, lang);
         visitor.visit_file(syntax_tree);
         visitor.issues
     }
@@ -231,6 +164,7 @@ impl Rule for BoxAbuseRule {
                 message: messages[0].to_string(),
                 severity: Severity::Spicy,
                 roast_level: RoastLevel::Sarcastic,
+                context: CodeContext::Production,
             });
         }
 
@@ -256,131 +190,6 @@ impl Rule for SliceAbuseRule {
     }
 }
 
-// Channel Visitor
-struct ChannelVisitor {
-    file_path: std::path::PathBuf,
-    issues: Vec<CodeIssue>,
-    channel_count: usize,
-}
-
-impl ChannelVisitor {
-    fn new(file_path: std::path::PathBuf) -> Self {
-        Self {
-            file_path,
-            issues: Vec::new(),
-            channel_count: 0,
-        }
-    }
-
-    fn check_channel_overuse(&mut self) {
-        if self.channel_count > 5 {
-            let messages = [
-                "Channel 用得比我发微信还频繁，你确定不是在写聊天软件？",
-                "这么多 Channel，你是想开通讯公司吗？",
-                "Channel 滥用！你的程序比电话交换机还复杂",
-                "Channel 数量超标，建议重新设计架构",
-                "这么多 Channel，我怀疑你在写分布式系统",
-            ];
-
-            self.issues.push(CodeIssue {
-                file_path: self.file_path.clone(),
-                line: 1,
-                column: 1,
-                rule_name: "channel-abuse".to_string(),
-                message: messages[self.issues.len() % messages.len()].to_string(),
-                severity: Severity::Spicy,
-                roast_level: RoastLevel::Sarcastic,
-            });
-        }
-    }
-}
-
-impl<'ast> Visit<'ast> for ChannelVisitor {
-    fn visit_type_path(&mut self, type_path: &'ast TypePath) {
-        let path_str = quote::quote!(#type_path).to_string();
-        if path_str.contains("Sender")
-            || path_str.contains("Receiver")
-            || path_str.contains("channel")
-        {
-            self.channel_count += 1;
-        }
-        syn::visit::visit_type_path(self, type_path);
-    }
-}
-
-// Async Visitor
-struct AsyncVisitor {
-    file_path: std::path::PathBuf,
-    issues: Vec<CodeIssue>,
-    async_count: usize,
-    await_count: usize,
-}
-
-impl AsyncVisitor {
-    fn new(file_path: std::path::PathBuf) -> Self {
-        Self {
-            file_path,
-            issues: Vec::new(),
-            async_count: 0,
-            await_count: 0,
-        }
-    }
-
-    fn check_async_abuse(&mut self) {
-        if self.async_count > 10 {
-            let messages = [
-                "Async 函数比我的异步人生还要复杂",
-                "这么多 async，你确定不是在写 JavaScript？",
-                "Async 滥用！建议学习一下同步编程的美好",
-                "异步函数过多，小心把自己绕晕了",
-            ];
-
-            self.issues.push(CodeIssue {
-                file_path: self.file_path.clone(),
-                line: 1,
-                column: 1,
-                rule_name: "async-abuse".to_string(),
-                message: messages[self.issues.len() % messages.len()].to_string(),
-                severity: Severity::Spicy,
-                roast_level: RoastLevel::Sarcastic,
-            });
-        }
-
-        if self.await_count > 20 {
-            let messages = [
-                "Await 用得比我等外卖还频繁",
-                "这么多 await，你的程序是在等什么？世界末日吗？",
-                "Await 过度使用，建议批量处理",
-                "等待次数过多，你的程序比我还有耐心",
-            ];
-
-            self.issues.push(CodeIssue {
-                file_path: self.file_path.clone(),
-                line: 1,
-                column: 1,
-                rule_name: "async-abuse".to_string(),
-                message: messages[self.issues.len() % messages.len()].to_string(),
-                severity: Severity::Mild,
-                roast_level: RoastLevel::Gentle,
-            });
-        }
-    }
-}
-
-impl<'ast> Visit<'ast> for AsyncVisitor {
-    fn visit_expr_async(&mut self, _async_expr: &'ast ExprAsync) {
-        self.async_count += 1;
-        self.check_async_abuse();
-        syn::visit::visit_expr_async(self, _async_expr);
-    }
-
-    fn visit_expr_await(&mut self, _await_expr: &'ast ExprAwait) {
-        self.await_count += 1;
-        self.check_async_abuse();
-        syn::visit::visit_expr_await(self, _await_expr);
-    }
-}
-
 // Dyn Trait Visitor
 struct DynTraitVisitor {
     file_path: std::path::PathBuf,
@@ -419,6 +228,7 @@ impl<'ast> Visit<'ast> for DynTraitVisitor {
                 message: messages[self.issues.len() % messages.len()].to_string(),
                 severity: Severity::Spicy,
                 roast_level: RoastLevel::Sarcastic,
+                context: CodeContext::Production,
             });
         }
 
@@ -426,169 +236,6 @@ impl<'ast> Visit<'ast> for DynTraitVisitor {
     }
 }
 
-// Unsafe Visitor
-struct UnsafeVisitor {
-    file_path: std::path::PathBuf,
-    issues: Vec<CodeIssue>,
-    unsafe_count: usize,
-    unsafe_fn_count: usize,
-    unsafe_impl_count: usize,
-    unsafe_trait_count: usize,
-}
-
-impl UnsafeVisitor {
-    fn new(file_path: std::path::PathBuf) -> Self {
-        Self {
-            file_path,
-            issues: Vec::new(),
-            unsafe_count: 0,
-            unsafe_fn_count: 0,
-            unsafe_impl_count: 0,
-            unsafe_trait_count: 0,
-        }
-    }
-
-    fn check_unsafe_in_content(&mut self, content: &str) {
-        // 检查 unsafe 函数
-        let unsafe_fn_matches = content.matches("unsafe fn").count();
-        self.unsafe_fn_count += unsafe_fn_matches;
-
-        // 检查 unsafe impl
-        let unsafe_impl_matches = content.matches("unsafe impl").count();
-        self.unsafe_impl_count += unsafe_impl_matches;
-
-        // 检查 unsafe trait
-        let unsafe_trait_matches = content.matches("unsafe trait").count();
-        self.unsafe_trait_count += unsafe_trait_matches;
-
-        // 检查原始指针操作
-        let raw_ptr_count = content.matches("*const").count() + content.matches("*mut").count();
-
-        // 检查内存操作函数
-        let dangerous_ops = [
-            "std::ptr::write",
-            "std::ptr::read",
-            "std::ptr::copy",
-            "std::mem::transmute",
-            "std::mem::forget",
-            "std::mem::uninitialized",
-            "std::slice::from_raw_parts",
-            "std::str::from_utf8_unchecked",
-            "Box::from_raw",
-            "Vec::from_raw_parts",
-            "String::from_raw_parts",
-        ];
-
-        let mut dangerous_op_count = 0;
-        for op in &dangerous_ops {
-            dangerous_op_count += content.matches(op).count();
-        }
-
-        self.generate_unsafe_issues(raw_ptr_count, dangerous_op_count);
-    }
-
-    fn generate_unsafe_issues(&mut self, raw_ptr_count: usize, dangerous_op_count: usize) {
-        // 检查 unsafe 函数过多
-        if self.unsafe_fn_count > 2 {
-            let messages = [
-                "Unsafe 函数比我的黑历史还多！你确定这还是 Rust 吗？",
-                "这么多 unsafe 函数，Rust 的安全保证都被你玩坏了",
-                "Unsafe 函数过多，建议重新考虑设计架构",
-                "你的 unsafe 函数让 Rust 编译器都开始怀疑人生了",
-            ];
-
-            self.issues.push(CodeIssue {
-                file_path: self.file_path.clone(),
-                line: 1,
-                column: 1,
-                rule_name: "unsafe-abuse".to_string(),
-                message: messages[self.issues.len() % messages.len()].to_string(),
-                severity: Severity::Nuclear,
-                roast_level: RoastLevel::Savage,
-            });
-        }
-
-        // 检查原始指针过多
-        if raw_ptr_count > 5 {
-            let messages = [
-                "原始指针用得比我换手机还频繁，你这是在写 C 语言吗？",
-                "这么多原始指针，内存安全已经不在服务区了",
-                "原始指针过多，建议使用安全的 Rust 抽象",
-                "你的指针操作让 Valgrind 都要加班了",
-            ];
-
-            self.issues.push(CodeIssue {
-                file_path: self.file_path.clone(),
-                line: 1,
-                column: 1,
-                rule_name: "unsafe-abuse".to_string(),
-                message: messages[self.issues.len() % messages.len()].to_string(),
-                severity: Severity::Nuclear,
-                roast_level: RoastLevel::Savage,
-            });
-        }
-
-        // 检查危险操作过多
-        if dangerous_op_count > 3 {
-            let messages = [
-                "危险的内存操作比我的危险驾驶还要多！",
-                "这些危险操作让我想起了 C++ 的恐怖回忆",
-                "内存操作过于危险，建议使用安全替代方案",
-                "你的代码比走钢丝还危险，小心内存泄漏！",
-            ];
-
-            self.issues.push(CodeIssue {
-                file_path: self.file_path.clone(),
-                line: 1,
-                column: 1,
-                rule_name: "unsafe-abuse".to_string(),
-                message: messages[self.issues.len() % messages.len()].to_string(),
-                severity: Severity::Nuclear,
-                roast_level: RoastLevel::Savage,
-            });
-        }
-    }
-}
-
-impl<'ast> Visit<'ast> for UnsafeVisitor {
-    fn visit_expr_unsafe(&mut self, _unsafe_expr: &'ast ExprUnsafe) {
-        self.unsafe_count += 1;
-
-        let messages = [
-            "Unsafe 代码！你这是在玩火还是在挑战 Rust 的底线？",
-            "又见 unsafe！安全性是什么？能吃吗？",
-            "Unsafe 使用者，恭喜你获得了'内存安全破坏者'称号",
-            "这个 unsafe 让我想起了 C 语言的恐怖回忆",
-            "Unsafe 代码：让 Rust 程序员夜不能寐的存在",
-        ];
-
-        let severity = if self.unsafe_count > 3 {
-            Severity::Nuclear
-        } else {
-            Severity::Spicy
-        };
-
-        self.issues.push(CodeIssue {
-            file_path: self.file_path.clone(),
-            line: 1,
-            column: 1,
-            rule_name: "unsafe-abuse".to_string(),
-            message: messages[self.issues.len() % messages.len()].to_string(),
-            severity,
-            roast_level: RoastLevel::Savage,
-        });
-
-        syn::visit::visit_expr_unsafe(self, _unsafe_expr);
-    }
-
-    fn visit_item_fn(&mut self, item_fn: &'ast ItemFn) {
-        if item_fn.sig.unsafety.is_some() {
-            self.unsafe_fn_count += 1;
-        }
-        syn::visit::visit_item_fn(self, item_fn);
-    }
-}
-
 // FFI Visitor
 struct FFIVisitor {
     file_path: std::path::PathBuf,
@@ -659,6 +306,7 @@ impl FFIVisitor {
                 message: messages[self.issues.len() % messages.len()].to_string(),
                 severity: Severity::Spicy,
                 roast_level: RoastLevel::Sarcastic,
+                context: CodeContext::Production,
             });
         }
 
@@ -679,6 +327,7 @@ impl FFIVisitor {
                 message: messages[self.issues.len() % messages.len()].to_string(),
                 severity: Severity::Nuclear,
                 roast_level: RoastLevel::Savage,
+                context: CodeContext::Production,
             });
         }
 
@@ -699,6 +348,7 @@ impl FFIVisitor {
                 message: messages[self.issues.len() % messages.len()].to_string(),
                 severity: Severity::Spicy,
                 roast_level: RoastLevel::Sarcastic,
+                context: CodeContext::Production,
             });
         }
 
@@ -719,6 +369,7 @@ impl FFIVisitor {
                 message: messages[self.issues.len() % messages.len()].to_string(),
                 severity: Severity::Spicy,
                 roast_level: RoastLevel::Sarcastic,
+                context: CodeContext::Production,
             });
         }
     }
@@ -752,6 +403,7 @@ impl<'ast> Visit<'ast> for FFIVisitor {
                 message: messages[self.issues.len() % messages.len()].to_string(),
                 severity: Severity::Spicy,
                 roast_level: RoastLevel::Sarcastic,
+                context: CodeContext::Production,
             });
         }
 
@@ -775,20 +427,165 @@ impl<'ast> Visit<'ast> for FFIVisitor {
 struct MacroVisitor {
     file_path: std::path::PathBuf,
     issues: Vec<CodeIssue>,
+    lang: String,
     macro_count: usize,
 }
 
 impl MacroVisitor {
-    fn new(file_path: std::path::PathBuf) -> Self {
+    fn new(file_path: std::path::PathBuf, lang: &str) -> Self {
         Self {
             file_path,
             issues: Vec::new(),
+            lang: lang.to_string(),
             macro_count: 0,
         }
     }
+
+    fn push_issue<T: Spanned>(
+        &mut self,
+        node: &T,
+        message: String,
+        severity: Severity,
+        roast_level: RoastLevel,
+    ) {
+        let (line, column) = get_position(node);
+        self.issues.push(CodeIssue {
+            file_path: self.file_path.clone(),
+            line,
+            column,
+            rule_name: "macro-abuse".to_string(),
+            message,
+            severity,
+            roast_level,
+            context: CodeContext::Production,
+        });
+    }
+
+    fn check_macro_rules(&mut self, item_macro: &ItemMacro) {
+        let Some(name) = item_macro.ident.as_ref() else {
+            return;
+        };
+        let name = name.to_string();
+        let arms = macro_rules_arms(item_macro.mac.tokens.clone());
+        if arms.is_empty() {
+            return;
+        }
+        let zh = self.lang == "zh-CN";
+
+        // 巨型宏：规则分支太多，或者某个分支展开后长得离谱
+        if arms.len() > MAX_MACRO_ARMS {
+            let message = if zh {
+                format!(
+                    "宏 {name}! 有 {} 个匹配分支，这是宏还是一个手写的解析器？考虑拆分或改用过程宏",
+                    arms.len()
+                )
+            } else {
+                format!(
+                    "macro {name}! has {} match arms - is it a macro or a hand-rolled parser? Split it or use a proc macro",
+                    arms.len()
+                )
+            };
+            self.push_issue(
+                &item_macro.ident,
+                message,
+                Severity::Spicy,
+                RoastLevel::Sarcastic,
+            );
+        }
+
+        for (matcher, transcriber) in &arms {
+            let span = transcriber.span();
+            let lines = span.end().line.saturating_sub(span.start().line) + 1;
+            if lines > MAX_TRANSCRIBER_LINES {
+                let message = if zh {
+                    format!("宏 {name}! 的一个分支展开成 {lines} 行代码，调试的时候准备哭吧，把逻辑挪进普通函数")
+                } else {
+                    format!("one arm of {name}! expands to {lines} lines - move the logic into a plain function")
+                };
+                self.push_issue(matcher, message, Severity::Mild, RoastLevel::Gentle);
+            }
+
+            // $x:expr 在展开里出现多次会被求值多次
+            for metavar in expr_metavars(matcher.stream()) {
+                let uses = count_metavar(transcriber.stream(), &metavar);
+                if uses > 1 {
+                    let message = if zh {
+                        format!("${metavar} 在 {name}! 的展开里用了 {uses} 次，有副作用的表达式会被求值 {uses} 次，先 let 绑定一下")
+                    } else {
+                        format!("${metavar} is expanded {uses} times in {name}!, side effects run {uses} times - bind it with let first")
+                    };
+                    self.push_issue(matcher, message, Severity::Spicy, RoastLevel::Sarcastic);
+                }
+            }
+        }
+
+        // #[macro_export] 的宏里用 crate:: 会指向调用者的 crate
+        let exported = item_macro
+            .attrs
+            .iter()
+            .any(|attr| attr.path().is_ident("macro_export"));
+        if exported {
+            if let Some(ident) = arms
+                .iter()
+                .find_map(|(_, transcriber)| bare_crate_path(transcriber.stream()))
+            {
+                let message = if zh {
+                    format!("导出的宏 {name}! 里写 crate:: 会解析到调用方的 crate，卫生性翻车了，请用 $crate::")
+                } else {
+                    format!("exported macro {name}! uses crate::, which resolves in the caller's crate - use $crate:: instead")
+                };
+                self.push_issue(&ident, message, Severity::Spicy, RoastLevel::Sarcastic);
+            }
+        }
+
+        // 递归宏：没有出口，或者靠 tt 吞噬器一个个吃 token
+        let recursive: Vec<_> = arms
+            .iter()
+            .filter(|(_, transcriber)| invokes_macro(transcriber.stream(), &name))
+            .collect();
+        if recursive.is_empty() {
+            return;
+        }
+
+        if recursive.len() == arms.len() {
+            let message = if zh {
+                format!("宏 {name}! 的每个分支都在调用自己，没有递归出口，编译器会一直展开到 recursion_limit")
+            } else {
+                format!("every arm of {name}! calls itself - there is no base case, expansion only stops at recursion_limit")
+            };
+            self.push_issue(
+                &item_macro.ident,
+                message,
+                Severity::Nuclear,
+                RoastLevel::Savage,
+            );
+        } else if recursive
+            .iter()
+            .any(|(matcher, _)| has_tt_repetition(matcher.stream()))
+        {
+            let message = if zh {
+                format!("{name}! 是 tt 吞噬器，每个 token 消耗一层递归，输入一长就会撞上 recursion_limit（默认 128）")
+            } else {
+                format!("{name}! is a tt muncher - each token costs a recursion level, long inputs hit recursion_limit (128 by default)")
+            };
+            self.push_issue(
+                &item_macro.ident,
+                message,
+                Severity::Mild,
+                RoastLevel::Gentle,
+            );
+        }
+    }
 }
 
 impl<'ast> Visit<'ast> for MacroVisitor {
+    fn visit_item_macro(&mut self, item_macro: &'ast ItemMacro) {
+        if item_macro.mac.path.is_ident("macro_rules") {
+            self.check_macro_rules(item_macro);
+        }
+        syn::visit::visit_item_macro(self, item_macro);
+    }
+
     fn visit_macro(&mut self, _macro: &'ast Macro) {
         self.macro_count += 1;
 
@@ -809,6 +606,7 @@ impl<'ast> Visit<'ast> for MacroVisitor {
                 message: messages[self.issues.len() % messages.len()].to_string(),
                 severity: Severity::Mild,
                 roast_level: RoastLevel::Gentle,
+                context: CodeContext::Production,
             });
         }
 
@@ -816,6 +614,126 @@ impl<'ast> Visit<'ast> for MacroVisitor {
     }
 }
 
+const MAX_MACRO_ARMS: usize = 8;
+const MAX_TRANSCRIBER_LINES: usize = 40;
+
+/// 把 macro_rules! 的主体拆成 (matcher, transcriber) 对
+fn macro_rules_arms(tokens: TokenStream) -> Vec<(Group, Group)> {
+    let mut arms = Vec::new();
+    let mut iter = tokens.into_iter().peekable();
+
+    while let Some(token) = iter.next() {
+        let TokenTree::Group(matcher) = token else {
+            continue;
+        };
+        // 跳过 `=>`
+        let arrow: Vec<_> = iter.by_ref().take(2).collect();
+        if arrow.len() != 2 {
+            break;
+        }
+        if let Some(TokenTree::Group(transcriber)) = iter.next() {
+            arms.push((matcher, transcriber));
+        }
+        if matches!(iter.peek(), Some(TokenTree::Punct(p)) if p.as_char() == ';') {
+            iter.next();
+        }
+    }
+
+    arms
+}
+
+/// matcher 里所有 `$name:expr` 形式的元变量
+fn expr_metavars(tokens: TokenStream) -> Vec<String> {
+    let tokens: Vec<TokenTree> = tokens.into_iter().collect();
+    let mut names = Vec::new();
+
+    for (index, token) in tokens.iter().enumerate() {
+        match token {
+            TokenTree::Punct(dollar) if dollar.as_char() == '$' => {
+                if let [TokenTree::Ident(name), TokenTree::Punct(colon), TokenTree::Ident(fragment), ..] =
+                    &tokens[index + 1..]
+                {
+                    if colon.as_char() == ':' && fragment == "expr" {
+                        names.push(name.to_string());
+                    }
+                }
+            }
+            TokenTree::Group(group) => names.extend(expr_metavars(group.stream())),
+            _ => {}
+        }
+    }
+
+    names
+}
+
+/// transcriber 里 `$name` 出现的次数
+fn count_metavar(tokens: TokenStream, name: &str) -> usize {
+    let mut count = 0;
+    let mut after_dollar = false;
+
+    for token in tokens {
+        match &token {
+            TokenTree::Ident(ident) if after_dollar && ident == name => count += 1,
+            TokenTree::Group(group) => count += count_metavar(group.stream(), name),
+            _ => {}
+        }
+        after_dollar = matches!(&token, TokenTree::Punct(p) if p.as_char() == '$');
+    }
+
+    count
+}
+
+/// transcriber 是否调用了名为 `name` 的宏
+fn invokes_macro(tokens: TokenStream, name: &str) -> bool {
+    let tokens: Vec<TokenTree> = tokens.into_iter().collect();
+    tokens.iter().enumerate().any(|(index, token)| match token {
+        TokenTree::Ident(ident) if ident == name => matches!(
+            tokens.get(index + 1),
+            Some(TokenTree::Punct(bang)) if bang.as_char() == '!'
+        ),
+        TokenTree::Group(group) => invokes_macro(group.stream(), name),
+        _ => false,
+    })
+}
+
+/// 没有 `$` 前缀的 `crate` 路径
+fn bare_crate_path(tokens: TokenStream) -> Option<proc_macro2::Ident> {
+    let mut after_dollar = false;
+
+    for token in tokens {
+        match &token {
+            TokenTree::Ident(ident) if ident == "crate" && !after_dollar => {
+                return Some(ident.clone());
+            }
+            TokenTree::Group(group) => {
+                if let Some(ident) = bare_crate_path(group.stream()) {
+                    return Some(ident);
+                }
+            }
+            _ => {}
+        }
+        after_dollar = matches!(&token, TokenTree::Punct(p) if p.as_char() == '$');
+    }
+
+    None
+}
+
+/// matcher 里是否有 `$($rest:tt)*` 这样的重复 tt
+fn has_tt_repetition(tokens: TokenStream) -> bool {
+    let tokens: Vec<TokenTree> = tokens.into_iter().collect();
+    tokens.iter().enumerate().any(|(index, token)| match token {
+        TokenTree::Punct(dollar) if dollar.as_char() == '$' => match tokens.get(index + 1) {
+            Some(TokenTree::Group(group)) => group
+                .stream()
+                .into_iter()
+                .any(|inner| matches!(inner, TokenTree::Ident(ident) if ident == "tt")),
+            _ => false,
+        },
+        TokenTree::Group(group) => has_tt_repetition(group.stream()),
+        _ => false,
+    })
+}
+
 // Module Visitor
 struct ModuleVisitor {
     file_path: std::path::PathBuf,
@@ -856,6 +774,7 @@ impl<'ast> Visit<'ast> for ModuleVisitor {
                 message: messages[self.issues.len() % messages.len()].to_string(),
                 severity: Severity::Spicy,
                 roast_level: RoastLevel::Sarcastic,
+                context: CodeContext::Production,
             });
         }
 
@@ -865,76 +784,205 @@ impl<'ast> Visit<'ast> for ModuleVisitor {
 }
 
 // Pattern Visitor
+/// match 分支超过这个数，通常该拆成方法或者查表了
+const MAX_MATCH_ARMS: usize = 10;
+
 struct PatternVisitor {
     file_path: std::path::PathBuf,
     issues: Vec<CodeIssue>,
-    complex_pattern_count: usize,
+    /// 本文件里定义的枚举：名字 -> 变体
+    enums: HashMap<String, Vec<String>>,
+    /// 当前所在 impl 的 Self 类型，用来解析 Self::Variant
+    impl_stack: Vec<Option<String>>,
+    lang: String,
 }
 
 impl PatternVisitor {
-    fn new(file_path: std::path::PathBuf) -> Self {
+    fn new(file_path: std::path::PathBuf, syntax_tree: &File, lang: &str) -> Self {
+        let mut collector = EnumCollector::default();
+        collector.visit_file(syntax_tree);
         Self {
             file_path,
             issues: Vec::new(),
-            complex_pattern_count: 0,
+            enums: collector.unambiguous_enums(),
+            impl_stack: Vec::new(),
+            lang: lang.to_string(),
         }
     }
 
-    fn check_pattern_complexity(&mut self, pattern_type: &str) {
-        self.complex_pattern_count += 1;
+    /// `Kind::A` / `Self::A(..)` / `Kind::B { .. }` -> (枚举名, 变体名)
+    fn enum_variant(&self, pat: &Pat) -> Option<(String, String)> {
+        let path = match pat {
+            Pat::Path(p) => &p.path,
+            Pat::TupleStruct(p) => &p.path,
+            Pat::Struct(p) => &p.path,
+            Pat::Ident(p) => {
+                return p
+                    .subpat
+                    .as_ref()
+                    .and_then(|(_, sub)| self.enum_variant(sub))
+            }
+            _ => return None,
+        };
+        let n = path.segments.len();
+        if n < 2 {
+            return None;
+        }
+        let owner = path.segments[n - 2].ident.to_string();
+        let owner = if owner == "Self" {
+            self.impl_stack.last().cloned().flatten()?
+        } else {
+            owner
+        };
+        self.enums
+            .contains_key(&owner)
+            .then(|| (owner, path.segments[n - 1].ident.to_string()))
+    }
 
-        if self.complex_pattern_count > 15 {
-            let messages = [
-                format!("{pattern_type}模式匹配比我的感情生活还复杂"),
-                format!("这么多{pattern_type}模式，你是在写解谜游戏吗？"),
-                format!("{pattern_type}模式过多，建议简化逻辑"),
-                format!("复杂的{pattern_type}模式让代码可读性直线下降"),
-            ];
+    /// `_ =>` 吞掉了本文件枚举里剩下的变体，以后加新变体也不会报错
+    fn check_hidden_variants(&mut self, match_expr: &ExprMatch) {
+        let Some(wild_arm) = match_expr
+            .arms
+            .iter()
+            .find(|arm| matches!(arm.pat, Pat::Wild(_)) && arm.guard.is_none())
+        else {
+            return;
+        };
 
-            self.issues.push(CodeIssue {
-                file_path: self.file_path.clone(),
-                line: 1,
-                column: 1,
-                rule_name: "pattern-matching-abuse".to_string(),
-                message: messages[self.issues.len() % messages.len()].to_string(),
-                severity: Severity::Mild,
-                roast_level: RoastLevel::Gentle,
-            });
+        let mut owner = None;
+        let mut matched = HashSet::new();
+        for arm in &match_expr.arms {
+            let pats: Vec<&Pat> = match &arm.pat {
+                Pat::Or(or) => or.cases.iter().collect(),
+                pat => vec![pat],
+            };
+            for pat in pats {
+                if let Some((enum_name, variant)) = self.enum_variant(pat) {
+                    owner = Some(enum_name);
+                    // 带守卫的分支不算覆盖了这个变体
+                    if arm.guard.is_none() {
+                        matched.insert(variant);
+                    }
+                }
+            }
+        }
+        // 只挑出一个变体的是 if let 的写法，`_` 是惯用的
+        let Some(enum_name) = owner.filter(|_| matched.len() >= 2) else {
+            return;
+        };
+        let hidden: Vec<String> = self.enums[&enum_name]
+            .iter()
+            .filter(|variant| !matched.contains(*variant))
+            .map(|variant| format!("{enum_name}::{variant}"))
+            .collect();
+        if hidden.is_empty() {
+            return;
         }
+        let suggestion = hidden.join(" | ");
+
+        let messages = if self.lang == "zh-CN" {
+            vec![
+                format!("`_` 分支悄悄吞掉了 {suggestion}，以后加新变体编译器也不会提醒你；改成 `{suggestion} =>`"),
+                format!("通配分支藏住了 {enum_name} 的变体，把它们列出来：`{suggestion} =>`"),
+            ]
+        } else {
+            vec![
+                format!("the `_` arm silently swallows {suggestion} and any variant added later; write `{suggestion} =>` instead"),
+                format!("a wildcard arm hides variants of {enum_name}, list them: `{suggestion} =>`"),
+            ]
+        };
+
+        let (line, column) = get_position(&wild_arm.pat);
+        self.issues.push(CodeIssue {
+            file_path: self.file_path.clone(),
+            line,
+            column,
+            rule_name: "pattern-matching-abuse".to_string(),
+            message: messages[self.issues.len() % messages.len()].clone(),
+            severity: Severity::Mild,
+            roast_level: RoastLevel::Gentle,
+            context: CodeContext::Production,
+        });
+    }
+
+    fn check_arm_count(&mut self, match_expr: &ExprMatch) {
+        let count = match_expr.arms.len();
+        if count <= MAX_MATCH_ARMS {
+            return;
+        }
+
+        let messages = if self.lang == "zh-CN" {
+            vec![
+                format!("{count} 个 match 分支，比我的人生选择还多"),
+                format!("这个 match 有 {count} 个分支，你确定不是在写状态机？考虑查表或者把逻辑挪到枚举的方法里"),
+            ]
+        } else {
+            vec![
+                format!("{count} match arms, more than my life choices"),
+                format!("this match has {count} arms - are you writing a state machine? Consider a lookup table or methods on the enum"),
+            ]
+        };
+
+        let (line, column) = get_position(match_expr);
+        self.issues.push(CodeIssue {
+            file_path: self.file_path.clone(),
+            line,
+            column,
+            rule_name: "pattern-matching-abuse".to_string(),
+            message: messages[self.issues.len() % messages.len()].clone(),
+            severity: Severity::Spicy,
+            roast_level: RoastLevel::Sarcastic,
+            context: CodeContext::Production,
+        });
     }
 }
 
 impl<'ast> Visit<'ast> for PatternVisitor {
-    fn visit_pat_tuple(&mut self, _tuple_pat: &'ast PatTuple) {
-        self.check_pattern_complexity("元组");
-        syn::visit::visit_pat_tuple(self, _tuple_pat);
+    fn visit_item_impl(&mut self, item_impl: &'ast ItemImpl) {
+        let self_name = match item_impl.self_ty.as_ref() {
+            Type::Path(type_path) => type_path.path.segments.last().map(|s| s.ident.to_string()),
+            _ => None,
+        };
+        self.impl_stack.push(self_name);
+        syn::visit::visit_item_impl(self, item_impl);
+        self.impl_stack.pop();
     }
 
-    fn visit_pat_slice(&mut self, _slice_pat: &'ast PatSlice) {
-        self.check_pattern_complexity("切片");
-        syn::visit::visit_pat_slice(self, _slice_pat);
+    fn visit_expr_match(&mut self, match_expr: &'ast ExprMatch) {
+        self.check_hidden_variants(match_expr);
+        self.check_arm_count(match_expr);
+        syn::visit::visit_expr_match(self, match_expr);
     }
+}
 
-    fn visit_expr_match(&mut self, match_expr: &'ast ExprMatch) {
-        if match_expr.arms.len() > 10 {
-            let messages = [
-                "Match 分支比我的人生选择还多",
-                "这么多 match 分支，你确定不是在写状态机？",
-                "Match 分支过多，建议重构",
-                "这个 match 比电视遥控器的按钮还多",
-            ];
+#[derive(Default)]
+struct EnumCollector {
+    enums: HashMap<String, Vec<String>>,
+    /// 不同模块里同名的枚举，只凭名字分不清 match 的是哪一个
+    ambiguous: HashSet<String>,
+}
 
-            self.issues.push(CodeIssue {
-                file_path: self.file_path.clone(),
-                line: 1,
-                column: 1,
-                rule_name: "pattern-matching-abuse".to_string(),
-                message: messages[self.issues.len() % messages.len()].to_string(),
-                severity: Severity::Spicy,
-                roast_level: RoastLevel::Sarcastic,
-            });
+impl EnumCollector {
+    fn unambiguous_enums(mut self) -> HashMap<String, Vec<String>> {
+        for name in &self.ambiguous {
+            self.enums.remove(name);
         }
-        syn::visit::visit_expr_match(self, match_expr);
+        self.enums
+    }
+}
+
+impl<'ast> Visit<'ast> for EnumCollector {
+    fn visit_item_enum(&mut self, item_enum: &'ast ItemEnum) {
+        let name = item_enum.ident.to_string();
+        let variants = item_enum
+            .variants
+            .iter()
+            .map(|variant| variant.ident.to_string())
+            .collect();
+        if self.enums.insert(name.clone(), variants).is_some() {
+            self.ambiguous.insert(name);
+        }
+        syn::visit::visit_item_enum(self, item_enum);
     }
 }
 
@@ -975,6 +1023,7 @@ impl<'ast> Visit<'ast> for ReferenceVisitor {
                 message: messages[self.issues.len() % messages.len()].to_string(),
                 severity: Severity::Mild,
                 roast_level: RoastLevel::Gentle,
+                context: CodeContext::Production,
             });
         }
 
@@ -1039,6 +1088,7 @@ impl<'ast> Visit<'ast> for SliceVisitor {
                 message: messages[self.issues.len() % messages.len()].to_string(),
                 severity: Severity::Mild,
                 roast_level: RoastLevel::Gentle,
+                context: CodeContext::Production,
             });
         }
 
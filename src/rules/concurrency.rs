@@ -0,0 +1,771 @@
+use std::collections::HashMap;
+use std::path::Path;
+use syn::{
+    visit::Visit, Block, Expr, ExprAsync, ExprAwait, ExprCall, ExprClosure, ExprForLoop, ExprLoop,
+    ExprMethodCall, ExprWhile, File, GenericArgument, ImplItemFn, ItemFn, ItemUse, Local, Macro,
+    Pat, PathArguments, Stmt, Type, TypePath, UseTree,
+};
+
+use crate::analyzer::{CodeContext, CodeIssue, Severity};
+use crate::rules::common::{is_lock_call, rule_issue};
+use crate::rules::macro_args::visit_macro_args;
+use crate::rules::{ContextPolicy, Rule};
+use crate::utils::get_position;
+
+/// 检测在循环里创建的无界 channel
+pub struct ChannelAbuseRule;
+
+/// 检测 async 代码里的阻塞调用，以及跨 .await 持有的 std 锁
+pub struct AsyncAbuseRule;
+
+/// 检测 Arc<Mutex<Vec<..>>> 之类的嵌套共享状态和 lock().unwrap() 链
+pub struct LockAbuseRule;
+
+impl Rule for ChannelAbuseRule {
+    fn name(&self) -> &'static str {
+        "channel-abuse"
+    }
+
+    fn context_policy(&self, context: CodeContext) -> ContextPolicy {
+        match context {
+            CodeContext::Test => ContextPolicy::Ignore,
+            CodeContext::Example | CodeContext::Bench => ContextPolicy::Downgrade,
+            CodeContext::Production | CodeContext::BuildScript => ContextPolicy::Report,
+        }
+    }
+
+    fn check(
+        &self,
+        file_path: &Path,
+        syntax_tree: &File,
+        _content: &str,
+        lang: &str,
+    ) -> Vec<CodeIssue> {
+        let mut visitor = ChannelVisitor::new(file_path.to_path_buf(), lang);
+        visitor.visit_file(syntax_tree);
+        visitor.issues
+    }
+}
+
+impl Rule for AsyncAbuseRule {
+    fn name(&self) -> &'static str {
+        "async-abuse"
+    }
+
+    fn context_policy(&self, context: CodeContext) -> ContextPolicy {
+        match context {
+            CodeContext::Production | CodeContext::BuildScript => ContextPolicy::Report,
+            _ => ContextPolicy::Downgrade,
+        }
+    }
+
+    fn check(
+        &self,
+        file_path: &Path,
+        syntax_tree: &File,
+        _content: &str,
+        lang: &str,
+    ) -> Vec<CodeIssue> {
+        let mut imports = StdImports::default();
+        imports.visit_file(syntax_tree);
+
+        let mut visitor = AsyncVisitor::new(file_path.to_path_buf(), lang, imports);
+        visitor.visit_file(syntax_tree);
+        visitor.issues
+    }
+}
+
+impl Rule for LockAbuseRule {
+    fn name(&self) -> &'static str {
+        "lock-abuse"
+    }
+
+    fn context_policy(&self, context: CodeContext) -> ContextPolicy {
+        match context {
+            CodeContext::Test => ContextPolicy::Ignore,
+            CodeContext::Example | CodeContext::Bench => ContextPolicy::Downgrade,
+            CodeContext::Production | CodeContext::BuildScript => ContextPolicy::Report,
+        }
+    }
+
+    fn check(
+        &self,
+        file_path: &Path,
+        syntax_tree: &File,
+        _content: &str,
+        lang: &str,
+    ) -> Vec<CodeIssue> {
+        let mut visitor = LockVisitor::new(file_path.to_path_buf(), lang);
+        visitor.visit_file(syntax_tree);
+        visitor.issues
+    }
+}
+
+// ============================================================================
+// 循环里的无界 channel
+// ============================================================================
+
+struct ChannelVisitor {
+    file_path: std::path::PathBuf,
+    issues: Vec<CodeIssue>,
+    lang: String,
+    loop_depth: usize,
+}
+
+impl ChannelVisitor {
+    fn new(file_path: std::path::PathBuf, lang: &str) -> Self {
+        Self {
+            file_path,
+            issues: Vec::new(),
+            lang: lang.to_string(),
+            loop_depth: 0,
+        }
+    }
+
+    fn check_channel_call(&mut self, call: &ExprCall) {
+        if self.loop_depth == 0 {
+            return;
+        }
+        let Expr::Path(func) = call.func.as_ref() else {
+            return;
+        };
+        let Some(name) = func.path.segments.last().map(|s| s.ident.to_string()) else {
+            return;
+        };
+
+        // std::sync::mpsc::channel() 没有容量参数，本身就是无界的；
+        // tokio 的 mpsc::channel(n) 带容量，是有界的
+        let unbounded = match name.as_str() {
+            "unbounded_channel" | "unbounded" => true,
+            "channel" => call.args.is_empty(),
+            _ => false,
+        };
+        if !unbounded {
+            return;
+        }
+
+        let messages = if self.lang == "zh-CN" {
+            vec![
+                format!("在循环里创建无界 channel {name}()，每一圈都新开一条没有背压的管道，内存会替你记住每一条消息"),
+                format!("循环里的 {name}()：生产者跑得比消费者快时，无界队列会一直涨到 OOM"),
+                format!("每次循环都 {name}() 一次？把 channel 提到循环外，或者换成有容量上限的 channel"),
+            ]
+        } else {
+            vec![
+                format!("unbounded {name}() created inside a loop - a new pipe with no backpressure every iteration"),
+                format!("{name}() in a loop: when producers outrun consumers, an unbounded queue grows until OOM"),
+                format!("a fresh {name}() per iteration? Hoist it out of the loop or use a bounded channel"),
+            ]
+        };
+
+        self.issues.push(rule_issue(
+            &self.file_path,
+            get_position(call),
+            "channel-abuse",
+            messages[self.issues.len() % messages.len()].clone(),
+            Severity::Spicy,
+        ));
+    }
+}
+
+impl<'ast> Visit<'ast> for ChannelVisitor {
+    fn visit_item_fn(&mut self, item_fn: &'ast ItemFn) {
+        let outer = std::mem::replace(&mut self.loop_depth, 0);
+        syn::visit::visit_item_fn(self, item_fn);
+        self.loop_depth = outer;
+    }
+
+    fn visit_expr_for_loop(&mut self, for_loop: &'ast ExprForLoop) {
+        // 迭代表达式只求值一次，不算在循环体里
+        self.visit_expr(&for_loop.expr);
+        self.loop_depth += 1;
+        self.visit_block(&for_loop.body);
+        self.loop_depth -= 1;
+    }
+
+    fn visit_expr_while(&mut self, while_loop: &'ast ExprWhile) {
+        self.loop_depth += 1;
+        syn::visit::visit_expr_while(self, while_loop);
+        self.loop_depth -= 1;
+    }
+
+    fn visit_expr_loop(&mut self, loop_expr: &'ast ExprLoop) {
+        self.loop_depth += 1;
+        syn::visit::visit_expr_loop(self, loop_expr);
+        self.loop_depth -= 1;
+    }
+
+    fn visit_expr_call(&mut self, call: &'ast ExprCall) {
+        self.check_channel_call(call);
+        syn::visit::visit_expr_call(self, call);
+    }
+
+    fn visit_macro(&mut self, mac: &'ast Macro) {
+        visit_macro_args(self, mac);
+    }
+}
+
+// ============================================================================
+// async 代码里的阻塞调用和跨 .await 的锁
+// ============================================================================
+
+/// 文件里从 std::fs / std::net / std::thread 导入的名字，名字 -> 完整路径
+#[derive(Default)]
+struct StdImports {
+    names: HashMap<String, String>,
+}
+
+impl StdImports {
+    fn collect(&mut self, tree: &UseTree, prefix: &mut Vec<String>) {
+        match tree {
+            UseTree::Path(path) => {
+                prefix.push(path.ident.to_string());
+                self.collect(&path.tree, prefix);
+                prefix.pop();
+            }
+            UseTree::Name(name) => self.record(prefix, &name.ident.to_string(), None),
+            UseTree::Rename(rename) => self.record(
+                prefix,
+                &rename.ident.to_string(),
+                Some(rename.rename.to_string()),
+            ),
+            UseTree::Group(group) => {
+                for item in &group.items {
+                    self.collect(item, prefix);
+                }
+            }
+            UseTree::Glob(_) => {}
+        }
+    }
+
+    fn record(&mut self, prefix: &[String], ident: &str, rename: Option<String>) {
+        let mut full: Vec<&str> = prefix.iter().map(String::as_str).collect();
+        if ident != "self" {
+            full.push(ident);
+        }
+        if full.len() < 2 || full[0] != "std" || !BLOCKING_MODULES.contains(&full[1]) {
+            return;
+        }
+        let local = rename.unwrap_or_else(|| full[full.len() - 1].to_string());
+        self.names.insert(local, full.join("::"));
+    }
+
+    /// 把调用路径展开成 std:: 开头的完整路径，不是 std 的返回 None
+    fn resolve(&self, segments: &[String]) -> Option<String> {
+        let first = segments.first()?;
+        if first == "std" {
+            return Some(segments.join("::"));
+        }
+        let base = self.names.get(first)?;
+        let mut full = vec![base.clone()];
+        full.extend(segments[1..].iter().cloned());
+        Some(full.join("::"))
+    }
+}
+
+impl<'ast> Visit<'ast> for StdImports {
+    fn visit_item_use(&mut self, item_use: &'ast ItemUse) {
+        self.collect(&item_use.tree, &mut Vec::new());
+    }
+}
+
+/// 在 async 上下文里会阻塞整个执行器线程的 std 模块
+const BLOCKING_MODULES: &[&str] = &["fs", "net", "thread"];
+
+struct AsyncVisitor {
+    file_path: std::path::PathBuf,
+    issues: Vec<CodeIssue>,
+    lang: String,
+    imports: StdImports,
+    in_async: bool,
+}
+
+impl AsyncVisitor {
+    fn new(file_path: std::path::PathBuf, lang: &str, imports: StdImports) -> Self {
+        Self {
+            file_path,
+            issues: Vec::new(),
+            lang: lang.to_string(),
+            imports,
+            in_async: false,
+        }
+    }
+
+    fn with_async<F: FnOnce(&mut Self)>(&mut self, in_async: bool, f: F) {
+        let outer = std::mem::replace(&mut self.in_async, in_async);
+        f(self);
+        self.in_async = outer;
+    }
+
+    fn check_blocking_call(&mut self, call: &ExprCall) {
+        let Expr::Path(func) = call.func.as_ref() else {
+            return;
+        };
+        let segments: Vec<String> = func
+            .path
+            .segments
+            .iter()
+            .map(|s| s.ident.to_string())
+            .collect();
+        let Some(full) = self.imports.resolve(&segments) else {
+            return;
+        };
+
+        let (line, column) = get_position(call);
+        if full == "std::thread::sleep" {
+            let messages = if self.lang == "zh-CN" {
+                vec![
+                    "async 函数里调用 thread::sleep，整个执行器线程陪你一起睡，其他任务全都饿着".to_string(),
+                    "在 async 里 thread::sleep？这不是等待，这是绑架执行器，换成 tokio::time::sleep(..).await".to_string(),
+                    "thread::sleep 出现在 async 代码里，同一线程上的所有 future 都被你按了暂停键".to_string(),
+                ]
+            } else {
+                vec![
+                    "thread::sleep inside async code puts the whole executor thread to sleep, every other task starves".to_string(),
+                    "thread::sleep in async? That's not waiting, that's kidnapping the executor - use tokio::time::sleep(..).await".to_string(),
+                    "thread::sleep in async code hits pause on every future sharing this thread".to_string(),
+                ]
+            };
+            self.issues.push(rule_issue(
+                &self.file_path,
+                (line, column),
+                "async-abuse",
+                messages[self.issues.len() % messages.len()].clone(),
+                Severity::Nuclear,
+            ));
+            return;
+        }
+
+        let module = if full.starts_with("std::fs::") {
+            "std::fs"
+        } else if full.starts_with("std::net::") {
+            "std::net"
+        } else {
+            return;
+        };
+        let call_name = full.trim_start_matches("std::");
+
+        let messages = if self.lang == "zh-CN" {
+            vec![
+                format!("async 代码里调用阻塞的 {call_name}，执行器线程在等磁盘/网络时什么也干不了"),
+                format!("{module} 是同步 API，在 async 里用 {call_name} 会卡住执行器，换成 tokio 的对应版本或 spawn_blocking"),
+                format!("{call_name} 会阻塞当前线程，async 函数里请用异步版本"),
+            ]
+        } else {
+            vec![
+                format!("blocking {call_name} in async code - the executor thread can't do anything while it waits on I/O"),
+                format!("{module} is a synchronous API, {call_name} in async stalls the executor - use the tokio equivalent or spawn_blocking"),
+                format!("{call_name} blocks the current thread, async functions want the async version"),
+            ]
+        };
+        self.issues.push(rule_issue(
+            &self.file_path,
+            (line, column),
+            "async-abuse",
+            messages[self.issues.len() % messages.len()].clone(),
+            Severity::Spicy,
+        ));
+    }
+
+    /// let guard = x.lock().unwrap(); 之后同一个块里还有 .await，并且中间没有 drop(guard)
+    fn check_guard_across_await(&mut self, block: &Block) {
+        for (index, stmt) in block.stmts.iter().enumerate() {
+            let Stmt::Local(local) = stmt else {
+                continue;
+            };
+            let Some(guard) = std_guard_binding(local) else {
+                continue;
+            };
+
+            for later in &block.stmts[index + 1..] {
+                if is_drop_of(later, &guard) {
+                    break;
+                }
+                let mut finder = AwaitFinder::default();
+                finder.visit_stmt(later);
+                let Some(await_line) = finder.line else {
+                    continue;
+                };
+
+                let messages = if self.lang == "zh-CN" {
+                    vec![
+                        format!("std 的锁守卫 {guard} 跨过了第 {await_line} 行的 .await，future 不再是 Send，而且随时可能死锁"),
+                        format!("{guard} 还没释放就 .await（第 {await_line} 行），别的任务想拿这把锁只能干等"),
+                        format!("持有 std::sync 锁 {guard} 时 .await（第 {await_line} 行），在 await 之前 drop 掉，或者换成 tokio::sync::Mutex"),
+                    ]
+                } else {
+                    vec![
+                        format!("std lock guard {guard} is held across the .await on line {await_line} - the future is no longer Send and may deadlock"),
+                        format!("{guard} is still locked at the .await on line {await_line}, every other task wanting this lock just waits"),
+                        format!("holding std::sync guard {guard} across .await (line {await_line}) - drop it before awaiting or use tokio::sync::Mutex"),
+                    ]
+                };
+
+                self.issues.push(rule_issue(
+                    &self.file_path,
+                    get_position(local),
+                    "async-abuse",
+                    messages[self.issues.len() % messages.len()].clone(),
+                    Severity::Nuclear,
+                ));
+                break;
+            }
+        }
+    }
+}
+
+impl<'ast> Visit<'ast> for AsyncVisitor {
+    fn visit_item_fn(&mut self, item_fn: &'ast ItemFn) {
+        let is_async = item_fn.sig.asyncness.is_some();
+        self.with_async(is_async, |v| syn::visit::visit_item_fn(v, item_fn));
+    }
+
+    fn visit_impl_item_fn(&mut self, impl_fn: &'ast ImplItemFn) {
+        let is_async = impl_fn.sig.asyncness.is_some();
+        self.with_async(is_async, |v| syn::visit::visit_impl_item_fn(v, impl_fn));
+    }
+
+    fn visit_expr_async(&mut self, async_expr: &'ast ExprAsync) {
+        self.with_async(true, |v| syn::visit::visit_expr_async(v, async_expr));
+    }
+
+    fn visit_expr_closure(&mut self, closure: &'ast ExprClosure) {
+        // spawn_blocking(|| std::fs::read(..)) 里的同步闭包正是阻塞调用该待的地方
+        let is_async = closure.asyncness.is_some();
+        self.with_async(is_async, |v| syn::visit::visit_expr_closure(v, closure));
+    }
+
+    fn visit_block(&mut self, block: &'ast Block) {
+        if self.in_async {
+            self.check_guard_across_await(block);
+        }
+        syn::visit::visit_block(self, block);
+    }
+
+    fn visit_expr_call(&mut self, call: &'ast ExprCall) {
+        if self.in_async {
+            self.check_blocking_call(call);
+        }
+        syn::visit::visit_expr_call(self, call);
+    }
+
+    fn visit_macro(&mut self, mac: &'ast Macro) {
+        visit_macro_args(self, mac);
+    }
+}
+
+/// 同步锁的守卫：x.lock().unwrap()、x.write().expect(..)、x.lock()? 之类，不带 .await
+fn std_guard_binding(local: &Local) -> Option<String> {
+    let Pat::Ident(pat_ident) = &local.pat else {
+        return None;
+    };
+    let init = &local.init.as_ref()?.expr;
+
+    let lock_call = match init.as_ref() {
+        Expr::MethodCall(call)
+            if matches!(call.method.to_string().as_str(), "unwrap" | "expect") =>
+        {
+            call.receiver.as_ref()
+        }
+        Expr::Try(try_expr) => try_expr.expr.as_ref(),
+        other => other,
+    };
+
+    match lock_call {
+        Expr::MethodCall(call) if is_lock_call(call) => Some(pat_ident.ident.to_string()),
+        _ => None,
+    }
+}
+
+/// drop(guard);
+fn is_drop_of(stmt: &Stmt, guard: &str) -> bool {
+    let Stmt::Expr(Expr::Call(call), _) = stmt else {
+        return false;
+    };
+    let Expr::Path(func) = call.func.as_ref() else {
+        return false;
+    };
+    func.path.is_ident("drop")
+        && matches!(call.args.first(), Some(Expr::Path(arg)) if arg.path.is_ident(guard))
+}
+
+/// 找当前 future 自己的 .await，不进入嵌套的 async 块和闭包
+#[derive(Default)]
+struct AwaitFinder {
+    line: Option<usize>,
+}
+
+impl<'ast> Visit<'ast> for AwaitFinder {
+    fn visit_expr_await(&mut self, await_expr: &'ast ExprAwait) {
+        if self.line.is_none() {
+            self.line = Some(get_position(&await_expr.await_token).0);
+        }
+        syn::visit::visit_expr_await(self, await_expr);
+    }
+
+    fn visit_expr_async(&mut self, _async_expr: &'ast ExprAsync) {}
+
+    fn visit_expr_closure(&mut self, _closure: &'ast ExprClosure) {}
+
+    fn visit_item(&mut self, _item: &'ast syn::Item) {}
+
+    fn visit_macro(&mut self, mac: &'ast Macro) {
+        visit_macro_args(self, mac);
+    }
+}
+
+// ============================================================================
+// 共享状态和锁
+// ============================================================================
+
+/// 放进 Arc<Mutex<..>> 之后只能整体加锁的集合类型
+const COLLECTION_TYPES: &[&str] = &[
+    "Vec", "VecDeque", "HashMap", "HashSet", "BTreeMap", "BTreeSet",
+];
+
+const LOCK_TYPES: &[&str] = &["Mutex", "RwLock"];
+
+struct LockVisitor {
+    file_path: std::path::PathBuf,
+    issues: Vec<CodeIssue>,
+    lang: String,
+    /// 在 let 语句里时记录这条语句是否已经报过共享集合
+    let_reported: Option<bool>,
+}
+
+impl LockVisitor {
+    fn new(file_path: std::path::PathBuf, lang: &str) -> Self {
+        Self {
+            file_path,
+            issues: Vec::new(),
+            lang: lang.to_string(),
+            let_reported: None,
+        }
+    }
+
+    fn report_shared_collection(&mut self, line: usize, column: usize, shape: &str) {
+        if let Some(reported) = &mut self.let_reported {
+            if *reported {
+                return;
+            }
+            *reported = true;
+        }
+
+        let messages = if self.lang == "zh-CN" {
+            vec![
+                format!("{shape}：整个集合一把大锁，所有线程排队进出，并发程序被你写成了串行程序"),
+                format!("{shape} 让每次读写都要锁住整个集合，考虑用 channel 传数据，或者 DashMap 之类的并发容器"),
+                format!("{shape} 是共享可变状态的经典套娃，先想想能不能让一个任务独占这份数据"),
+            ]
+        } else {
+            vec![
+                format!("{shape}: one big lock around the whole collection, your concurrent program now runs single file"),
+                format!("{shape} locks the entire collection for every access - send data over a channel or use a concurrent map like DashMap"),
+                format!("{shape} is the classic shared-mutable-state nesting doll, consider letting one task own the data"),
+            ]
+        };
+        self.issues.push(rule_issue(
+            &self.file_path,
+            (line, column),
+            "lock-abuse",
+            messages[self.issues.len() % messages.len()].clone(),
+            Severity::Mild,
+        ));
+    }
+
+    fn check_lock_unwrap(&mut self, call: &ExprMethodCall) {
+        if !matches!(call.method.to_string().as_str(), "unwrap" | "expect") {
+            return;
+        }
+        let Expr::MethodCall(lock_call) = call.receiver.as_ref() else {
+            return;
+        };
+        if !is_lock_call(lock_call) {
+            return;
+        }
+        let lock = lock_call.method.to_string();
+        let (line, column) = get_position(&lock_call.method);
+
+        // a.lock().unwrap().b.lock().unwrap()：一个表达式里拿两把锁
+        if contains_lock_unwrap(&lock_call.receiver) {
+            let messages = if self.lang == "zh-CN" {
+                vec![
+                    format!("一条链里连着拿两把锁（.{lock}().unwrap() 套 .{lock}().unwrap()），加锁顺序一旦不一致就是死锁"),
+                    format!("嵌套的 .{lock}().unwrap() 链，这是死锁的标准配方"),
+                ]
+            } else {
+                vec![
+                    format!("two locks taken in one chain (.{lock}().unwrap() inside .{lock}().unwrap()) - inconsistent lock order means deadlock"),
+                    format!("nested .{lock}().unwrap() chain, the textbook recipe for a deadlock"),
+                ]
+            };
+            self.issues.push(rule_issue(
+                &self.file_path,
+                (line, column),
+                "lock-abuse",
+                messages[self.issues.len() % messages.len()].clone(),
+                Severity::Spicy,
+            ));
+            return;
+        }
+
+        let messages = if self.lang == "zh-CN" {
+            vec![
+                format!(".{lock}().unwrap()：某个线程持锁 panic 之后，锁中毒，所有线程跟着一起 panic"),
+                format!(".{lock}().unwrap() 把锁中毒变成了连锁崩溃，处理 PoisonError 或者换成 parking_lot"),
+                format!("又一个 .{lock}().unwrap()，一次 panic 就能让整个程序多米诺骨牌式倒下"),
+            ]
+        } else {
+            vec![
+                format!(".{lock}().unwrap(): once a thread panics holding the lock, it's poisoned and every other thread panics too"),
+                format!(".{lock}().unwrap() turns lock poisoning into a cascade of panics - handle PoisonError or use parking_lot"),
+                format!("another .{lock}().unwrap(), one panic and the whole program falls like dominoes"),
+            ]
+        };
+        self.issues.push(rule_issue(
+            &self.file_path,
+            (line, column),
+            "lock-abuse",
+            messages[self.issues.len() % messages.len()].clone(),
+            Severity::Mild,
+        ));
+    }
+
+    /// Arc::new(Mutex::new(Vec::new())) 这种没写类型标注的构造
+    fn check_shared_collection_ctor(&mut self, call: &ExprCall) {
+        if call_path_name(call).as_deref() != Some("Arc::new") {
+            return;
+        }
+        let Some(Expr::Call(lock_ctor)) = call.args.first() else {
+            return;
+        };
+        let Some(lock_name) = call_path_name(lock_ctor) else {
+            return;
+        };
+        let Some(lock) = lock_name.strip_suffix("::new") else {
+            return;
+        };
+        if !LOCK_TYPES.contains(&lock) {
+            return;
+        }
+
+        let collection = match lock_ctor.args.first() {
+            Some(Expr::Call(inner)) => {
+                call_path_name(inner).and_then(|name| name.split("::").next().map(str::to_string))
+            }
+            Some(Expr::Macro(mac)) if mac.mac.path.is_ident("vec") => Some("Vec".to_string()),
+            _ => None,
+        };
+        let Some(collection) = collection.filter(|c| COLLECTION_TYPES.contains(&c.as_str())) else {
+            return;
+        };
+
+        let (line, column) = get_position(call);
+        let shape = format!("Arc<{lock}<{collection}<..>>>");
+        self.report_shared_collection(line, column, &shape);
+    }
+}
+
+impl<'ast> Visit<'ast> for LockVisitor {
+    fn visit_type_path(&mut self, type_path: &'ast TypePath) {
+        if let Some(shape) = shared_collection_shape(type_path) {
+            let (line, column) = get_position(type_path);
+            self.report_shared_collection(line, column, &shape);
+            // 里层的类型已经包含在这一条里了
+            return;
+        }
+        syn::visit::visit_type_path(self, type_path);
+    }
+
+    fn visit_local(&mut self, local: &'ast Local) {
+        // let s: Arc<Mutex<Vec<_>>> = Arc::new(Mutex::new(Vec::new()))：类型标注和构造说的是同一件事
+        let outer = self.let_reported.replace(false);
+        syn::visit::visit_local(self, local);
+        self.let_reported = outer;
+    }
+
+    fn visit_expr_method_call(&mut self, call: &'ast ExprMethodCall) {
+        self.check_lock_unwrap(call);
+        syn::visit::visit_expr_method_call(self, call);
+    }
+
+    fn visit_expr_call(&mut self, call: &'ast ExprCall) {
+        self.check_shared_collection_ctor(call);
+        syn::visit::visit_expr_call(self, call);
+    }
+
+    fn visit_macro(&mut self, mac: &'ast Macro) {
+        visit_macro_args(self, mac);
+    }
+}
+
+/// Arc<Mutex<Vec<T>>> -> Some("Arc<Mutex<Vec<..>>>")
+fn shared_collection_shape(type_path: &TypePath) -> Option<String> {
+    let (arc, lock_ty) = single_generic(type_path)?;
+    if arc != "Arc" {
+        return None;
+    }
+    let (lock, inner_ty) = single_generic(type_path_of(lock_ty)?)?;
+    if !LOCK_TYPES.contains(&lock.as_str()) {
+        return None;
+    }
+    let collection = type_path_of(inner_ty)?
+        .path
+        .segments
+        .last()?
+        .ident
+        .to_string();
+    if !COLLECTION_TYPES.contains(&collection.as_str()) {
+        return None;
+    }
+    Some(format!("Arc<{lock}<{collection}<..>>>"))
+}
+
+/// 类型名和它的第一个泛型参数
+fn single_generic(type_path: &TypePath) -> Option<(String, &Type)> {
+    let segment = type_path.path.segments.last()?;
+    let PathArguments::AngleBracketed(args) = &segment.arguments else {
+        return None;
+    };
+    let first = args.args.iter().find_map(|arg| match arg {
+        GenericArgument::Type(ty) => Some(ty),
+        _ => None,
+    })?;
+    Some((segment.ident.to_string(), first))
+}
+
+fn type_path_of(ty: &Type) -> Option<&TypePath> {
+    match ty {
+        Type::Path(type_path) => Some(type_path),
+        _ => None,
+    }
+}
+
+/// 函数调用路径的最后两段，例如 Arc::new、Mutex::new
+fn call_path_name(call: &ExprCall) -> Option<String> {
+    let Expr::Path(func) = call.func.as_ref() else {
+        return None;
+    };
+    let segments: Vec<String> = func
+        .path
+        .segments
+        .iter()
+        .map(|s| s.ident.to_string())
+        .collect();
+    let start = segments.len().saturating_sub(2);
+    Some(segments[start..].join("::"))
+}
+
+fn contains_lock_unwrap(expr: &Expr) -> bool {
+    match expr {
+        Expr::MethodCall(call) => {
+            let is_lock_unwrap = matches!(call.method.to_string().as_str(), "unwrap" | "expect")
+                && matches!(call.receiver.as_ref(), Expr::MethodCall(inner) if is_lock_call(inner));
+            is_lock_unwrap || contains_lock_unwrap(&call.receiver)
+        }
+        Expr::Field(field) => contains_lock_unwrap(&field.base),
+        Expr::Paren(paren) => contains_lock_unwrap(&paren.expr),
+        Expr::Try(try_expr) => contains_lock_unwrap(&try_expr.expr),
+        _ => false,
+    }
+}
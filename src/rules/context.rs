@@ -0,0 +1,129 @@
+use std::path::Path;
+use syn::{spanned::Spanned, visit::Visit, Attribute, File, ImplItemFn, ItemFn, ItemImpl, ItemMod};
+
+use crate::analyzer::CodeContext;
+
+/// 规则在某种代码上下文中的处理方式
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ContextPolicy {
+    /// 照常报告
+    Report,
+    /// 报告，但降级为 Mild / Gentle
+    Downgrade,
+    /// 不报告
+    Ignore,
+}
+
+/// 记录一个文件里每段代码属于哪种上下文
+///
+/// 文件级上下文由路径决定（tests/、benches/、examples/、build.rs），
+/// 文件内部的 `#[cfg(test)]` 模块、`#[test]` 和 `#[bench]` 函数会覆盖它。
+pub struct ContextMap {
+    file_context: CodeContext,
+    regions: Vec<(usize, usize, CodeContext)>,
+}
+
+impl ContextMap {
+    pub fn new(file_path: &Path, syntax_tree: &File) -> Self {
+        let mut collector = RegionCollector {
+            regions: Vec::new(),
+        };
+        collector.visit_file(syntax_tree);
+
+        Self {
+            file_context: CodeContext::from_path(file_path),
+            regions: collector.regions,
+        }
+    }
+
+    /// 某一行所处的上下文，嵌套时以最内层为准
+    pub fn context_at(&self, line: usize) -> CodeContext {
+        self.regions
+            .iter()
+            .filter(|(start, end, _)| (*start..=*end).contains(&line))
+            .min_by_key(|(start, end, _)| end - start)
+            .map(|(_, _, context)| *context)
+            .unwrap_or(self.file_context)
+    }
+
+    /// 去掉测试代码后剩下的源码行，供按文本计数的规则使用
+    pub fn non_test_lines<'a>(&'a self, content: &'a str) -> impl Iterator<Item = &'a str> + 'a {
+        content
+            .lines()
+            .enumerate()
+            .filter(move |(index, _)| !self.context_at(index + 1).is_test_code())
+            .map(|(_, line)| line)
+    }
+}
+
+struct RegionCollector {
+    regions: Vec<(usize, usize, CodeContext)>,
+}
+
+impl RegionCollector {
+    fn record<T: Spanned>(&mut self, node: &T, attrs: &[Attribute]) {
+        let context = if attrs.iter().any(is_bench_attr) {
+            CodeContext::Bench
+        } else if attrs
+            .iter()
+            .any(|attr| is_test_attr(attr) || is_cfg_test_attr(attr))
+        {
+            CodeContext::Test
+        } else {
+            return;
+        };
+
+        let span = node.span();
+        self.regions
+            .push((span.start().line, span.end().line, context));
+    }
+}
+
+impl<'ast> Visit<'ast> for RegionCollector {
+    fn visit_item_mod(&mut self, item_mod: &'ast ItemMod) {
+        self.record(item_mod, &item_mod.attrs);
+        syn::visit::visit_item_mod(self, item_mod);
+    }
+
+    fn visit_item_fn(&mut self, item_fn: &'ast ItemFn) {
+        self.record(item_fn, &item_fn.attrs);
+        syn::visit::visit_item_fn(self, item_fn);
+    }
+
+    fn visit_item_impl(&mut self, item_impl: &'ast ItemImpl) {
+        self.record(item_impl, &item_impl.attrs);
+        syn::visit::visit_item_impl(self, item_impl);
+    }
+
+    fn visit_impl_item_fn(&mut self, impl_fn: &'ast ImplItemFn) {
+        self.record(impl_fn, &impl_fn.attrs);
+        syn::visit::visit_impl_item_fn(self, impl_fn);
+    }
+}
+
+/// #[test]、#[tokio::test]、#[rstest] 之类
+pub(crate) fn is_test_attr(attr: &Attribute) -> bool {
+    attr.path()
+        .segments
+        .last()
+        .is_some_and(|segment| segment.ident == "test" || segment.ident == "rstest")
+}
+
+/// #[cfg(test)]，以及 #[cfg(all(test, ...))]，但不包括 #[cfg(not(test))]
+pub(crate) fn is_cfg_test_attr(attr: &Attribute) -> bool {
+    if !attr.path().is_ident("cfg") {
+        return false;
+    }
+    let tokens = match attr.meta.require_list() {
+        Ok(list) => list.tokens.to_string(),
+        Err(_) => return false,
+    };
+    tokens
+        .split(|c: char| !c.is_alphanumeric() && c != '_')
+        .any(|word| word == "test")
+        && !tokens.contains("not")
+}
+
+fn is_bench_attr(attr: &Attribute) -> bool {
+    attr.path().is_ident("bench")
+}
@@ -0,0 +1,652 @@
+use std::collections::HashSet;
+use std::path::Path;
+
+use syn::{
+    visit::Visit, Attribute, Block, Expr, ExprLit, File, ImplItem, Item, ItemFn, ItemImpl,
+    ItemTrait, Lit, Macro, Meta, ReturnType, Signature, TraitItem,
+};
+
+use crate::analyzer::{CodeContext, CodeIssue, Severity};
+use crate::rules::common::{is_library_file, is_public, rule_issue, type_name};
+use crate::rules::{ContextPolicy, Rule};
+use crate::utils::get_position;
+
+/// 公开条目没有 `///` 文档
+pub struct MissingDocsRule;
+
+/// 文档只是把名字又念了一遍（`get_foo` 上的 "Gets the foo"）
+pub struct ParrotDocsRule;
+
+/// 文档里的示例代码已经不是合法的 Rust 了
+pub struct BrokenDocExampleRule;
+
+/// 返回 Result 却没有 `# Errors`，会 panic! 却没有 `# Panics`
+pub struct MissingDocSectionsRule;
+
+/// 文档债只算正式代码的
+fn doc_context_policy(context: CodeContext) -> ContextPolicy {
+    match context {
+        CodeContext::Production => ContextPolicy::Report,
+        _ => ContextPolicy::Ignore,
+    }
+}
+
+impl Rule for MissingDocsRule {
+    fn name(&self) -> &'static str {
+        "missing-docs"
+    }
+
+    fn context_policy(&self, context: CodeContext) -> ContextPolicy {
+        doc_context_policy(context)
+    }
+
+    fn check(
+        &self,
+        file_path: &Path,
+        syntax_tree: &File,
+        _content: &str,
+        lang: &str,
+    ) -> Vec<CodeIssue> {
+        if !is_library_file(file_path) {
+            return Vec::new();
+        }
+        DocSurface::collect(syntax_tree).missing_docs(file_path, lang)
+    }
+}
+
+impl Rule for ParrotDocsRule {
+    fn name(&self) -> &'static str {
+        "parrot-docs"
+    }
+
+    fn context_policy(&self, context: CodeContext) -> ContextPolicy {
+        doc_context_policy(context)
+    }
+
+    fn check(
+        &self,
+        file_path: &Path,
+        syntax_tree: &File,
+        _content: &str,
+        lang: &str,
+    ) -> Vec<CodeIssue> {
+        DocSurface::collect(syntax_tree).parrot_docs(file_path, lang)
+    }
+}
+
+impl Rule for BrokenDocExampleRule {
+    fn name(&self) -> &'static str {
+        "broken-doc-example"
+    }
+
+    fn context_policy(&self, context: CodeContext) -> ContextPolicy {
+        doc_context_policy(context)
+    }
+
+    fn check(
+        &self,
+        file_path: &Path,
+        syntax_tree: &File,
+        _content: &str,
+        lang: &str,
+    ) -> Vec<CodeIssue> {
+        DocSurface::collect(syntax_tree).broken_examples(file_path, lang)
+    }
+}
+
+impl Rule for MissingDocSectionsRule {
+    fn name(&self) -> &'static str {
+        "missing-doc-sections"
+    }
+
+    fn context_policy(&self, context: CodeContext) -> ContextPolicy {
+        doc_context_policy(context)
+    }
+
+    fn check(
+        &self,
+        file_path: &Path,
+        syntax_tree: &File,
+        _content: &str,
+        lang: &str,
+    ) -> Vec<CodeIssue> {
+        if !is_library_file(file_path) {
+            return Vec::new();
+        }
+        DocSurface::collect(syntax_tree).missing_sections(file_path, lang)
+    }
+}
+
+// ============================================================================
+// 文档注释解析
+// ============================================================================
+
+/// 复述名字时常见的填充词，去掉之后剩下的才是真正的信息
+const FILLER_WORDS: &[&str] = &[
+    "a", "an", "the", "this", "that", "of", "for", "to", "in", "on", "with", "by", "from", "and",
+    "or", "is", "are", "be", "it", "its", "self", "return", "returns", "create", "creates", "make",
+    "makes", "new", "value", "function", "method", "struct", "type", "instance", "object",
+];
+
+/// 超过这么多个词的文档，就算用词和名字重合，也多少说了点东西
+const PARROT_MAX_WORDS: usize = 8;
+
+/// rustdoc 会当作 Rust 编译的代码块属性
+const RUST_FENCE_ATTRS: &[&str] = &["rust", "should_panic", "no_run"];
+
+/// 一条条目上的全部文档注释，按源码行拆开
+struct DocComment {
+    lines: Vec<(usize, String)>,
+}
+
+/// 文档里的一个 Rust 代码块
+struct CodeBlock {
+    line: usize,
+    code: String,
+}
+
+impl DocComment {
+    fn from_attrs(attrs: &[Attribute]) -> Self {
+        let mut lines = Vec::new();
+
+        for attr in attrs {
+            let Meta::NameValue(meta) = &attr.meta else {
+                continue;
+            };
+            if !meta.path.is_ident("doc") {
+                continue;
+            }
+            let Expr::Lit(ExprLit {
+                lit: Lit::Str(text),
+                ..
+            }) = &meta.value
+            else {
+                continue;
+            };
+            // `/** */` 块注释会把多行塞进同一个属性
+            let (line, _) = get_position(attr);
+            for (offset, text) in text.value().lines().enumerate() {
+                let text = text.strip_prefix(' ').unwrap_or(text);
+                lines.push((line + offset, text.to_string()));
+            }
+        }
+
+        Self { lines }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.lines.iter().all(|(_, text)| text.trim().is_empty())
+    }
+
+    /// 代码块之外的正文行
+    fn prose(&self) -> Vec<&str> {
+        let mut in_fence = false;
+        let mut prose = Vec::new();
+        for (_, text) in &self.lines {
+            if is_fence(text) {
+                in_fence = !in_fence;
+            } else if !in_fence {
+                prose.push(text.as_str());
+            }
+        }
+        prose
+    }
+
+    fn has_section(&self, section: &str) -> bool {
+        self.prose().iter().any(|text| {
+            let text = text.trim();
+            text.starts_with('#') && text.trim_start_matches('#').trim() == section
+        })
+    }
+
+    /// 会被 rustdoc 当作 Rust 的代码块，`# ` 开头的隐藏行去掉前缀
+    fn rust_code_blocks(&self) -> Vec<CodeBlock> {
+        let mut blocks = Vec::new();
+        let mut current: Option<(CodeBlock, bool)> = None;
+
+        for (line, text) in &self.lines {
+            if !is_fence(text) {
+                if let Some((block, true)) = current.as_mut() {
+                    block.code.push_str(unhide(text));
+                    block.code.push('\n');
+                }
+                continue;
+            }
+            match current.take() {
+                Some((block, is_rust)) => {
+                    if is_rust {
+                        blocks.push(block);
+                    }
+                }
+                None => {
+                    let info = text.trim().trim_start_matches(['`', '~']);
+                    let block = CodeBlock {
+                        line: *line,
+                        code: String::new(),
+                    };
+                    current = Some((block, is_rust_fence(info)));
+                }
+            }
+        }
+
+        blocks
+    }
+}
+
+fn is_fence(text: &str) -> bool {
+    let text = text.trim_start();
+    text.starts_with("```") || text.starts_with("~~~")
+}
+
+/// 没写语言，或者只写了 rust / should_panic / no_run / editionXXXX
+fn is_rust_fence(info: &str) -> bool {
+    info.split([',', ' '])
+        .map(str::trim)
+        .filter(|attr| !attr.is_empty())
+        .all(|attr| RUST_FENCE_ATTRS.contains(&attr) || attr.starts_with("edition"))
+}
+
+/// rustdoc 的隐藏行：`# code` -> `code`，`#` -> 空行，`##` -> `#`
+fn unhide(text: &str) -> &str {
+    let trimmed = text.trim_start();
+    if trimmed == "#" {
+        ""
+    } else if let Some(rest) = trimmed.strip_prefix("# ") {
+        rest
+    } else if trimmed.starts_with("##") {
+        &trimmed[1..]
+    } else {
+        text
+    }
+}
+
+/// 示例代码可以是完整的文件，也可以是 main 函数体里的语句
+fn parses_as_rust(code: &str) -> bool {
+    syn::parse_file(code).is_ok() || syn::parse_str::<Block>(&format!("{{\n{code}\n}}")).is_ok()
+}
+
+/// `get_foo` / `HttpClient` -> ["get", "foo"] / ["http", "client"]
+fn name_words(name: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    for part in name.split('_').filter(|part| !part.is_empty()) {
+        let mut word = String::new();
+        for ch in part.chars() {
+            if ch.is_uppercase() && !word.is_empty() {
+                words.push(std::mem::take(&mut word));
+            }
+            word.extend(ch.to_lowercase());
+        }
+        if !word.is_empty() {
+            words.push(word);
+        }
+    }
+    words
+}
+
+/// 简单去掉复数/第三人称的 s，让 "Gets" 能对上 `get`
+fn stem(word: &str) -> &str {
+    if word.len() > 3 {
+        word.strip_suffix('s').unwrap_or(word)
+    } else {
+        word
+    }
+}
+
+// ============================================================================
+// 收集带文档的条目
+// ============================================================================
+
+struct DocItem<'ast> {
+    /// 类型的方法带上类型名，比如 `Config::new`
+    display_name: String,
+    /// 名字里的词，方法还包括所属类型名里的词
+    words: Vec<String>,
+    position: (usize, usize),
+    is_pub: bool,
+    docs: DocComment,
+    sig: Option<&'ast Signature>,
+    block: Option<&'ast Block>,
+}
+
+#[derive(Default)]
+struct DocSurface<'ast> {
+    items: Vec<DocItem<'ast>>,
+    /// `//!` 模块文档
+    inner_docs: Vec<DocComment>,
+}
+
+impl<'ast> DocSurface<'ast> {
+    fn collect(syntax_tree: &'ast File) -> Self {
+        let mut surface = Self::default();
+        surface
+            .inner_docs
+            .push(DocComment::from_attrs(&syntax_tree.attrs));
+        surface.visit_file(syntax_tree);
+        surface
+    }
+
+    fn push_item(
+        &mut self,
+        ident: &syn::Ident,
+        owner: Option<&str>,
+        attrs: &[Attribute],
+        is_pub: bool,
+        body: Option<(&'ast Signature, Option<&'ast Block>)>,
+    ) {
+        let name = ident.to_string();
+        let mut words = name_words(&name);
+        let display_name = match owner {
+            Some(owner) => {
+                words.extend(name_words(owner));
+                format!("{owner}::{name}")
+            }
+            None => name,
+        };
+
+        self.items.push(DocItem {
+            display_name,
+            words,
+            position: get_position(ident),
+            is_pub,
+            docs: DocComment::from_attrs(attrs),
+            sig: body.map(|(sig, _)| sig),
+            block: body.and_then(|(_, block)| block),
+        });
+    }
+
+    fn missing_docs(&self, file_path: &Path, lang: &str) -> Vec<CodeIssue> {
+        let mut issues = Vec::new();
+
+        for item in self.items.iter().filter(|item| item.is_pub) {
+            if !item.docs.is_empty() {
+                continue;
+            }
+            let name = &item.display_name;
+
+            let messages = if lang == "zh-CN" {
+                vec![
+                    format!("公开的 {name} 一行文档都没有，用户只能去读源码猜它是干嘛的"),
+                    format!("{name} 是公开 API，却连一句 /// 都舍不得写"),
+                    format!("{name} 没有文档，cargo doc 生成出来就是一片空白"),
+                ]
+            } else {
+                vec![
+                    format!(
+                        "public {name} has no docs at all - users get to read the source and guess"
+                    ),
+                    format!("{name} is public API and didn't earn a single /// line"),
+                    format!("{name} is undocumented, cargo doc will render a blank page for it"),
+                ]
+            };
+
+            issues.push(rule_issue(
+                file_path,
+                item.position,
+                "missing-docs",
+                messages[issues.len() % messages.len()].clone(),
+                Severity::Mild,
+            ));
+        }
+
+        issues
+    }
+
+    fn parrot_docs(&self, file_path: &Path, lang: &str) -> Vec<CodeIssue> {
+        let mut issues = Vec::new();
+
+        for item in &self.items {
+            if item.docs.is_empty() || !item.docs.rust_code_blocks().is_empty() {
+                continue;
+            }
+            let prose = item.docs.prose().join(" ");
+            if prose.trim_start().starts_with('#') {
+                continue;
+            }
+            let doc_words: Vec<String> = prose
+                .split(|c: char| !c.is_alphanumeric())
+                .filter(|word| !word.is_empty())
+                .map(str::to_lowercase)
+                .collect();
+            if doc_words.is_empty() || doc_words.len() > PARROT_MAX_WORDS {
+                continue;
+            }
+            let name_words: HashSet<&str> = item.words.iter().map(|w| stem(w)).collect();
+            let says_something = doc_words.iter().any(|word| {
+                !FILLER_WORDS.contains(&word.as_str()) && !name_words.contains(stem(word))
+            });
+            if says_something {
+                continue;
+            }
+            let name = &item.display_name;
+            let doc = prose.trim();
+
+            let messages = if lang == "zh-CN" {
+                vec![
+                    format!(
+                        "{name} 的文档 \"{doc}\" 只是把名字念了一遍，说说它什么时候用、有什么坑"
+                    ),
+                    format!("\"{doc}\"——谢谢，光看 {name} 这个名字我也猜到了"),
+                    format!("{name} 的文档是名字的复读机，写点名字里看不出来的东西"),
+                ]
+            } else {
+                vec![
+                    format!("the docs on {name} (\"{doc}\") just read the name back - say when to use it and what can go wrong"),
+                    format!("\"{doc}\" - thanks, the name {name} already told me that"),
+                    format!("{name} has parrot docs, write down what the name can't tell"),
+                ]
+            };
+
+            issues.push(rule_issue(
+                file_path,
+                item.position,
+                "parrot-docs",
+                messages[issues.len() % messages.len()].clone(),
+                Severity::Mild,
+            ));
+        }
+
+        issues
+    }
+
+    fn broken_examples(&self, file_path: &Path, lang: &str) -> Vec<CodeIssue> {
+        let mut issues = Vec::new();
+
+        // `//!` 模块文档用文件名指代
+        let file_name = file_path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let docs = self
+            .inner_docs
+            .iter()
+            .map(|docs| (file_name.as_str(), docs))
+            .chain(
+                self.items
+                    .iter()
+                    .map(|item| (item.display_name.as_str(), &item.docs)),
+            );
+
+        for (name, docs) in docs {
+            for block in docs.rust_code_blocks() {
+                if block.code.trim().is_empty() || parses_as_rust(&block.code) {
+                    continue;
+                }
+
+                let messages = if lang == "zh-CN" {
+                    vec![
+                        format!("{name} 文档里的示例根本不是合法的 Rust，doctest 一跑就挂"),
+                        format!("{name} 的示例代码已经过期了，解析都过不了，还指望用户照着抄？"),
+                        format!("{name} 的示例解析失败：不是 Rust 就标上 text，是 Rust 就把它修好"),
+                    ]
+                } else {
+                    vec![
+                        format!("the example in the docs of {name} isn't valid Rust, the doctest will fail"),
+                        format!("the example on {name} went stale and no longer parses - and users are supposed to copy it?"),
+                        format!("the example on {name} fails to parse: tag it `text` if it isn't Rust, fix it if it is"),
+                    ]
+                };
+
+                issues.push(rule_issue(
+                    file_path,
+                    (block.line, 1),
+                    "broken-doc-example",
+                    messages[issues.len() % messages.len()].clone(),
+                    Severity::Spicy,
+                ));
+            }
+        }
+
+        issues
+    }
+
+    fn missing_sections(&self, file_path: &Path, lang: &str) -> Vec<CodeIssue> {
+        let mut issues = Vec::new();
+
+        for item in self.items.iter().filter(|item| item.is_pub) {
+            // 完全没写文档的交给 missing-docs
+            let Some(sig) = item.sig else {
+                continue;
+            };
+            if item.docs.is_empty() {
+                continue;
+            }
+
+            let mut missing = Vec::new();
+            if returns_result(sig) && !item.docs.has_section("Errors") {
+                missing.push("# Errors");
+            }
+            if item.block.is_some_and(calls_panic) && !item.docs.has_section("Panics") {
+                missing.push("# Panics");
+            }
+            if missing.is_empty() {
+                continue;
+            }
+            let name = &item.display_name;
+            let sections = missing.join(" / ");
+
+            let messages = if lang == "zh-CN" {
+                vec![
+                    format!("{name} 的文档缺少 {sections} 章节，调用者不知道什么情况下会失败"),
+                    format!("{name} 会失败却不说怎么失败，补上 {sections}"),
+                    format!("{name} 的文档只讲了顺利的情况，{sections} 呢？"),
+                ]
+            } else {
+                vec![
+                    format!("the docs of {name} have no {sections} section, callers can't tell when it fails"),
+                    format!("{name} can fail but won't say how - add {sections}"),
+                    format!("{name} only documents the happy path, where is {sections}?"),
+                ]
+            };
+
+            issues.push(rule_issue(
+                file_path,
+                item.position,
+                "missing-doc-sections",
+                messages[issues.len() % messages.len()].clone(),
+                Severity::Mild,
+            ));
+        }
+
+        issues
+    }
+}
+
+impl<'ast> Visit<'ast> for DocSurface<'ast> {
+    fn visit_item(&mut self, item: &'ast Item) {
+        let documented = match item {
+            Item::Struct(i) => Some((&i.ident, &i.attrs, &i.vis)),
+            Item::Enum(i) => Some((&i.ident, &i.attrs, &i.vis)),
+            Item::Union(i) => Some((&i.ident, &i.attrs, &i.vis)),
+            Item::Trait(i) => Some((&i.ident, &i.attrs, &i.vis)),
+            Item::Type(i) => Some((&i.ident, &i.attrs, &i.vis)),
+            Item::Const(i) => Some((&i.ident, &i.attrs, &i.vis)),
+            Item::Static(i) => Some((&i.ident, &i.attrs, &i.vis)),
+            // `pub mod foo;` 的文档写在 foo.rs 的 `//!` 里，这里只看内联模块
+            Item::Mod(i) if i.content.is_some() => Some((&i.ident, &i.attrs, &i.vis)),
+            _ => None,
+        };
+        if let Some((ident, attrs, vis)) = documented {
+            self.push_item(ident, None, attrs, is_public(vis), None);
+        }
+        syn::visit::visit_item(self, item);
+    }
+
+    fn visit_item_fn(&mut self, item_fn: &'ast ItemFn) {
+        self.push_item(
+            &item_fn.sig.ident,
+            None,
+            &item_fn.attrs,
+            is_public(&item_fn.vis),
+            Some((&item_fn.sig, Some(&item_fn.block))),
+        );
+        syn::visit::visit_item_fn(self, item_fn);
+    }
+
+    fn visit_item_impl(&mut self, item_impl: &'ast ItemImpl) {
+        // trait 实现的方法文档继承自 trait，只看固有 impl
+        if item_impl.trait_.is_none() {
+            let owner = type_name(&item_impl.self_ty);
+            for item in &item_impl.items {
+                let ImplItem::Fn(impl_fn) = item else {
+                    continue;
+                };
+                self.push_item(
+                    &impl_fn.sig.ident,
+                    owner.as_deref(),
+                    &impl_fn.attrs,
+                    is_public(&impl_fn.vis),
+                    Some((&impl_fn.sig, Some(&impl_fn.block))),
+                );
+            }
+        }
+        syn::visit::visit_item_impl(self, item_impl);
+    }
+
+    fn visit_item_trait(&mut self, item_trait: &'ast ItemTrait) {
+        let owner = item_trait.ident.to_string();
+        let is_pub = is_public(&item_trait.vis);
+        for item in &item_trait.items {
+            let TraitItem::Fn(trait_fn) = item else {
+                continue;
+            };
+            self.push_item(
+                &trait_fn.sig.ident,
+                Some(&owner),
+                &trait_fn.attrs,
+                is_pub,
+                Some((&trait_fn.sig, trait_fn.default.as_ref())),
+            );
+        }
+        syn::visit::visit_item_trait(self, item_trait);
+    }
+}
+
+/// Result<T, E>、io::Result<T> 以及各种 crate 自己的 Result 别名
+fn returns_result(sig: &Signature) -> bool {
+    let ReturnType::Type(_, ty) = &sig.output else {
+        return false;
+    };
+    type_name(ty).is_some_and(|name| name == "Result")
+}
+
+fn calls_panic(block: &Block) -> bool {
+    let mut finder = PanicFinder::default();
+    finder.visit_block(block);
+    finder.found
+}
+
+/// 找函数体里的 panic!，嵌套的条目不算
+#[derive(Default)]
+struct PanicFinder {
+    found: bool,
+}
+
+impl<'ast> Visit<'ast> for PanicFinder {
+    fn visit_item(&mut self, _item: &'ast Item) {}
+
+    fn visit_macro(&mut self, mac: &'ast Macro) {
+        if mac.path.segments.last().is_some_and(|s| s.ident == "panic") {
+            self.found = true;
+        }
+    }
+}
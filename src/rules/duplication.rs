@@ -2,8 +2,8 @@ use std::collections::HashMap;
 use std::path::Path;
 use syn::{visit::Visit, Block, File};
 
-use crate::analyzer::{CodeIssue, RoastLevel, Severity};
-use crate::rules::Rule;
+use crate::analyzer::{CodeContext, CodeIssue, RoastLevel, Severity};
+use crate::rules::{ContextPolicy, Rule};
 
 /// code duplication detection rule
 pub struct CodeDuplicationRule;
@@ -13,6 +13,14 @@ impl Rule for CodeDuplicationRule {
         "code-duplication"
     }
 
+    fn context_policy(&self, context: CodeContext) -> ContextPolicy {
+        match context {
+            // 测试用例之间的相似结构通常是刻意的
+            CodeContext::Test | CodeContext::Bench => ContextPolicy::Downgrade,
+            _ => ContextPolicy::Report,
+        }
+    }
+
     fn check(
         &self,
         file_path: &Path,
@@ -115,6 +123,7 @@ impl DuplicationVisitor {
                     message: messages[issues.len() % messages.len()].clone(),
                     severity,
                     roast_level: RoastLevel::Sarcastic,
+                    context: CodeContext::Production,
                 });
             }
         }
@@ -158,6 +167,7 @@ impl DuplicationVisitor {
                     message: messages[issues.len() % messages.len()].clone(),
                     severity: Severity::Spicy,
                     roast_level: RoastLevel::Sarcastic,
+                    context: CodeContext::Production,
                 });
             }
         }
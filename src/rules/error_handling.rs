@@ -0,0 +1,605 @@
+use std::collections::HashSet;
+use std::path::Path;
+use syn::{
+    visit::Visit, Expr, ExprMethodCall, File, GenericArgument, ImplItemFn, Item, ItemEnum, ItemFn,
+    ItemType, Local, Pat, PathArguments, ReturnType, Signature, Stmt, TraitItemFn, Type,
+    TypeParamBound,
+};
+
+use crate::analyzer::{CodeContext, CodeIssue, Severity};
+use crate::rules::common::{
+    derived_traits, is_library_file, is_public, rule_issue, RESULT_METHODS,
+};
+use crate::rules::{ContextPolicy, Rule};
+use crate::utils::get_position;
+
+/// 检测公开 API 里的 Result<T, String>
+pub struct StringlyErrorRule;
+
+impl Rule for StringlyErrorRule {
+    fn name(&self) -> &'static str {
+        "stringly-error"
+    }
+
+    fn context_policy(&self, context: CodeContext) -> ContextPolicy {
+        match context {
+            CodeContext::Production => ContextPolicy::Report,
+            _ => ContextPolicy::Ignore,
+        }
+    }
+
+    fn check(
+        &self,
+        file_path: &Path,
+        syntax_tree: &File,
+        _content: &str,
+        lang: &str,
+    ) -> Vec<CodeIssue> {
+        let mut visitor = ErrorSignatureVisitor::new(file_path.to_path_buf(), lang, false);
+        visitor.visit_file(syntax_tree);
+        visitor.issues
+    }
+}
+
+/// 检测库代码里到处都是 Box<dyn Error>
+pub struct BoxedErrorRule;
+
+impl Rule for BoxedErrorRule {
+    fn name(&self) -> &'static str {
+        "boxed-error"
+    }
+
+    fn context_policy(&self, context: CodeContext) -> ContextPolicy {
+        match context {
+            CodeContext::Production => ContextPolicy::Report,
+            _ => ContextPolicy::Ignore,
+        }
+    }
+
+    fn check(
+        &self,
+        file_path: &Path,
+        syntax_tree: &File,
+        _content: &str,
+        lang: &str,
+    ) -> Vec<CodeIssue> {
+        // 二进制程序里用 Box<dyn Error> 没什么问题，只有库才需要具体的错误类型
+        if !is_library_file(file_path) {
+            return Vec::new();
+        }
+
+        let mut visitor = ErrorSignatureVisitor::new(file_path.to_path_buf(), lang, true);
+        visitor.visit_file(syntax_tree);
+        visitor.issues
+    }
+}
+
+/// 检测被悄悄吞掉的错误：let _ = ...、.ok();、map_err(|_| ...)
+pub struct SwallowedErrorRule;
+
+impl Rule for SwallowedErrorRule {
+    fn name(&self) -> &'static str {
+        "swallowed-error"
+    }
+
+    fn context_policy(&self, context: CodeContext) -> ContextPolicy {
+        match context {
+            CodeContext::Test => ContextPolicy::Ignore,
+            CodeContext::Example | CodeContext::Bench => ContextPolicy::Downgrade,
+            CodeContext::Production | CodeContext::BuildScript => ContextPolicy::Report,
+        }
+    }
+
+    fn check(
+        &self,
+        file_path: &Path,
+        syntax_tree: &File,
+        _content: &str,
+        lang: &str,
+    ) -> Vec<CodeIssue> {
+        let mut visitor = SwallowedErrorVisitor::new(file_path.to_path_buf(), lang);
+        visitor.visit_file(syntax_tree);
+        visitor.issues
+    }
+}
+
+/// 检测没有实现 Display / Error 的错误枚举
+pub struct IncompleteErrorTypeRule;
+
+impl Rule for IncompleteErrorTypeRule {
+    fn name(&self) -> &'static str {
+        "incomplete-error-type"
+    }
+
+    fn context_policy(&self, context: CodeContext) -> ContextPolicy {
+        match context {
+            CodeContext::Test => ContextPolicy::Ignore,
+            _ => ContextPolicy::Report,
+        }
+    }
+
+    fn check(
+        &self,
+        file_path: &Path,
+        syntax_tree: &File,
+        _content: &str,
+        lang: &str,
+    ) -> Vec<CodeIssue> {
+        let mut collector = ErrorTypeCollector::default();
+        collector.visit_file(syntax_tree);
+        collector.into_issues(file_path, lang)
+    }
+}
+
+// ============================================================================
+// 函数签名里的错误类型
+// ============================================================================
+
+struct ErrorSignatureVisitor {
+    file_path: std::path::PathBuf,
+    issues: Vec<CodeIssue>,
+    lang: String,
+    /// true 检查 Box<dyn Error>，false 检查 Result<T, String>
+    boxed: bool,
+}
+
+impl ErrorSignatureVisitor {
+    fn new(file_path: std::path::PathBuf, lang: &str, boxed: bool) -> Self {
+        Self {
+            file_path,
+            issues: Vec::new(),
+            lang: lang.to_string(),
+            boxed,
+        }
+    }
+
+    fn check_signature(&mut self, sig: &Signature) {
+        let ReturnType::Type(_, ty) = &sig.output else {
+            return;
+        };
+        let name = sig.ident.to_string();
+
+        if self.boxed {
+            if contains_boxed_error(ty) {
+                self.report_boxed(ty.as_ref(), &format!("{name}()"));
+            }
+            return;
+        }
+
+        let Some(error_ty) = result_error_type(ty) else {
+            return;
+        };
+        if !is_string_type(error_ty) {
+            return;
+        }
+
+        let messages = if self.lang == "zh-CN" {
+            vec![
+                format!("公开函数 {name}() 返回 Result<_, String>，调用者只能拿字符串做 contains 匹配，求你定义个错误枚举吧"),
+                format!("{name}() 的错误是一串文字，调用者没法区分“文件不存在”和“权限不足”"),
+                format!("{name}() 用 String 当错误类型，类型系统在旁边默默流泪"),
+            ]
+        } else {
+            vec![
+                format!("pub fn {name}() returns Result<_, String> - callers can only grep your error text, define an error enum"),
+                format!("{name}() fails with a string, callers can't tell 'not found' from 'permission denied'"),
+                format!("{name}() uses String as its error type, the type system is quietly crying"),
+            ]
+        };
+
+        self.issues.push(rule_issue(
+            &self.file_path,
+            get_position(ty.as_ref()),
+            "stringly-error",
+            messages[self.issues.len() % messages.len()].clone(),
+            Severity::Spicy,
+        ));
+    }
+
+    fn report_boxed(&mut self, ty: &Type, item: &str) {
+        let messages = if self.lang == "zh-CN" {
+            vec![
+                format!("库代码 {item} 暴露了 Box<dyn Error>，调用者想处理具体错误只能 downcast 碰运气"),
+                format!("{item} 把错误装进了黑盒子，库的用户拆都拆不开，用 thiserror 定义具体错误类型吧"),
+                format!("Box<dyn Error> 出现在库的 {item} 里，这是应用程序的写法，不是库的"),
+            ]
+        } else {
+            vec![
+                format!("library API {item} exposes Box<dyn Error> - callers have to downcast and pray"),
+                format!("{item} hides its errors in a black box, give the library a concrete error type (thiserror helps)"),
+                format!("Box<dyn Error> in library API {item} - that's application style, not library style"),
+            ]
+        };
+
+        self.issues.push(rule_issue(
+            &self.file_path,
+            get_position(ty),
+            "boxed-error",
+            messages[self.issues.len() % messages.len()].clone(),
+            Severity::Mild,
+        ));
+    }
+}
+
+impl<'ast> Visit<'ast> for ErrorSignatureVisitor {
+    fn visit_item_fn(&mut self, item_fn: &'ast ItemFn) {
+        if is_public(&item_fn.vis) {
+            self.check_signature(&item_fn.sig);
+        }
+        syn::visit::visit_item_fn(self, item_fn);
+    }
+
+    fn visit_impl_item_fn(&mut self, impl_fn: &'ast ImplItemFn) {
+        if is_public(&impl_fn.vis) {
+            self.check_signature(&impl_fn.sig);
+        }
+        syn::visit::visit_impl_item_fn(self, impl_fn);
+    }
+
+    fn visit_item_trait(&mut self, item_trait: &'ast syn::ItemTrait) {
+        // 公开 trait 的方法同样是公开 API
+        if is_public(&item_trait.vis) {
+            for item in &item_trait.items {
+                if let syn::TraitItem::Fn(TraitItemFn { sig, .. }) = item {
+                    self.check_signature(sig);
+                }
+            }
+        }
+        syn::visit::visit_item_trait(self, item_trait);
+    }
+
+    fn visit_item_type(&mut self, item_type: &'ast ItemType) {
+        // type Result<T> = std::result::Result<T, Box<dyn Error>>;
+        if self.boxed && is_public(&item_type.vis) && contains_boxed_error(&item_type.ty) {
+            let name = format!("type {}", item_type.ident);
+            self.report_boxed(&item_type.ty, &name);
+        }
+        syn::visit::visit_item_type(self, item_type);
+    }
+}
+
+// ============================================================================
+// 被吞掉的错误
+// ============================================================================
+
+/// 返回值被丢掉也无所谓的常见方法（返回旧值或 Option，而不是错误）
+const DISCARDABLE_METHODS: &[&str] = &[
+    "insert", "remove", "replace", "take", "pop", "push", "swap", "set", "entry",
+];
+
+struct SwallowedErrorVisitor {
+    file_path: std::path::PathBuf,
+    issues: Vec<CodeIssue>,
+    lang: String,
+}
+
+impl SwallowedErrorVisitor {
+    fn new(file_path: std::path::PathBuf, lang: &str) -> Self {
+        Self {
+            file_path,
+            issues: Vec::new(),
+            lang: lang.to_string(),
+        }
+    }
+
+    fn check_let_underscore(&mut self, local: &Local) {
+        if !matches!(local.pat, Pat::Wild(_)) {
+            return;
+        }
+        let Some(init) = &local.init else {
+            return;
+        };
+        let Some(call) = call_name(&init.expr) else {
+            return;
+        };
+        if DISCARDABLE_METHODS.contains(&call.as_str()) || !can_fail(&init.expr, &call) {
+            return;
+        }
+
+        let messages = if self.lang == "zh-CN" {
+            vec![
+                format!("let _ = {call}(...) 把可能的错误直接扔进了垃圾桶，出了问题连日志都没有"),
+                format!("let _ = {call}(...)：眼不见心不烦，但 bug 不会因此消失"),
+                format!("{call}() 失败了也没人知道，至少记一条日志或者用 ? 往上传"),
+            ]
+        } else {
+            vec![
+                format!(
+                    "let _ = {call}(...) throws a possible error in the trash, not even a log line"
+                ),
+                format!("let _ = {call}(...): out of sight, out of mind - the bug stays though"),
+                format!("nobody will know when {call}() fails - log it or propagate it with ?"),
+            ]
+        };
+
+        self.issues.push(rule_issue(
+            &self.file_path,
+            get_position(local),
+            "swallowed-error",
+            messages[self.issues.len() % messages.len()].clone(),
+            Severity::Mild,
+        ));
+    }
+
+    fn check_discarded_ok(&mut self, expr: &Expr) {
+        let Expr::MethodCall(method_call) = expr else {
+            return;
+        };
+        if method_call.method != "ok" || !method_call.args.is_empty() {
+            return;
+        }
+
+        let messages = if self.lang == "zh-CN" {
+            vec![
+                "`.ok();` 把 Result 变成 Option 然后扔掉，这是在销毁证据".to_string(),
+                "`.ok();` 一行代码让错误人间蒸发，调试的时候你会想念它的".to_string(),
+                "用 `.ok();` 忽略错误？至少写个注释解释为什么可以忽略".to_string(),
+            ]
+        } else {
+            vec![
+                "`.ok();` turns the Result into an Option and throws it away - that's destroying evidence".to_string(),
+                "`.ok();` makes the error vanish, you'll miss it while debugging".to_string(),
+                "ignoring errors with `.ok();`? At least leave a comment explaining why it's fine".to_string(),
+            ]
+        };
+
+        self.issues.push(rule_issue(
+            &self.file_path,
+            get_position(&method_call.method),
+            "swallowed-error",
+            messages[self.issues.len() % messages.len()].clone(),
+            Severity::Spicy,
+        ));
+    }
+
+    fn check_map_err(&mut self, method_call: &ExprMethodCall) {
+        if method_call.method != "map_err" {
+            return;
+        }
+        let Some(Expr::Closure(closure)) = method_call.args.first() else {
+            return;
+        };
+        if closure.inputs.len() != 1 || !matches!(closure.inputs[0], Pat::Wild(_)) {
+            return;
+        }
+
+        let messages = if self.lang == "zh-CN" {
+            vec![
+                "map_err(|_| ...) 把原始错误丢了，日志里只剩一句“出错了”".to_string(),
+                "map_err(|_| ...)：错误的来龙去脉被一刀切断，把原错误作为 source 保留下来"
+                    .to_string(),
+                "丢掉错误原因的 map_err，排查问题时你会想穿越回来打自己".to_string(),
+            ]
+        } else {
+            vec![
+                "map_err(|_| ...) drops the original error, the log will just say 'something failed'".to_string(),
+                "map_err(|_| ...) cuts off the cause - keep the original error as the source".to_string(),
+                "a map_err that discards the cause - future you debugging this will not be amused".to_string(),
+            ]
+        };
+
+        self.issues.push(rule_issue(
+            &self.file_path,
+            get_position(&method_call.method),
+            "swallowed-error",
+            messages[self.issues.len() % messages.len()].clone(),
+            Severity::Spicy,
+        ));
+    }
+}
+
+impl<'ast> Visit<'ast> for SwallowedErrorVisitor {
+    fn visit_stmt(&mut self, stmt: &'ast Stmt) {
+        match stmt {
+            Stmt::Local(local) => self.check_let_underscore(local),
+            Stmt::Expr(expr, Some(_)) => self.check_discarded_ok(expr),
+            _ => {}
+        }
+        syn::visit::visit_stmt(self, stmt);
+    }
+
+    fn visit_expr_method_call(&mut self, method_call: &'ast ExprMethodCall) {
+        self.check_map_err(method_call);
+        syn::visit::visit_expr_method_call(self, method_call);
+    }
+}
+
+// ============================================================================
+// 错误类型是否完整
+// ============================================================================
+
+#[derive(Default)]
+struct ErrorTypeCollector<'ast> {
+    error_enums: Vec<&'ast ItemEnum>,
+    display_impls: HashSet<String>,
+    error_impls: HashSet<String>,
+}
+
+impl<'ast> ErrorTypeCollector<'ast> {
+    fn into_issues(self, file_path: &Path, lang: &str) -> Vec<CodeIssue> {
+        let mut issues = Vec::new();
+
+        for item_enum in &self.error_enums {
+            let name = item_enum.ident.to_string();
+            let derives = derived_traits(&item_enum.attrs);
+            // thiserror 的 #[derive(Error)] 会同时实现 Display 和 Error
+            let has_error = self.error_impls.contains(&name) || derives.contains("Error");
+            let has_display =
+                self.display_impls.contains(&name) || derives.contains("Display") || has_error;
+
+            let missing = match (has_display, has_error) {
+                (true, true) => continue,
+                (false, false) => "Display + std::error::Error",
+                (true, false) => "std::error::Error",
+                (false, true) => "Display",
+            };
+
+            let messages = if lang == "zh-CN" {
+                vec![
+                    format!("错误枚举 {name} 没有实现 {missing}，没法用 ? 转成 Box<dyn Error>，也打印不出人话"),
+                    format!("{name} 叫 Error 却不实现 {missing}，就像一个不会报警的警报器"),
+                    format!("给 {name} 补上 {missing}，或者直接 #[derive(thiserror::Error)]"),
+                ]
+            } else {
+                vec![
+                    format!("error enum {name} doesn't implement {missing} - it can't be printed or converted with ?"),
+                    format!("{name} is called an error but lacks {missing}, like an alarm that can't ring"),
+                    format!("implement {missing} for {name}, or just #[derive(thiserror::Error)]"),
+                ]
+            };
+
+            let severity = if is_public(&item_enum.vis) {
+                Severity::Spicy
+            } else {
+                Severity::Mild
+            };
+
+            issues.push(rule_issue(
+                file_path,
+                get_position(&item_enum.ident),
+                "incomplete-error-type",
+                messages[issues.len() % messages.len()].clone(),
+                severity,
+            ));
+        }
+
+        issues
+    }
+}
+
+impl<'ast> Visit<'ast> for ErrorTypeCollector<'ast> {
+    fn visit_item(&mut self, item: &'ast Item) {
+        match item {
+            Item::Enum(item_enum) if item_enum.ident.to_string().ends_with("Error") => {
+                self.error_enums.push(item_enum);
+            }
+            Item::Impl(item_impl) => {
+                if let (Some((_, trait_path, _)), Type::Path(self_ty)) =
+                    (&item_impl.trait_, item_impl.self_ty.as_ref())
+                {
+                    let trait_name = trait_path.segments.last().map(|s| s.ident.to_string());
+                    let self_name = self_ty.path.segments.last().map(|s| s.ident.to_string());
+                    if let (Some(trait_name), Some(self_name)) = (trait_name, self_name) {
+                        match trait_name.as_str() {
+                            "Display" => {
+                                self.display_impls.insert(self_name);
+                            }
+                            "Error" => {
+                                self.error_impls.insert(self_name);
+                            }
+                            _ => {}
+                        }
+                    }
+                }
+            }
+            _ => {}
+        }
+        syn::visit::visit_item(self, item);
+    }
+}
+
+// ============================================================================
+// 辅助函数
+// ============================================================================
+
+/// Result<T, E> 中的 E
+fn result_error_type(ty: &Type) -> Option<&Type> {
+    let Type::Path(type_path) = ty else {
+        return None;
+    };
+    let segment = type_path.path.segments.last()?;
+    if segment.ident != "Result" {
+        return None;
+    }
+    let PathArguments::AngleBracketed(args) = &segment.arguments else {
+        return None;
+    };
+    args.args
+        .iter()
+        .filter_map(|arg| match arg {
+            GenericArgument::Type(ty) => Some(ty),
+            _ => None,
+        })
+        .nth(1)
+}
+
+/// String、&str、&'static str
+fn is_string_type(ty: &Type) -> bool {
+    match ty {
+        Type::Path(type_path) => type_path.path.is_ident("String"),
+        Type::Reference(reference) => {
+            matches!(reference.elem.as_ref(), Type::Path(p) if p.path.is_ident("str"))
+        }
+        _ => false,
+    }
+}
+
+/// 类型里是否有 Box<dyn Error>（包括 Box<dyn Error + Send + Sync>）
+fn contains_boxed_error(ty: &Type) -> bool {
+    struct BoxedErrorFinder {
+        found: bool,
+    }
+
+    impl<'ast> Visit<'ast> for BoxedErrorFinder {
+        fn visit_type_path(&mut self, type_path: &'ast syn::TypePath) {
+            if let Some(segment) = type_path.path.segments.last() {
+                if segment.ident == "Box" {
+                    if let PathArguments::AngleBracketed(args) = &segment.arguments {
+                        self.found |= args.args.iter().any(|arg| {
+                            matches!(arg, GenericArgument::Type(Type::TraitObject(object))
+                                if object.bounds.iter().any(is_error_bound))
+                        });
+                    }
+                }
+            }
+            syn::visit::visit_type_path(self, type_path);
+        }
+    }
+
+    let mut finder = BoxedErrorFinder { found: false };
+    finder.visit_type(ty);
+    finder.found
+}
+
+fn is_error_bound(bound: &TypeParamBound) -> bool {
+    matches!(bound, TypeParamBound::Trait(trait_bound)
+        if trait_bound.path.segments.last().is_some_and(|s| s.ident == "Error"))
+}
+
+/// 被调用的函数或方法名
+/// 文件、IO、网络、环境变量这些模块里的函数基本都返回 Result
+const FALLIBLE_MODULES: &[&str] = &["fs", "io", "net", "env"];
+
+/// 有证据表明这个调用可能失败：常见的返回 Result 的方法、`try_`/`write`/`send`/`flush`
+/// 开头的名字，或者 `std::fs::...` 这类路径；`len()`、`count()` 扔掉结果不会丢错误
+fn can_fail(expr: &Expr, call: &str) -> bool {
+    let fallible_path = match expr {
+        Expr::Call(call) => match call.func.as_ref() {
+            Expr::Path(path) => path
+                .path
+                .segments
+                .iter()
+                .any(|segment| FALLIBLE_MODULES.contains(&segment.ident.to_string().as_str())),
+            _ => false,
+        },
+        Expr::Await(expr_await) => return can_fail(&expr_await.base, call),
+        _ => false,
+    };
+    fallible_path
+        || RESULT_METHODS.contains(&call)
+        || ["try_", "write", "send", "flush"]
+            .iter()
+            .any(|prefix| call.starts_with(prefix))
+}
+
+fn call_name(expr: &Expr) -> Option<String> {
+    match expr {
+        Expr::Call(call) => match call.func.as_ref() {
+            Expr::Path(path) => path.path.segments.last().map(|s| s.ident.to_string()),
+            _ => None,
+        },
+        Expr::MethodCall(method_call) => Some(method_call.method.to_string()),
+        Expr::Await(expr_await) => call_name(&expr_await.base),
+        _ => None,
+    }
+}
@@ -1,7 +1,7 @@
 use std::path::Path;
 use syn::{visit::Visit, File, ItemMod, ItemUse};
 
-use crate::analyzer::{CodeIssue, RoastLevel, Severity};
+use crate::analyzer::{CodeContext, CodeIssue, RoastLevel, Severity};
 use crate::rules::Rule;
 
 /// Detects files that are too long (>1000 lines)
@@ -64,6 +64,7 @@ impl Rule for FileStructureRule {
                 message: format!("{} ({}行)", message, line_count),
                 severity,
                 roast_level: RoastLevel::Sarcastic,
+                context: CodeContext::Production,
             });
         }
 
@@ -184,6 +185,7 @@ impl ImportChaosVisitor {
             message: message.to_string(),
             severity: Severity::Mild,
             roast_level: RoastLevel::Sarcastic,
+            context: CodeContext::Production,
         });
     }
 
@@ -215,6 +217,7 @@ impl ImportChaosVisitor {
             message: message.to_string(),
             severity: Severity::Mild,
             roast_level: RoastLevel::Sarcastic,
+            context: CodeContext::Production,
         });
     }
 }
@@ -282,6 +285,7 @@ impl ModuleNestingVisitor {
                 message: format!("{} (深度: {})", message, self.nesting_depth),
                 severity,
                 roast_level: RoastLevel::Sarcastic,
+                context: CodeContext::Production,
             });
         }
     }
@@ -1,9 +1,11 @@
 use std::path::Path;
-use syn::{visit::Visit, File, Ident};
+use syn::File;
 
-use crate::analyzer::{CodeIssue, RoastLevel, Severity};
+use crate::analyzer::{CodeContext, CodeIssue, RoastLevel, Severity};
+use crate::rules::bindings::{collect_bindings, Binding};
+use crate::rules::naming::{conventional_case, split_words};
+use crate::rules::spelling::is_common_word;
 use crate::rules::Rule;
-use crate::utils::get_position;
 
 /// 检测无意义的占位符命名：foo, bar, baz, qux, test, temp 等
 pub struct MeaninglessNamingRule;
@@ -20,9 +22,9 @@ impl Rule for MeaninglessNamingRule {
         _content: &str,
         lang: &str,
     ) -> Vec<CodeIssue> {
-        let mut visitor = MeaninglessNamingVisitor::new(file_path.to_path_buf(), lang);
-        visitor.visit_file(syntax_tree);
-        visitor.issues
+        let mut checker = MeaninglessNamingChecker::new(file_path.to_path_buf(), lang);
+        checker.check_bindings(&collect_bindings(syntax_tree));
+        checker.issues
     }
 }
 
@@ -41,9 +43,9 @@ impl Rule for HungarianNotationRule {
         _content: &str,
         lang: &str,
     ) -> Vec<CodeIssue> {
-        let mut visitor = HungarianNotationVisitor::new(file_path.to_path_buf(), lang);
-        visitor.visit_file(syntax_tree);
-        visitor.issues
+        let mut checker = HungarianNotationChecker::new(file_path.to_path_buf(), lang);
+        checker.check_bindings(&collect_bindings(syntax_tree));
+        checker.issues
     }
 }
 
@@ -62,23 +64,44 @@ impl Rule for AbbreviationAbuseRule {
         _content: &str,
         lang: &str,
     ) -> Vec<CodeIssue> {
-        let mut visitor = AbbreviationAbuseVisitor::new(file_path.to_path_buf(), lang);
-        visitor.visit_file(syntax_tree);
-        visitor.issues
+        let mut checker = AbbreviationAbuseChecker::new(file_path.to_path_buf(), lang);
+        checker.check_bindings(&collect_bindings(syntax_tree));
+        checker.issues
+    }
+}
+
+/// 检测拼音命名、中英混杂和非 ASCII 标识符：yonghu, getYonghuList, 用户名
+pub struct PinyinNamingRule;
+
+impl Rule for PinyinNamingRule {
+    fn name(&self) -> &'static str {
+        "pinyin-naming"
+    }
+
+    fn check(
+        &self,
+        file_path: &Path,
+        syntax_tree: &File,
+        _content: &str,
+        lang: &str,
+    ) -> Vec<CodeIssue> {
+        let mut checker = PinyinNamingChecker::new(file_path.to_path_buf(), lang);
+        checker.check_bindings(&collect_bindings(syntax_tree));
+        checker.issues
     }
 }
 
 // ============================================================================
-// Visitor 实现
+// 无意义命名检测
 // ============================================================================
 
-struct MeaninglessNamingVisitor {
+struct MeaninglessNamingChecker {
     file_path: std::path::PathBuf,
     issues: Vec<CodeIssue>,
     lang: String,
 }
 
-impl MeaninglessNamingVisitor {
+impl MeaninglessNamingChecker {
     fn new(file_path: std::path::PathBuf, lang: &str) -> Self {
         Self {
             file_path,
@@ -114,12 +137,6 @@ impl MeaninglessNamingVisitor {
             "handler",
             "processor",
             "controller",
-            // 中文拼音（常见的）
-            "yonghu",
-            "mima",
-            "denglu",
-            "zhuce",
-            "shuju",
         ];
 
         let name_lower = name.to_lowercase();
@@ -128,7 +145,8 @@ impl MeaninglessNamingVisitor {
             .any(|&bad_name| name_lower == bad_name)
     }
 
-    fn create_issue(&self, name: &str, line: usize, column: usize) -> CodeIssue {
+    fn create_issue(&self, binding: &Binding) -> CodeIssue {
+        let name = binding.name.as_str();
         let messages = if self.lang == "zh-CN" {
             vec![
                 format!("变量名 '{}' 比我的网名还随意", name),
@@ -155,24 +173,23 @@ impl MeaninglessNamingVisitor {
 
         CodeIssue {
             file_path: self.file_path.clone(),
-            line,
-            column,
+            line: binding.line,
+            column: binding.column,
             rule_name: "meaningless-naming".to_string(),
-            message: messages[self.issues.len() % messages.len()].clone(),
+            message: messages[self.issues.len() % messages.len()].clone()
+                + &binding.uses_note(&self.lang),
             severity,
             roast_level: RoastLevel::Sarcastic,
+            context: CodeContext::Production,
         }
     }
-}
 
-impl<'ast> Visit<'ast> for MeaninglessNamingVisitor {
-    fn visit_ident(&mut self, ident: &'ast Ident) {
-        let name = ident.to_string();
-        if self.is_meaningless_name(&name) {
-            let (line, column) = get_position(ident);
-            self.issues.push(self.create_issue(&name, line, column));
+    fn check_bindings(&mut self, bindings: &[Binding]) {
+        for binding in bindings {
+            if self.is_meaningless_name(&binding.name) {
+                self.issues.push(self.create_issue(binding));
+            }
         }
-        syn::visit::visit_ident(self, ident);
     }
 }
 
@@ -180,13 +197,13 @@ impl<'ast> Visit<'ast> for MeaninglessNamingVisitor {
 // 匈牙利命名法检测
 // ============================================================================
 
-struct HungarianNotationVisitor {
+struct HungarianNotationChecker {
     file_path: std::path::PathBuf,
     issues: Vec<CodeIssue>,
     lang: String,
 }
 
-impl HungarianNotationVisitor {
+impl HungarianNotationChecker {
     fn new(file_path: std::path::PathBuf, lang: &str) -> Self {
         Self {
             file_path,
@@ -223,7 +240,8 @@ impl HungarianNotationVisitor {
         false
     }
 
-    fn create_issue(&self, name: &str, line: usize, column: usize) -> CodeIssue {
+    fn create_issue(&self, binding: &Binding) -> CodeIssue {
+        let name = binding.name.as_str();
         let messages = if self.lang == "zh-CN" {
             vec![
                 format!("'{}' 使用了匈牙利命名法？这不是1990年代了", name),
@@ -256,24 +274,23 @@ impl HungarianNotationVisitor {
 
         CodeIssue {
             file_path: self.file_path.clone(),
-            line,
-            column,
+            line: binding.line,
+            column: binding.column,
             rule_name: "hungarian-notation".to_string(),
-            message: messages[self.issues.len() % messages.len()].clone(),
+            message: messages[self.issues.len() % messages.len()].clone()
+                + &binding.uses_note(&self.lang),
             severity: Severity::Mild,
             roast_level: RoastLevel::Sarcastic,
+            context: CodeContext::Production,
         }
     }
-}
 
-impl<'ast> Visit<'ast> for HungarianNotationVisitor {
-    fn visit_ident(&mut self, ident: &'ast Ident) {
-        let name = ident.to_string();
-        if self.is_hungarian_notation(&name) {
-            let (line, column) = get_position(ident);
-            self.issues.push(self.create_issue(&name, line, column));
+    fn check_bindings(&mut self, bindings: &[Binding]) {
+        for binding in bindings {
+            if self.is_hungarian_notation(&binding.name) {
+                self.issues.push(self.create_issue(binding));
+            }
         }
-        syn::visit::visit_ident(self, ident);
     }
 }
 
@@ -281,13 +298,13 @@ impl<'ast> Visit<'ast> for HungarianNotationVisitor {
 // 过度缩写检测
 // ============================================================================
 
-struct AbbreviationAbuseVisitor {
+struct AbbreviationAbuseChecker {
     file_path: std::path::PathBuf,
     issues: Vec<CodeIssue>,
     lang: String,
 }
 
-impl AbbreviationAbuseVisitor {
+impl AbbreviationAbuseChecker {
     fn new(file_path: std::path::PathBuf, lang: &str) -> Self {
         Self {
             file_path,
@@ -339,7 +356,8 @@ impl AbbreviationAbuseVisitor {
         None
     }
 
-    fn create_issue(&self, name: &str, suggestion: &str, line: usize, column: usize) -> CodeIssue {
+    fn create_issue(&self, binding: &Binding, suggestion: &str) -> CodeIssue {
+        let name = binding.name.as_str();
         let messages = if self.lang == "zh-CN" {
             vec![
                 format!("'{}' 缩写得太狠了，建议用 '{}'", name, suggestion),
@@ -378,24 +396,450 @@ impl AbbreviationAbuseVisitor {
 
         CodeIssue {
             file_path: self.file_path.clone(),
-            line,
-            column,
+            line: binding.line,
+            column: binding.column,
             rule_name: "abbreviation-abuse".to_string(),
-            message: messages[self.issues.len() % messages.len()].clone(),
+            message: messages[self.issues.len() % messages.len()].clone()
+                + &binding.uses_note(&self.lang),
             severity: Severity::Mild,
             roast_level: RoastLevel::Gentle,
+            context: CodeContext::Production,
         }
     }
+
+    fn check_bindings(&mut self, bindings: &[Binding]) {
+        for binding in bindings {
+            if let Some(suggestion) = self.is_bad_abbreviation(&binding.name) {
+                self.issues.push(self.create_issue(binding, suggestion));
+            }
+        }
+    }
+}
+
+// ============================================================================
+// 拼音命名检测
+// ============================================================================
+
+/// 普通话的全部音节（不带声调），用来判断一个单词能否完整切分成拼音
+const PINYIN_SYLLABLES: &[&str] = &[
+    "a", "ai", "an", "ang", "ao", "ba", "bai", "ban", "bang", "bao", "bei", "ben", "beng", "bi",
+    "bian", "biao", "bie", "bin", "bing", "bo", "bu", "ca", "cai", "can", "cang", "cao", "ce",
+    "cen", "ceng", "cha", "chai", "chan", "chang", "chao", "che", "chen", "cheng", "chi", "chong",
+    "chou", "chu", "chuai", "chuan", "chuang", "chui", "chun", "chuo", "ci", "cong", "cou", "cu",
+    "cuan", "cui", "cun", "cuo", "da", "dai", "dan", "dang", "dao", "de", "dei", "den", "deng",
+    "di", "dian", "diao", "die", "ding", "diu", "dong", "dou", "du", "duan", "dui", "dun", "duo",
+    "e", "ei", "en", "eng", "er", "fa", "fan", "fang", "fei", "fen", "feng", "fo", "fou", "fu",
+    "ga", "gai", "gan", "gang", "gao", "ge", "gei", "gen", "geng", "gong", "gou", "gu", "gua",
+    "guai", "guan", "guang", "gui", "gun", "guo", "ha", "hai", "han", "hang", "hao", "he", "hei",
+    "hen", "heng", "hong", "hou", "hu", "hua", "huai", "huan", "huang", "hui", "hun", "huo", "ji",
+    "jia", "jian", "jiang", "jiao", "jie", "jin", "jing", "jiong", "jiu", "ju", "juan", "jue",
+    "jun", "ka", "kai", "kan", "kang", "kao", "ke", "ken", "keng", "kong", "kou", "ku", "kua",
+    "kuai", "kuan", "kuang", "kui", "kun", "kuo", "la", "lai", "lan", "lang", "lao", "le", "lei",
+    "leng", "li", "lia", "lian", "liang", "liao", "lie", "lin", "ling", "liu", "long", "lou", "lu",
+    "lv", "luan", "lue", "lun", "luo", "ma", "mai", "man", "mang", "mao", "me", "mei", "men",
+    "meng", "mi", "mian", "miao", "mie", "min", "ming", "miu", "mo", "mou", "mu", "na", "nai",
+    "nan", "nang", "nao", "ne", "nei", "nen", "neng", "ni", "nian", "niang", "niao", "nie", "nin",
+    "ning", "niu", "nong", "nou", "nu", "nv", "nuan", "nue", "nuo", "o", "ou", "pa", "pai", "pan",
+    "pang", "pao", "pei", "pen", "peng", "pi", "pian", "piao", "pie", "pin", "ping", "po", "pou",
+    "pu", "qi", "qia", "qian", "qiang", "qiao", "qie", "qin", "qing", "qiong", "qiu", "qu", "quan",
+    "que", "qun", "ran", "rang", "rao", "re", "ren", "reng", "ri", "rong", "rou", "ru", "ruan",
+    "rui", "run", "ruo", "sa", "sai", "san", "sang", "sao", "se", "sen", "seng", "sha", "shai",
+    "shan", "shang", "shao", "she", "shei", "shen", "sheng", "shi", "shou", "shu", "shua", "shuai",
+    "shuan", "shuang", "shui", "shun", "shuo", "si", "song", "sou", "su", "suan", "sui", "sun",
+    "suo", "ta", "tai", "tan", "tang", "tao", "te", "teng", "ti", "tian", "tiao", "tie", "ting",
+    "tong", "tou", "tu", "tuan", "tui", "tun", "tuo", "wa", "wai", "wan", "wang", "wei", "wen",
+    "weng", "wo", "wu", "xi", "xia", "xian", "xiang", "xiao", "xie", "xin", "xing", "xiong", "xiu",
+    "xu", "xuan", "xue", "xun", "ya", "yan", "yang", "yao", "ye", "yi", "yin", "ying", "yo",
+    "yong", "you", "yu", "yuan", "yue", "yun", "za", "zai", "zan", "zang", "zao", "ze", "zei",
+    "zen", "zeng", "zha", "zhai", "zhan", "zhang", "zhao", "zhe", "zhei", "zhen", "zheng", "zhi",
+    "zhong", "zhou", "zhu", "zhua", "zhuai", "zhuan", "zhuang", "zhui", "zhun", "zhuo", "zi",
+    "zong", "zou", "zu", "zuan", "zui", "zun", "zuo",
+];
+
+/// 英文里少见、拼音里常见的字母组合；只靠音节切分的话 `media`、`banana` 也会被当成拼音
+const PINYIN_MARKERS: &[&str] = &[
+    "zh", "x", "q", "iao", "iu", "ui", "uo", "ong", "ian", "uan", "ao", "ai", "ei", "ou", "eng",
+    "ang",
+];
+
+/// 碰巧能切分成拼音、又带拼音特征的英文单词
+const ENGLISH_LOOKALIKES: &[&str] = &[
+    "adequate",
+    "along",
+    "alongside",
+    "anger",
+    "angle",
+    "antique",
+    "asian",
+    "awaiting",
+    "bangle",
+    "bengali",
+    "boxing",
+    "canadian",
+    "chaining",
+    "change",
+    "changer",
+    "danger",
+    "dangle",
+    "dangling",
+    "debian",
+    "deque",
+    "disengage",
+    "dongle",
+    "endian",
+    "engage",
+    "equalize",
+    "exiting",
+    "ganglia",
+    "gangrene",
+    "guidance",
+    "guide",
+    "haiku",
+    "hanger",
+    "house",
+    "hungarian",
+    "language",
+    "leisure",
+    "lexing",
+    "longer",
+    "louse",
+    "mailing",
+    "manger",
+    "mangle",
+    "mangling",
+    "maxima",
+    "median",
+    "mixing",
+    "mouse",
+    "opaque",
+    "orange",
+    "outing",
+    "pouting",
+    "quarantine",
+    "quasi",
+    "queen",
+    "querier",
+    "querying",
+    "quota",
+    "quote",
+    "quoting",
+    "range",
+    "ranger",
+    "reliance",
+    "remaining",
+    "retaining",
+    "route",
+    "routine",
+    "routing",
+    "sequence",
+    "shebang",
+    "shouting",
+    "suite",
+    "tangle",
+    "taxing",
+    "touting",
+    "waiting",
+    "waxing",
+];
+
+/// 常见业务拼音和对应的中文、英文
+const PINYIN_GLOSSARY: &[(&str, &str, &str)] = &[
+    ("yonghuming", "用户名", "username"),
+    ("yonghu", "用户", "user"),
+    ("mima", "密码", "password"),
+    ("denglu", "登录", "login"),
+    ("zhuce", "注册", "register"),
+    ("tuichu", "退出", "logout"),
+    ("shuju", "数据", "data"),
+    ("shuru", "输入", "input"),
+    ("shuchu", "输出", "output"),
+    ("xinxi", "信息", "info"),
+    ("dingdan", "订单", "order"),
+    ("shangpin", "商品", "product"),
+    ("jiage", "价格", "price"),
+    ("shuliang", "数量", "quantity"),
+    ("jine", "金额", "amount"),
+    ("zhifu", "支付", "payment"),
+    ("kucun", "库存", "inventory"),
+    ("gouwuche", "购物车", "cart"),
+    ("kehu", "客户", "customer"),
+    ("guanliyuan", "管理员", "admin"),
+    ("quanxian", "权限", "permission"),
+    ("juese", "角色", "role"),
+    ("xuesheng", "学生", "student"),
+    ("laoshi", "老师", "teacher"),
+    ("chengji", "成绩", "score"),
+    ("mingcheng", "名称", "name"),
+    ("mingzi", "名字", "name"),
+    ("xingming", "姓名", "full_name"),
+    ("dizhi", "地址", "address"),
+    ("dianhua", "电话", "phone"),
+    ("shouji", "手机", "mobile"),
+    ("youxiang", "邮箱", "email"),
+    ("riqi", "日期", "date"),
+    ("shijian", "时间", "time"),
+    ("zhuangtai", "状态", "status"),
+    ("leixing", "类型", "kind"),
+    ("liebiao", "列表", "list"),
+    ("jieguo", "结果", "result"),
+    ("cuowu", "错误", "error"),
+    ("xiaoxi", "消息", "message"),
+    ("wenjian", "文件", "file"),
+    ("lujing", "路径", "path"),
+    ("peizhi", "配置", "config"),
+    ("canshu", "参数", "params"),
+    ("fanhui", "返回", "return"),
+    ("huoqu", "获取", "fetch"),
+    ("chaxun", "查询", "query"),
+    ("tianjia", "添加", "add"),
+    ("xinzeng", "新增", "create"),
+    ("shanchu", "删除", "delete"),
+    ("xiugai", "修改", "update"),
+    ("baocun", "保存", "save"),
+    ("jisuan", "计算", "calculate"),
+    ("yanzheng", "验证", "validate"),
+    ("jiancha", "检查", "check"),
+    ("chuli", "处理", "process"),
+    ("fasong", "发送", "send"),
+    ("jieshou", "接收", "receive"),
+    ("shangchuan", "上传", "upload"),
+    ("xiazai", "下载", "download"),
+    ("zongshu", "总数", "total"),
+    ("bianhao", "编号", "number"),
+    ("miaoshu", "描述", "description"),
+    ("biaoti", "标题", "title"),
+    ("neirong", "内容", "content"),
+    ("tupian", "图片", "image"),
+    ("anniu", "按钮", "button"),
+    ("kaishi", "开始", "start"),
+    ("jieshu", "结束", "end"),
+    ("chenggong", "成功", "success"),
+    ("shibai", "失败", "failure"),
+];
+
+#[derive(Clone, Copy, PartialEq)]
+enum Script {
+    Pinyin,
+    Hanzi,
+}
+
+/// 用词表把一段拼音或汉字完整翻译成英文单词，翻不完就放弃
+fn translate(text: &str, script: Script) -> Option<Vec<&'static str>> {
+    if text.is_empty() {
+        return Some(Vec::new());
+    }
+    PINYIN_GLOSSARY
+        .iter()
+        .find_map(|&(pinyin, hanzi, english)| {
+            let key = if script == Script::Pinyin {
+                pinyin
+            } else {
+                hanzi
+            };
+            let rest = text.strip_prefix(key)?;
+            let mut words = vec![english];
+            words.extend(translate(rest, script)?);
+            Some(words)
+        })
+}
+
+/// 最少能切成几个拼音音节，切不完返回 None
+fn syllable_count(word: &str) -> Option<usize> {
+    let mut best: Vec<Option<usize>> = vec![None; word.len() + 1];
+    best[0] = Some(0);
+    for end in 1..=word.len() {
+        for start in end.saturating_sub(6)..end {
+            if let (Some(count), Some(syllable)) = (best[start], word.get(start..end)) {
+                if PINYIN_SYLLABLES.contains(&syllable) && best[end].is_none_or(|b| count + 1 < b) {
+                    best[end] = Some(count + 1);
+                }
+            }
+        }
+    }
+    best[word.len()]
+}
+
+/// 词表以外的拼音只认至少两个音节、带拼音特征的长单词，宁可漏报也不误伤英文
+fn looks_like_pinyin(word: &str) -> bool {
+    word.len() >= 5
+        && !ENGLISH_LOOKALIKES.contains(&word)
+        && PINYIN_MARKERS.iter().any(|marker| word.contains(marker))
+        && syllable_count(word).is_some_and(|count| count >= 2)
+}
+
+enum PinyinFinding {
+    /// 整个名字都是拼音
+    Pinyin,
+    /// 拼音和英文混在一起
+    Mixed,
+    /// 直接用了汉字等非 ASCII 字符
+    NonAscii,
+}
+
+struct PinyinNamingChecker {
+    file_path: std::path::PathBuf,
+    issues: Vec<CodeIssue>,
+    lang: String,
 }
 
-impl<'ast> Visit<'ast> for AbbreviationAbuseVisitor {
-    fn visit_ident(&mut self, ident: &'ast Ident) {
-        let name = ident.to_string();
-        if let Some(suggestion) = self.is_bad_abbreviation(&name) {
-            let (line, column) = get_position(ident);
-            self.issues
-                .push(self.create_issue(&name, suggestion, line, column));
+impl PinyinNamingChecker {
+    fn new(file_path: std::path::PathBuf, lang: &str) -> Self {
+        Self {
+            file_path,
+            issues: Vec::new(),
+            lang: lang.to_string(),
+        }
+    }
+
+    /// 返回问题类型和翻译好的 snake_case 名字（词表里查不到就没有建议）
+    fn analyze(&self, name: &str) -> Option<(PinyinFinding, Option<String>)> {
+        if !name.is_ascii() {
+            return Some((PinyinFinding::NonAscii, Self::translate_non_ascii(name)));
+        }
+
+        let words: Vec<String> = split_words(name).iter().map(|w| w.to_lowercase()).collect();
+        let mut pinyin_words = 0;
+        let mut english_words = 0;
+        let mut translated = Some(Vec::new());
+        let mut start = 0;
+        while start < words.len() {
+            // 驼峰会把 `yonghuMing` 拆开，先试着把相邻几段拼起来整体查词表，能拼长就拼长
+            if let Some((end, english)) = (start + 1..=words.len()).rev().find_map(|end| {
+                Some((end, translate(&words[start..end].concat(), Script::Pinyin)?))
+            }) {
+                pinyin_words += end - start;
+                if let Some(translated) = translated.as_mut() {
+                    translated.extend(english.iter().map(|w| w.to_string()));
+                }
+                start = end;
+                continue;
+            }
+
+            let word = &words[start];
+            start += 1;
+            if looks_like_pinyin(word) {
+                pinyin_words += 1;
+                translated = None;
+            } else if syllable_count(word).is_some() && !is_common_word(word) {
+                // 能完整切成拼音音节、又不是常用英文词的短段不算英文，免得把 `ming` 当成混写；
+                // 光靠它不足以认定是拼音，所以不计数
+                translated = None;
+            } else {
+                if word.chars().any(|c| c.is_ascii_alphabetic()) {
+                    english_words += 1;
+                }
+                if let Some(translated) = translated.as_mut() {
+                    translated.push(word.clone());
+                }
+            }
+        }
+
+        if pinyin_words == 0 {
+            return None;
+        }
+        let finding = if english_words > 0 {
+            PinyinFinding::Mixed
+        } else {
+            PinyinFinding::Pinyin
+        };
+        Some((finding, translated.map(|words| words.join("_"))))
+    }
+
+    /// `用户_id`、`获取用户` 这类名字：ASCII 部分原样保留，汉字部分查词表
+    fn translate_non_ascii(name: &str) -> Option<String> {
+        let mut words = Vec::new();
+        for part in name.split('_').filter(|part| !part.is_empty()) {
+            let mut rest = part;
+            while let Some(first) = rest.chars().next() {
+                let run_len = rest
+                    .find(|c: char| c.is_ascii() != first.is_ascii())
+                    .unwrap_or(rest.len());
+                let (run, tail) = rest.split_at(run_len);
+                if first.is_ascii() {
+                    words.extend(split_words(run).iter().map(|w| w.to_lowercase()));
+                } else {
+                    words.extend(translate(run, Script::Hanzi)?.iter().map(|w| w.to_string()));
+                }
+                rest = tail;
+            }
+        }
+        Some(words.join("_"))
+    }
+
+    fn create_issue(
+        &self,
+        binding: &Binding,
+        finding: PinyinFinding,
+        suggestion: Option<String>,
+    ) -> CodeIssue {
+        let name = binding.name.as_str();
+        let kind = binding.kind.label(&self.lang);
+        let messages = match (self.lang == "zh-CN", &finding) {
+            (true, PinyinFinding::Pinyin) => vec![
+                format!("{} '{}' 是拼音，读代码还得先把声调猜一遍", kind, name),
+                format!("拼音命名 '{}'？外国同事看不懂，中国同事也得念出声才懂", name),
+                format!("'{}' 这个拼音有好几种汉字组合，读者只能靠缘分", name),
+            ],
+            (true, PinyinFinding::Mixed) => vec![
+                format!("{} '{}' 中英混搭，一半拼音一半英文，像夹生饭", kind, name),
+                format!("'{}' 拼音加英文，读者得同时开两个输入法", name),
+                format!("中英混杂的 '{}'，既不是中文也不是英文，是 Chinglish", name),
+            ],
+            (true, PinyinFinding::NonAscii) => vec![
+                format!("{} '{}' 用了非 ASCII 字符，换个键盘布局就没法输入了", kind, name),
+                format!("'{}' 直接用汉字命名？grep 和 IDE 补全都要哭了", name),
+                format!("非 ASCII 标识符 '{}'，协作者得先装好输入法才能调用", name),
+            ],
+            (false, PinyinFinding::Pinyin) => vec![
+                format!("{} '{}' is pinyin - readers have to guess the tones first", kind, name),
+                format!("Pinyin name '{}'? Foreign teammates can't read it and Chinese teammates have to sound it out", name),
+                format!("'{}' maps to several possible Chinese words - good luck to the reader", name),
+            ],
+            (false, PinyinFinding::Mixed) => vec![
+                format!("{} '{}' mixes pinyin and English, half-cooked in two languages", kind, name),
+                format!("'{}' needs two input methods to read: half pinyin, half English", name),
+                format!("Mixed-language '{}' is neither Chinese nor English, it's Chinglish", name),
+            ],
+            (false, PinyinFinding::NonAscii) => vec![
+                format!("{} '{}' uses non-ASCII characters - switch keyboard layouts and you can't type it", kind, name),
+                format!("Naming things '{}' in Chinese characters? grep and autocomplete are crying", name),
+                format!("Non-ASCII identifier '{}' - collaborators need an IME just to call it", name),
+            ],
+        };
+
+        let mut message = messages[self.issues.len() % messages.len()].clone();
+        if let Some(suggestion) = suggestion {
+            let suggestion = conventional_case(binding.kind, &suggestion);
+            if self.lang == "zh-CN" {
+                message += &format!("，建议用 '{}'", suggestion);
+            } else {
+                message += &format!(", try '{}'", suggestion);
+            }
+        }
+
+        let severity = match finding {
+            PinyinFinding::Pinyin => Severity::Mild,
+            PinyinFinding::Mixed | PinyinFinding::NonAscii => Severity::Spicy,
+        };
+
+        CodeIssue {
+            file_path: self.file_path.clone(),
+            line: binding.line,
+            column: binding.column,
+            rule_name: "pinyin-naming".to_string(),
+            message: message + &binding.uses_note(&self.lang),
+            severity,
+            roast_level: RoastLevel::Sarcastic,
+            context: CodeContext::Production,
+        }
+    }
+
+    fn check_bindings(&mut self, bindings: &[Binding]) {
+        for binding in bindings {
+            if let Some((finding, suggestion)) = self.analyze(&binding.name) {
+                self.issues
+                    .push(self.create_issue(binding, finding, suggestion));
+            }
         }
-        syn::visit::visit_ident(self, ident);
     }
 }
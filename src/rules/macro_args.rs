@@ -0,0 +1,121 @@
+use syn::{parse::ParseStream, punctuated::Punctuated, visit::Visit, Expr, Macro, Pat, Token};
+
+/// 参数就是逗号分隔表达式的常见宏
+const EXPR_LIST_MACROS: &[&str] = &[
+    "assert",
+    "assert_eq",
+    "assert_ne",
+    "debug_assert",
+    "debug_assert_eq",
+    "debug_assert_ne",
+    "format",
+    "format_args",
+    "print",
+    "println",
+    "eprint",
+    "eprintln",
+    "write",
+    "writeln",
+    "panic",
+    "unreachable",
+    "todo",
+    "unimplemented",
+    "dbg",
+    "join",
+    "try_join",
+    "debug",
+    "info",
+    "warn",
+    "error",
+    "trace",
+    "bail",
+    "anyhow",
+    "ensure",
+];
+
+/// 把常见宏的参数解析成表达式；不认识或解析失败的宏返回空列表
+///
+/// syn 不会展开宏，宏调用里的代码对普通的 Visitor 是不可见的。
+/// 解析出来的表达式保留了原始的 span，报告的行号仍然准确。
+pub fn parse_macro_args(mac: &Macro) -> Vec<Expr> {
+    let Some(name) = mac.path.segments.last().map(|s| s.ident.to_string()) else {
+        return Vec::new();
+    };
+
+    let parsed = match name.as_str() {
+        "vec" => mac.parse_body_with(parse_vec_args),
+        "select" => mac.parse_body_with(parse_select_args),
+        _ if EXPR_LIST_MACROS.contains(&name.as_str()) => mac
+            .parse_body_with(Punctuated::<Expr, Token![,]>::parse_terminated)
+            .map(|args| args.into_iter().collect()),
+        _ => return Vec::new(),
+    };
+
+    parsed.unwrap_or_default()
+}
+
+/// 让 Visitor 继续深入宏参数；在 Visitor 的 `visit_macro` 里调用
+pub fn visit_macro_args<V>(visitor: &mut V, mac: &Macro)
+where
+    V: for<'a> Visit<'a>,
+{
+    for arg in parse_macro_args(mac) {
+        visitor.visit_expr(&arg);
+    }
+}
+
+/// vec![a, b, c] 或 vec![elem; n]
+fn parse_vec_args(input: ParseStream) -> syn::Result<Vec<Expr>> {
+    if input.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let first: Expr = input.parse()?;
+    if input.peek(Token![;]) {
+        input.parse::<Token![;]>()?;
+        let count: Expr = input.parse()?;
+        return Ok(vec![first, count]);
+    }
+
+    let mut args = vec![first];
+    while input.peek(Token![,]) {
+        input.parse::<Token![,]>()?;
+        if input.is_empty() {
+            break;
+        }
+        args.push(input.parse()?);
+    }
+    Ok(args)
+}
+
+/// tokio::select! { biased; pat = fut, if cond => handler, else => handler }
+fn parse_select_args(input: ParseStream) -> syn::Result<Vec<Expr>> {
+    let mut args = Vec::new();
+
+    if input.peek(syn::Ident) && input.fork().parse::<syn::Ident>()? == "biased" {
+        input.parse::<syn::Ident>()?;
+        input.parse::<Token![;]>()?;
+    }
+
+    while !input.is_empty() {
+        if input.peek(Token![else]) {
+            input.parse::<Token![else]>()?;
+        } else {
+            Pat::parse_single(input)?;
+            input.parse::<Token![=]>()?;
+            args.push(input.call(Expr::parse_without_eager_brace)?);
+            if input.peek(Token![,]) && input.peek2(Token![if]) {
+                input.parse::<Token![,]>()?;
+                input.parse::<Token![if]>()?;
+                args.push(input.call(Expr::parse_without_eager_brace)?);
+            }
+        }
+        input.parse::<Token![=>]>()?;
+        args.push(input.parse()?);
+        if input.peek(Token![,]) {
+            input.parse::<Token![,]>()?;
+        }
+    }
+
+    Ok(args)
+}
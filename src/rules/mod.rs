@@ -1,19 +1,31 @@
 use std::path::Path;
 use syn::File;
 
-use crate::analyzer::CodeIssue;
+use crate::analyzer::{CodeContext, CodeIssue, RoastLevel, Severity};
 
 pub mod advanced_rust;
+pub mod api_hygiene;
+pub mod bindings;
 pub mod code_smells;
+pub(crate) mod common;
 pub mod complexity;
 pub mod comprehensive_rust;
+pub mod concurrency;
+pub mod context;
+pub mod documentation;
 pub mod duplication;
+pub mod error_handling;
 pub mod file_structure;
 pub mod garbage_naming;
+pub mod macro_args;
 pub mod naming;
 pub mod rust_patterns;
 pub mod rust_specific;
+pub mod spelling;
 pub mod student_code;
+pub mod todos;
+pub mod type_design;
+pub mod unsafe_audit;
 
 pub trait Rule {
     #[allow(dead_code)]
@@ -25,10 +37,27 @@ pub trait Rule {
         content: &str,
         lang: &str,
     ) -> Vec<CodeIssue>;
+
+    /// How this rule treats code outside production (tests, examples, benches, build scripts)
+    fn context_policy(&self, _context: CodeContext) -> ContextPolicy {
+        ContextPolicy::Report
+    }
+}
+
+pub use context::{ContextMap, ContextPolicy};
+
+/// 用户可调的规则参数，来自命令行
+#[derive(Debug, Clone, Default)]
+pub struct RuleConfig {
+    /// 除了内置常见值之外，不算魔法数字的数字
+    pub allowed_numbers: Vec<i64>,
+    /// 拼写检查时当作正确单词的项目词汇
+    pub custom_words: Vec<String>,
 }
 
 pub struct RuleEngine {
     rules: Vec<Box<dyn Rule>>,
+    config: RuleConfig,
 }
 
 impl Default for RuleEngine {
@@ -39,20 +68,27 @@ impl Default for RuleEngine {
 
 impl RuleEngine {
     pub fn new() -> Self {
+        Self::with_config(RuleConfig::default())
+    }
+
+    pub fn with_config(config: RuleConfig) -> Self {
         let rules: Vec<Box<dyn Rule>> = vec![
             // Add various detection rules
             Box::new(naming::TerribleNamingRule),
             Box::new(naming::SingleLetterVariableRule),
+            Box::new(naming::NamingConventionRule),
             // Add garbage naming detection rules
             Box::new(garbage_naming::MeaninglessNamingRule),
             Box::new(garbage_naming::HungarianNotationRule),
             Box::new(garbage_naming::AbbreviationAbuseRule),
+            Box::new(garbage_naming::PinyinNamingRule),
+            Box::new(spelling::IdentifierTypoRule::new(&config.custom_words)),
             // Add student code detection rules
             Box::new(student_code::PrintlnDebuggingRule),
             Box::new(student_code::PanicAbuseRule),
-            Box::new(student_code::TodoCommentRule),
+            Box::new(todos::TodoCommentRule),
             // Add code smell detection rules
-            Box::new(code_smells::MagicNumberRule),
+            Box::new(code_smells::MagicNumberRule::new(&config.allowed_numbers)),
             Box::new(code_smells::GodFunctionRule),
             Box::new(code_smells::CommentedCodeRule),
             Box::new(code_smells::DeadCodeRule),
@@ -66,16 +102,42 @@ impl RuleEngine {
             Box::new(duplication::CodeDuplicationRule),
             Box::new(rust_specific::UnwrapAbuseRule),
             Box::new(rust_specific::UnnecessaryCloneRule),
+            // Add error handling rules
+            Box::new(error_handling::StringlyErrorRule),
+            Box::new(error_handling::BoxedErrorRule),
+            Box::new(error_handling::SwallowedErrorRule),
+            Box::new(error_handling::IncompleteErrorTypeRule),
+            // Add public API hygiene rules
+            Box::new(api_hygiene::OwnedRefParamRule),
+            Box::new(api_hygiene::BoolParamSoupRule),
+            Box::new(api_hygiene::LeakyStructRule),
+            Box::new(api_hygiene::MissingNonExhaustiveRule),
+            Box::new(api_hygiene::MissingDebugRule),
+            Box::new(api_hygiene::LeakyImplTraitRule),
+            // Add documentation rules
+            Box::new(documentation::MissingDocsRule),
+            Box::new(documentation::ParrotDocsRule),
+            Box::new(documentation::BrokenDocExampleRule),
+            Box::new(documentation::MissingDocSectionsRule),
+            // Add type design rules
+            Box::new(type_design::GodStructRule),
+            Box::new(type_design::OptionSoupRule),
+            Box::new(type_design::DataClumpRule),
+            Box::new(type_design::PrimitiveObsessionRule),
+            Box::new(type_design::HugeImplRule),
             // Add advanced Rust-specific rules
             Box::new(advanced_rust::ComplexClosureRule),
             Box::new(advanced_rust::LifetimeAbuseRule),
             Box::new(advanced_rust::TraitComplexityRule),
             Box::new(advanced_rust::GenericAbuseRule),
+            // Add concurrency rules
+            Box::new(concurrency::ChannelAbuseRule),
+            Box::new(concurrency::AsyncAbuseRule),
+            Box::new(concurrency::LockAbuseRule),
+            // Add unsafe audit rules
+            Box::new(unsafe_audit::UnsafeAbuseRule),
             // Add comprehensive Rust feature rules
-            Box::new(comprehensive_rust::ChannelAbuseRule),
-            Box::new(comprehensive_rust::AsyncAbuseRule),
             Box::new(comprehensive_rust::DynTraitAbuseRule),
-            Box::new(comprehensive_rust::UnsafeAbuseRule),
             Box::new(comprehensive_rust::FFIAbuseRule),
             Box::new(comprehensive_rust::MacroAbuseRule),
             Box::new(comprehensive_rust::ModuleComplexityRule),
@@ -89,7 +151,7 @@ impl RuleEngine {
             Box::new(file_structure::ModuleNestingRule),
         ];
 
-        Self { rules }
+        Self { rules, config }
     }
 
     pub fn check_file(
@@ -100,11 +162,36 @@ impl RuleEngine {
         lang: &str,
     ) -> Vec<CodeIssue> {
         let mut issues = Vec::new();
+        let contexts = ContextMap::new(file_path, syntax_tree);
 
         for rule in &self.rules {
-            issues.extend(rule.check(file_path, syntax_tree, content, lang));
+            for mut issue in rule.check(file_path, syntax_tree, content, lang) {
+                issue.context = contexts.context_at(issue.line);
+                match rule.context_policy(issue.context) {
+                    ContextPolicy::Report => {}
+                    ContextPolicy::Downgrade => {
+                        issue.severity = Severity::Mild;
+                        issue.roast_level = RoastLevel::Gentle;
+                    }
+                    ContextPolicy::Ignore => continue,
+                }
+                issues.push(issue);
+            }
         }
 
         issues
     }
+
+    /// 生产代码里的魔法数字出现位置，供项目级的重复统计使用
+    pub(crate) fn magic_numbers(
+        &self,
+        file_path: &Path,
+        syntax_tree: &File,
+    ) -> Vec<code_smells::MagicNumber> {
+        let contexts = ContextMap::new(file_path, syntax_tree);
+        code_smells::collect_magic_numbers(file_path, syntax_tree, &self.config.allowed_numbers)
+            .into_iter()
+            .filter(|occurrence| contexts.context_at(occurrence.line) == CodeContext::Production)
+            .collect()
+    }
 }
@@ -1,9 +1,16 @@
 use regex::Regex;
+use std::collections::HashSet;
 use std::path::Path;
-use syn::{visit::Visit, File, Ident};
+use syn::{
+    visit::Visit, File, ImplItem, ItemEnum, ItemImpl, ItemStruct, ItemTrait, ReturnType, Signature,
+    TraitItem, Type,
+};
 
-use crate::analyzer::{CodeIssue, RoastLevel, Severity};
+use crate::analyzer::{CodeContext, CodeIssue, RoastLevel, Severity};
+use crate::rules::bindings::{collect_bindings, Binding, BindingKind};
+use crate::rules::common::{derived_traits, rule_issue, type_name};
 use crate::rules::Rule;
+use crate::utils::get_position;
 
 pub struct TerribleNamingRule;
 
@@ -19,9 +26,9 @@ impl Rule for TerribleNamingRule {
         _content: &str,
         lang: &str,
     ) -> Vec<CodeIssue> {
-        let mut visitor = NamingVisitor::new(file_path.to_path_buf(), lang);
-        visitor.visit_file(syntax_tree);
-        visitor.issues
+        let mut checker = NamingChecker::new(file_path.to_path_buf(), lang);
+        checker.check_bindings(&collect_bindings(syntax_tree));
+        checker.issues
     }
 }
 
@@ -39,20 +46,45 @@ impl Rule for SingleLetterVariableRule {
         _content: &str,
         lang: &str,
     ) -> Vec<CodeIssue> {
-        let mut visitor = SingleLetterVisitor::new(file_path.to_path_buf(), lang);
+        let mut checker = SingleLetterChecker::new(file_path.to_path_buf(), lang);
+        checker.check_bindings(&collect_bindings(syntax_tree));
+        checker.issues
+    }
+}
+
+/// 检测违反 Rust 命名惯例的名字：大小写风格、缩写词大小写、get_ 前缀和 as_/to_/into_ 的所有权约定
+pub struct NamingConventionRule;
+
+impl Rule for NamingConventionRule {
+    fn name(&self) -> &'static str {
+        "naming-convention"
+    }
+
+    fn check(
+        &self,
+        file_path: &Path,
+        syntax_tree: &File,
+        _content: &str,
+        lang: &str,
+    ) -> Vec<CodeIssue> {
+        let mut visitor = NamingConventionVisitor::new(file_path.to_path_buf(), lang);
+        let mut copy_types = CopyTypes::default();
+        copy_types.visit_file(syntax_tree);
+        visitor.copy_types = copy_types.0;
+        visitor.check_bindings(&collect_bindings(syntax_tree));
         visitor.visit_file(syntax_tree);
         visitor.issues
     }
 }
 
-struct NamingVisitor {
+struct NamingChecker {
     file_path: std::path::PathBuf,
     issues: Vec<CodeIssue>,
     terrible_names: Regex,
     lang: String,
 }
 
-impl NamingVisitor {
+impl NamingChecker {
     fn new(file_path: std::path::PathBuf, lang: &str) -> Self {
         let terrible_names = Regex::new(r"^(data|info|temp|tmp|val|value|item|thing|stuff|obj|object|manager|handler|helper|util|utils|a|b|c|d|e|f|g|h|test|func|function)(\d+)?$").unwrap();
 
@@ -64,49 +96,125 @@ impl NamingVisitor {
         }
     }
 
-    fn check_name(&mut self, ident: &Ident, context: &str) {
-        let name = ident.to_string();
+    fn check_bindings(&mut self, bindings: &[Binding]) {
+        for binding in bindings {
+            // 泛型参数 T、U 这类单字母是惯例
+            if binding.kind != BindingKind::Generic
+                && self.terrible_names.is_match(&binding.name.to_lowercase())
+            {
+                self.push_issue(binding);
+            }
+        }
+    }
+
+    fn push_issue(&mut self, binding: &Binding) {
+        let name = &binding.name;
+        let ctx = binding.kind.label(&self.lang);
+
+        // 根据语言设置选择消息
+        let messages = if self.lang == "zh-CN" {
+            vec![
+                format!("{} '{}' - 比我的编程技能还要抽象", ctx, name),
+                format!(
+                    "{} '{}' - 这个名字告诉我你已经放弃治疗了，建议直接转行卖煎饼果子",
+                    ctx, name
+                ),
+                format!(
+                    "{} '{}' - 用这个做名字？你是想让维护代码的人哭着辞职吗？",
+                    ctx, name
+                ),
+                format!("{} '{}' - 恭喜你发明了最没有意义的标识符", ctx, name),
+                format!("{} '{}' - 创意程度约等于给孩子起名叫'小明'", ctx, name),
+                format!(
+                    "{} '{}' - 看到这个名字，我的智商都下降了，现在只能数到3了",
+                    ctx, name
+                ),
+            ]
+        } else {
+            vec![
+                format!(
+                    "{} '{}' - more abstract than my programming skills",
+                    ctx, name
+                ),
+                format!(
+                    "{} '{}' - this name tells me you've given up on life and should sell hotdogs",
+                    ctx, name
+                ),
+                format!(
+                    "{} '{}' - using this name? trying to make maintainers cry and quit?",
+                    ctx, name
+                ),
+                format!(
+                    "{} '{}' - congrats on inventing the most meaningless identifier",
+                    ctx, name
+                ),
+                format!(
+                    "{} '{}' - creativity level of naming a kid 'Child'",
+                    ctx, name
+                ),
+                format!(
+                    "{} '{}' - seeing this name, my IQ dropped to single digits",
+                    ctx, name
+                ),
+            ]
+        };
+
+        let message_index =
+            (self.issues.len() + name.len() + name.chars().next().unwrap_or('a') as usize)
+                % messages.len();
+
+        self.issues.push(CodeIssue {
+            file_path: self.file_path.clone(),
+            line: binding.line,
+            column: binding.column,
+            rule_name: "terrible-naming".to_string(),
+            message: messages[message_index].clone() + &binding.uses_note(&self.lang),
+            severity: Severity::Spicy,
+            roast_level: RoastLevel::Sarcastic,
+            context: CodeContext::Production,
+        });
+    }
+}
+
+struct SingleLetterChecker {
+    file_path: std::path::PathBuf,
+    issues: Vec<CodeIssue>,
+    lang: String,
+}
+
+impl SingleLetterChecker {
+    fn new(file_path: std::path::PathBuf, lang: &str) -> Self {
+        Self {
+            file_path,
+            issues: Vec::new(),
+            lang: lang.to_string(),
+        }
+    }
+
+    fn check_bindings(&mut self, bindings: &[Binding]) {
+        for binding in bindings.iter().filter(|binding| binding.kind.is_local()) {
+            let name = &binding.name;
+
+            // Exclude common single-letter variables (like loop counters i, j, k)
+            if name.len() != 1 || matches!(name.as_str(), "i" | "j" | "k" | "x" | "y" | "z") {
+                continue;
+            }
 
-        if self.terrible_names.is_match(&name.to_lowercase()) {
-            // 根据语言设置选择消息
             let messages = if self.lang == "zh-CN" {
-                // 中文消息
-                let ctx = if context == "函数名" {
-                    "函数名"
-                } else {
-                    "变量名"
-                };
                 vec![
-                    format!("{} '{}' - 比我的编程技能还要抽象", ctx, name),
-                    format!(
-                        "{} '{}' - 这个名字告诉我你已经放弃治疗了，建议直接转行卖煎饼果子",
-                        ctx, name
-                    ),
-                    format!(
-                        "{} '{}' - 用这个做名字？你是想让维护代码的人哭着辞职吗？",
-                        ctx, name
-                    ),
-                    format!("{} '{}' - 恭喜你发明了最没有意义的标识符", ctx, name),
-                    format!("{} '{}' - 创意程度约等于给孩子起名叫'小明'", ctx, name),
-                    format!(
-                        "{} '{}' - 看到这个名字，我的智商都下降了，现在只能数到3了",
-                        ctx, name
-                    ),
+                    format!("单字母变量 '{name}'？你是在写数学公式还是在折磨读代码的人？"),
+                    format!("变量 '{name}'？这是变量名还是你键盘坏了？"),
+                    format!("用 '{name}' 做变量名，你可能需要一本《如何给变量起名》的书"),
+                    format!("单字母变量 '{name}'：让代码比古埃及象形文字还难懂"),
+                    format!("变量 '{name}' 的信息量约等于一个句号"),
                 ]
             } else {
-                // 英文消息
-                let ctx = if context == "Function" {
-                    "Function"
-                } else {
-                    "Variable"
-                };
                 vec![
-                    format!("{} '{}' - more abstract than my programming skills", ctx, name),
-                    format!("{} '{}' - this name tells me you've given up on life and should sell hotdogs", ctx, name),
-                    format!("{} '{}' - using this name? trying to make maintainers cry and quit?", ctx, name),
-                    format!("{} '{}' - congrats on inventing the most meaningless identifier", ctx, name),
-                    format!("{} '{}' - creativity level of naming a kid 'Child'", ctx, name),
-                    format!("{} '{}' - seeing this name, my IQ dropped to single digits", ctx, name),
+                    format!("Single-letter variable '{name}'? Writing math formulas or torturing readers?"),
+                    format!("Variable '{name}'? Is that a name or did your keyboard break?"),
+                    format!("Naming a variable '{name}' - maybe read a book on naming things"),
+                    format!("Single-letter variable '{name}': harder to read than hieroglyphics"),
+                    format!("Variable '{name}' carries about as much information as a full stop"),
                 ]
             };
 
@@ -116,86 +224,382 @@ impl NamingVisitor {
 
             self.issues.push(CodeIssue {
                 file_path: self.file_path.clone(),
-                line: self.issues.len() + 2, // 简单的行号估算，避免都是1:1
-                column: (name.len() % 10) + 1, // 基于名字长度的列号
-                rule_name: "terrible-naming".to_string(),
-                message: messages[message_index].clone(),
-                severity: Severity::Spicy,
-                roast_level: RoastLevel::Sarcastic,
+                line: binding.line,
+                column: binding.column,
+                rule_name: "single-letter-variable".to_string(),
+                message: messages[message_index].clone() + &binding.uses_note(&self.lang),
+                severity: Severity::Mild,
+                roast_level: RoastLevel::Gentle,
+                context: CodeContext::Production,
             });
         }
     }
 }
 
-impl<'ast> Visit<'ast> for NamingVisitor {
-    fn visit_ident(&mut self, ident: &'ast Ident) {
-        let context = if self.lang == "zh-CN" {
-            "变量名"
-        } else {
-            "Variable"
-        };
-        self.check_name(ident, context);
-        syn::visit::visit_ident(self, ident);
+// ============================================================================
+// Rust 命名惯例
+// ============================================================================
+
+/// 按 `_` 和大小写边界把标识符拆成单词，连续大写当作一个缩写词：
+/// `HTTPServer` -> `HTTP`, `Server`；`parse_json_v2` -> `parse`, `json`, `v2`
+pub(crate) fn split_words(name: &str) -> Vec<String> {
+    let mut words = Vec::new();
+
+    for part in name.split('_').filter(|part| !part.is_empty()) {
+        let chars: Vec<char> = part.chars().collect();
+        let mut current = String::new();
+        for (index, &ch) in chars.iter().enumerate() {
+            let prev = index.checked_sub(1).map(|i| chars[i]);
+            let next = chars.get(index + 1).copied();
+            let starts_word = ch.is_uppercase()
+                && prev.is_some_and(|p| {
+                    p.is_lowercase()
+                        || p.is_ascii_digit()
+                        || (p.is_uppercase() && next.is_some_and(char::is_lowercase))
+                });
+            if starts_word && !current.is_empty() {
+                words.push(std::mem::take(&mut current));
+            }
+            current.push(ch);
+        }
+        if !current.is_empty() {
+            words.push(current);
+        }
     }
 
-    // 添加函数名检测
-    fn visit_item_fn(&mut self, func: &'ast syn::ItemFn) {
-        let context = if self.lang == "zh-CN" {
-            "函数名"
-        } else {
-            "Function"
-        };
-        self.check_name(&func.sig.ident, context);
-        syn::visit::visit_item_fn(self, func);
+    words
+}
+
+fn leading_underscores(name: &str) -> &str {
+    &name[..name.len() - name.trim_start_matches('_').len()]
+}
+
+fn to_snake_case(name: &str) -> String {
+    let words: Vec<String> = split_words(name)
+        .iter()
+        .map(|word| word.to_lowercase())
+        .collect();
+    format!("{}{}", leading_underscores(name), words.join("_"))
+}
+
+fn to_screaming_snake_case(name: &str) -> String {
+    to_snake_case(name).to_uppercase()
+}
+
+fn to_upper_camel_case(name: &str) -> String {
+    let words: String = split_words(name)
+        .iter()
+        .map(|word| {
+            let mut chars = word.chars();
+            match chars.next() {
+                Some(first) => first
+                    .to_uppercase()
+                    .chain(chars.flat_map(char::to_lowercase))
+                    .collect(),
+                None => String::new(),
+            }
+        })
+        .collect();
+    format!("{}{}", leading_underscores(name), words)
+}
+
+#[derive(Clone, Copy)]
+enum CaseStyle {
+    Snake,
+    UpperCamel,
+    ScreamingSnake,
+}
+
+impl CaseStyle {
+    fn for_kind(kind: BindingKind) -> Self {
+        match kind {
+            BindingKind::Type | BindingKind::Variant | BindingKind::Generic => {
+                CaseStyle::UpperCamel
+            }
+            BindingKind::Constant => CaseStyle::ScreamingSnake,
+            _ => CaseStyle::Snake,
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            CaseStyle::Snake => "snake_case",
+            CaseStyle::UpperCamel => "UpperCamelCase",
+            CaseStyle::ScreamingSnake => "SCREAMING_SNAKE_CASE",
+        }
+    }
+
+    fn matches(self, name: &str) -> bool {
+        let bare = name.trim_start_matches('_');
+        match self {
+            CaseStyle::Snake => !bare.chars().any(char::is_uppercase),
+            CaseStyle::ScreamingSnake => !bare.chars().any(char::is_lowercase),
+            CaseStyle::UpperCamel => {
+                bare.starts_with(|c: char| c.is_uppercase()) && !bare.contains('_')
+            }
+        }
     }
+
+    fn convert(self, name: &str) -> String {
+        match self {
+            CaseStyle::Snake => to_snake_case(name),
+            CaseStyle::UpperCamel => to_upper_camel_case(name),
+            CaseStyle::ScreamingSnake => to_screaming_snake_case(name),
+        }
+    }
+}
+
+/// 把 snake_case 名字转换成该类条目应有的大小写风格
+pub(crate) fn conventional_case(kind: BindingKind, snake_name: &str) -> String {
+    CaseStyle::for_kind(kind).convert(snake_name)
 }
 
-#[allow(dead_code)]
-struct SingleLetterVisitor {
+/// `get_` 后面跟这些词时不是简单的 getter，标准库也这么用
+const GETTER_EXCEPTIONS: &[&str] = &["mut", "unchecked", "unchecked_mut", "ref", "pin_mut"];
+
+/// 返回这些类型说明发生了分配，属于 `to_` 的范畴
+const OWNED_TYPES: &[&str] = &[
+    "String", "Vec", "Box", "PathBuf", "OsString", "HashMap", "BTreeMap", "HashSet", "BTreeSet",
+];
+
+struct NamingConventionVisitor {
     file_path: std::path::PathBuf,
     issues: Vec<CodeIssue>,
     lang: String,
+    /// 本文件里实现了 Copy 的类型，它们的 as_ 方法按值接收 self 是惯例
+    copy_types: HashSet<String>,
 }
 
-impl SingleLetterVisitor {
+impl NamingConventionVisitor {
     fn new(file_path: std::path::PathBuf, lang: &str) -> Self {
         Self {
             file_path,
             issues: Vec::new(),
             lang: lang.to_string(),
+            copy_types: HashSet::new(),
         }
     }
+
+    fn push_issue(&mut self, line: usize, column: usize, message: String) {
+        self.issues.push(rule_issue(
+            &self.file_path,
+            (line, column),
+            "naming-convention",
+            message,
+            Severity::Mild,
+        ));
+    }
+
+    fn check_bindings(&mut self, bindings: &[Binding]) {
+        // 拼音和中文标识符交给 pinyin 规则
+        for binding in bindings.iter().filter(|binding| binding.name.is_ascii()) {
+            let name = &binding.name;
+            let kind = binding.kind.label(&self.lang);
+            let style = CaseStyle::for_kind(binding.kind);
+
+            // 常量泛型参数用 SCREAMING_SNAKE_CASE 也是惯例
+            let generic_const =
+                binding.kind == BindingKind::Generic && CaseStyle::ScreamingSnake.matches(name);
+
+            if !style.matches(name) && !generic_const {
+                let fixed = style.convert(name);
+                let message = if self.lang == "zh-CN" {
+                    format!(
+                        "{} '{}' 不符合 Rust 的 {} 惯例，应该叫 `{}`",
+                        kind,
+                        name,
+                        style.label(),
+                        fixed
+                    )
+                } else {
+                    format!(
+                        "{} '{}' ignores Rust's {} convention - call it `{}`",
+                        kind,
+                        name,
+                        style.label(),
+                        fixed
+                    )
+                };
+                self.push_issue(binding.line, binding.column, message);
+                continue;
+            }
+
+            // 缩写词在 UpperCamelCase 里算一个单词：HttpServer 而不是 HTTPServer
+            let shouting = matches!(style, CaseStyle::UpperCamel)
+                && !generic_const
+                && split_words(name)
+                    .iter()
+                    .any(|word| word.len() > 1 && !word.chars().any(char::is_lowercase));
+            if shouting {
+                let fixed = to_upper_camel_case(name);
+                let message = if self.lang == "zh-CN" {
+                    format!(
+                        "{} '{}' 里的缩写词在大喊大叫，Rust 里缩写也算一个单词：`{}`",
+                        kind, name, fixed
+                    )
+                } else {
+                    format!(
+                        "{} '{}' is shouting its acronym - Rust treats acronyms as one word: `{}`",
+                        kind, name, fixed
+                    )
+                };
+                self.push_issue(binding.line, binding.column, message);
+            }
+        }
+    }
+
+    /// 方法名和签名的约定：getter 不加 get_，as_/to_/into_ 要和所有权对得上
+    fn check_method(&mut self, sig: &Signature, self_is_copy: bool) {
+        let Some(receiver) = sig.receiver() else {
+            return;
+        };
+        let name = sig.ident.to_string();
+        let borrowed = receiver.reference.is_some();
+        let extra_params = sig.inputs.len() - 1;
+        let output = match &sig.output {
+            ReturnType::Default => None,
+            ReturnType::Type(_, ty) => Some(&**ty),
+        };
+        let returns_ref = matches!(output, Some(Type::Reference(_)));
+        let returns_owned = matches!(output, Some(Type::Path(path))
+            if path.path.segments.last().is_some_and(|s| OWNED_TYPES.contains(&s.ident.to_string().as_str())));
+
+        let zh = self.lang == "zh-CN";
+        let message = if let Some(field) = name.strip_prefix("get_") {
+            let is_getter = borrowed
+                && receiver.mutability.is_none()
+                && extra_params == 0
+                && output.is_some()
+                && !GETTER_EXCEPTIONS.contains(&field);
+            if !is_getter {
+                return;
+            }
+            if zh {
+                format!(
+                    "getter '{}' 不需要 get_ 前缀，Rust API 指南建议直接叫 `{}`",
+                    name, field
+                )
+            } else {
+                format!(
+                    "Getter '{}' doesn't need a get_ prefix - the API guidelines say just `{}`",
+                    name, field
+                )
+            }
+        } else if let Some(rest) = name.strip_prefix("as_") {
+            // NonNull::as_ptr(self) 这样的 Copy 类型按值接收 self 和借用一样便宜
+            if !borrowed && !self_is_copy {
+                if zh {
+                    format!(
+                        "'{}' 按值拿走了 self，这是 into_ 的活：`into_{}`",
+                        name, rest
+                    )
+                } else {
+                    format!(
+                        "'{}' takes self by value - that's an into_ conversion: `into_{}`",
+                        name, rest
+                    )
+                }
+            } else if returns_owned {
+                if zh {
+                    format!(
+                        "'{}' 返回新分配的值，as_ 应该是免费的借用，改叫 `to_{}`",
+                        name, rest
+                    )
+                } else {
+                    format!(
+                        "'{}' allocates a new value, but as_ promises a free borrow - call it `to_{}`",
+                        name, rest
+                    )
+                }
+            } else {
+                return;
+            }
+        } else if let Some(rest) = name.strip_prefix("to_") {
+            if !returns_ref {
+                return;
+            }
+            if zh {
+                format!("'{}' 返回的是借用，这是 as_ 的约定：`as_{}`", name, rest)
+            } else {
+                format!(
+                    "'{}' returns a borrow - that's an as_ conversion: `as_{}`",
+                    name, rest
+                )
+            }
+        } else if let Some(rest) = name.strip_prefix("into_") {
+            if !borrowed {
+                return;
+            }
+            if zh {
+                format!(
+                    "'{}' 只借用了 self，into_ 应该消耗 self，改成按值接收或者叫 `to_{}`",
+                    name, rest
+                )
+            } else {
+                format!(
+                    "'{}' only borrows self, but into_ should consume it - take self by value or call it `to_{}`",
+                    name, rest
+                )
+            }
+        } else {
+            return;
+        };
+
+        let (line, column) = get_position(&sig.ident);
+        self.push_issue(line, column, message);
+    }
 }
 
-impl<'ast> Visit<'ast> for SingleLetterVisitor {
-    fn visit_pat_ident(&mut self, pat_ident: &'ast syn::PatIdent) {
-        let name = pat_ident.ident.to_string();
+impl<'ast> Visit<'ast> for NamingConventionVisitor {
+    fn visit_item_impl(&mut self, item: &'ast ItemImpl) {
+        // trait 实现的方法名由 trait 决定
+        if item.trait_.is_none() {
+            let self_is_copy =
+                type_name(&item.self_ty).is_some_and(|name| self.copy_types.contains(&name));
+            for impl_item in &item.items {
+                if let ImplItem::Fn(method) = impl_item {
+                    self.check_method(&method.sig, self_is_copy);
+                }
+            }
+        }
+        syn::visit::visit_item_impl(self, item);
+    }
+
+    fn visit_item_trait(&mut self, item: &'ast ItemTrait) {
+        for trait_item in &item.items {
+            if let TraitItem::Fn(method) = trait_item {
+                self.check_method(&method.sig, false);
+            }
+        }
+        syn::visit::visit_item_trait(self, item);
+    }
+}
 
-        // Exclude common single-letter variables (like loop counters i, j, k)
-        if name.len() == 1 && !matches!(name.as_str(), "i" | "j" | "k" | "x" | "y" | "z") {
-            let messages = [
-                format!("单字母变量 '{name}'？你是在写数学公式还是在折磨读代码的人？"),
-                format!("变量 '{name}'？这是变量名还是你键盘坏了？"),
-                format!("用 '{name}' 做变量名，你可能需要一本《如何给变量起名》的书"),
-                format!("单字母变量 '{name}'：让代码比古埃及象形文字还难懂"),
-                format!("变量 '{name}' 的信息量约等于一个句号"),
-            ];
+/// derive(Copy) 或者 impl Copy 的类型名
+#[derive(Default)]
+struct CopyTypes(HashSet<String>);
 
-            let message_index =
-                (self.issues.len() + name.len() + name.chars().next().unwrap_or('a') as usize)
-                    % messages.len();
+impl<'ast> Visit<'ast> for CopyTypes {
+    fn visit_item_struct(&mut self, item: &'ast ItemStruct) {
+        if derived_traits(&item.attrs).contains("Copy") {
+            self.0.insert(item.ident.to_string());
+        }
+    }
 
-            self.issues.push(CodeIssue {
-                file_path: self.file_path.clone(),
-                line: self.issues.len() + 10, // 不同的行号范围
-                column: (name.len() % 5) + 1,
-                rule_name: "single-letter-variable".to_string(),
-                message: messages[message_index].clone(),
-                severity: Severity::Mild,
-                roast_level: RoastLevel::Gentle,
-            });
+    fn visit_item_enum(&mut self, item: &'ast ItemEnum) {
+        if derived_traits(&item.attrs).contains("Copy") {
+            self.0.insert(item.ident.to_string());
         }
+    }
 
-        syn::visit::visit_pat_ident(self, pat_ident);
+    fn visit_item_impl(&mut self, item: &'ast ItemImpl) {
+        let is_copy = item
+            .trait_
+            .as_ref()
+            .is_some_and(|(_, path, _)| path.segments.last().is_some_and(|s| s.ident == "Copy"));
+        if let Some(name) = type_name(&item.self_ty).filter(|_| is_copy) {
+            self.0.insert(name);
+        }
+        syn::visit::visit_item_impl(self, item);
     }
 }
@@ -1,13 +1,20 @@
+use std::collections::{HashMap, HashSet};
 use std::path::Path;
 use syn::{
-    visit::Visit, Expr, ExprForLoop, ExprMatch, ExprMethodCall, File, Pat, PatIdent, Type, TypePath,
+    parse::ParseStream, spanned::Spanned, visit::Visit, BinOp, Block, Expr, ExprAssign, ExprBinary,
+    ExprForLoop, ExprLit, ExprLoop, ExprMatch, ExprMethodCall, ExprWhile, File, FnArg,
+    GenericArgument, ImplItemFn, ItemFn, ItemImpl, Lit, Local, Macro, Pat, PatIdent, PathArguments,
+    RangeLimits, Signature, Stmt, Token, TraitItemFn, Type, TypePath,
 };
 
-use crate::analyzer::{CodeIssue, RoastLevel, Severity};
+use crate::analyzer::{CodeContext, CodeIssue, RoastLevel, Severity};
+use crate::rules::common::{is_public, rule_issue};
+use crate::rules::macro_args::{parse_macro_args, visit_macro_args};
 use crate::rules::Rule;
-use crate::utils::get_position;
+use crate::utils::{get_position, get_source_text};
 
-/// 检测到处用 String 而不用 &str
+/// 检测真正浪费的 String 分配：&String 参数、只读的 String 参数、对 String 再 to_string()、
+/// 单参数的 format!、as_str().to_string() 来回转换、循环里的 + 拼接和 push_str(&format!(..))
 pub struct StringAbuseRule;
 
 impl Rule for StringAbuseRule {
@@ -22,25 +29,14 @@ impl Rule for StringAbuseRule {
         content: &str,
         lang: &str,
     ) -> Vec<CodeIssue> {
-        let mut visitor = StringAbuseVisitor::new(file_path.to_path_buf(), lang);
+        let mut visitor = StringAbuseVisitor::new(file_path.to_path_buf(), content, lang);
         visitor.visit_file(syntax_tree);
-
-        // 检查内容中的 String 使用模式
-        let string_new_count = content.matches("String::new()").count();
-        let string_from_count = content.matches("String::from(").count();
-        let to_string_count = content.matches(".to_string()").count();
-
-        if string_new_count + string_from_count + to_string_count > 5 {
-            visitor.add_excessive_string_conversion_issue(
-                string_new_count + string_from_count + to_string_count,
-            );
-        }
-
         visitor.issues
     }
 }
 
-/// 检测不必要的 Vec 分配
+/// 检测真正浪费的 Vec 用法：Vec::new() 后紧跟已知次数的 push、&Vec 参数、collect 之后马上 iter()/len()、
+/// len() == 0、尺寸固定的 Vec<Vec<T>>、循环里对 Vec 反复 contains
 pub struct VecAbuseRule;
 
 impl Rule for VecAbuseRule {
@@ -55,23 +51,13 @@ impl Rule for VecAbuseRule {
         content: &str,
         lang: &str,
     ) -> Vec<CodeIssue> {
-        let mut visitor = VecAbuseVisitor::new(file_path.to_path_buf(), lang);
+        let mut visitor = VecAbuseVisitor::new(file_path.to_path_buf(), content, lang);
         visitor.visit_file(syntax_tree);
-
-        // 检查内容中的 Vec 使用模式
-        let vec_new_count = content.matches("Vec::new()").count();
-        let _vec_with_capacity_count = content.matches("Vec::with_capacity(").count();
-        let _vec_macro_count = content.matches("vec![").count();
-
-        if vec_new_count > 3 {
-            visitor.add_excessive_vec_allocation_issue(vec_new_count);
-        }
-
         visitor.issues
     }
 }
 
-/// 检测用循环代替迭代器的情况
+/// 检测能换成 collect / sum / find / enumerate / flat_map 的循环
 pub struct IteratorAbuseRule;
 
 impl Rule for IteratorAbuseRule {
@@ -83,16 +69,16 @@ impl Rule for IteratorAbuseRule {
         &self,
         file_path: &Path,
         syntax_tree: &File,
-        _content: &str,
+        content: &str,
         lang: &str,
     ) -> Vec<CodeIssue> {
-        let mut visitor = IteratorAbuseVisitor::new(file_path.to_path_buf(), lang);
+        let mut visitor = IteratorAbuseVisitor::new(file_path.to_path_buf(), content, lang);
         visitor.visit_file(syntax_tree);
         visitor.issues
     }
 }
 
-/// 检测可以用 if let 的复杂 match
+/// 检测能改写成 if let / let else / Option 组合子 / ? 的 match
 pub struct MatchAbuseRule;
 
 impl Rule for MatchAbuseRule {
@@ -104,10 +90,10 @@ impl Rule for MatchAbuseRule {
         &self,
         file_path: &Path,
         syntax_tree: &File,
-        _content: &str,
+        content: &str,
         lang: &str,
     ) -> Vec<CodeIssue> {
-        let mut visitor = MatchAbuseVisitor::new(file_path.to_path_buf(), lang);
+        let mut visitor = MatchAbuseVisitor::new(file_path.to_path_buf(), content, lang);
         visitor.visit_file(syntax_tree);
         visitor.issues
     }
@@ -117,374 +103,2515 @@ impl Rule for MatchAbuseRule {
 // Visitor 实现
 // ============================================================================
 
-struct StringAbuseVisitor {
+struct StringAbuseVisitor<'a> {
     file_path: std::path::PathBuf,
+    content: &'a str,
     issues: Vec<CodeIssue>,
     lang: String,
+    /// 每个函数一层，记录哪些变量已知是 String / &String
+    scopes: Vec<StringScope>,
+    loop_depth: usize,
+    in_trait_impl: bool,
+}
+
+#[derive(Default)]
+struct StringScope {
+    owned: HashSet<String>,
+    borrowed: HashSet<String>,
 }
 
-impl StringAbuseVisitor {
-    fn new(file_path: std::path::PathBuf, lang: &str) -> Self {
+/// 只借用 String 的方法，调用它们不需要所有权
+const READ_ONLY_STR_METHODS: &[&str] = &[
+    "len",
+    "is_empty",
+    "as_str",
+    "as_bytes",
+    "as_ref",
+    "chars",
+    "char_indices",
+    "bytes",
+    "lines",
+    "split",
+    "splitn",
+    "rsplit",
+    "split_whitespace",
+    "split_once",
+    "rsplit_once",
+    "trim",
+    "trim_start",
+    "trim_end",
+    "strip_prefix",
+    "strip_suffix",
+    "starts_with",
+    "ends_with",
+    "contains",
+    "find",
+    "rfind",
+    "matches",
+    "parse",
+    "get",
+    "replace",
+    "to_lowercase",
+    "to_uppercase",
+    "to_ascii_lowercase",
+    "to_ascii_uppercase",
+    "eq_ignore_ascii_case",
+    "is_char_boundary",
+];
+
+/// 参数按引用取用的格式化类宏
+const FORMAT_MACROS: &[&str] = &[
+    "format",
+    "format_args",
+    "print",
+    "println",
+    "eprint",
+    "eprintln",
+    "write",
+    "writeln",
+    "panic",
+    "assert",
+    "assert_eq",
+    "assert_ne",
+    "debug_assert",
+    "debug_assert_eq",
+    "debug_assert_ne",
+    "trace",
+    "debug",
+    "info",
+    "warn",
+    "error",
+];
+
+impl<'a> StringAbuseVisitor<'a> {
+    fn new(file_path: std::path::PathBuf, content: &'a str, lang: &str) -> Self {
         Self {
             file_path,
+            content,
             issues: Vec::new(),
             lang: lang.to_string(),
+            scopes: Vec::new(),
+            loop_depth: 0,
+            in_trait_impl: false,
+        }
+    }
+
+    fn text<T: Spanned>(&self, node: &T) -> String {
+        get_source_text(self.content, node).unwrap_or_else(|| "..".to_string())
+    }
+
+    fn is_owned_string(&self, expr: &Expr) -> bool {
+        let Expr::Path(path) = peel(expr) else {
+            return false;
+        };
+        path.path.get_ident().is_some_and(|ident| {
+            self.scopes.last().is_some_and(|scope| {
+                let name = ident.to_string();
+                scope.owned.contains(&name) || scope.borrowed.contains(&name)
+            })
+        })
+    }
+
+    /// 表达式的结果一定是 String
+    fn produces_string(&self, expr: &Expr) -> bool {
+        match peel(expr) {
+            Expr::Macro(mac) => mac.mac.path.is_ident("format"),
+            Expr::Call(call) => matches!(call.func.as_ref(), Expr::Path(func)
+                if func.path.segments.len() == 2
+                    && func.path.segments[0].ident == "String"
+                    && matches!(func.path.segments[1].ident.to_string().as_str(), "new" | "from" | "with_capacity")),
+            Expr::MethodCall(call) => match call.method.to_string().as_str() {
+                "to_string" | "to_uppercase" | "to_lowercase" | "repeat" => true,
+                "to_owned" | "into" => {
+                    matches!(peel(&call.receiver), Expr::Lit(lit) if matches!(lit.lit, Lit::Str(_)))
+                }
+                "clone" => self.is_owned_string(&call.receiver),
+                _ => false,
+            },
+            Expr::Binary(binary) if matches!(binary.op, BinOp::Add(_)) => {
+                self.produces_string(&binary.left) || self.is_owned_string(&binary.left)
+            }
+            _ => false,
+        }
+    }
+
+    /// 进入一个函数：登记参数类型，检查 &String 参数和只读的 String 参数
+    fn check_fn(&mut self, sig: &Signature, block: &Block, public: bool, signature_fixed: bool) {
+        let mut scope = StringScope::default();
+
+        for arg in &sig.inputs {
+            let FnArg::Typed(pat_type) = arg else {
+                continue;
+            };
+            let Pat::Ident(PatIdent {
+                ident, mutability, ..
+            }) = pat_type.pat.as_ref()
+            else {
+                continue;
+            };
+            let name = ident.to_string();
+
+            if is_string_type(&pat_type.ty) {
+                scope.owned.insert(name.clone());
+                if !signature_fixed && mutability.is_none() && is_only_read(block, &name) {
+                    self.report_read_only_param(&pat_type.ty, &sig.ident, &name, public);
+                }
+            } else if let Type::Reference(reference) = pat_type.ty.as_ref() {
+                if reference.mutability.is_none() && is_string_type(&reference.elem) {
+                    scope.borrowed.insert(name.clone());
+                    // 公开函数的 &String 由 owned-ref-param 负责
+                    if !signature_fixed && !public {
+                        self.report_string_ref_param(&pat_type.ty, &sig.ident, &name);
+                    }
+                }
+            }
         }
+
+        self.scopes.push(scope);
+        let outer_loop_depth = std::mem::replace(&mut self.loop_depth, 0);
+        self.visit_block(block);
+        self.loop_depth = outer_loop_depth;
+        self.scopes.pop();
     }
 
-    fn add_excessive_string_conversion_issue(&mut self, count: usize) {
+    fn report_string_ref_param(&mut self, ty: &Type, fn_name: &syn::Ident, name: &str) {
         let messages = if self.lang == "zh-CN" {
             vec![
-                format!("{} 次 String 转换？你是在开字符串工厂吗？", count),
-                format!("这么多 String 分配，内存都要哭了",),
-                format!("{} 个 String 转换，考虑用 &str 吧", count),
-                format!("String 用得比我换衣服还频繁",),
-                format!("到处都是 String::from()，性能在哭泣",),
+                format!("{fn_name}() 的参数 {name}: &String 多绕了一层，改成 &str，调用者传字面量也不用先分配"),
+                format!("&String 参数 {name} 只能接 String，&str 什么都能接，{fn_name}() 用 &str 就够了"),
             ]
         } else {
             vec![
-                format!(
-                    "{} String conversions? Are you running a string factory?",
-                    count
-                ),
-                format!("So many String allocations, memory is crying"),
-                format!("{} String conversions - consider using &str", count),
-                format!("You use String more than I change clothes"),
-                format!("String::from() everywhere - performance is weeping"),
+                format!("{fn_name}() takes {name}: &String - take &str so callers can pass literals without allocating"),
+                format!("&String param {name} only accepts a String, &str accepts everything - {fn_name}() only needs &str"),
             ]
         };
-
-        self.issues.push(CodeIssue {
-            file_path: self.file_path.clone(),
-            line: 1,
-            column: 1,
-            rule_name: "string-abuse".to_string(),
-            message: messages[count % messages.len()].clone(),
-            severity: Severity::Spicy,
-            roast_level: RoastLevel::Sarcastic,
-        });
+        self.issues.push(rule_issue(
+            &self.file_path,
+            get_position(ty),
+            "string-abuse",
+            messages[self.issues.len() % messages.len()].clone(),
+            Severity::Mild,
+        ));
     }
 
-    fn check_string_parameter(&mut self, ty: &Type) {
-        if let Type::Path(TypePath { path, .. }) = ty {
-            if let Some(segment) = path.segments.last() {
-                if segment.ident == "String" {
-                    let messages = if self.lang == "zh-CN" {
-                        vec![
-                            "参数用 String？考虑用 &str 吧",
-                            "String 参数会强制调用者分配内存",
-                            "这个 String 参数设计得不太优雅",
-                            "用 &str 参数会更灵活",
-                        ]
-                    } else {
-                        vec![
-                            "String parameter? Consider using &str",
-                            "String parameter forces caller to allocate",
-                            "This String parameter design isn't elegant",
-                            "&str parameter would be more flexible",
-                        ]
-                    };
+    fn report_read_only_param(
+        &mut self,
+        ty: &Type,
+        fn_name: &syn::Ident,
+        name: &str,
+        public: bool,
+    ) {
+        let messages = if self.lang == "zh-CN" {
+            vec![
+                format!("{fn_name}() 拿走了 String 参数 {name} 的所有权却只读它，改成 &str，调用者就不用为你 clone"),
+                format!("{name}: String 在 {fn_name}() 里从没被移走或修改，&str 就够了"),
+            ]
+        } else {
+            vec![
+                format!("{fn_name}() takes ownership of String param {name} but only reads it - take &str and spare callers the clone"),
+                format!("{name}: String is never moved or modified in {fn_name}(), &str is enough"),
+            ]
+        };
+        let severity = if public {
+            Severity::Spicy
+        } else {
+            Severity::Mild
+        };
+        self.issues.push(rule_issue(
+            &self.file_path,
+            get_position(ty),
+            "string-abuse",
+            messages[self.issues.len() % messages.len()].clone(),
+            severity,
+        ));
+    }
 
-                    let (line, column) = get_position(ty);
-                    self.issues.push(CodeIssue {
-                        file_path: self.file_path.clone(),
-                        line,
-                        column,
-                        rule_name: "string-abuse".to_string(),
-                        message: messages[self.issues.len() % messages.len()].to_string(),
-                        severity: Severity::Mild,
-                        roast_level: RoastLevel::Gentle,
-                    });
+    /// format!("{}", x) 只是为了转成字符串
+    fn check_trivial_format(&mut self, mac: &Macro) {
+        if !mac.path.is_ident("format") {
+            return;
+        }
+        let args = parse_macro_args(mac);
+        let Some(Expr::Lit(ExprLit {
+            lit: Lit::Str(template),
+            ..
+        })) = args.first()
+        else {
+            return;
+        };
+        let template = template.value();
+        let value = match args.get(1..) {
+            Some([value]) if template == "{}" => match peel(value) {
+                Expr::Binary(_) | Expr::Cast(_) | Expr::Unary(_) | Expr::Range(_) => {
+                    format!("({})", self.text(value))
                 }
+                _ => self.text(value),
+            },
+            // format!("{name}") 内联参数
+            Some([])
+                if template.starts_with('{')
+                    && template.ends_with('}')
+                    && template[1..template.len() - 1]
+                        .chars()
+                        .all(|c| c.is_alphanumeric() || c == '_')
+                    && template.len() > 2 =>
+            {
+                template[1..template.len() - 1].to_string()
             }
+            _ => return,
+        };
+
+        let already_string = args.get(1).is_some_and(|value| self.is_owned_string(value))
+            || self.scopes.last().is_some_and(|scope| {
+                scope.owned.contains(&value) || scope.borrowed.contains(&value)
+            });
+        let better = if already_string {
+            format!("{value}.clone()")
+        } else {
+            format!("{value}.to_string()")
+        };
+
+        let messages = if self.lang == "zh-CN" {
+            vec![
+                format!("format!(\"{template}\") 只有一个参数，这就是 {better}，不用走一遍格式化"),
+                format!("用 format! 把 {value} 转成字符串太绕了，直接写 {better}"),
+            ]
+        } else {
+            vec![
+                format!("format!(\"{template}\") with a single argument is just {better} with extra formatting machinery"),
+                format!("format! to stringify {value} is a detour - write {better}"),
+            ]
+        };
+        self.issues.push(rule_issue(
+            &self.file_path,
+            get_position(mac),
+            "string-abuse",
+            messages[self.issues.len() % messages.len()].clone(),
+            Severity::Mild,
+        ));
+    }
+
+    /// push_str(&format!(..)) 和 += &format!(..) 先造一个临时 String 再拷贝
+    fn check_format_temporary(&mut self, position: (usize, usize), target: &Expr, arg: &Expr) {
+        let Expr::Reference(reference) = peel(arg) else {
+            return;
+        };
+        let Expr::Macro(mac) = peel(&reference.expr) else {
+            return;
+        };
+        if !mac.mac.path.is_ident("format") {
+            return;
         }
+        let target = self.text(target);
+        let args = match self.text(&mac.mac.tokens) {
+            args if args.chars().count() <= 40 => args,
+            _ => "..".to_string(),
+        };
+
+        let messages = if self.lang == "zh-CN" {
+            vec![
+                format!("push_str(&format!(..)) 先分配一个临时 String 再拷贝进去，用 write!({target}, {args}) 直接写进 {target}（需要 use std::fmt::Write）"),
+                format!("这个 format! 的结果只活了一瞬间，write!({target}, ..) 可以省掉这次分配"),
+            ]
+        } else {
+            vec![
+                format!("push_str(&format!(..)) allocates a temporary String just to copy it, write!({target}, {args}) formats straight into {target} (use std::fmt::Write)"),
+                format!("this format! result lives for an instant - write!({target}, ..) skips the allocation"),
+            ]
+        };
+        self.issues.push(rule_issue(
+            &self.file_path,
+            position,
+            "string-abuse",
+            messages[self.issues.len() % messages.len()].clone(),
+            Severity::Mild,
+        ));
     }
-}
 
-impl<'ast> Visit<'ast> for StringAbuseVisitor {
-    fn visit_expr_method_call(&mut self, method_call: &'ast ExprMethodCall) {
-        if method_call.method == "to_string" {
-            let messages = if self.lang == "zh-CN" {
+    /// 循环里 s = s + .. / s = s.clone() + .. / s = format!("{}{}", s, ..)
+    fn check_loop_concat(&mut self, assign: &ExprAssign) {
+        let Expr::Path(target) = peel(&assign.left) else {
+            return;
+        };
+        let Some(target) = target.path.get_ident().map(|ident| ident.to_string()) else {
+            return;
+        };
+        let is_target =
+            |expr: &Expr| matches!(peel(expr), Expr::Path(path) if path.path.is_ident(&target));
+
+        let (quadratic, via) = match peel(&assign.right) {
+            Expr::Binary(binary) if matches!(binary.op, BinOp::Add(_)) => {
+                let mut left = binary.left.as_ref();
+                while let Expr::Binary(inner) = peel(left) {
+                    if !matches!(inner.op, BinOp::Add(_)) {
+                        break;
+                    }
+                    left = &inner.left;
+                }
+                let string_like = self.is_owned_string(left)
+                    || has_str_operand(&assign.right)
+                    || self.produces_string(left);
+                match peel(left) {
+                    Expr::MethodCall(call)
+                        if call.method == "clone" && is_target(&call.receiver) && string_like =>
+                    {
+                        (true, "+")
+                    }
+                    expr if is_target(expr) && string_like => (false, "+"),
+                    _ => return,
+                }
+            }
+            Expr::Macro(mac) if mac.mac.path.is_ident("format") => {
+                let args = parse_macro_args(&mac.mac);
+                let inline = matches!(args.first(), Some(Expr::Lit(ExprLit { lit: Lit::Str(template), .. }))
+                    if template.value().contains(&format!("{{{target}}}")));
+                if !inline && !args.iter().skip(1).any(is_target) {
+                    return;
+                }
+                (true, "format!")
+            }
+            _ => return,
+        };
+
+        let messages = if self.lang == "zh-CN" {
+            if quadratic {
                 vec![
-                    "又见 to_string()，真的需要分配内存吗？",
-                    "to_string() 调用，考虑是否必要",
-                    "这个 to_string() 可能可以避免",
-                    "to_string() 会分配内存，确定需要吗？",
+                    format!("循环里用 {via} 重新拼出 {target}，每一轮都把已有内容整个拷贝一遍，总共是 O(n²)；改成 {target}.push_str(..) 或 write!({target}, ..)"),
+                    format!("{target} 越长这个循环越慢：{via} 每次都造一个新 String，用 push_str 往同一个缓冲区里追加"),
                 ]
             } else {
                 vec![
-                    "Another to_string() - do you really need to allocate?",
-                    "to_string() call - consider if necessary",
-                    "This to_string() might be avoidable",
-                    "to_string() allocates memory - sure you need it?",
+                    format!("循环里写 {target} = {target} + ..，换成 {target}.push_str(..) 或 write!({target}, ..) 更直接，循环前 String::with_capacity 预留空间"),
+                    format!("{target} = {target} + .. 每轮都把 {target} 移进移出，push_str 意图更清楚"),
                 ]
-            };
+            }
+        } else if quadratic {
+            vec![
+                format!("rebuilding {target} with {via} inside a loop copies everything built so far on every pass, O(n²) total - use {target}.push_str(..) or write!({target}, ..)"),
+                format!("this loop gets slower as {target} grows: {via} makes a new String each time, append to one buffer with push_str"),
+            ]
+        } else {
+            vec![
+                format!("{target} = {target} + .. in a loop - {target}.push_str(..) or write!({target}, ..) says what you mean, and String::with_capacity before the loop avoids regrowth"),
+                format!("{target} = {target} + .. moves {target} in and out every iteration, push_str is clearer"),
+            ]
+        };
+        let severity = if quadratic {
+            Severity::Spicy
+        } else {
+            Severity::Mild
+        };
+        self.issues.push(rule_issue(
+            &self.file_path,
+            get_position(assign),
+            "string-abuse",
+            messages[self.issues.len() % messages.len()].clone(),
+            severity,
+        ));
+    }
 
-            let (line, column) = get_position(method_call);
-            self.issues.push(CodeIssue {
-                file_path: self.file_path.clone(),
-                line,
-                column,
-                rule_name: "string-abuse".to_string(),
-                message: messages[self.issues.len() % messages.len()].to_string(),
-                severity: Severity::Mild,
-                roast_level: RoastLevel::Gentle,
-            });
+    fn bind_local(&mut self, local: &Local) {
+        let (pat, ty) = match &local.pat {
+            Pat::Type(pat_type) => (pat_type.pat.as_ref(), Some(pat_type.ty.as_ref())),
+            pat => (pat, None),
+        };
+        let Pat::Ident(PatIdent { ident, .. }) = pat else {
+            return;
+        };
+        let owned = match ty {
+            Some(ty) => is_string_type(ty),
+            None => local
+                .init
+                .as_ref()
+                .is_some_and(|init| init.diverge.is_none() && self.produces_string(&init.expr)),
+        };
+        let borrowed =
+            ty.is_some_and(|ty| matches!(ty, Type::Reference(r) if is_string_type(&r.elem)));
+
+        let Some(scope) = self.scopes.last_mut() else {
+            return;
+        };
+        let name = ident.to_string();
+        // 同名重新绑定会遮蔽之前的变量
+        scope.owned.remove(&name);
+        scope.borrowed.remove(&name);
+        if owned {
+            scope.owned.insert(name);
+        } else if borrowed {
+            scope.borrowed.insert(name);
         }
-        syn::visit::visit_expr_method_call(self, method_call);
     }
+}
 
-    fn visit_type(&mut self, ty: &'ast Type) {
-        self.check_string_parameter(ty);
-        syn::visit::visit_type(self, ty);
+impl<'ast> Visit<'ast> for StringAbuseVisitor<'_> {
+    fn visit_macro(&mut self, mac: &'ast Macro) {
+        self.check_trivial_format(mac);
+        visit_macro_args(self, mac);
     }
-}
 
-// ============================================================================
-// Vec 滥用检测
-// ============================================================================
+    fn visit_item_fn(&mut self, item_fn: &'ast ItemFn) {
+        let public = is_public(&item_fn.vis);
+        self.check_fn(&item_fn.sig, &item_fn.block, public, false);
+    }
 
-struct VecAbuseVisitor {
-    file_path: std::path::PathBuf,
-    issues: Vec<CodeIssue>,
-    lang: String,
-}
+    fn visit_item_impl(&mut self, item_impl: &'ast ItemImpl) {
+        let outer = std::mem::replace(&mut self.in_trait_impl, item_impl.trait_.is_some());
+        syn::visit::visit_item_impl(self, item_impl);
+        self.in_trait_impl = outer;
+    }
 
-impl VecAbuseVisitor {
-    fn new(file_path: std::path::PathBuf, lang: &str) -> Self {
-        Self {
-            file_path,
-            issues: Vec::new(),
-            lang: lang.to_string(),
-        }
+    fn visit_impl_item_fn(&mut self, impl_fn: &'ast ImplItemFn) {
+        let public = is_public(&impl_fn.vis);
+        // trait 实现的签名由 trait 决定，改不了
+        let signature_fixed = self.in_trait_impl;
+        self.check_fn(&impl_fn.sig, &impl_fn.block, public, signature_fixed);
     }
 
-    fn add_excessive_vec_allocation_issue(&mut self, count: usize) {
-        let messages = if self.lang == "zh-CN" {
-            vec![
-                format!("{} 个 Vec::new()？你在收集什么？", count),
-                format!("这么多 Vec 分配，考虑用数组或切片",),
-                format!("{} 次 Vec 分配，内存分配器很忙", count),
-                format!("Vec 用得这么多，确定都需要动态数组吗？",),
-            ]
-        } else {
-            vec![
-                format!("{} Vec::new()s? What are you collecting?", count),
-                format!("So many Vec allocations - consider arrays or slices"),
-                format!("{} Vec allocations - memory allocator is busy", count),
-                format!("So many Vecs - sure you need all these dynamic arrays?"),
-            ]
-        };
+    fn visit_trait_item_fn(&mut self, trait_fn: &'ast TraitItemFn) {
+        if let Some(block) = &trait_fn.default {
+            self.check_fn(&trait_fn.sig, block, true, true);
+        }
+    }
 
-        self.issues.push(CodeIssue {
-            file_path: self.file_path.clone(),
-            line: 1,
-            column: 1,
-            rule_name: "vec-abuse".to_string(),
-            message: messages[count % messages.len()].clone(),
-            severity: Severity::Mild,
-            roast_level: RoastLevel::Gentle,
-        });
+    fn visit_local(&mut self, local: &'ast Local) {
+        syn::visit::visit_local(self, local);
+        self.bind_local(local);
     }
-}
 
-impl<'ast> Visit<'ast> for VecAbuseVisitor {
     fn visit_expr_method_call(&mut self, method_call: &'ast ExprMethodCall) {
-        // 检测 Vec::new() 调用
-        if let Expr::Path(path_expr) = &*method_call.receiver {
-            if let Some(segment) = path_expr.path.segments.last() {
-                if segment.ident == "Vec" && method_call.method == "new" {
+        let method = method_call.method.to_string();
+        let receiver = peel(&method_call.receiver);
+
+        if method == "to_string" && self.is_owned_string(receiver) {
+            let value = self.text(receiver);
+            let messages = if self.lang == "zh-CN" {
+                vec![
+                    format!("{value} 已经是 String 了，to_string() 又走一遍格式化再分配；要副本就 {value}.clone()，不要副本就直接借用"),
+                    format!("对 String 调 to_string()：{value} 本来就是 String，看看能不能直接移动或者借用"),
+                ]
+            } else {
+                vec![
+                    format!("{value} is already a String, to_string() goes through formatting to copy it - use {value}.clone() if you need a copy, or just borrow it"),
+                    format!("to_string() on a String: {value} already is one, can it be moved or borrowed instead?"),
+                ]
+            };
+            self.issues.push(rule_issue(
+                &self.file_path,
+                get_position(&method_call.method),
+                "string-abuse",
+                messages[self.issues.len() % messages.len()].clone(),
+                Severity::Mild,
+            ));
+        } else if matches!(method.as_str(), "to_string" | "to_owned") {
+            if let Expr::MethodCall(inner) = receiver {
+                // 别的类型的 as_str() 转 String 是正常的类型转换
+                if inner.method == "as_str" && self.is_owned_string(&inner.receiver) {
+                    let value = self.text(&inner.receiver);
                     let messages = if self.lang == "zh-CN" {
                         vec![
-                            "Vec::new() 出现，确定需要动态数组吗？",
-                            "又一个 Vec::new()，考虑用数组",
-                            "Vec::new() 会分配堆内存",
-                            "这个 Vec::new() 可能可以优化",
+                            format!("{value}.as_str().{method}() 先借成 &str 再拷回 String，就是 {value}.clone()"),
+                            format!("as_str().{method}() 绕了一圈又回到 String，直接 clone() 或者干脆借用"),
                         ]
                     } else {
                         vec![
-                            "Vec::new() spotted - sure you need a dynamic array?",
-                            "Another Vec::new() - consider using an array",
-                            "Vec::new() allocates heap memory",
-                            "This Vec::new() might be optimizable",
+                            format!("{value}.as_str().{method}() borrows as &str only to copy back into a String - that's {value}.clone()"),
+                            format!("as_str().{method}() is a round trip back to String, clone() it or just borrow"),
                         ]
                     };
-
-                    let (line, column) = get_position(method_call);
-                    self.issues.push(CodeIssue {
-                        file_path: self.file_path.clone(),
-                        line,
-                        column,
-                        rule_name: "vec-abuse".to_string(),
-                        message: messages[self.issues.len() % messages.len()].to_string(),
-                        severity: Severity::Mild,
-                        roast_level: RoastLevel::Gentle,
-                    });
+                    self.issues.push(rule_issue(
+                        &self.file_path,
+                        get_position(&method_call.method),
+                        "string-abuse",
+                        messages[self.issues.len() % messages.len()].clone(),
+                        Severity::Mild,
+                    ));
                 }
             }
+        } else if method == "push_str" && method_call.args.len() == 1 {
+            self.check_format_temporary(
+                get_position(&method_call.method),
+                &method_call.receiver,
+                &method_call.args[0],
+            );
         }
+
         syn::visit::visit_expr_method_call(self, method_call);
     }
-}
 
-// ============================================================================
-// 迭代器滥用检测
-// ============================================================================
+    fn visit_expr_binary(&mut self, binary: &'ast ExprBinary) {
+        if matches!(binary.op, BinOp::AddAssign(_)) {
+            self.check_format_temporary(get_position(binary), &binary.left, &binary.right);
+        }
+        syn::visit::visit_expr_binary(self, binary);
+    }
 
-struct IteratorAbuseVisitor {
-    file_path: std::path::PathBuf,
-    issues: Vec<CodeIssue>,
-    lang: String,
+    fn visit_expr_assign(&mut self, assign: &'ast ExprAssign) {
+        if self.loop_depth > 0 {
+            self.check_loop_concat(assign);
+        }
+        syn::visit::visit_expr_assign(self, assign);
+    }
+
+    fn visit_expr_for_loop(&mut self, for_loop: &'ast ExprForLoop) {
+        self.visit_expr(&for_loop.expr);
+        self.loop_depth += 1;
+        self.visit_block(&for_loop.body);
+        self.loop_depth -= 1;
+    }
+
+    fn visit_expr_while(&mut self, while_loop: &'ast ExprWhile) {
+        self.loop_depth += 1;
+        syn::visit::visit_expr_while(self, while_loop);
+        self.loop_depth -= 1;
+    }
+
+    fn visit_expr_loop(&mut self, loop_expr: &'ast ExprLoop) {
+        self.loop_depth += 1;
+        syn::visit::visit_expr_loop(self, loop_expr);
+        self.loop_depth -= 1;
+    }
 }
 
-impl IteratorAbuseVisitor {
-    fn new(file_path: std::path::PathBuf, lang: &str) -> Self {
-        Self {
-            file_path,
-            issues: Vec::new(),
-            lang: lang.to_string(),
+fn is_string_type(ty: &Type) -> bool {
+    matches!(ty, Type::Path(TypePath { qself: None, path })
+        if path.segments.last().is_some_and(|s| s.ident == "String" && s.arguments.is_none()))
+}
+
+/// + 链里有字符串字面量或者 &x，说明是在拼字符串而不是做加法
+fn has_str_operand(expr: &Expr) -> bool {
+    match peel(expr) {
+        Expr::Binary(binary) if matches!(binary.op, BinOp::Add(_)) => {
+            has_str_operand(&binary.left) || has_str_operand(&binary.right)
         }
+        Expr::Lit(lit) => matches!(lit.lit, Lit::Str(_)),
+        Expr::Reference(_) => true,
+        _ => false,
     }
+}
 
-    fn check_simple_for_loop(&mut self, for_loop: &ExprForLoop) {
-        // 检测简单的 for 循环，可能可以用迭代器替代
-        if let Pat::Ident(PatIdent { ident, .. }) = for_loop.pat.as_ref() {
-            let _var_name = ident.to_string();
+/// 函数体里对参数 name 的每一次使用都只是借用
+fn is_only_read(block: &Block, name: &str) -> bool {
+    let mut uses = ParamUses {
+        name,
+        reads: 0,
+        other: 0,
+    };
+    uses.visit_block(block);
+    uses.reads > 0 && uses.other == 0
+}
 
-            // 检测常见的可以用迭代器替代的模式
-            let loop_body = format!("{:?}", for_loop.body);
+/// 统计一个 String 参数的用法：借用、调用只读方法、格式化算只读，其他（移走、修改、clone、遮蔽）都不算
+struct ParamUses<'a> {
+    name: &'a str,
+    reads: usize,
+    other: usize,
+}
 
-            // 如果循环体很简单，建议用迭代器
-            if loop_body.lines().count() < 5 {
-                let messages = if self.lang == "zh-CN" {
-                    vec![
-                        format!("简单的 for 循环，考虑用迭代器链"),
-                        format!("这个循环可以用 .iter().for_each() 替代"),
-                        format!("迭代器比传统循环更 Rust 风格"),
-                        format!("考虑用函数式编程风格重写这个循环"),
-                    ]
+impl ParamUses<'_> {
+    fn is_param(&self, expr: &Expr) -> bool {
+        matches!(peel(expr), Expr::Path(path) if path.path.is_ident(self.name))
+    }
+}
+
+impl<'ast> Visit<'ast> for ParamUses<'_> {
+    fn visit_expr(&mut self, expr: &'ast Expr) {
+        match expr {
+            Expr::Path(path) if path.path.is_ident(self.name) => self.other += 1,
+            Expr::MethodCall(call) if self.is_param(&call.receiver) => {
+                if READ_ONLY_STR_METHODS.contains(&call.method.to_string().as_str()) {
+                    self.reads += 1;
                 } else {
-                    vec![
-                        format!("Simple for loop - consider using iterator chains"),
-                        format!("This loop could use .iter().for_each() instead"),
-                        format!("Iterators are more idiomatic than traditional loops"),
-                        format!("Consider rewriting this loop in functional style"),
-                    ]
-                };
+                    self.other += 1;
+                }
+                for arg in &call.args {
+                    self.visit_expr(arg);
+                }
+            }
+            Expr::Reference(reference)
+                if reference.mutability.is_none() && self.is_param(&reference.expr) =>
+            {
+                self.reads += 1;
+            }
+            Expr::Index(index) if self.is_param(&index.expr) => {
+                self.reads += 1;
+                self.visit_expr(&index.index);
+            }
+            Expr::Binary(binary)
+                if matches!(
+                    binary.op,
+                    BinOp::Eq(_)
+                        | BinOp::Ne(_)
+                        | BinOp::Lt(_)
+                        | BinOp::Le(_)
+                        | BinOp::Gt(_)
+                        | BinOp::Ge(_)
+                ) =>
+            {
+                for side in [&binary.left, &binary.right] {
+                    if self.is_param(side) {
+                        self.reads += 1;
+                    } else {
+                        self.visit_expr(side);
+                    }
+                }
+            }
+            _ => syn::visit::visit_expr(self, expr),
+        }
+    }
 
-                let (line, column) = get_position(for_loop);
-                self.issues.push(CodeIssue {
-                    file_path: self.file_path.clone(),
-                    line,
-                    column,
-                    rule_name: "iterator-abuse".to_string(),
-                    message: messages[self.issues.len() % messages.len()].to_string(),
-                    severity: Severity::Mild,
-                    roast_level: RoastLevel::Gentle,
-                });
+    fn visit_macro(&mut self, mac: &'ast Macro) {
+        let is_format = mac
+            .path
+            .segments
+            .last()
+            .is_some_and(|s| FORMAT_MACROS.contains(&s.ident.to_string().as_str()));
+        if !is_format {
+            visit_macro_args(self, mac);
+            return;
+        }
+        for arg in parse_macro_args(mac) {
+            if self.is_param(&arg) {
+                self.reads += 1;
+            } else {
+                self.visit_expr(&arg);
             }
         }
+        // format!("{name}") 内联引用参数
+        if mac.tokens.to_string().contains(&format!("{{{}", self.name)) {
+            self.reads += 1;
+        }
     }
-}
 
-impl<'ast> Visit<'ast> for IteratorAbuseVisitor {
-    fn visit_expr_for_loop(&mut self, for_loop: &'ast ExprForLoop) {
-        self.check_simple_for_loop(for_loop);
-        syn::visit::visit_expr_for_loop(self, for_loop);
+    fn visit_local(&mut self, local: &'ast Local) {
+        syn::visit::visit_local(self, local);
+        let shadows = match &local.pat {
+            Pat::Ident(ident) => ident.ident == self.name,
+            Pat::Type(pat_type) => {
+                matches!(pat_type.pat.as_ref(), Pat::Ident(ident) if ident.ident == self.name)
+            }
+            _ => false,
+        };
+        if shadows {
+            self.other += 1;
+        }
     }
+
+    fn visit_item_fn(&mut self, _item_fn: &'ast ItemFn) {}
 }
 
 // ============================================================================
-// Match 滥用检测
+// Vec 滥用检测
 // ============================================================================
 
-struct MatchAbuseVisitor {
+struct VecAbuseVisitor<'a> {
     file_path: std::path::PathBuf,
+    content: &'a str,
     issues: Vec<CodeIssue>,
     lang: String,
+    /// 当前函数里已知是 Vec 的变量，值是绑定时所在的循环层数
+    vecs: HashMap<String, usize>,
+    /// 用常量尺寸 vec![vec![..; N]; M] 建出来、到目前为止没有改过尺寸的矩阵
+    matrices: Vec<FixedMatrix>,
+    loop_depth: usize,
+    in_trait_impl: bool,
 }
 
-impl MatchAbuseVisitor {
-    fn new(file_path: std::path::PathBuf, lang: &str) -> Self {
+struct FixedMatrix {
+    name: String,
+    position: (usize, usize),
+    elem: String,
+    rows: String,
+    cols: String,
+}
+
+/// 会改变 Vec 长度的方法
+const RESIZING_VEC_METHODS: &[&str] = &[
+    "push",
+    "pop",
+    "insert",
+    "remove",
+    "swap_remove",
+    "extend",
+    "extend_from_slice",
+    "append",
+    "truncate",
+    "resize",
+    "resize_with",
+    "clear",
+    "retain",
+    "retain_mut",
+    "drain",
+    "dedup",
+    "dedup_by",
+    "dedup_by_key",
+    "split_off",
+];
+
+/// 闭包会对每个元素各跑一次的迭代器适配器
+const PER_ITEM_ADAPTERS: &[&str] = &[
+    "filter",
+    "filter_map",
+    "flat_map",
+    "any",
+    "all",
+    "position",
+    "for_each",
+    "retain",
+    "take_while",
+    "skip_while",
+    "partition",
+];
+
+impl<'a> VecAbuseVisitor<'a> {
+    fn new(file_path: std::path::PathBuf, content: &'a str, lang: &str) -> Self {
         Self {
             file_path,
+            content,
             issues: Vec::new(),
             lang: lang.to_string(),
+            vecs: HashMap::new(),
+            matrices: Vec::new(),
+            loop_depth: 0,
+            in_trait_impl: false,
         }
     }
 
-    fn check_simple_match(&mut self, match_expr: &ExprMatch) {
-        let arms_count = match_expr.arms.len();
-
-        // 检测只有两个分支的 match，可能可以用 if let 替代
-        if arms_count == 2 {
-            let match_str = format!("{match_expr:?}");
+    fn text<T: Spanned>(&self, node: &T) -> String {
+        get_source_text(self.content, node).unwrap_or_else(|| "..".to_string())
+    }
 
-            // 检测 Option 或 Result 的简单匹配
-            if match_str.contains("Some") && match_str.contains("None") {
-                let messages = if self.lang == "zh-CN" {
-                    vec![
-                        "简单的 Option match，考虑用 if let",
-                        "这个 match 可以用 if let Some() 替代",
-                        "Option 的二分支 match 不如 if let 简洁",
-                        "if let 比 match 更适合这种情况",
-                    ]
-                } else {
-                    vec![
-                        "Simple Option match - consider using if let",
-                        "This match could use if let Some() instead",
-                        "Two-arm Option match is less concise than if let",
-                        "if let is more suitable for this case than match",
-                    ]
-                };
+    /// 表达式是一个已知为 Vec 的局部变量或参数，返回变量名
+    fn known_vec(&self, expr: &Expr) -> Option<String> {
+        let Expr::Path(path) = peel(expr) else {
+            return None;
+        };
+        let name = path.path.get_ident()?.to_string();
+        self.vecs.contains_key(&name).then_some(name)
+    }
 
-                let (line, column) = get_position(match_expr);
-                self.issues.push(CodeIssue {
-                    file_path: self.file_path.clone(),
-                    line,
-                    column,
-                    rule_name: "match-abuse".to_string(),
-                    message: messages[self.issues.len() % messages.len()].to_string(),
-                    severity: Severity::Mild,
-                    roast_level: RoastLevel::Gentle,
-                });
-            }
+    /// 进入一个函数：登记 Vec 参数，检查 &Vec 参数，函数结束时报告没改过尺寸的矩阵
+    fn check_fn(&mut self, sig: &Signature, block: &Block, public: bool, signature_fixed: bool) {
+        let outer_vecs = std::mem::take(&mut self.vecs);
+        let outer_matrices = std::mem::take(&mut self.matrices);
+        let outer_loop_depth = std::mem::replace(&mut self.loop_depth, 0);
 
-            if match_str.contains("Ok") && match_str.contains("Err") {
-                let messages = if self.lang == "zh-CN" {
-                    vec![
-                        "简单的 Result match，考虑用 if let",
-                        "这个 match 可以用 if let Ok() 替代",
-                        "Result 的二分支 match 可以简化",
-                        "或者考虑用 ? 操作符",
-                    ]
-                } else {
-                    vec![
-                        "Simple Result match - consider using if let",
-                        "This match could use if let Ok() instead",
-                        "Two-arm Result match can be simplified",
-                        "Or consider using the ? operator",
-                    ]
-                };
+        for arg in &sig.inputs {
+            let FnArg::Typed(pat_type) = arg else {
+                continue;
+            };
+            let Pat::Ident(PatIdent { ident, .. }) = pat_type.pat.as_ref() else {
+                continue;
+            };
+            let name = ident.to_string();
 
-                let (line, column) = get_position(match_expr);
-                self.issues.push(CodeIssue {
-                    file_path: self.file_path.clone(),
-                    line,
-                    column,
-                    rule_name: "match-abuse".to_string(),
-                    message: messages[self.issues.len() % messages.len()].to_string(),
-                    severity: Severity::Mild,
-                    roast_level: RoastLevel::Gentle,
-                });
+            if vec_elem(&pat_type.ty).is_some() {
+                self.vecs.insert(name, 0);
+            } else if let Type::Reference(reference) = pat_type.ty.as_ref() {
+                if let Some(elem) = vec_elem(&reference.elem) {
+                    self.vecs.insert(name.clone(), 0);
+                    // 公开函数的 &Vec 由 owned-ref-param 负责
+                    if reference.mutability.is_none() && !signature_fixed && !public {
+                        let elem = self.text(elem);
+                        self.report_vec_ref_param(&pat_type.ty, &sig.ident, &name, &elem);
+                    }
+                }
             }
         }
+
+        self.visit_block(block);
+        for matrix in std::mem::take(&mut self.matrices) {
+            self.report_fixed_matrix(matrix);
+        }
+
+        self.vecs = outer_vecs;
+        self.matrices = outer_matrices;
+        self.loop_depth = outer_loop_depth;
+    }
+
+    fn report_vec_ref_param(&mut self, ty: &Type, fn_name: &syn::Ident, name: &str, elem: &str) {
+        let messages = if self.lang == "zh-CN" {
+            vec![
+                format!("{fn_name}() 的参数 {name}: &Vec<{elem}> 改成 &[{elem}]，数组、切片、Vec 都能直接传进来"),
+                format!("&Vec 参数 {name} 只能接 Vec，{fn_name}() 只读它的话 &[{elem}] 就够了"),
+            ]
+        } else {
+            vec![
+                format!("{fn_name}() takes {name}: &Vec<{elem}> - take &[{elem}] and accept arrays, slices and Vecs alike"),
+                format!("&Vec param {name} only accepts a Vec, if {fn_name}() just reads it &[{elem}] is enough"),
+            ]
+        };
+        self.issues.push(rule_issue(
+            &self.file_path,
+            get_position(ty),
+            "vec-abuse",
+            messages[self.issues.len() % messages.len()].clone(),
+            Severity::Mild,
+        ));
+    }
+
+    fn report_fixed_matrix(&mut self, matrix: FixedMatrix) {
+        let FixedMatrix {
+            name,
+            position,
+            elem,
+            rows,
+            cols,
+        } = matrix;
+        let messages = if self.lang == "zh-CN" {
+            vec![
+                format!("{name} 是 {rows}×{cols} 的固定矩阵，Vec<Vec<_>> 却为每一行单独分配一次堆内存；尺寸不变就用 [[{elem}; {cols}]; {rows}]，或者一个 vec![{elem}; {rows} * {cols}] 的扁平 Vec 按 i * {cols} + j 取下标"),
+                format!("{name} 从头到尾没改过尺寸，Vec<Vec<_>> 的每一行都是一次分配、一层间接寻址，换成 [[{elem}; {cols}]; {rows}]"),
+            ]
+        } else {
+            vec![
+                format!("{name} is a fixed {rows}x{cols} matrix, yet Vec<Vec<_>> allocates every row separately - use [[{elem}; {cols}]; {rows}], or one flat vec![{elem}; {rows} * {cols}] indexed by i * {cols} + j"),
+                format!("{name} never changes shape, but each row of a Vec<Vec<_>> is its own allocation and indirection - make it [[{elem}; {cols}]; {rows}]"),
+            ]
+        };
+        self.issues.push(rule_issue(
+            &self.file_path,
+            position,
+            "vec-abuse",
+            messages[self.issues.len() % messages.len()].clone(),
+            Severity::Mild,
+        ));
+    }
+
+    /// let mut v = Vec::new(); 后面紧跟着已知次数的 push
+    fn check_known_pushes(&mut self, stmts: &[Stmt]) {
+        for (index, stmt) in stmts.iter().enumerate() {
+            let Stmt::Local(local) = stmt else {
+                continue;
+            };
+            let Some(name) = empty_vec_binding(local) else {
+                continue;
+            };
+            let rest = &stmts[index + 1..];
+            let pushed: Vec<&Expr> = rest
+                .iter()
+                .map_while(|stmt| pushed_value(stmt, &name))
+                .collect();
+
+            if pushed.len() >= 2 {
+                let items = pushed
+                    .iter()
+                    .map(|value| self.text(*value))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                let items = if items.chars().count() <= 40 {
+                    items
+                } else {
+                    "..".to_string()
+                };
+                let count = pushed.len();
+                let messages = if self.lang == "zh-CN" {
+                    vec![
+                        format!("{name} 先 Vec::new() 再连着 push {count} 次，元素一开始就知道，直接写 vec![{items}]，一次分配到位"),
+                        format!("Vec::new() 后面紧跟 {count} 个 push 会一路扩容，vec![{items}] 更短也更快"),
+                    ]
+                } else {
+                    vec![
+                        format!("{name} starts as Vec::new() and then gets {count} pushes in a row - the elements are known up front, write vec![{items}] and allocate once"),
+                        format!("Vec::new() followed by {count} pushes regrows along the way, vec![{items}] is shorter and faster"),
+                    ]
+                };
+                self.issues.push(rule_issue(
+                    &self.file_path,
+                    get_position(local),
+                    "vec-abuse",
+                    messages[self.issues.len() % messages.len()].clone(),
+                    Severity::Mild,
+                ));
+                continue;
+            }
+
+            // 单个 push 的循环体由 iterator-abuse 建议 collect
+            let Some(Stmt::Expr(Expr::ForLoop(for_loop), _)) = rest.first() else {
+                continue;
+            };
+            if !pushed.is_empty()
+                || for_loop.body.stmts.len() < 2
+                || !pushes_once_per_iteration(&for_loop.body, &name)
+            {
+                continue;
+            }
+            let Some(len) = self.known_len(&for_loop.expr) else {
+                continue;
+            };
+            let messages = if self.lang == "zh-CN" {
+                vec![
+                    format!("{name} 在紧接着的循环里每轮 push 一次，循环次数是 {len}，用 Vec::with_capacity({len}) 一次分配够，省掉中途扩容"),
+                    format!("循环次数已经知道（{len}），{name} 还从 Vec::new() 开始一点点扩容，换成 Vec::with_capacity({len})"),
+                ]
+            } else {
+                vec![
+                    format!("{name} gets one push per iteration of the loop right after it, and the loop runs {len} times - Vec::with_capacity({len}) allocates once instead of regrowing"),
+                    format!("the iteration count is known ({len}), yet {name} starts from Vec::new() and regrows - use Vec::with_capacity({len})"),
+                ]
+            };
+            self.issues.push(rule_issue(
+                &self.file_path,
+                get_position(local),
+                "vec-abuse",
+                messages[self.issues.len() % messages.len()].clone(),
+                Severity::Mild,
+            ));
+        }
+    }
+
+    /// for 循环头的迭代次数能直接写出来时返回它
+    fn known_len(&self, iter: &Expr) -> Option<String> {
+        let len_of = |expr: &Expr| match peel(expr) {
+            Expr::Path(_) | Expr::Field(_) => Some(format!("{}.len()", self.text(expr))),
+            _ => None,
+        };
+        match peel(iter) {
+            Expr::Range(range) if matches!(range.limits, RangeLimits::HalfOpen(_)) => {
+                let end = range.end.as_deref()?;
+                let end = match peel(end) {
+                    Expr::Binary(_) => format!("({})", self.text(end)),
+                    _ => self.text(end),
+                };
+                match range.start.as_deref().map(peel) {
+                    Some(Expr::Lit(ExprLit {
+                        lit: Lit::Int(start),
+                        ..
+                    })) if start.base10_digits() == "0" => Some(end),
+                    Some(start @ (Expr::Lit(_) | Expr::Path(_))) => {
+                        Some(format!("{end} - {}", self.text(start)))
+                    }
+                    _ => None,
+                }
+            }
+            Expr::Reference(reference) => len_of(&reference.expr),
+            Expr::MethodCall(call)
+                if call.args.is_empty()
+                    && matches!(
+                        call.method.to_string().as_str(),
+                        "iter" | "iter_mut" | "into_iter"
+                    ) =>
+            {
+                len_of(&call.receiver)
+            }
+            expr => self.known_vec(expr).map(|name| format!("{name}.len()")),
+        }
+    }
+
+    /// .collect::<Vec<_>>() 之后马上 .iter() / .into_iter() / .len()
+    fn check_collect_then(&mut self, method_call: &ExprMethodCall) {
+        let method = method_call.method.to_string();
+        if !method_call.args.is_empty() || !matches!(method.as_str(), "iter" | "into_iter" | "len")
+        {
+            return;
+        }
+        let Expr::MethodCall(collect) = peel(&method_call.receiver) else {
+            return;
+        };
+        if !collects_into_vec(collect) {
+            return;
+        }
+
+        let messages = match (method.as_str(), self.lang.as_str()) {
+            ("len", "zh-CN") => vec![
+                "collect::<Vec<_>>().len() 只为了数个数就分配了一整个 Vec，用 .count()".to_string(),
+                "先 collect 再 len()，中间的 Vec 纯属浪费，直接 .count()".to_string(),
+            ],
+            ("len", _) => vec![
+                "collect::<Vec<_>>().len() allocates a whole Vec just to count - use .count()".to_string(),
+                "collect then len() wastes the Vec in between, just .count()".to_string(),
+            ],
+            (_, "zh-CN") => vec![
+                format!("collect::<Vec<_>>() 之后马上 .{method}()，中间这个 Vec 分配完就扔；去掉 collect，在原来的迭代器上接着写"),
+                format!("collect 完立刻又 .{method}() 回迭代器，这个 Vec 白分配了"),
+            ],
+            _ => vec![
+                format!("collect::<Vec<_>>() immediately followed by .{method}() allocates a Vec only to throw it away - drop the collect and keep chaining on the original iterator"),
+                format!("collecting and then calling .{method}() turns it straight back into an iterator, the Vec is wasted"),
+            ],
+        };
+        self.issues.push(rule_issue(
+            &self.file_path,
+            get_position(&collect.method),
+            "vec-abuse",
+            messages[self.issues.len() % messages.len()].clone(),
+            Severity::Spicy,
+        ));
+    }
+
+    /// v.len() == 0 / v.len() > 0 之类的判空
+    fn check_len_zero(&mut self, binary: &ExprBinary) {
+        let len_of = |expr: &Expr| match peel(expr) {
+            Expr::MethodCall(call) if call.method == "len" && call.args.is_empty() => {
+                self.known_vec(&call.receiver)
+            }
+            _ => None,
+        };
+        let is_int = |expr: &Expr, value: &str| matches!(peel(expr), Expr::Lit(ExprLit { lit: Lit::Int(int), .. }) if int.base10_digits() == value);
+        let (left, right) = (binary.left.as_ref(), binary.right.as_ref());
+
+        let (name, empty) = match (&binary.op, len_of(left), len_of(right)) {
+            (BinOp::Eq(_), Some(name), _) if is_int(right, "0") => (name, true),
+            (BinOp::Eq(_), _, Some(name)) if is_int(left, "0") => (name, true),
+            (BinOp::Ne(_), Some(name), _) | (BinOp::Gt(_), Some(name), _) if is_int(right, "0") => {
+                (name, false)
+            }
+            (BinOp::Ne(_), _, Some(name)) | (BinOp::Lt(_), _, Some(name)) if is_int(left, "0") => {
+                (name, false)
+            }
+            (BinOp::Ge(_), Some(name), _) if is_int(right, "1") => (name, false),
+            (BinOp::Le(_), _, Some(name)) if is_int(left, "1") => (name, false),
+            _ => return,
+        };
+
+        let original = self.text(binary);
+        let better = if empty {
+            format!("{name}.is_empty()")
+        } else {
+            format!("!{name}.is_empty()")
+        };
+        let messages = if self.lang == "zh-CN" {
+            vec![
+                format!("判断 Vec 是否为空直接写 {better}，比 {original} 更能表达意图"),
+                format!("{original} 绕了一下，写成 {better}"),
+            ]
+        } else {
+            vec![
+                format!(
+                    "to check whether a Vec is empty write {better}, it says more than {original}"
+                ),
+                format!("{original} is a roundabout {better}"),
+            ]
+        };
+        self.issues.push(rule_issue(
+            &self.file_path,
+            get_position(binary),
+            "vec-abuse",
+            messages[self.issues.len() % messages.len()].clone(),
+            Severity::Mild,
+        ));
+    }
+
+    /// 循环里对同一个 Vec 反复 contains，每次都线性扫描
+    fn check_contains_in_loop(&mut self, method_call: &ExprMethodCall) {
+        if method_call.method != "contains" || method_call.args.len() != 1 {
+            return;
+        }
+        let Some(name) = self.known_vec(&method_call.receiver) else {
+            return;
+        };
+        // 在循环里新建的 Vec 每轮都不一样，换成 HashSet 也省不了什么
+        if self.vecs[&name] >= self.loop_depth {
+            return;
+        }
+
+        let messages = if self.lang == "zh-CN" {
+            vec![
+                format!("循环里对 {name} 调 contains()，每次都线性扫描一遍，整体 O(n×m)；循环前把它收集成 HashSet 再查"),
+                format!("{name}.contains(..) 在循环里反复跑，Vec 查找是 O(n)，要查很多次就该换 HashSet"),
+            ]
+        } else {
+            vec![
+                format!("contains() on {name} inside a loop scans the whole Vec every time, O(n×m) overall - collect it into a HashSet before the loop"),
+                format!("{name}.contains(..) runs on every iteration and Vec lookup is O(n), repeated membership tests want a HashSet"),
+            ]
+        };
+        self.issues.push(rule_issue(
+            &self.file_path,
+            get_position(&method_call.method),
+            "vec-abuse",
+            messages[self.issues.len() % messages.len()].clone(),
+            Severity::Spicy,
+        ));
+    }
+
+    /// 改变矩阵尺寸的调用：grid.push(..)、grid[i].push(..)
+    fn release_matrix(&mut self, target: &Expr) {
+        let mut root = peel(target);
+        while let Expr::Index(index) = root {
+            root = peel(&index.expr);
+        }
+        if let Expr::Path(path) = root {
+            if let Some(ident) = path.path.get_ident() {
+                self.matrices.retain(|matrix| *ident != matrix.name);
+            }
+        }
+    }
+
+    fn bind_local(&mut self, local: &Local) {
+        let (pat, ty) = match &local.pat {
+            Pat::Type(pat_type) => (pat_type.pat.as_ref(), Some(pat_type.ty.as_ref())),
+            pat => (pat, None),
+        };
+        let Pat::Ident(PatIdent { ident, .. }) = pat else {
+            return;
+        };
+        let name = ident.to_string();
+        let init = local
+            .init
+            .as_ref()
+            .filter(|init| init.diverge.is_none())
+            .map(|init| init.expr.as_ref());
+
+        let is_vec = match ty {
+            Some(ty) => vec_elem(ty).is_some(),
+            None => init.is_some_and(produces_vec),
+        };
+        // 同名重新绑定会遮蔽之前的变量
+        self.vecs.remove(&name);
+        if is_vec {
+            self.vecs.insert(name.clone(), self.loop_depth);
+        }
+
+        if let Some((elem, rows, cols)) = init.and_then(fixed_matrix) {
+            self.matrices.push(FixedMatrix {
+                name,
+                position: get_position(local),
+                elem: self.text(&elem),
+                rows: self.text(&rows),
+                cols: self.text(&cols),
+            });
+        }
+    }
+}
+
+impl<'ast> Visit<'ast> for VecAbuseVisitor<'_> {
+    fn visit_macro(&mut self, mac: &'ast Macro) {
+        visit_macro_args(self, mac);
+    }
+
+    fn visit_item_fn(&mut self, item_fn: &'ast ItemFn) {
+        let public = is_public(&item_fn.vis);
+        self.check_fn(&item_fn.sig, &item_fn.block, public, false);
+    }
+
+    fn visit_item_impl(&mut self, item_impl: &'ast ItemImpl) {
+        let outer = std::mem::replace(&mut self.in_trait_impl, item_impl.trait_.is_some());
+        syn::visit::visit_item_impl(self, item_impl);
+        self.in_trait_impl = outer;
+    }
+
+    fn visit_impl_item_fn(&mut self, impl_fn: &'ast ImplItemFn) {
+        let public = is_public(&impl_fn.vis);
+        // trait 实现的签名由 trait 决定，改不了
+        let signature_fixed = self.in_trait_impl;
+        self.check_fn(&impl_fn.sig, &impl_fn.block, public, signature_fixed);
+    }
+
+    fn visit_trait_item_fn(&mut self, trait_fn: &'ast TraitItemFn) {
+        if let Some(block) = &trait_fn.default {
+            self.check_fn(&trait_fn.sig, block, true, true);
+        }
+    }
+
+    fn visit_block(&mut self, block: &'ast Block) {
+        self.check_known_pushes(&block.stmts);
+        syn::visit::visit_block(self, block);
+    }
+
+    fn visit_local(&mut self, local: &'ast Local) {
+        syn::visit::visit_local(self, local);
+        self.bind_local(local);
+    }
+
+    fn visit_expr_method_call(&mut self, method_call: &'ast ExprMethodCall) {
+        self.check_collect_then(method_call);
+        if self.loop_depth > 0 {
+            self.check_contains_in_loop(method_call);
+        }
+        if RESIZING_VEC_METHODS.contains(&method_call.method.to_string().as_str()) {
+            self.release_matrix(&method_call.receiver);
+        }
+
+        if PER_ITEM_ADAPTERS.contains(&method_call.method.to_string().as_str()) {
+            self.visit_expr(&method_call.receiver);
+            self.loop_depth += 1;
+            for arg in &method_call.args {
+                self.visit_expr(arg);
+            }
+            self.loop_depth -= 1;
+        } else {
+            syn::visit::visit_expr_method_call(self, method_call);
+        }
+    }
+
+    fn visit_expr_binary(&mut self, binary: &'ast ExprBinary) {
+        self.check_len_zero(binary);
+        syn::visit::visit_expr_binary(self, binary);
+    }
+
+    fn visit_expr_assign(&mut self, assign: &'ast ExprAssign) {
+        // grid[i] = vec![..] 换掉了一整行
+        if let Expr::Index(index) = peel(&assign.left) {
+            self.release_matrix(&index.expr);
+        }
+        syn::visit::visit_expr_assign(self, assign);
+    }
+
+    fn visit_expr_for_loop(&mut self, for_loop: &'ast ExprForLoop) {
+        self.visit_expr(&for_loop.expr);
+        self.loop_depth += 1;
+        self.visit_block(&for_loop.body);
+        self.loop_depth -= 1;
+    }
+
+    fn visit_expr_while(&mut self, while_loop: &'ast ExprWhile) {
+        self.loop_depth += 1;
+        syn::visit::visit_expr_while(self, while_loop);
+        self.loop_depth -= 1;
+    }
+
+    fn visit_expr_loop(&mut self, loop_expr: &'ast ExprLoop) {
+        self.loop_depth += 1;
+        syn::visit::visit_expr_loop(self, loop_expr);
+        self.loop_depth -= 1;
+    }
+}
+
+/// Vec<T> 的元素类型
+fn vec_elem(ty: &Type) -> Option<&Type> {
+    let Type::Path(TypePath { qself: None, path }) = ty else {
+        return None;
+    };
+    let segment = path.segments.last().filter(|s| s.ident == "Vec")?;
+    let PathArguments::AngleBracketed(args) = &segment.arguments else {
+        return None;
+    };
+    match args.args.first() {
+        Some(GenericArgument::Type(elem)) if args.args.len() == 1 => Some(elem),
+        _ => None,
+    }
+}
+
+/// 表达式的结果一定是 Vec
+fn produces_vec(expr: &Expr) -> bool {
+    match peel(expr) {
+        Expr::Macro(mac) => mac.mac.path.is_ident("vec"),
+        Expr::Call(call) => matches!(call.func.as_ref(), Expr::Path(func)
+            if func.path.segments.len() == 2
+                && func.path.segments[0].ident == "Vec"
+                && matches!(func.path.segments[1].ident.to_string().as_str(), "new" | "from" | "with_capacity")),
+        Expr::MethodCall(call) => call.method == "to_vec" || collects_into_vec(call),
+        _ => false,
+    }
+}
+
+/// .collect::<Vec<_>>()
+fn collects_into_vec(call: &ExprMethodCall) -> bool {
+    call.method == "collect"
+        && call.turbofish.as_ref().is_some_and(|turbofish| {
+            matches!(turbofish.args.first(), Some(GenericArgument::Type(ty)) if vec_elem(ty).is_some())
+        })
+}
+
+/// let mut v = Vec::new(); 或 let mut v = vec![];
+fn empty_vec_binding(local: &Local) -> Option<String> {
+    let pat = match &local.pat {
+        Pat::Type(pat_type) => pat_type.pat.as_ref(),
+        pat => pat,
+    };
+    let Pat::Ident(PatIdent {
+        ident,
+        mutability: Some(_),
+        ..
+    }) = pat
+    else {
+        return None;
+    };
+    let init = local.init.as_ref().filter(|init| init.diverge.is_none())?;
+    let empty = match peel(&init.expr) {
+        Expr::Macro(mac) => mac.mac.path.is_ident("vec") && mac.mac.tokens.is_empty(),
+        Expr::Call(call) => {
+            call.args.is_empty()
+                && matches!(call.func.as_ref(), Expr::Path(func)
+                    if func.path.segments.len() == 2
+                        && func.path.segments[0].ident == "Vec"
+                        && func.path.segments[1].ident == "new")
+        }
+        _ => false,
+    };
+    empty.then(|| ident.to_string())
+}
+
+/// 语句是 name.push(value); 时返回 value
+fn pushed_value<'s>(stmt: &'s Stmt, name: &str) -> Option<&'s Expr> {
+    let Stmt::Expr(Expr::MethodCall(call), Some(_)) = stmt else {
+        return None;
+    };
+    let is_target = matches!(peel(&call.receiver), Expr::Path(path) if path.path.is_ident(name));
+    (is_target && call.method == "push" && call.args.len() == 1).then(|| &call.args[0])
+}
+
+/// 循环体每一轮恰好 push 一次：push 在最外层，整个循环体里只有这一处 push，也没有 continue / break
+fn pushes_once_per_iteration(body: &Block, name: &str) -> bool {
+    let mut counter = PushCounter {
+        name,
+        pushes: 0,
+        exits: 0,
+    };
+    counter.visit_block(body);
+    counter.pushes == 1
+        && counter.exits == 0
+        && body
+            .stmts
+            .iter()
+            .any(|stmt| pushed_value(stmt, name).is_some())
+}
+
+struct PushCounter<'a> {
+    name: &'a str,
+    pushes: usize,
+    exits: usize,
+}
+
+impl<'ast> Visit<'ast> for PushCounter<'_> {
+    fn visit_expr_method_call(&mut self, call: &'ast ExprMethodCall) {
+        if call.method == "push"
+            && matches!(peel(&call.receiver), Expr::Path(path) if path.path.is_ident(self.name))
+        {
+            self.pushes += 1;
+        }
+        syn::visit::visit_expr_method_call(self, call);
+    }
+
+    fn visit_expr_continue(&mut self, _continue: &'ast syn::ExprContinue) {
+        self.exits += 1;
+    }
+
+    fn visit_expr_break(&mut self, _break: &'ast syn::ExprBreak) {
+        self.exits += 1;
+    }
+
+    fn visit_macro(&mut self, mac: &'ast Macro) {
+        visit_macro_args(self, mac);
+    }
+}
+
+/// vec![vec![elem; cols]; rows]，行列数都是字面量或常量
+fn fixed_matrix(expr: &Expr) -> Option<(Expr, Expr, Expr)> {
+    let Expr::Macro(outer) = peel(expr) else {
+        return None;
+    };
+    let (row, rows) = vec_repeat(&outer.mac)?;
+    let Expr::Macro(inner) = peel(&row) else {
+        return None;
+    };
+    let (elem, cols) = vec_repeat(&inner.mac)?;
+    (is_constant_len(&rows) && is_constant_len(&cols)).then_some((elem, rows, cols))
+}
+
+/// vec![elem; n] 的两个部分
+fn vec_repeat(mac: &Macro) -> Option<(Expr, Expr)> {
+    if !mac.path.is_ident("vec") {
+        return None;
+    }
+    mac.parse_body_with(|input: ParseStream| {
+        let elem: Expr = input.parse()?;
+        input.parse::<Token![;]>()?;
+        let len: Expr = input.parse()?;
+        Ok((elem, len))
+    })
+    .ok()
+}
+
+/// 整数字面量或者 SCREAMING_CASE 的常量
+fn is_constant_len(expr: &Expr) -> bool {
+    match peel(expr) {
+        Expr::Lit(ExprLit {
+            lit: Lit::Int(_), ..
+        }) => true,
+        Expr::Path(path) => path.path.get_ident().is_some_and(|ident| {
+            let name = ident.to_string();
+            name.chars().any(|c| c.is_ascii_uppercase())
+                && name
+                    .chars()
+                    .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_')
+        }),
+        _ => false,
+    }
+}
+
+// ============================================================================
+// 迭代器滥用检测
+// ============================================================================
+
+struct IteratorAbuseVisitor {
+    file_path: std::path::PathBuf,
+    issues: Vec<CodeIssue>,
+    content: String,
+    lang: String,
+    /// 紧挨在循环前面的 `let mut x = Vec::new()/0/1`，按循环位置记录
+    fresh_inits: HashMap<(usize, usize), FreshInit>,
+}
+
+/// 循环前刚初始化好的累加器，只有它才能整个换成 `let x = ...collect()/sum()`
+struct FreshInit {
+    name: String,
+    /// 写出来的类型：`let mut total: u64 = 0` 的 `u64`，或者字面量后缀 `0u64`
+    ty: Option<String>,
+    value: FreshValue,
+}
+
+#[derive(PartialEq)]
+enum FreshValue {
+    EmptyVec,
+    Zero,
+    One,
+}
+
+/// 能换成迭代器适配器的循环形状
+enum LoopRewrite {
+    /// 循环里 push -> collect
+    Collect,
+    /// 累加器 -> sum / product / count / fold
+    Accumulate(&'static str),
+    /// 找到就 break / return -> find / any / position
+    Search(&'static str),
+    /// for i in 0..v.len() -> iter() / iter().enumerate()
+    Enumerate,
+    /// 两层循环拼 pair -> flat_map
+    FlatMap,
+}
+
+/// 把 for 循环头翻译成迭代器链的起点
+struct LoopHead {
+    /// 可以直接接适配器的写法：`v.iter()`、`(0..n)`
+    base: String,
+    /// 闭包参数
+    param: String,
+    /// `for i in 0..v.len()` 里的 (v, i)
+    index: Option<(String, String)>,
+    /// 循环体只通过 v[i] 用到 i，可以直接迭代元素
+    index_only: bool,
+    /// 循环体给 v[i] 赋值或者可变借用它，要用 iter_mut()
+    index_mut: bool,
+}
+
+impl LoopHead {
+    /// 只用 v[i] 的下标循环，把 v[i] 换成元素本身
+    fn rewrite(&self, text: String) -> String {
+        match &self.index {
+            Some((vec, index)) if self.index_only => {
+                text.replace(&format!("{vec}[{index}]"), "item")
+            }
+            _ => text,
+        }
+    }
+}
+
+impl IteratorAbuseVisitor {
+    fn new(file_path: std::path::PathBuf, content: &str, lang: &str) -> Self {
+        Self {
+            file_path,
+            issues: Vec::new(),
+            content: content.to_string(),
+            lang: lang.to_string(),
+            fresh_inits: HashMap::new(),
+        }
+    }
+
+    fn text<T: Spanned>(&self, node: &T) -> String {
+        get_source_text(&self.content, node).unwrap_or_else(|| "..".to_string())
+    }
+
+    /// `let mut out = Vec::new();`、`let mut total: u64 = 0;`、`let mut n = 1u32;`
+    fn fresh_init(&self, local: &Local) -> Option<FreshInit> {
+        let (pat, ty) = match &local.pat {
+            Pat::Type(pat_type) => (pat_type.pat.as_ref(), Some(self.text(&pat_type.ty))),
+            pat => (pat, None),
+        };
+        let Pat::Ident(pat_ident) = pat else {
+            return None;
+        };
+        let init = local.init.as_ref().filter(|init| init.diverge.is_none())?;
+        let (value, suffix) = match init.expr.as_ref() {
+            Expr::Call(call) if is_empty_vec_ctor(call) => (FreshValue::EmptyVec, ""),
+            Expr::Macro(mac) if mac.mac.path.is_ident("vec") && mac.mac.tokens.is_empty() => {
+                (FreshValue::EmptyVec, "")
+            }
+            Expr::Lit(ExprLit {
+                lit: Lit::Int(int), ..
+            }) => match int.base10_digits() {
+                "0" => (FreshValue::Zero, int.suffix()),
+                "1" => (FreshValue::One, int.suffix()),
+                _ => return None,
+            },
+            Expr::Lit(ExprLit {
+                lit: Lit::Float(float),
+                ..
+            }) => match float
+                .base10_digits()
+                .trim_end_matches('0')
+                .trim_end_matches('.')
+            {
+                "" => (FreshValue::Zero, float.suffix()),
+                "1" => (FreshValue::One, float.suffix()),
+                _ => return None,
+            },
+            _ => return None,
+        };
+        let suffix = (!suffix.is_empty()).then(|| suffix.to_string());
+        Some(FreshInit {
+            name: pat_ident.ident.to_string(),
+            ty: ty.or(suffix),
+            value,
+        })
+    }
+
+    fn loop_head(&self, for_loop: &ExprForLoop) -> LoopHead {
+        let param = self.text(&for_loop.pat);
+        let iter = for_loop.expr.as_ref();
+
+        if let Some((vec_expr, index)) = index_range(for_loop) {
+            let vec = self.text(vec_expr);
+            let (index_only, index_mut) = index_uses(&for_loop.body, vec_expr, &index);
+            let (base, param) = if index_only {
+                let method = if index_mut { "iter_mut" } else { "iter" };
+                (format!("{vec}.{method}()"), "item".to_string())
+            } else {
+                (format!("({})", self.text(iter)), param)
+            };
+            return LoopHead {
+                base,
+                param,
+                index: Some((vec, index)),
+                index_only,
+                index_mut,
+            };
+        }
+
+        let base = match iter {
+            Expr::Reference(reference) => {
+                let method = if reference.mutability.is_some() {
+                    "iter_mut"
+                } else {
+                    "iter"
+                };
+                format!("{}.{method}()", self.text(&reference.expr))
+            }
+            Expr::Range(_) => format!("({})", self.text(iter)),
+            Expr::MethodCall(_) | Expr::Call(_) => self.text(iter),
+            _ => format!("{}.into_iter()", self.text(iter)),
+        };
+        LoopHead {
+            base,
+            param,
+            index: None,
+            index_only: false,
+            index_mut: false,
+        }
+    }
+
+    fn check_for_loop(&mut self, for_loop: &ExprForLoop) -> Option<LoopRewrite> {
+        let head = self.loop_head(for_loop);
+        let body = single_expr(&for_loop.body);
+        let fresh = self.fresh_inits.get(&get_position(for_loop));
+
+        let found = match body {
+            Some(Expr::ForLoop(inner)) => self.nested_pairs(&head, inner, fresh),
+            Some(body) => self
+                .collect_or_accumulate(&head, body, fresh)
+                .or_else(|| self.search(&head, for_loop, body)),
+            None => None,
+        };
+        let (rewrite, suggestion) = found.or_else(|| {
+            let (vec, index) = head.index.as_ref()?;
+            let method = if head.index_mut { "iter_mut" } else { "iter" };
+            let suggestion = if head.index_only {
+                format!("for item in {vec}.{method}() {{ .. }}")
+            } else {
+                format!("for ({index}, item) in {vec}.{method}().enumerate() {{ .. }}")
+            };
+            Some((LoopRewrite::Enumerate, suggestion))
+        })?;
+
+        let messages = match (&rewrite, self.lang.as_str()) {
+            (LoopRewrite::Collect, "zh-CN") => vec![
+                format!("循环里 push 就是 collect：{suggestion}"),
+                format!("手写的 collect，改成 {suggestion}"),
+            ],
+            (LoopRewrite::Collect, _) => vec![
+                format!("push in a loop is collect: {suggestion}"),
+                format!("a hand-written collect, write {suggestion}"),
+            ],
+            (LoopRewrite::Accumulate(method), "zh-CN") => vec![
+                format!("累加器循环就是 {method}：{suggestion}"),
+                format!("不用自己维护累加变量，{suggestion}"),
+            ],
+            (LoopRewrite::Accumulate(method), _) => vec![
+                format!("an accumulator loop is {method}: {suggestion}"),
+                format!("no need to babysit an accumulator, {suggestion}"),
+            ],
+            (LoopRewrite::Search(method), "zh-CN") => vec![
+                format!("找到就 break 的循环就是 {method}：{suggestion}"),
+                format!("手写的搜索，{method} 一行搞定：{suggestion}"),
+            ],
+            (LoopRewrite::Search(method), _) => vec![
+                format!("a find-and-break loop is {method}: {suggestion}"),
+                format!("a hand-rolled search is one {method} call: {suggestion}"),
+            ],
+            (LoopRewrite::Enumerate, "zh-CN") => vec![
+                format!("用下标遍历还要自己做边界检查，直接迭代：{suggestion}"),
+                format!("for i in 0..len() 是 C 语言的写法，Rust 里写 {suggestion}"),
+            ],
+            (LoopRewrite::Enumerate, _) => vec![
+                format!("indexing by hand means bounds checks on every access, iterate instead: {suggestion}"),
+                format!("for i in 0..len() is C, in Rust write {suggestion}"),
+            ],
+            (LoopRewrite::FlatMap, "zh-CN") => vec![
+                format!("两层循环拼组合就是 flat_map：{suggestion}"),
+                format!("嵌套循环只为了 push 一对值？{suggestion}"),
+            ],
+            (LoopRewrite::FlatMap, _) => vec![
+                format!("nested loops building pairs are flat_map: {suggestion}"),
+                format!("two loops just to push pairs? {suggestion}"),
+            ],
+        };
+
+        let (line, column) = get_position(for_loop);
+        self.issues.push(CodeIssue {
+            file_path: self.file_path.clone(),
+            line,
+            column,
+            rule_name: "iterator-abuse".to_string(),
+            message: messages[self.issues.len() % messages.len()].clone(),
+            severity: Severity::Mild,
+            roast_level: RoastLevel::Gentle,
+            context: CodeContext::Production,
+        });
+
+        Some(rewrite)
+    }
+
+    /// 过滤条件：`if cond { stmt }` -> (Some(cond), stmt)
+    fn unwrap_filter<'a>(&self, body: &'a Expr) -> (Option<&'a Expr>, &'a Expr) {
+        if let Expr::If(expr_if) = body {
+            if expr_if.else_branch.is_none() {
+                if let Some(inner) = single_expr(&expr_if.then_branch) {
+                    return (Some(&expr_if.cond), inner);
+                }
+            }
+        }
+        (None, body)
+    }
+
+    /// 累加器在循环前刚初始化时整个换成 let；否则原来的值还要保留，用 extend 或者 `+=`
+    fn collect_or_accumulate(
+        &self,
+        head: &LoopHead,
+        body: &Expr,
+        fresh: Option<&FreshInit>,
+    ) -> Option<(LoopRewrite, String)> {
+        let (cond, stmt) = self.unwrap_filter(body);
+        let param = &head.param;
+        let filter = cond
+            .map(|cond| format!(".filter(|{param}| {})", head.rewrite(self.text(cond))))
+            .unwrap_or_default();
+        let map = |value: &Expr| {
+            let value = head.rewrite(self.text(value));
+            if &value == param {
+                String::new()
+            } else if value == format!("*{param}") {
+                ".copied()".to_string()
+            } else {
+                format!(".map(|{param}| {value})")
+            }
+        };
+
+        // 只有普通的局部变量才能换成 let 绑定，v[i] *= 2 是原地修改
+        let local_target = |target: &Expr| {
+            let Expr::Path(path) = target else {
+                return None;
+            };
+            let name = path.path.get_ident()?.to_string();
+            let is_index = head.index.as_ref().is_some_and(|(_, index)| *index == name);
+            (!is_index).then_some(name)
+        };
+        let fresh_as = |target: &str, value: FreshValue| {
+            fresh.filter(|fresh| fresh.name == target && fresh.value == value)
+        };
+        let annotation = |fresh: &FreshInit| {
+            fresh
+                .ty
+                .as_ref()
+                .map(|ty| format!(": {ty}"))
+                .unwrap_or_default()
+        };
+
+        match stmt {
+            Expr::MethodCall(call) if call.method == "push" && call.args.len() == 1 => {
+                let target = local_target(&call.receiver)?;
+                let chain = format!("{}{filter}{}", head.base, map(&call.args[0]));
+                let suggestion = match fresh_as(&target, FreshValue::EmptyVec) {
+                    Some(fresh) => {
+                        let ty = fresh.ty.as_deref().unwrap_or("Vec<_>");
+                        format!("let {target}: {ty} = {chain}.collect();")
+                    }
+                    None => format!("{target}.extend({chain});"),
+                };
+                Some((LoopRewrite::Collect, suggestion))
+            }
+            Expr::Binary(binary) => {
+                let target = local_target(&binary.left)?;
+                let is_one = matches!(binary.right.as_ref(), Expr::Lit(lit) if matches!(&lit.lit, Lit::Int(int) if int.base10_digits() == "1"));
+                let chain = format!("{}{filter}{}", head.base, map(&binary.right));
+                let (method, suggestion) = match binary.op {
+                    BinOp::AddAssign(_) if is_one => {
+                        let counted = format!("{}{filter}.count()", head.base);
+                        let suggestion = match fresh_as(&target, FreshValue::Zero) {
+                            Some(FreshInit { ty: Some(ty), .. }) if ty != "usize" => {
+                                format!("let {target} = {counted} as {ty};")
+                            }
+                            Some(_) => format!("let {target} = {counted};"),
+                            None => format!("{target} += {counted};"),
+                        };
+                        ("count", suggestion)
+                    }
+                    BinOp::AddAssign(_) => {
+                        let suggestion = match fresh_as(&target, FreshValue::Zero) {
+                            Some(fresh) => {
+                                format!("let {target}{} = {chain}.sum();", annotation(fresh))
+                            }
+                            None => format!("{target} += {chain}.sum::<_>();"),
+                        };
+                        ("sum", suggestion)
+                    }
+                    BinOp::MulAssign(_) => {
+                        let suggestion = match fresh_as(&target, FreshValue::One) {
+                            Some(fresh) => {
+                                format!("let {target}{} = {chain}.product();", annotation(fresh))
+                            }
+                            None => format!("{target} *= {chain}.product::<_>();"),
+                        };
+                        ("product", suggestion)
+                    }
+                    BinOp::SubAssign(_)
+                    | BinOp::BitOrAssign(_)
+                    | BinOp::BitAndAssign(_)
+                    | BinOp::BitXorAssign(_) => {
+                        let op = &binary.op;
+                        let op = quote::quote!(#op).to_string();
+                        let op = op.trim_end_matches('=').trim();
+                        let value = head.rewrite(self.text(&binary.right));
+                        // fold 从累加器当前的值开始，之前的状态不会丢
+                        (
+                            "fold",
+                            format!(
+                                "let {target} = {}{filter}.fold({target}, |acc, {param}| acc {op} {value});",
+                                head.base
+                            ),
+                        )
+                    }
+                    _ => return None,
+                };
+                Some((LoopRewrite::Accumulate(method), suggestion))
+            }
+            _ => None,
+        }
+    }
+
+    /// `if cond { found = x; break; }` / `if cond { return Some(x); }`
+    fn search(
+        &self,
+        head: &LoopHead,
+        for_loop: &ExprForLoop,
+        body: &Expr,
+    ) -> Option<(LoopRewrite, String)> {
+        let Expr::If(expr_if) = body else {
+            return None;
+        };
+        if expr_if.else_branch.is_some() {
+            return None;
+        }
+        let (found, value) = found_value(&expr_if.then_branch)?;
+        let cond = head.rewrite(self.text(&expr_if.cond));
+        let param = &head.param;
+
+        // 找到的是下标：enumerate 出来的 i，或者 0..len() 的 i
+        let enumerate = match (for_loop.expr.as_ref(), for_loop.pat.as_ref()) {
+            (Expr::MethodCall(call), Pat::Tuple(tuple))
+                if call.method == "enumerate" && tuple.elems.len() == 2 =>
+            {
+                Some((
+                    self.text(&call.receiver),
+                    self.text(&tuple.elems[0]),
+                    self.text(&tuple.elems[1]),
+                ))
+            }
+            _ => None,
+        };
+        let yields = |name: &str| matches!(some_payload(value), Some(Expr::Path(p)) if p.path.is_ident(name));
+
+        // 找到就返回 true 是 any，找到就返回 false 是条件取反的 all
+        if let Expr::Lit(ExprLit {
+            lit: Lit::Bool(found),
+            ..
+        }) = value
+        {
+            return Some(if found.value {
+                (
+                    LoopRewrite::Search("any"),
+                    format!("{}.any(|{param}| {cond})", head.base),
+                )
+            } else {
+                (
+                    LoopRewrite::Search("all"),
+                    format!(
+                        "{}.all(|{param}| {})",
+                        head.base,
+                        negate(&expr_if.cond, &cond)
+                    ),
+                )
+            });
+        }
+        // return Err(..) 之类的提前返回不是搜索
+        let payload = match found {
+            Found::Return => some_payload(value)?,
+            Found::Assign => some_payload(value).unwrap_or(value),
+        };
+        if let Some((base, index, item)) = &enumerate {
+            if yields(index) {
+                return Some((
+                    LoopRewrite::Search("position"),
+                    format!("{base}.position(|{item}| {cond})"),
+                ));
+            }
+        }
+        if let Some((vec, index)) = &head.index {
+            if yields(index) {
+                let cond = self
+                    .text(&expr_if.cond)
+                    .replace(&format!("{vec}[{index}]"), "item");
+                return Some((
+                    LoopRewrite::Search("position"),
+                    format!("{vec}.iter().position(|item| {cond})"),
+                ));
+            }
+        }
+        let payload = head.rewrite(self.text(payload));
+        let map = if &payload == param {
+            String::new()
+        } else {
+            format!(".map(|{param}| {payload})")
+        };
+        Some((
+            LoopRewrite::Search("find"),
+            format!("{}.find(|{param}| {cond}){map}", head.base),
+        ))
+    }
+
+    /// for a in xs { for b in ys { out.push((a, b)) } }
+    fn nested_pairs(
+        &self,
+        head: &LoopHead,
+        inner: &ExprForLoop,
+        fresh: Option<&FreshInit>,
+    ) -> Option<(LoopRewrite, String)> {
+        let Some(Expr::MethodCall(call)) = single_expr(&inner.body) else {
+            return None;
+        };
+        if call.method != "push" || call.args.len() != 1 {
+            return None;
+        }
+        let inner_head = self.loop_head(inner);
+        let target = self.text(&call.receiver);
+        let value = self.text(&call.args[0]);
+        let chain = format!(
+            "{}.flat_map(|{}| {}.map(move |{}| {value}))",
+            head.base, head.param, inner_head.base, inner_head.param
+        );
+        let suggestion = match fresh.filter(|f| f.name == target && f.value == FreshValue::EmptyVec)
+        {
+            Some(fresh) => {
+                let ty = fresh.ty.as_deref().unwrap_or("Vec<_>");
+                format!("let {target}: {ty} = {chain}.collect();")
+            }
+            None => format!("{target}.extend({chain});"),
+        };
+        Some((LoopRewrite::FlatMap, suggestion))
+    }
+}
+
+fn is_compound_assign(op: &BinOp) -> bool {
+    matches!(
+        op,
+        BinOp::AddAssign(_)
+            | BinOp::SubAssign(_)
+            | BinOp::MulAssign(_)
+            | BinOp::DivAssign(_)
+            | BinOp::RemAssign(_)
+            | BinOp::BitXorAssign(_)
+            | BinOp::BitAndAssign(_)
+            | BinOp::BitOrAssign(_)
+            | BinOp::ShlAssign(_)
+            | BinOp::ShrAssign(_)
+    )
+}
+
+/// `Vec::new()`、`Vec::with_capacity(n)`
+fn is_empty_vec_ctor(call: &syn::ExprCall) -> bool {
+    let Expr::Path(func) = call.func.as_ref() else {
+        return false;
+    };
+    let names: Vec<String> = func
+        .path
+        .segments
+        .iter()
+        .rev()
+        .take(2)
+        .map(|segment| segment.ident.to_string())
+        .collect();
+    matches!(
+        names.iter().map(String::as_str).collect::<Vec<_>>()[..],
+        ["new" | "with_capacity", "Vec"]
+    )
+}
+
+/// 只有一条表达式语句的块
+fn single_expr(block: &Block) -> Option<&Expr> {
+    match &block.stmts[..] {
+        [Stmt::Expr(expr, _)] => Some(expr),
+        _ => None,
+    }
+}
+
+/// `for i in 0..v.len()` -> (v, i)
+fn index_range(for_loop: &ExprForLoop) -> Option<(&Expr, String)> {
+    let Pat::Ident(PatIdent { ident, .. }) = for_loop.pat.as_ref() else {
+        return None;
+    };
+    let Expr::Range(range) = for_loop.expr.as_ref() else {
+        return None;
+    };
+    if !matches!(range.limits, RangeLimits::HalfOpen(_)) {
+        return None;
+    }
+    let starts_at_zero = matches!(range.start.as_deref(), Some(Expr::Lit(lit))
+        if matches!(&lit.lit, Lit::Int(int) if int.base10_digits() == "0"));
+    let Some(Expr::MethodCall(len)) = range.end.as_deref() else {
+        return None;
+    };
+    if !starts_at_zero || len.method != "len" || !len.args.is_empty() {
+        return None;
+    }
+    Some((&len.receiver, ident.to_string()))
+}
+
+/// 循环体里下标变量的用法：(是不是只出现在 v[i] 里, 有没有通过 v[i] 修改元素)
+fn index_uses(body: &Block, vec_expr: &Expr, index: &str) -> (bool, bool) {
+    let mut counter = IndexUseCounter {
+        vec: quote::quote!(#vec_expr).to_string(),
+        index,
+        indexed: 0,
+        other: 0,
+        mutated: false,
+    };
+    counter.visit_block(body);
+    (counter.indexed > 0 && counter.other == 0, counter.mutated)
+}
+
+struct IndexUseCounter<'a> {
+    vec: String,
+    index: &'a str,
+    indexed: usize,
+    other: usize,
+    mutated: bool,
+}
+
+impl IndexUseCounter<'_> {
+    /// `v[i]`，或者 `v[i].field` 这样落在元素里面的位置
+    fn is_element(&self, expr: &Expr) -> bool {
+        match expr {
+            Expr::Index(index_expr) => {
+                let vec = &index_expr.expr;
+                matches!(index_expr.index.as_ref(), Expr::Path(p) if p.path.is_ident(self.index))
+                    && quote::quote!(#vec).to_string() == self.vec
+            }
+            Expr::Field(field) => self.is_element(&field.base),
+            Expr::Paren(paren) => self.is_element(&paren.expr),
+            _ => false,
+        }
+    }
+}
+
+impl<'ast> Visit<'ast> for IndexUseCounter<'_> {
+    fn visit_expr(&mut self, expr: &'ast Expr) {
+        let written = match expr {
+            Expr::Assign(assign) => Some(assign.left.as_ref()),
+            Expr::Binary(binary) if is_compound_assign(&binary.op) => Some(binary.left.as_ref()),
+            Expr::Reference(reference) if reference.mutability.is_some() => {
+                Some(reference.expr.as_ref())
+            }
+            _ => None,
+        };
+        if written.is_some_and(|place| self.is_element(place)) {
+            self.mutated = true;
+        }
+
+        match expr {
+            Expr::Index(index_expr) if matches!(index_expr.index.as_ref(), Expr::Path(p) if p.path.is_ident(self.index)) =>
+            {
+                let vec = &index_expr.expr;
+                if quote::quote!(#vec).to_string() == self.vec {
+                    self.indexed += 1;
+                } else {
+                    self.other += 1;
+                }
+            }
+            Expr::Path(path) if path.path.is_ident(self.index) => self.other += 1,
+            _ => syn::visit::visit_expr(self, expr),
+        }
+    }
+
+    fn visit_macro(&mut self, mac: &'ast Macro) {
+        visit_macro_args(self, mac);
+    }
+}
+
+/// 搜索循环交出结果的方式
+enum Found {
+    /// `return v;`
+    Return,
+    /// `x = v; break;`
+    Assign,
+}
+
+/// 找到之后交出去的值：`x = v; break;` 里的 v，或者 `return v;` 里的 v
+fn found_value(block: &Block) -> Option<(Found, &Expr)> {
+    match &block.stmts[..] {
+        [Stmt::Expr(Expr::Return(ret), _)] => Some((Found::Return, ret.expr.as_deref()?)),
+        [Stmt::Expr(Expr::Assign(assign), _), Stmt::Expr(Expr::Break(brk), _)]
+            if brk.expr.is_none() =>
+        {
+            Some((Found::Assign, &assign.right))
+        }
+        _ => None,
+    }
+}
+
+/// 条件取反：`!a` 去掉 `!`，`a < b` 换成 `a >= b`，其余加括号
+fn negate(cond: &Expr, text: &str) -> String {
+    match cond {
+        Expr::Unary(unary) if matches!(unary.op, syn::UnOp::Not(_)) => {
+            text.trim_start_matches('!').trim().to_string()
+        }
+        Expr::Binary(binary) => {
+            let flipped = match binary.op {
+                BinOp::Lt(_) => Some((" < ", " >= ")),
+                BinOp::Le(_) => Some((" <= ", " > ")),
+                BinOp::Gt(_) => Some((" > ", " <= ")),
+                BinOp::Ge(_) => Some((" >= ", " < ")),
+                BinOp::Eq(_) => Some((" == ", " != ")),
+                BinOp::Ne(_) => Some((" != ", " == ")),
+                _ => None,
+            };
+            match flipped {
+                Some((from, to)) if text.matches(from).count() == 1 => text.replacen(from, to, 1),
+                _ => format!("!({text})"),
+            }
+        }
+        Expr::Path(_) | Expr::MethodCall(_) | Expr::Call(_) | Expr::Field(_) => format!("!{text}"),
+        _ => format!("!({text})"),
+    }
+}
+
+impl<'ast> Visit<'ast> for IteratorAbuseVisitor {
+    fn visit_macro(&mut self, mac: &'ast Macro) {
+        visit_macro_args(self, mac);
+    }
+
+    fn visit_block(&mut self, block: &'ast Block) {
+        for pair in block.stmts.windows(2) {
+            if let [Stmt::Local(local), Stmt::Expr(Expr::ForLoop(for_loop), _)] = pair {
+                if let Some(fresh) = self.fresh_init(local) {
+                    self.fresh_inits.insert(get_position(for_loop), fresh);
+                }
+            }
+        }
+        syn::visit::visit_block(self, block);
+    }
+
+    fn visit_expr_for_loop(&mut self, for_loop: &'ast ExprForLoop) {
+        // 内层循环已经算进 flat_map 的改写里了
+        if let Some(LoopRewrite::FlatMap) = self.check_for_loop(for_loop) {
+            return;
+        }
+        syn::visit::visit_expr_for_loop(self, for_loop);
+    }
+}
+
+// ============================================================================
+// Match 滥用检测
+// ============================================================================
+
+struct MatchAbuseVisitor {
+    file_path: std::path::PathBuf,
+    issues: Vec<CodeIssue>,
+    content: String,
+    lang: String,
+}
+
+/// 能被更简单写法替代的 match 形状
+enum MatchRewrite {
+    /// match 一个 bool
+    Bool,
+    /// Some(v) => Some(f(v)), None => None
+    Map,
+    /// Some(v) => v, None => default
+    UnwrapOr(&'static str),
+    /// Some(v) => v, None => return None；Ok(v) => v, Err(e) => return Err(e)
+    Try,
+    /// 一个分支只取出绑定，另一个分支直接跳走
+    LetElse,
+    /// 另一个分支什么也不做
+    IfLet,
+}
+
+impl MatchAbuseVisitor {
+    fn new(file_path: std::path::PathBuf, content: &str, lang: &str) -> Self {
+        Self {
+            file_path,
+            issues: Vec::new(),
+            content: content.to_string(),
+            lang: lang.to_string(),
+        }
+    }
+
+    /// 源码片段，太长的用 .. 代替
+    fn snippet<T: Spanned>(&self, node: &T) -> String {
+        match get_source_text(&self.content, node) {
+            Some(text) if text.chars().count() <= 40 => text,
+            _ => "..".to_string(),
+        }
+    }
+
+    fn check_match(&mut self, match_expr: &ExprMatch) {
+        if match_expr.arms.iter().any(|arm| arm.guard.is_some()) {
+            return;
+        }
+
+        let Some((rewrite, suggestion)) = self.rewrite_of(match_expr) else {
+            return;
+        };
+
+        let messages = match (rewrite, self.lang.as_str()) {
+            (MatchRewrite::Bool, "zh-CN") => vec![
+                format!("对 bool 做 match？这就是 if/else：{suggestion}"),
+                format!("match true/false 是把 if 写复杂了，改成 {suggestion}"),
+            ],
+            (MatchRewrite::Bool, _) => vec![
+                format!("matching on a bool? That's an if/else: {suggestion}"),
+                format!("match true/false is just a longer if, write {suggestion}"),
+            ],
+            (MatchRewrite::Map, "zh-CN") => vec![
+                format!("这个 match 就是 Option::map：{suggestion}"),
+                format!("Some 进 Some 出、None 进 None 出，写成 {suggestion}"),
+            ],
+            (MatchRewrite::Map, _) => vec![
+                format!("this match is Option::map: {suggestion}"),
+                format!("Some in, Some out, None in, None out - write {suggestion}"),
+            ],
+            (MatchRewrite::UnwrapOr(method), "zh-CN") => vec![
+                format!("取值或者给默认值，{method} 一行搞定：{suggestion}"),
+                format!("这个 match 就是 {method}，改成 {suggestion}"),
+            ],
+            (MatchRewrite::UnwrapOr(method), _) => vec![
+                format!("value or a default is one {method} call: {suggestion}"),
+                format!("this match is {method}, write {suggestion}"),
+            ],
+            (MatchRewrite::Try, "zh-CN") => vec![
+                format!("手写的错误传播，? 操作符就是干这个的：{suggestion}"),
+                format!("遇到 None/Err 就原样返回？写成 {suggestion}"),
+            ],
+            (MatchRewrite::Try, _) => vec![
+                format!("hand-rolled error propagation, that's what ? is for: {suggestion}"),
+                format!("returning None/Err unchanged? Write {suggestion}"),
+            ],
+            (MatchRewrite::LetElse, "zh-CN") => vec![
+                format!("取出值、不匹配就跳走，这是 let else：{suggestion}"),
+                format!("这个 match 可以写成 let else，少一层缩进：{suggestion}"),
+            ],
+            (MatchRewrite::LetElse, _) => vec![
+                format!("bind or bail out is exactly let else: {suggestion}"),
+                format!("this match reads better as let else, one level flatter: {suggestion}"),
+            ],
+            (MatchRewrite::IfLet, "zh-CN") => vec![
+                format!("另一个分支什么都不做，用 if let：{suggestion}"),
+                format!("两个分支只有一个干活，写成 {suggestion}"),
+            ],
+            (MatchRewrite::IfLet, _) => vec![
+                format!("the other arm does nothing, use if let: {suggestion}"),
+                format!("only one of the two arms does any work, write {suggestion}"),
+            ],
+        };
+
+        let (line, column) = get_position(match_expr);
+        self.issues.push(CodeIssue {
+            file_path: self.file_path.clone(),
+            line,
+            column,
+            rule_name: "match-abuse".to_string(),
+            message: messages[self.issues.len() % messages.len()].clone(),
+            severity: Severity::Mild,
+            roast_level: RoastLevel::Gentle,
+            context: CodeContext::Production,
+        });
+    }
+
+    /// 识别 match 的形状，给出改写建议
+    fn rewrite_of(&self, match_expr: &ExprMatch) -> Option<(MatchRewrite, String)> {
+        let scrutinee = self.snippet(&match_expr.expr);
+
+        if match_expr.arms.iter().any(|arm| is_bool_pat(&arm.pat)) {
+            return Some((
+                MatchRewrite::Bool,
+                format!("if {scrutinee} {{ .. }} else {{ .. }}"),
+            ));
+        }
+
+        let [first, second] = &match_expr.arms[..] else {
+            return None;
+        };
+        // hit 是干活的分支，miss 是 `_` / None / Err(_) 这种兜底分支
+        let (hit, miss) = if is_catch_all(&first.pat) && !is_catch_all(&second.pat) {
+            (second, first)
+        } else {
+            (first, second)
+        };
+
+        if let Some((variant, binding)) = variant_binding(&hit.pat) {
+            let body = peel(&hit.body);
+            let miss_body = peel(&miss.body);
+            let yields_binding = is_ident(body, &binding);
+
+            match variant.as_str() {
+                "Some" if is_none_pat(&miss.pat) || is_wild(&miss.pat) => {
+                    if let Some(inner) = some_payload(body) {
+                        if is_none_expr(miss_body) {
+                            let inner = self.snippet(inner);
+                            return Some((
+                                MatchRewrite::Map,
+                                format!("{scrutinee}.map(|{binding}| {inner})"),
+                            ));
+                        }
+                    }
+                    if yields_binding {
+                        if returns(miss_body, is_none_expr) {
+                            return Some((MatchRewrite::Try, format!("{scrutinee}?")));
+                        }
+                        if !diverges(miss_body) {
+                            let (method, suggestion) =
+                                unwrap_or_call(miss_body, self.snippet(miss_body));
+                            return Some((
+                                MatchRewrite::UnwrapOr(method),
+                                format!("{scrutinee}.{suggestion}"),
+                            ));
+                        }
+                    }
+                }
+                "Ok" if yields_binding
+                    && returns(miss_body, |e| is_err_passthrough(e, &miss.pat)) =>
+                {
+                    return Some((MatchRewrite::Try, format!("{scrutinee}?")));
+                }
+                _ => {}
+            }
+        }
+
+        // 通用的二分支形状：一个分支有绑定，另一个分支是 _ / None / Err(_)
+        if !is_catch_all(&miss.pat) {
+            return None;
+        }
+        let pattern = self.snippet(&hit.pat);
+        let miss_body = peel(&miss.body);
+
+        if let Some((_, binding)) = variant_binding(&hit.pat) {
+            if is_ident(peel(&hit.body), &binding) && diverges(miss_body) {
+                let bail = self.snippet(miss_body);
+                return Some((
+                    MatchRewrite::LetElse,
+                    format!("let {pattern} = {scrutinee} else {{ {bail} }};"),
+                ));
+            }
+        }
+        if is_empty_body(miss_body) && !is_empty_body(peel(&hit.body)) {
+            return Some((
+                MatchRewrite::IfLet,
+                format!("if let {pattern} = {scrutinee} {{ .. }}"),
+            ));
+        }
+
+        None
+    }
+}
+
+/// { expr } 只有一条表达式的块，拆开看里面
+fn peel(expr: &Expr) -> &Expr {
+    match expr {
+        Expr::Block(block) if block.block.stmts.len() == 1 => match &block.block.stmts[0] {
+            Stmt::Expr(inner, _) => peel(inner),
+            Stmt::Macro(_) | Stmt::Local(_) | Stmt::Item(_) => expr,
+        },
+        Expr::Paren(paren) => peel(&paren.expr),
+        _ => expr,
+    }
+}
+
+fn last_ident(path: &syn::Path) -> Option<String> {
+    path.segments.last().map(|s| s.ident.to_string())
+}
+
+fn is_bool_pat(pat: &Pat) -> bool {
+    matches!(pat, Pat::Lit(lit) if matches!(lit.lit, Lit::Bool(_)))
+}
+
+fn is_wild(pat: &Pat) -> bool {
+    matches!(pat, Pat::Wild(_))
+}
+
+fn is_none_pat(pat: &Pat) -> bool {
+    match pat {
+        Pat::Ident(ident) => ident.ident == "None" && ident.subpat.is_none(),
+        Pat::Path(path) => path.path.is_ident("None"),
+        _ => false,
+    }
+}
+
+/// `_`、`None`、`Err(_)` 这些"剩下的都算"的模式
+fn is_catch_all(pat: &Pat) -> bool {
+    match pat {
+        Pat::TupleStruct(tuple) => {
+            last_ident(&tuple.path).is_some_and(|name| name == "Err")
+                && tuple
+                    .elems
+                    .iter()
+                    .all(|elem| matches!(elem, Pat::Wild(_) | Pat::Ident(_)))
+        }
+        _ => is_wild(pat) || is_none_pat(pat),
+    }
+}
+
+/// `Some(v)` / `Ok(v)` / `Kind::A(v)` -> (变体名, 绑定名)
+fn variant_binding(pat: &Pat) -> Option<(String, String)> {
+    let Pat::TupleStruct(tuple) = pat else {
+        return None;
+    };
+    let [Pat::Ident(binding)] = &tuple.elems.iter().collect::<Vec<_>>()[..] else {
+        return None;
+    };
+    if binding.subpat.is_some() || last_ident(&tuple.path).is_some_and(|name| name == "Err") {
+        return None;
+    }
+    Some((last_ident(&tuple.path)?, binding.ident.to_string()))
+}
+
+fn is_ident(expr: &Expr, name: &str) -> bool {
+    matches!(expr, Expr::Path(path) if path.path.is_ident(name))
+}
+
+fn is_none_expr(expr: &Expr) -> bool {
+    is_ident(expr, "None")
+}
+
+/// Some(inner) 里的 inner
+fn some_payload(expr: &Expr) -> Option<&Expr> {
+    let Expr::Call(call) = expr else {
+        return None;
+    };
+    if !is_ident(&call.func, "Some") || call.args.len() != 1 {
+        return None;
+    }
+    call.args.first()
+}
+
+/// `return <value>` 并且 value 满足条件
+fn returns(expr: &Expr, value: impl Fn(&Expr) -> bool) -> bool {
+    matches!(expr, Expr::Return(ret) if ret.expr.as_deref().is_some_and(value))
+}
+
+/// Err(e) => return Err(e) / Err(e.into()) / Err(From::from(e))
+fn is_err_passthrough(expr: &Expr, miss_pat: &Pat) -> bool {
+    let Pat::TupleStruct(tuple) = miss_pat else {
+        return false;
+    };
+    let Some(Pat::Ident(err)) = tuple.elems.first() else {
+        return false;
+    };
+    let err = err.ident.to_string();
+    let Expr::Call(call) = expr else {
+        return false;
+    };
+    if !is_ident(&call.func, "Err") || call.args.len() != 1 {
+        return false;
+    }
+    match &call.args[0] {
+        Expr::MethodCall(method) => method.method == "into" && is_ident(&method.receiver, &err),
+        Expr::Call(from) => {
+            matches!(from.func.as_ref(), Expr::Path(p) if p.path.segments.last().is_some_and(|s| s.ident == "from"))
+                && from.args.len() == 1
+                && is_ident(&from.args[0], &err)
+        }
+        arg => is_ident(arg, &err),
+    }
+}
+
+/// return / break / continue / panic! 之类不会往下走的表达式
+fn diverges(expr: &Expr) -> bool {
+    match expr {
+        Expr::Return(_) | Expr::Break(_) | Expr::Continue(_) => true,
+        Expr::Macro(mac) => last_ident(&mac.mac.path).is_some_and(|name| {
+            matches!(
+                name.as_str(),
+                "panic" | "unreachable" | "todo" | "unimplemented"
+            )
+        }),
+        Expr::Block(block) => block.block.stmts.last().is_some_and(|stmt| match stmt {
+            Stmt::Expr(inner, _) => diverges(inner),
+            Stmt::Macro(mac) => last_ident(&mac.mac.path).is_some_and(|name| {
+                matches!(
+                    name.as_str(),
+                    "panic" | "unreachable" | "todo" | "unimplemented"
+                )
+            }),
+            _ => false,
+        }),
+        _ => false,
+    }
+}
+
+fn is_empty_body(expr: &Expr) -> bool {
+    match expr {
+        Expr::Block(block) => block.block.stmts.is_empty(),
+        Expr::Tuple(tuple) => tuple.elems.is_empty(),
+        _ => false,
+    }
+}
+
+/// 默认值是字面量用 unwrap_or，是 Default 用 unwrap_or_default，要计算的用 unwrap_or_else
+fn unwrap_or_call(default: &Expr, text: String) -> (&'static str, String) {
+    let is_default = match default {
+        Expr::Call(call) => matches!(call.func.as_ref(), Expr::Path(p)
+            if p.path.segments.len() == 2
+                && matches!(p.path.segments[1].ident.to_string().as_str(), "default" | "new")
+                && call.args.is_empty()),
+        _ => false,
+    };
+    if is_default {
+        ("unwrap_or_default", "unwrap_or_default()".to_string())
+    } else if matches!(default, Expr::Lit(_) | Expr::Path(_)) {
+        ("unwrap_or", format!("unwrap_or({text})"))
+    } else {
+        ("unwrap_or_else", format!("unwrap_or_else(|| {text})"))
+    }
+}
+
+impl<'ast> Visit<'ast> for MatchAbuseVisitor {
+    fn visit_macro(&mut self, mac: &'ast Macro) {
+        visit_macro_args(self, mac);
     }
-}
 
-impl<'ast> Visit<'ast> for MatchAbuseVisitor {
     fn visit_expr_match(&mut self, match_expr: &'ast ExprMatch) {
-        self.check_simple_match(match_expr);
+        self.check_match(match_expr);
         syn::visit::visit_expr_match(self, match_expr);
     }
 }
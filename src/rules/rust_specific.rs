@@ -1,8 +1,17 @@
+use std::collections::{HashMap, HashSet};
 use std::path::Path;
-use syn::{visit::Visit, ExprMethodCall, File};
+use syn::{
+    punctuated::Punctuated, visit::Visit, Attribute, BinOp, Block, Expr, ExprClosure, ExprIf,
+    ExprMethodCall, File, FnArg, ImplItemFn, Item, ItemFn, ItemMod, Lit, Local, Macro, Member, Pat,
+    ReturnType, Signature, Stmt, Token, Type,
+};
 
-use crate::analyzer::{CodeIssue, RoastLevel, Severity};
-use crate::rules::Rule;
+use crate::analyzer::{CodeContext, CodeIssue, RoastLevel, Severity};
+use crate::rules::common::{is_lock_call, RESULT_METHODS};
+use crate::rules::context::{is_cfg_test_attr, is_test_attr};
+use crate::rules::macro_args::visit_macro_args;
+use crate::rules::{ContextPolicy, Rule};
+use crate::utils::get_position;
 
 pub struct UnwrapAbuseRule;
 
@@ -11,14 +20,21 @@ impl Rule for UnwrapAbuseRule {
         "unwrap-abuse"
     }
 
+    fn context_policy(&self, context: CodeContext) -> ContextPolicy {
+        match context {
+            CodeContext::Test => ContextPolicy::Ignore,
+            _ => ContextPolicy::Report,
+        }
+    }
+
     fn check(
         &self,
         file_path: &Path,
         syntax_tree: &File,
         _content: &str,
-        _lang: &str,
+        lang: &str,
     ) -> Vec<CodeIssue> {
-        let mut visitor = UnwrapVisitor::new(file_path.to_path_buf());
+        let mut visitor = UnwrapVisitor::new(file_path.to_path_buf(), lang);
         visitor.visit_file(syntax_tree);
         visitor.issues
     }
@@ -36,116 +52,1018 @@ impl Rule for UnnecessaryCloneRule {
         file_path: &Path,
         syntax_tree: &File,
         _content: &str,
-        _lang: &str,
+        lang: &str,
     ) -> Vec<CodeIssue> {
-        let mut visitor = CloneVisitor::new(file_path.to_path_buf());
+        let mut visitor = CloneVisitor::new(file_path.to_path_buf(), lang);
+        visitor.collect_shared_fields(syntax_tree);
         visitor.visit_file(syntax_tree);
         visitor.issues
     }
 }
 
+/// 函数返回类型的粗略分类，用于判断能否改用 `?`
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ReturnKind {
+    Result,
+    Option,
+    Other,
+}
+
+/// 通常返回 Option 的方法
+const OPTION_METHODS: &[&str] = &[
+    "get",
+    "get_mut",
+    "first",
+    "last",
+    "next",
+    "pop",
+    "find",
+    "position",
+    "max",
+    "min",
+    "checked_add",
+    "checked_sub",
+    "checked_mul",
+    "checked_div",
+    "parent",
+    "file_name",
+    "extension",
+    "to_str",
+    "strip_prefix",
+    "strip_suffix",
+    "split_once",
+    "ok",
+];
+
 struct UnwrapVisitor {
     file_path: std::path::PathBuf,
     issues: Vec<CodeIssue>,
-    unwrap_count: usize,
+    lang: String,
+    file_context: CodeContext,
+    test_depth: usize,
+    main_depth: usize,
+    return_stack: Vec<ReturnKind>,
+    guarded: Vec<String>,
 }
 
 impl UnwrapVisitor {
-    fn new(file_path: std::path::PathBuf) -> Self {
+    fn new(file_path: std::path::PathBuf, lang: &str) -> Self {
+        let file_context = CodeContext::from_path(&file_path);
         Self {
             file_path,
             issues: Vec::new(),
-            unwrap_count: 0,
+            lang: lang.to_string(),
+            file_context,
+            test_depth: 0,
+            main_depth: 0,
+            return_stack: Vec::new(),
+            guarded: Vec::new(),
         }
     }
-}
 
-impl<'ast> Visit<'ast> for UnwrapVisitor {
-    fn visit_expr_method_call(&mut self, method_call: &'ast ExprMethodCall) {
-        if method_call.method == "unwrap" {
-            self.unwrap_count += 1;
-
-            let messages = [
-                "又一个 unwrap()！你是想让程序在生产环境里爆炸吗？",
-                "unwrap() 大师！错误处理是什么？能吃吗？",
-                "看到这个 unwrap()，我仿佛听到了程序崩溃的声音",
-                "unwrap() 使用者，恭喜你获得了'程序炸弹制造专家'称号",
-                "这个 unwrap() 就像定时炸弹，不知道什么时候会爆",
-                "又见 unwrap()！建议使用 match 或 if let，除非你喜欢 panic",
-            ];
-
-            let severity = if self.unwrap_count > 5 {
-                Severity::Nuclear
-            } else if self.unwrap_count > 2 {
-                Severity::Spicy
+    /// #[test] / #[cfg(test)] / tests 目录：unwrap 是惯用写法
+    fn in_test(&self) -> bool {
+        self.file_context == CodeContext::Test || self.test_depth > 0
+    }
+
+    /// main 函数、examples、benches、build.rs：崩溃的代价较低；
+    /// 其余的库代码和业务代码里，panic 会直接甩给调用者
+    fn is_relaxed(&self) -> bool {
+        self.file_context != CodeContext::Production || self.main_depth > 0
+    }
+
+    fn current_return(&self) -> ReturnKind {
+        self.return_stack
+            .last()
+            .copied()
+            .unwrap_or(ReturnKind::Other)
+    }
+
+    fn is_guarded(&self, receiver: &Expr) -> bool {
+        self.guarded.contains(&expr_key(receiver))
+    }
+
+    /// 根据函数返回类型和接收者类型给出 `?` 改写建议
+    fn question_mark_suggestion(&self, receiver: &Expr) -> Option<String> {
+        let zh = self.lang == "zh-CN";
+        match (self.current_return(), receiver_kind(receiver)) {
+            (ReturnKind::Result, Some(ReturnKind::Option)) => Some(if zh {
+                "函数返回 Result，可以用 .ok_or(...)? 传播错误".to_string()
             } else {
-                Severity::Mild
-            };
+                "the function returns Result, use .ok_or(...)? to propagate".to_string()
+            }),
+            (ReturnKind::Option, Some(ReturnKind::Result)) => Some(if zh {
+                "函数返回 Option，可以用 .ok()? 传播".to_string()
+            } else {
+                "the function returns Option, use .ok()? to propagate".to_string()
+            }),
+            (ReturnKind::Result, _) => Some(if zh {
+                "函数返回 Result，直接用 ? 传播错误".to_string()
+            } else {
+                "the function returns Result, just use ?".to_string()
+            }),
+            (ReturnKind::Option, _) => Some(if zh {
+                "函数返回 Option，直接用 ? 传播".to_string()
+            } else {
+                "the function returns Option, just use ?".to_string()
+            }),
+            (ReturnKind::Other, _) => None,
+        }
+    }
+
+    fn check_unwrap(&mut self, method_call: &ExprMethodCall) {
+        let method = method_call.method.to_string();
+        if self.in_test() {
+            return;
+        }
+        let relaxed = self.is_relaxed();
+
+        let zh = self.lang == "zh-CN";
+        let receiver = &*method_call.receiver;
+        // .lock().unwrap() 归 lock-abuse 管，它会讲清楚锁中毒的问题
+        if matches!(method.as_str(), "unwrap" | "expect")
+            && matches!(receiver, Expr::MethodCall(call) if is_lock_call(call))
+        {
+            return;
+        }
+        let suggestion = self.question_mark_suggestion(receiver);
+
+        let (severity, roast_level, advice) = match method.as_str() {
+            "unwrap_unchecked" => {
+                let severity = if relaxed {
+                    Severity::Spicy
+                } else {
+                    Severity::Nuclear
+                };
+                let advice = if zh {
+                    "判断错了就是未定义行为，不是 panic".to_string()
+                } else {
+                    "if you're wrong this is undefined behaviour, not a panic".to_string()
+                };
+                (severity, RoastLevel::Savage, advice)
+            }
+            "unwrap_or_default" => {
+                // 只关心吞掉错误的情况：Result 被静默替换为默认值
+                if relaxed || receiver_kind(receiver) != Some(ReturnKind::Result) {
+                    return;
+                }
+                let advice = suggestion.unwrap_or_else(|| {
+                    if zh {
+                        "错误被默默吞掉了，至少记录一下日志".to_string()
+                    } else {
+                        "the error is silently swallowed, at least log it".to_string()
+                    }
+                });
+                (Severity::Mild, RoastLevel::Gentle, advice)
+            }
+            _ if self.is_guarded(receiver) => {
+                let advice = if zh {
+                    "前面已经检查过了，用 if let / let else 把检查和取值合二为一".to_string()
+                } else {
+                    "already checked above, use if let / let else to check and bind at once"
+                        .to_string()
+                };
+                (Severity::Mild, RoastLevel::Gentle, advice)
+            }
+            _ if relaxed => {
+                let advice = suggestion.unwrap_or_else(|| {
+                    if zh {
+                        "入口代码里还能忍，但给个 expect 说明原因吧".to_string()
+                    } else {
+                        "tolerable in entry-point code, but an expect message would help"
+                            .to_string()
+                    }
+                });
+                (Severity::Mild, RoastLevel::Gentle, advice)
+            }
+            "expect" => match suggestion {
+                Some(advice) => (Severity::Spicy, RoastLevel::Sarcastic, advice),
+                None => {
+                    let advice = if zh {
+                        "至少写明了原因，但调用者依然会 panic".to_string()
+                    } else {
+                        "at least it explains itself, but callers still get a panic".to_string()
+                    };
+                    (Severity::Mild, RoastLevel::Gentle, advice)
+                }
+            },
+            _ => {
+                let advice = suggestion.unwrap_or_else(|| {
+                    if zh {
+                        "用 match、if let 或者让函数返回 Result".to_string()
+                    } else {
+                        "use match, if let, or make the function return Result".to_string()
+                    }
+                });
+                (Severity::Spicy, RoastLevel::Sarcastic, advice)
+            }
+        };
 
-            let roast_level = if self.unwrap_count > 5 {
-                RoastLevel::Savage
+        let messages = if method == "unwrap_or_default" {
+            if zh {
+                vec![
+                    "unwrap_or_default() 把错误变成了默认值，bug 从此隐身".to_string(),
+                    "错误去哪了？被 unwrap_or_default() 吃掉了".to_string(),
+                ]
             } else {
-                RoastLevel::Sarcastic
-            };
+                vec![
+                    "unwrap_or_default() turns errors into defaults, bugs go invisible".to_string(),
+                    "Where did the error go? unwrap_or_default() ate it".to_string(),
+                ]
+            }
+        } else if zh {
+            vec![
+                format!("又一个 {method}()！你是想让程序在生产环境里爆炸吗？"),
+                format!("{method}() 大师！错误处理是什么？能吃吗？"),
+                format!("看到这个 {method}()，我仿佛听到了程序崩溃的声音"),
+                format!("这个 {method}() 就像定时炸弹，不知道什么时候会爆"),
+            ]
+        } else {
+            vec![
+                format!("Another {method}()! Trying to blow up production?"),
+                format!("{method}() master! What is error handling, can you eat it?"),
+                format!("Seeing this {method}(), I can already hear the crash"),
+                format!("This {method}() is a time bomb waiting to go off"),
+            ]
+        };
+
+        let (line, column) = get_position(&method_call.method);
+        self.issues.push(CodeIssue {
+            file_path: self.file_path.clone(),
+            line,
+            column,
+            rule_name: "unwrap-abuse".to_string(),
+            message: format!(
+                "{} ({})",
+                messages[self.issues.len() % messages.len()],
+                advice
+            ),
+            severity,
+            roast_level,
+            context: CodeContext::Production,
+        });
+    }
+
+    fn visit_fn_like(
+        &mut self,
+        attrs: &[Attribute],
+        sig: &Signature,
+        visit_inner: impl FnOnce(&mut Self),
+    ) {
+        let is_test = attrs.iter().any(is_test_attr);
+        let is_main = sig.ident == "main";
+
+        if is_test {
+            self.test_depth += 1;
+        }
+        if is_main {
+            self.main_depth += 1;
+        }
+        self.return_stack.push(return_kind(&sig.output));
+
+        visit_inner(self);
+
+        self.return_stack.pop();
+        if is_main {
+            self.main_depth -= 1;
+        }
+        if is_test {
+            self.test_depth -= 1;
+        }
+    }
+}
+
+impl<'ast> Visit<'ast> for UnwrapVisitor {
+    fn visit_macro(&mut self, mac: &'ast Macro) {
+        visit_macro_args(self, mac);
+    }
 
-            self.issues.push(CodeIssue {
-                file_path: self.file_path.clone(),
-                line: 1, // 简化处理
-                column: 1,
-                rule_name: "unwrap-abuse".to_string(),
-                message: messages[self.issues.len() % messages.len()].to_string(),
-                severity,
-                roast_level,
-            });
+    fn visit_item_mod(&mut self, item_mod: &'ast ItemMod) {
+        let is_test = item_mod.attrs.iter().any(is_cfg_test_attr);
+        if is_test {
+            self.test_depth += 1;
+        }
+        syn::visit::visit_item_mod(self, item_mod);
+        if is_test {
+            self.test_depth -= 1;
+        }
+    }
+
+    fn visit_item_fn(&mut self, item_fn: &'ast ItemFn) {
+        self.visit_fn_like(&item_fn.attrs, &item_fn.sig, |visitor| {
+            syn::visit::visit_item_fn(visitor, item_fn)
+        });
+    }
+
+    fn visit_impl_item_fn(&mut self, impl_fn: &'ast ImplItemFn) {
+        self.visit_fn_like(&impl_fn.attrs, &impl_fn.sig, |visitor| {
+            syn::visit::visit_impl_item_fn(visitor, impl_fn)
+        });
+    }
+
+    fn visit_expr_closure(&mut self, closure: &'ast ExprClosure) {
+        // 闭包里的 ? 作用于闭包本身，返回类型通常未知
+        self.return_stack.push(ReturnKind::Other);
+        syn::visit::visit_expr_closure(self, closure);
+        self.return_stack.pop();
+    }
+
+    fn visit_expr_if(&mut self, expr_if: &'ast ExprIf) {
+        self.visit_expr(&expr_if.cond);
+
+        let then_guards = guard_keys(&expr_if.cond, &["is_some", "is_ok"]);
+        let pushed = then_guards.len();
+        self.guarded.extend(then_guards);
+        self.visit_block(&expr_if.then_branch);
+        self.guarded.truncate(self.guarded.len() - pushed);
+
+        if let Some((_, else_branch)) = &expr_if.else_branch {
+            let else_guards = guard_keys(&expr_if.cond, &["is_none", "is_err"]);
+            let pushed = else_guards.len();
+            self.guarded.extend(else_guards);
+            self.visit_expr(else_branch);
+            self.guarded.truncate(self.guarded.len() - pushed);
+        }
+    }
+
+    fn visit_block(&mut self, block: &'ast Block) {
+        // `if x.is_none() { return; }` 之后，x 在本块剩余部分中都是安全的
+        let mut pushed = 0;
+        for stmt in &block.stmts {
+            self.visit_stmt(stmt);
+            if let Stmt::Expr(Expr::If(expr_if), _) = stmt {
+                if expr_if.else_branch.is_none() && block_diverges(&expr_if.then_branch) {
+                    let guards = guard_keys(&expr_if.cond, &["is_none", "is_err"]);
+                    pushed += guards.len();
+                    self.guarded.extend(guards);
+                }
+            }
+        }
+        self.guarded.truncate(self.guarded.len() - pushed);
+    }
+
+    fn visit_expr_method_call(&mut self, method_call: &'ast ExprMethodCall) {
+        if matches!(
+            method_call.method.to_string().as_str(),
+            "unwrap" | "expect" | "unwrap_unchecked" | "unwrap_or_default"
+        ) {
+            self.check_unwrap(method_call);
         }
 
         syn::visit::visit_expr_method_call(self, method_call);
     }
 }
 
+fn return_kind(output: &ReturnType) -> ReturnKind {
+    if let ReturnType::Type(_, ty) = output {
+        if let Type::Path(type_path) = ty.as_ref() {
+            if let Some(segment) = type_path.path.segments.last() {
+                let name = segment.ident.to_string();
+                if name == "Option" {
+                    return ReturnKind::Option;
+                }
+                // Result、io::Result、anyhow::Result、自定义的 FooResult
+                if name.ends_with("Result") {
+                    return ReturnKind::Result;
+                }
+            }
+        }
+    }
+    ReturnKind::Other
+}
+
+/// 通过方法名粗略推断接收者是 Option 还是 Result
+fn receiver_kind(receiver: &Expr) -> Option<ReturnKind> {
+    let name = match receiver {
+        Expr::MethodCall(call) => call.method.to_string(),
+        Expr::Call(call) => match call.func.as_ref() {
+            Expr::Path(path) => path.path.segments.last()?.ident.to_string(),
+            _ => return None,
+        },
+        Expr::Paren(paren) => return receiver_kind(&paren.expr),
+        _ => return None,
+    };
+
+    if OPTION_METHODS.contains(&name.as_str()) {
+        Some(ReturnKind::Option)
+    } else if RESULT_METHODS.contains(&name.as_str()) {
+        Some(ReturnKind::Result)
+    } else {
+        None
+    }
+}
+
+fn expr_key(expr: &Expr) -> String {
+    quote::quote!(#expr).to_string()
+}
+
+/// 收集条件表达式中 `x.is_some()` 这类检查的接收者
+fn guard_keys(cond: &Expr, methods: &[&str]) -> Vec<String> {
+    match cond {
+        Expr::MethodCall(call) if methods.contains(&call.method.to_string().as_str()) => {
+            vec![expr_key(&call.receiver)]
+        }
+        Expr::Binary(binary) if matches!(binary.op, BinOp::And(_)) => {
+            let mut keys = guard_keys(&binary.left, methods);
+            keys.extend(guard_keys(&binary.right, methods));
+            keys
+        }
+        Expr::Paren(paren) => guard_keys(&paren.expr, methods),
+        _ => Vec::new(),
+    }
+}
+
+/// 块是否以 return / break / continue / panic! 之类结束
+fn block_diverges(block: &Block) -> bool {
+    match block.stmts.last() {
+        Some(Stmt::Expr(expr, _)) => {
+            matches!(expr, Expr::Return(_) | Expr::Break(_) | Expr::Continue(_))
+                || matches!(expr, Expr::Macro(m) if is_diverging_macro(&m.mac))
+        }
+        Some(Stmt::Macro(stmt_macro)) => is_diverging_macro(&stmt_macro.mac),
+        _ => false,
+    }
+}
+
+fn is_diverging_macro(mac: &syn::Macro) -> bool {
+    mac.path.segments.last().is_some_and(|segment| {
+        matches!(
+            segment.ident.to_string().as_str(),
+            "panic" | "unreachable" | "bail" | "todo" | "unimplemented"
+        )
+    })
+}
+
+/// 绑定的粗略类型，用于判断 clone() 的语义
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum BindingKind {
+    /// 普通的拥有所有权的局部变量
+    Owned,
+    /// &str 和字符串字面量：clone() 只复制引用本身
+    Reference,
+    /// &Vec<T>、&String 之类的其他引用：方法调用会自动解引用，clone() 复制的是整个值
+    Borrowed,
+    /// 数字、字符、布尔之类的 Copy 值
+    Copy,
+    Rc,
+    Arc,
+}
+
+/// 只读取数据、按引用接收 self 的常见方法
+const READ_ONLY_METHODS: &[&str] = &[
+    "len",
+    "is_empty",
+    "iter",
+    "as_str",
+    "as_ref",
+    "as_slice",
+    "as_bytes",
+    "contains",
+    "contains_key",
+    "get",
+    "starts_with",
+    "ends_with",
+    "first",
+    "last",
+    "chars",
+    "lines",
+    "to_lowercase",
+    "to_uppercase",
+    "trim",
+    "split",
+    "eq",
+    "cmp",
+    "hash",
+    "fmt",
+];
+
+/// 参数按引用使用的格式化宏
+const FORMAT_MACROS: &[&str] = &[
+    "println",
+    "print",
+    "eprintln",
+    "eprint",
+    "format",
+    "write",
+    "writeln",
+    "panic",
+    "assert",
+    "assert_eq",
+    "assert_ne",
+    "debug",
+    "info",
+    "warn",
+    "error",
+    "trace",
+];
+
 struct CloneVisitor {
     file_path: std::path::PathBuf,
     issues: Vec<CodeIssue>,
-    clone_count: usize,
+    lang: String,
+    bindings: HashMap<String, BindingKind>,
+    shared_fields: HashMap<String, BindingKind>,
+    reported: HashSet<(usize, usize)>,
 }
 
 impl CloneVisitor {
-    fn new(file_path: std::path::PathBuf) -> Self {
+    fn new(file_path: std::path::PathBuf, lang: &str) -> Self {
         Self {
             file_path,
             issues: Vec::new(),
-            clone_count: 0,
+            lang: lang.to_string(),
+            bindings: HashMap::new(),
+            shared_fields: HashMap::new(),
+            reported: HashSet::new(),
         }
     }
+
+    /// 预先收集结构体中 Rc/Arc 类型的字段，用于识别 self.field.clone()
+    fn collect_shared_fields(&mut self, syntax_tree: &File) {
+        for item in &syntax_tree.items {
+            if let Item::Struct(item_struct) = item {
+                for field in &item_struct.fields {
+                    if let (Some(ident), Some(kind)) = (&field.ident, type_kind(&field.ty)) {
+                        if matches!(kind, BindingKind::Rc | BindingKind::Arc) {
+                            self.shared_fields.insert(ident.to_string(), kind);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    fn receiver_kind(&self, receiver: &Expr) -> Option<BindingKind> {
+        match receiver {
+            Expr::Path(_) => self.bindings.get(&path_ident(receiver)?).copied(),
+            Expr::Field(field) if is_self(&field.base) => match &field.member {
+                Member::Named(ident) => self.shared_fields.get(&ident.to_string()).copied(),
+                Member::Unnamed(_) => None,
+            },
+            Expr::Paren(paren) => self.receiver_kind(&paren.expr),
+            _ => None,
+        }
+    }
+
+    fn report(
+        &mut self,
+        method_call: &ExprMethodCall,
+        detail: String,
+        severity: Severity,
+        roast_level: RoastLevel,
+    ) {
+        let (line, column) = get_position(&method_call.method);
+        if !self.reported.insert((line, column)) {
+            return;
+        }
+
+        let messages = if self.lang == "zh-CN" {
+            [
+                "clone() 狂魔！",
+                "你确定不是在写 Java？",
+                "Rust 的借用检查器在哭泣：",
+                "这个 clone() 让我想起了复印机店的老板：",
+            ]
+        } else {
+            [
+                "clone() maniac!",
+                "Are you sure this isn't Java?",
+                "The borrow checker is crying:",
+                "This clone() runs a copy shop:",
+            ]
+        };
+
+        self.issues.push(CodeIssue {
+            file_path: self.file_path.clone(),
+            line,
+            column,
+            rule_name: "unnecessary-clone".to_string(),
+            message: format!(
+                "{} {}",
+                messages[self.issues.len() % messages.len()],
+                detail
+            ),
+            severity,
+            roast_level,
+            context: CodeContext::Production,
+        });
+    }
+
+    /// 只看 clone() 表达式本身就能判断的问题
+    fn check_clone_expr(&mut self, method_call: &ExprMethodCall) {
+        let zh = self.lang == "zh-CN";
+        let receiver = &*method_call.receiver;
+
+        if let Expr::Lit(lit) = receiver {
+            let detail = if matches!(lit.lit, Lit::Str(_)) {
+                if zh {
+                    "对字符串字面量调用 clone() 得到的还是 &str，什么都没复制".to_string()
+                } else {
+                    "cloning a string literal just gives you the same &str back".to_string()
+                }
+            } else if zh {
+                "字面量是 Copy 的，clone() 纯属多余".to_string()
+            } else {
+                "literals are Copy, the clone() does nothing".to_string()
+            };
+            self.report(method_call, detail, Severity::Mild, RoastLevel::Gentle);
+            return;
+        }
+
+        if let Some(producer) = fresh_owned_value(receiver) {
+            let detail = if zh {
+                format!("{producer} 已经产生了新的值，再 clone() 一次是双倍浪费")
+            } else {
+                format!("{producer} already produces a fresh value, cloning it again is double the waste")
+            };
+            self.report(method_call, detail, Severity::Mild, RoastLevel::Sarcastic);
+            return;
+        }
+
+        let name = receiver_name(receiver);
+        match self.receiver_kind(receiver) {
+            Some(BindingKind::Reference) => {
+                let detail = if zh {
+                    format!("'{name}' 本身就是引用，clone() 只是复制了一个引用")
+                } else {
+                    format!("'{name}' is already a reference, clone() just copies the reference")
+                };
+                self.report(method_call, detail, Severity::Mild, RoastLevel::Gentle);
+            }
+            Some(BindingKind::Copy) => {
+                let detail = if zh {
+                    format!("'{name}' 是 Copy 类型，直接用 {name} 就是复制，clone() 纯属多余")
+                } else {
+                    format!("'{name}' is Copy, using {name} already copies it, the clone() does nothing")
+                };
+                self.report(method_call, detail, Severity::Mild, RoastLevel::Gentle);
+            }
+            Some(kind @ (BindingKind::Rc | BindingKind::Arc)) => {
+                let ty = if kind == BindingKind::Rc { "Rc" } else { "Arc" };
+                let detail = if zh {
+                    format!("'{name}' 是 {ty}，用 {ty}::clone(&{name}) 表明这只是增加引用计数")
+                } else {
+                    format!("'{name}' is an {ty}, write {ty}::clone(&{name}) to show it's only a refcount bump")
+                };
+                self.report(method_call, detail, Severity::Mild, RoastLevel::Gentle);
+            }
+            _ => {}
+        }
+    }
+
+    /// 在块的语句序列中检查 clone 与后续使用的关系
+    fn check_block_clones(&mut self, block: &Block) {
+        let zh = self.lang == "zh-CN";
+        let mut owned_locals: HashSet<String> = HashSet::new();
+
+        for (index, stmt) in block.stmts.iter().enumerate() {
+            let rest = &block.stmts[index + 1..];
+
+            // let copy = original.clone(); 之后 copy 只被借用
+            if let Stmt::Local(local) = stmt {
+                if let (Some(copy), Some(init)) = (pat_ident(&local.pat), &local.init) {
+                    if let Expr::MethodCall(call) = init.expr.as_ref() {
+                        if call.method == "clone" && call.args.is_empty() {
+                            let uses = collect_uses(&copy, rest);
+                            if !uses.is_empty()
+                                && uses.iter().all(|use_kind| *use_kind == UseKind::Borrow)
+                                && !original_changes_while_used(&call.receiver, &copy, rest)
+                            {
+                                let original = receiver_name(&call.receiver);
+                                let detail = if zh {
+                                    format!("'{copy}' 复制自 '{original}'，之后却只被借用，直接用 &{original} 就行")
+                                } else {
+                                    format!("'{copy}' is cloned from '{original}' but only ever borrowed, use &{original} instead")
+                                };
+                                self.report(call, detail, Severity::Spicy, RoastLevel::Sarcastic);
+                            }
+                        }
+                    }
+                }
+            }
+
+            // original 在 clone 之后再也没被使用，可以直接 move
+            for call in top_level_clones(stmt) {
+                let Some(original) = path_ident(&call.receiver) else {
+                    continue;
+                };
+                if !owned_locals.contains(&original)
+                    || !collect_uses(&original, rest).is_empty()
+                    || collect_uses(&original, std::slice::from_ref(stmt)).len() != 1
+                {
+                    continue;
+                }
+                let detail = if zh {
+                    format!("这是 '{original}' 的最后一次使用，直接 move 掉，不用 clone")
+                } else {
+                    format!("this is the last use of '{original}', move it instead of cloning")
+                };
+                self.report(call, detail, Severity::Mild, RoastLevel::Sarcastic);
+            }
+
+            if let Stmt::Local(local) = stmt {
+                if let Some(name) = pat_ident(&local.pat) {
+                    if self.bindings.get(&name) == Some(&BindingKind::Owned) {
+                        owned_locals.insert(name);
+                    } else {
+                        owned_locals.remove(&name);
+                    }
+                }
+            }
+        }
+    }
+
+    fn visit_fn_scoped(
+        &mut self,
+        inputs: &Punctuated<FnArg, Token![,]>,
+        visit_inner: impl FnOnce(&mut Self),
+    ) {
+        let saved = std::mem::take(&mut self.bindings);
+        for input in inputs {
+            if let FnArg::Typed(pat_type) = input {
+                if let (Some(name), Some(kind)) =
+                    (pat_ident(&pat_type.pat), type_kind(&pat_type.ty))
+                {
+                    self.bindings.insert(name, kind);
+                }
+            }
+        }
+        visit_inner(self);
+        self.bindings = saved;
+    }
 }
 
 impl<'ast> Visit<'ast> for CloneVisitor {
+    fn visit_macro(&mut self, mac: &'ast Macro) {
+        visit_macro_args(self, mac);
+    }
+
+    fn visit_item_fn(&mut self, item_fn: &'ast ItemFn) {
+        self.visit_fn_scoped(&item_fn.sig.inputs, |visitor| {
+            syn::visit::visit_item_fn(visitor, item_fn)
+        });
+    }
+
+    fn visit_impl_item_fn(&mut self, impl_fn: &'ast ImplItemFn) {
+        self.visit_fn_scoped(&impl_fn.sig.inputs, |visitor| {
+            syn::visit::visit_impl_item_fn(visitor, impl_fn)
+        });
+    }
+
+    fn visit_local(&mut self, local: &'ast Local) {
+        syn::visit::visit_local(self, local);
+
+        if let Some(name) = pat_ident(&local.pat) {
+            let annotated = match &local.pat {
+                Pat::Type(pat_type) => type_kind(&pat_type.ty),
+                _ => None,
+            };
+            let kind = annotated
+                .or_else(|| local.init.as_ref().and_then(|init| init_kind(&init.expr)))
+                .unwrap_or(BindingKind::Owned);
+            self.bindings.insert(name, kind);
+        }
+    }
+
+    fn visit_block(&mut self, block: &'ast Block) {
+        syn::visit::visit_block(self, block);
+        self.check_block_clones(block);
+    }
+
     fn visit_expr_method_call(&mut self, method_call: &'ast ExprMethodCall) {
-        if method_call.method == "clone" {
-            self.clone_count += 1;
-
-            // Simple heuristic detection: if there are multiple clones on the same line or nearby, they might be unnecessary
-            if self.clone_count > 3 {
-                let messages = [
-                    "clone() 狂魔！你是想把内存用完吗？",
-                    "这么多 clone()，你确定不是在写 Java？",
-                    "clone() 使用过度！Rust 的借用检查器在哭泣",
-                    "又见 clone()！也许你需要重新学习一下 Rust 的所有权系统",
-                    "这些 clone() 让我想起了复印机店的老板",
-                ];
-
-                self.issues.push(CodeIssue {
-                    file_path: self.file_path.clone(),
-                    line: 1, // 简化处理
-                    column: 1,
-                    rule_name: "unnecessary-clone".to_string(),
-                    message: messages[self.issues.len() % messages.len()].to_string(),
-                    severity: Severity::Spicy,
-                    roast_level: RoastLevel::Sarcastic,
-                });
-            }
+        if method_call.method == "clone" && method_call.args.is_empty() {
+            self.check_clone_expr(method_call);
         }
 
         syn::visit::visit_expr_method_call(self, method_call);
     }
 }
+
+/// 变量的一次使用是借用还是可能的移动
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum UseKind {
+    Borrow,
+    Other,
+}
+
+struct UseCollector<'a> {
+    name: &'a str,
+    uses: Vec<UseKind>,
+}
+
+impl<'ast> Visit<'ast> for UseCollector<'_> {
+    fn visit_expr(&mut self, expr: &'ast Expr) {
+        match expr {
+            Expr::Reference(reference)
+                if reference.mutability.is_none()
+                    && path_ident(&reference.expr).as_deref() == Some(self.name) =>
+            {
+                self.uses.push(UseKind::Borrow);
+            }
+            Expr::MethodCall(call) if path_ident(&call.receiver).as_deref() == Some(self.name) => {
+                let method = call.method.to_string();
+                self.uses
+                    .push(if READ_ONLY_METHODS.contains(&method.as_str()) {
+                        UseKind::Borrow
+                    } else {
+                        UseKind::Other
+                    });
+                for arg in &call.args {
+                    self.visit_expr(arg);
+                }
+            }
+            Expr::Path(_) if path_ident(expr).as_deref() == Some(self.name) => {
+                self.uses.push(UseKind::Other);
+            }
+            _ => syn::visit::visit_expr(self, expr),
+        }
+    }
+
+    fn visit_macro(&mut self, mac: &'ast Macro) {
+        let is_format = mac
+            .path
+            .get_ident()
+            .is_some_and(|ident| FORMAT_MACROS.contains(&ident.to_string().as_str()));
+
+        if let Ok(args) = mac.parse_body_with(Punctuated::<Expr, Token![,]>::parse_terminated) {
+            for arg in &args {
+                if is_format && path_ident(arg).as_deref() == Some(self.name) {
+                    self.uses.push(UseKind::Borrow);
+                } else {
+                    self.visit_expr(arg);
+                }
+            }
+            return;
+        }
+
+        // 无法解析的宏：按标识符保守计数
+        count_token_uses(mac.tokens.clone(), self.name, &mut self.uses);
+    }
+}
+
+fn count_token_uses(tokens: proc_macro2::TokenStream, name: &str, uses: &mut Vec<UseKind>) {
+    for token in tokens {
+        match token {
+            proc_macro2::TokenTree::Ident(ident) if ident == name => uses.push(UseKind::Other),
+            proc_macro2::TokenTree::Group(group) => count_token_uses(group.stream(), name, uses),
+            _ => {}
+        }
+    }
+}
+
+/// 副本还活着的时候原值被修改或移走了：这个 clone 是有意留的快照
+fn original_changes_while_used(original: &Expr, copy: &str, rest: &[Stmt]) -> bool {
+    let Some(last_use) = rest
+        .iter()
+        .rposition(|stmt| !collect_uses(copy, std::slice::from_ref(stmt)).is_empty())
+    else {
+        return false;
+    };
+    let live = &rest[..=last_use];
+    // self.items.clone() 之类只能追踪到根变量，区间里对它的任何非只读使用都算
+    root_ident(original).is_some_and(|name| collect_uses(&name, live).contains(&UseKind::Other))
+}
+
+fn root_ident(expr: &Expr) -> Option<String> {
+    match expr {
+        Expr::Field(field) => root_ident(&field.base),
+        Expr::Paren(paren) => root_ident(&paren.expr),
+        _ => path_ident(expr),
+    }
+}
+
+fn collect_uses(name: &str, stmts: &[Stmt]) -> Vec<UseKind> {
+    let mut collector = UseCollector {
+        name,
+        uses: Vec::new(),
+    };
+    for stmt in stmts {
+        collector.visit_stmt(stmt);
+    }
+    collector.uses
+}
+
+/// 找出语句中不处于循环或闭包内部的 clone() 调用
+struct TopLevelCloneFinder<'ast> {
+    calls: Vec<&'ast ExprMethodCall>,
+}
+
+impl<'ast> Visit<'ast> for TopLevelCloneFinder<'ast> {
+    fn visit_expr_method_call(&mut self, call: &'ast ExprMethodCall) {
+        if call.method == "clone" && call.args.is_empty() {
+            self.calls.push(call);
+        }
+        syn::visit::visit_expr_method_call(self, call);
+    }
+
+    fn visit_expr_for_loop(&mut self, _: &'ast syn::ExprForLoop) {}
+    fn visit_expr_while(&mut self, _: &'ast syn::ExprWhile) {}
+    fn visit_expr_loop(&mut self, _: &'ast syn::ExprLoop) {}
+    fn visit_expr_closure(&mut self, _: &'ast ExprClosure) {}
+    fn visit_item(&mut self, _: &'ast Item) {}
+}
+
+fn top_level_clones(stmt: &Stmt) -> Vec<&ExprMethodCall> {
+    let mut finder = TopLevelCloneFinder { calls: Vec::new() };
+    finder.visit_stmt(stmt);
+    finder.calls
+}
+
+fn pat_ident(pat: &Pat) -> Option<String> {
+    match pat {
+        Pat::Ident(pat_ident) => Some(pat_ident.ident.to_string()),
+        Pat::Type(pat_type) => pat_ident(&pat_type.pat),
+        _ => None,
+    }
+}
+
+fn path_ident(expr: &Expr) -> Option<String> {
+    match expr {
+        Expr::Path(path) if path.qself.is_none() => {
+            path.path.get_ident().map(|ident| ident.to_string())
+        }
+        _ => None,
+    }
+}
+
+fn is_self(expr: &Expr) -> bool {
+    path_ident(expr).as_deref() == Some("self")
+}
+
+fn receiver_name(receiver: &Expr) -> String {
+    quote::quote!(#receiver).to_string().replace(' ', "")
+}
+
+fn type_kind(ty: &Type) -> Option<BindingKind> {
+    match ty {
+        Type::Reference(reference) => match &*reference.elem {
+            Type::Path(path) if path.path.is_ident("str") => Some(BindingKind::Reference),
+            _ => Some(BindingKind::Borrowed),
+        },
+        Type::Path(type_path) => {
+            let segment = type_path.path.segments.last()?;
+            match segment.ident.to_string().as_str() {
+                "Rc" => Some(BindingKind::Rc),
+                "Arc" => Some(BindingKind::Arc),
+                _ => Some(BindingKind::Owned),
+            }
+        }
+        _ => None,
+    }
+}
+
+/// 从初始化表达式推断绑定的类型
+fn init_kind(init: &Expr) -> Option<BindingKind> {
+    match init {
+        Expr::Reference(_) => Some(BindingKind::Borrowed),
+        Expr::Lit(lit) => match lit.lit {
+            Lit::Str(_) | Lit::ByteStr(_) => Some(BindingKind::Reference),
+            Lit::Int(_) | Lit::Float(_) | Lit::Char(_) | Lit::Bool(_) | Lit::Byte(_) => {
+                Some(BindingKind::Copy)
+            }
+            _ => None,
+        },
+        Expr::Call(call) => {
+            let Expr::Path(func) = call.func.as_ref() else {
+                return None;
+            };
+            let segments: Vec<String> = func
+                .path
+                .segments
+                .iter()
+                .map(|segment| segment.ident.to_string())
+                .collect();
+            match segments.iter().rev().nth(1).map(String::as_str) {
+                Some("Rc") => Some(BindingKind::Rc),
+                Some("Arc") => Some(BindingKind::Arc),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+/// 会产生全新拥有值的表达式：对它们再 clone 毫无意义
+fn fresh_owned_value(expr: &Expr) -> Option<String> {
+    match expr {
+        Expr::MethodCall(call)
+            if matches!(
+                call.method.to_string().as_str(),
+                "to_string" | "to_owned" | "to_vec" | "clone" | "collect"
+            ) =>
+        {
+            Some(format!(".{}()", call.method))
+        }
+        Expr::Macro(expr_macro)
+            if expr_macro
+                .mac
+                .path
+                .get_ident()
+                .is_some_and(|ident| ident == "format" || ident == "vec") =>
+        {
+            let path = &expr_macro.mac.path;
+            Some(format!("{}!", quote::quote!(#path)))
+        }
+        _ => None,
+    }
+}
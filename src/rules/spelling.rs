@@ -0,0 +1,613 @@
+use std::collections::HashSet;
+use std::path::Path;
+use std::sync::OnceLock;
+use syn::File;
+
+use crate::analyzer::{CodeContext, CodeIssue, RoastLevel, Severity};
+use crate::rules::bindings::{collect_bindings, Binding};
+use crate::rules::naming::split_words;
+use crate::rules::Rule;
+
+/// 检测标识符里的拼写错误：recieve, lenght, adress
+pub struct IdentifierTypoRule {
+    custom_words: HashSet<String>,
+}
+
+impl IdentifierTypoRule {
+    /// `custom_words` 是项目自己的词汇（产品名、领域术语），不区分大小写
+    pub fn new(custom_words: &[String]) -> Self {
+        Self {
+            custom_words: custom_words
+                .iter()
+                .map(|word| word.trim().to_lowercase())
+                .filter(|word| !word.is_empty())
+                .collect(),
+        }
+    }
+}
+
+impl Rule for IdentifierTypoRule {
+    fn name(&self) -> &'static str {
+        "identifier-typo"
+    }
+
+    fn check(
+        &self,
+        file_path: &Path,
+        syntax_tree: &File,
+        _content: &str,
+        lang: &str,
+    ) -> Vec<CodeIssue> {
+        let mut checker = SpellChecker {
+            file_path: file_path.to_path_buf(),
+            issues: Vec::new(),
+            lang: lang.to_string(),
+            dictionary: Dictionary::bundled(),
+            custom_words: &self.custom_words,
+        };
+        checker.check_bindings(&collect_bindings(syntax_tree));
+        checker.issues
+    }
+}
+
+// ============================================================================
+// 词典
+// ============================================================================
+
+/// 英文词表，常用词在前；来源、许可证和生成脚本见 spelling/README.md
+const ENGLISH_WORDS: &str = include_str!("spelling/english.txt");
+
+/// 英文词典不认识的 Rust 和编程术语
+const JARGON_WORDS: &str = include_str!("spelling/jargon.txt");
+
+/// 代码里最常见的拼写错误和正确写法，命中时不用再靠编辑距离猜
+const COMMON_TYPOS: &[(&str, &str)] = &[
+    ("accesible", "accessible"),
+    ("accomodate", "accommodate"),
+    ("accross", "across"),
+    ("acess", "access"),
+    ("acheive", "achieve"),
+    ("adress", "address"),
+    ("agressive", "aggressive"),
+    ("algoritm", "algorithm"),
+    ("allready", "already"),
+    ("alocate", "allocate"),
+    ("alogrithm", "algorithm"),
+    ("ambigious", "ambiguous"),
+    ("anonymus", "anonymous"),
+    ("aquire", "acquire"),
+    ("arbitary", "arbitrary"),
+    ("arguement", "argument"),
+    ("asign", "assign"),
+    ("assosiate", "associate"),
+    ("asyncronous", "asynchronous"),
+    ("atribute", "attribute"),
+    ("attribtue", "attribute"),
+    ("availabe", "available"),
+    ("availible", "available"),
+    ("avaliable", "available"),
+    ("beggining", "beginning"),
+    ("begining", "beginning"),
+    ("beleive", "believe"),
+    ("bounday", "boundary"),
+    ("buffor", "buffer"),
+    ("cacluate", "calculate"),
+    ("calcualte", "calculate"),
+    ("calender", "calendar"),
+    ("charachter", "character"),
+    ("charater", "character"),
+    ("childs", "children"),
+    ("chosing", "choosing"),
+    ("colum", "column"),
+    ("comand", "command"),
+    ("commited", "committed"),
+    ("comparision", "comparison"),
+    ("compatability", "compatibility"),
+    ("compatable", "compatible"),
+    ("compilier", "compiler"),
+    ("completly", "completely"),
+    ("concurent", "concurrent"),
+    ("conditon", "condition"),
+    ("conenction", "connection"),
+    ("configuraiton", "configuration"),
+    ("connecton", "connection"),
+    ("consistant", "consistent"),
+    ("containg", "containing"),
+    ("continous", "continuous"),
+    ("contoller", "controller"),
+    ("convertion", "conversion"),
+    ("coordiante", "coordinate"),
+    ("copmlete", "complete"),
+    ("corectly", "correctly"),
+    ("coresponding", "corresponding"),
+    ("curent", "current"),
+    ("currrent", "current"),
+    ("defualt", "default"),
+    ("definately", "definitely"),
+    ("definiton", "definition"),
+    ("delimeter", "delimiter"),
+    ("dependancy", "dependency"),
+    ("dependecy", "dependency"),
+    ("depedency", "dependency"),
+    ("desciption", "description"),
+    ("descripton", "description"),
+    ("destory", "destroy"),
+    ("determin", "determine"),
+    ("diffrent", "different"),
+    ("dimention", "dimension"),
+    ("directroy", "directory"),
+    ("dissapear", "disappear"),
+    ("docuemnt", "document"),
+    ("duplicat", "duplicate"),
+    ("efficent", "efficient"),
+    ("elemnt", "element"),
+    ("enviroment", "environment"),
+    ("environemnt", "environment"),
+    ("equivelent", "equivalent"),
+    ("exection", "execution"),
+    ("exeption", "exception"),
+    ("existance", "existence"),
+    ("existant", "existent"),
+    ("explict", "explicit"),
+    ("extention", "extension"),
+    ("faild", "failed"),
+    ("filname", "filename"),
+    ("finsh", "finish"),
+    ("folowing", "following"),
+    ("formated", "formatted"),
+    ("fucntion", "function"),
+    ("funciton", "function"),
+    ("funtion", "function"),
+    ("garantee", "guarantee"),
+    ("guarentee", "guarantee"),
+    ("heigth", "height"),
+    ("hierachy", "hierarchy"),
+    ("identifer", "identifier"),
+    ("implemention", "implementation"),
+    ("implmentation", "implementation"),
+    ("inital", "initial"),
+    ("initalize", "initialize"),
+    ("inteface", "interface"),
+    ("interupt", "interrupt"),
+    ("intial", "initial"),
+    ("intialize", "initialize"),
+    ("intialized", "initialized"),
+    ("iterater", "iterator"),
+    ("itterate", "iterate"),
+    ("lable", "label"),
+    ("langauge", "language"),
+    ("lengh", "length"),
+    ("lenght", "length"),
+    ("libary", "library"),
+    ("libray", "library"),
+    ("lisence", "license"),
+    ("maintainance", "maintenance"),
+    ("mannually", "manually"),
+    ("maxium", "maximum"),
+    ("mesage", "message"),
+    ("messsage", "message"),
+    ("minumum", "minimum"),
+    ("mising", "missing"),
+    ("nubmer", "number"),
+    ("numer", "number"),
+    ("occurence", "occurrence"),
+    ("occured", "occurred"),
+    ("occuring", "occurring"),
+    ("ommit", "omit"),
+    ("ommitted", "omitted"),
+    ("operaton", "operation"),
+    ("optinal", "optional"),
+    ("orignal", "original"),
+    ("outout", "output"),
+    ("overriden", "overridden"),
+    ("paramater", "parameter"),
+    ("paramter", "parameter"),
+    ("parrallel", "parallel"),
+    ("passowrd", "password"),
+    ("pasword", "password"),
+    ("perfomance", "performance"),
+    ("permision", "permission"),
+    ("pervious", "previous"),
+    ("posible", "possible"),
+    ("postion", "position"),
+    ("precendence", "precedence"),
+    ("preferance", "preference"),
+    ("prefered", "preferred"),
+    ("previuos", "previous"),
+    ("priviledge", "privilege"),
+    ("proccess", "process"),
+    ("proces", "process"),
+    ("protoype", "prototype"),
+    ("quere", "query"),
+    ("querry", "query"),
+    ("reciept", "receipt"),
+    ("recieve", "receive"),
+    ("recieved", "received"),
+    ("reciever", "receiver"),
+    ("recieving", "receiving"),
+    ("recomend", "recommend"),
+    ("recursivly", "recursively"),
+    ("refered", "referred"),
+    ("refference", "reference"),
+    ("relevent", "relevant"),
+    ("remvoe", "remove"),
+    ("reponse", "response"),
+    ("repositry", "repository"),
+    ("requrest", "request"),
+    ("resouce", "resource"),
+    ("respone", "response"),
+    ("responce", "response"),
+    ("retreive", "retrieve"),
+    ("retrive", "retrieve"),
+    ("reuslt", "result"),
+    ("seperate", "separate"),
+    ("seperator", "separator"),
+    ("sequnce", "sequence"),
+    ("serach", "search"),
+    ("shoud", "should"),
+    ("sigle", "single"),
+    ("simliar", "similar"),
+    ("sotre", "store"),
+    ("specifed", "specified"),
+    ("speficic", "specific"),
+    ("stirng", "string"),
+    ("strnig", "string"),
+    ("succesful", "successful"),
+    ("successfull", "successful"),
+    ("sucess", "success"),
+    ("suport", "support"),
+    ("supress", "suppress"),
+    ("syncronize", "synchronize"),
+    ("temperatue", "temperature"),
+    ("threshhold", "threshold"),
+    ("timout", "timeout"),
+    ("tranform", "transform"),
+    ("transaciton", "transaction"),
+    ("truely", "truly"),
+    ("udpate", "update"),
+    ("unkown", "unknown"),
+    ("untill", "until"),
+    ("usefull", "useful"),
+    ("vaildate", "validate"),
+    ("valdiate", "validate"),
+    ("valiable", "variable"),
+    ("varaible", "variable"),
+    ("varible", "variable"),
+    ("verison", "version"),
+    ("visable", "visible"),
+    ("widht", "width"),
+    ("wierd", "weird"),
+    ("writen", "written"),
+];
+
+/// 词形变化：去掉后缀之后还原出的词根要在词典里
+const SUFFIXES: &[&str] = &[
+    "ments", "ment", "ness", "able", "ible", "ings", "ing", "ions", "ion", "ity", "ers", "er",
+    "est", "ies", "ied", "ed", "es", "ly", "al", "s", "d",
+];
+
+/// 加在已知单词前面仍然是合法写法的前缀：`unparsed`、`reindex`、`subtree`
+const PREFIXES: &[&str] = &[
+    "multi", "inter", "super", "over", "sub", "pre", "non", "un", "re",
+];
+
+/// 太短的单词大多是缩写（`ctx`、`buf`、`tcp`），交给 AbbreviationAbuseRule
+const MIN_WORD_LEN: usize = 5;
+
+/// 词典前多少个词算常用词
+const COMMON_WORDS: usize = 4000;
+
+/// 最多给出几个候选词
+const MAX_SUGGESTIONS: usize = 3;
+
+struct Dictionary {
+    words: HashSet<&'static str>,
+    /// 最常用的那部分词，复合词里两三个字母的片段（`ptr`、`vec`、`row`）只认这些
+    common: HashSet<&'static str>,
+    /// 候选词，常用词在前，用来给建议排序
+    ranked: Vec<&'static str>,
+}
+
+impl Dictionary {
+    fn bundled() -> &'static Dictionary {
+        static DICTIONARY: OnceLock<Dictionary> = OnceLock::new();
+        DICTIONARY.get_or_init(|| {
+            let ranked: Vec<&'static str> = JARGON_WORDS
+                .lines()
+                .chain(ENGLISH_WORDS.lines())
+                .map(str::trim)
+                .filter(|line| !line.is_empty() && !line.starts_with('#'))
+                .collect();
+            Dictionary {
+                words: ranked.iter().copied().collect(),
+                common: ranked.iter().copied().take(COMMON_WORDS).collect(),
+                ranked,
+            }
+        })
+    }
+}
+
+/// 常用英文词，拼音检查用它区分 `name` 这种恰好能切成拼音音节的英文单词
+pub(crate) fn is_common_word(word: &str) -> bool {
+    Dictionary::bundled().common.contains(word)
+}
+
+/// 拼写检查器按单词查字典，每个名字最多报一次
+struct SpellChecker<'a> {
+    file_path: std::path::PathBuf,
+    issues: Vec<CodeIssue>,
+    lang: String,
+    dictionary: &'static Dictionary,
+    custom_words: &'a HashSet<String>,
+}
+
+impl SpellChecker<'_> {
+    fn contains(&self, word: &str) -> bool {
+        self.dictionary.words.contains(word) || self.custom_words.contains(word)
+    }
+
+    /// 词典里的词、加前缀的派生词、多一个字母前后缀的变量名（`nexti`、`gfile`），
+    /// 以及两个已知片段粘成的复合词（`rowptr`、`inlinevec`）
+    fn is_known(&self, word: &str) -> bool {
+        self.is_word_form(word)
+            || PREFIXES.iter().any(|prefix| {
+                word.strip_prefix(prefix)
+                    .is_some_and(|rest| rest.len() >= 3 && self.is_word_form(rest))
+            })
+            || [&word[1..], &word[..word.len() - 1]]
+                .iter()
+                .any(|rest| rest.len() >= 4 && self.is_word_form(rest))
+            || (2..=word.len() - 2).any(|split| {
+                let (head, tail) = word.split_at(split);
+                self.is_compound_part(head) && self.is_compound_part(tail)
+            })
+    }
+
+    /// 太短的片段要足够常用，否则 `timout` 也能拼成 `tim` + `out`
+    fn is_compound_part(&self, part: &str) -> bool {
+        if part.len() >= 4 {
+            self.is_word_form(part)
+        } else {
+            self.dictionary.common.contains(part) || self.custom_words.contains(part)
+        }
+    }
+
+    fn is_word_form(&self, word: &str) -> bool {
+        // 词典里只有派生形式也算认识：`clumps` -> `clump`，`inscribed` -> `inscribe`
+        self.contains(word)
+            || ["s", "d", "ed"]
+                .iter()
+                .any(|suffix| self.contains(&format!("{word}{suffix}")))
+            || SUFFIXES.iter().any(|suffix| {
+                word.strip_suffix(suffix)
+                    .filter(|stem| stem.len() >= 3)
+                    .is_some_and(|stem| self.is_stem(stem, suffix))
+            })
+    }
+
+    /// `parsing` -> `parse`，`mapped` -> `map`，`copies` -> `copy`
+    fn is_stem(&self, stem: &str, suffix: &str) -> bool {
+        if self.contains(stem) || self.contains(&format!("{stem}e")) {
+            return true;
+        }
+        if matches!(suffix, "ies" | "ied") && self.contains(&format!("{stem}y")) {
+            return true;
+        }
+        let bytes = stem.as_bytes();
+        bytes.len() >= 4
+            && bytes[bytes.len() - 1] == bytes[bytes.len() - 2]
+            && self.contains(&stem[..stem.len() - 1])
+    }
+
+    /// 常见错别字表，也认带词尾的写法：`recieves` -> `receives`
+    fn known_typo(word: &str) -> Option<String> {
+        COMMON_TYPOS.iter().find_map(|&(typo, correct)| {
+            let rest = word.strip_prefix(typo)?;
+            ["", "s", "es", "ed", "ing", "er", "ers", "ly"]
+                .contains(&rest)
+                .then(|| format!("{correct}{rest}"))
+        })
+    }
+
+    /// 编辑距离最近的几个词典词；首字母很少打错，只在同一个首字母里找
+    fn suggestions(&self, word: &str) -> Vec<String> {
+        let max_distance = if word.len() >= 8 { 2 } else { 1 };
+        let mut candidates: Vec<(usize, &str)> = self
+            .dictionary
+            .ranked
+            .iter()
+            .copied()
+            .chain(self.custom_words.iter().map(String::as_str))
+            .filter(|candidate| {
+                candidate.len() >= MIN_WORD_LEN - 1
+                    && candidate.as_bytes()[0] == word.as_bytes()[0]
+                    && candidate.len().abs_diff(word.len()) <= max_distance
+            })
+            .filter_map(|candidate| {
+                let distance = edit_distance(word, candidate);
+                (distance <= max_distance).then_some((distance, candidate))
+            })
+            .collect();
+        // 距离相同时保持词频顺序
+        candidates.sort_by_key(|&(distance, _)| distance);
+
+        let mut suggestions: Vec<String> = Vec::new();
+        for (_, candidate) in candidates {
+            if !suggestions.iter().any(|s| s == candidate) {
+                suggestions.push(candidate.to_string());
+            }
+            if suggestions.len() == MAX_SUGGESTIONS {
+                break;
+            }
+        }
+        suggestions
+    }
+
+    /// 返回名字里的错别字和候选词；不认识但也找不到相近词的多半是专有名词，不报
+    fn misspellings(&self, name: &str) -> Vec<(String, Vec<String>)> {
+        let has_lowercase = name.chars().any(|c| c.is_ascii_lowercase());
+        split_words(name)
+            .into_iter()
+            .filter(|word| {
+                word.len() >= MIN_WORD_LEN && word.chars().all(|c| c.is_ascii_alphabetic())
+            })
+            // 全大写的单词多半是缩写：`XMLParser` 里的 `XML`，`CAVLC` 这样的常量
+            .filter(|word| {
+                word.chars().any(|c| c.is_ascii_lowercase())
+                    || (!has_lowercase && word.len() > MIN_WORD_LEN)
+            })
+            .map(|word| word.to_lowercase())
+            .filter_map(|word| {
+                // 表里的错别字即使能拆成已知片段（`adress` = `a` + `dress`）也要报，
+                // 但本身是正经单词的不算：`process` 不是 `proces` + `s`
+                let suggestions = match Self::known_typo(&word) {
+                    Some(_) if self.is_word_form(&word) => return None,
+                    Some(correct) => vec![correct],
+                    None if self.is_known(&word) => return None,
+                    None => self.suggestions(&word),
+                };
+                (!suggestions.is_empty()).then_some((word, suggestions))
+            })
+            .collect()
+    }
+
+    /// 把每个错别字换成它的第一个候选词，分隔符和每个单词的大小写保持原样
+    fn corrected_name(name: &str, typos: &[(String, Vec<String>)]) -> String {
+        let mut fixed = String::new();
+        let mut rest = name;
+        for word in split_words(name) {
+            let start = rest.find(word.as_str()).unwrap_or(0);
+            fixed.push_str(&rest[..start]);
+            let lower = word.to_lowercase();
+            match typos.iter().find(|(typo, _)| *typo == lower) {
+                Some((_, suggestions)) => fixed.push_str(&match_case(&word, &suggestions[0])),
+                None => fixed.push_str(&word),
+            }
+            rest = &rest[start + word.len()..];
+        }
+        fixed.push_str(rest);
+        fixed
+    }
+
+    /// 一个名字只报一次，把里面所有错别字连同候选词一起列出来
+    fn create_issue(&self, binding: &Binding, typos: &[(String, Vec<String>)]) -> CodeIssue {
+        let name = binding.name.as_str();
+        let kind = binding.kind.label(&self.lang);
+        let fixed = Self::corrected_name(name, typos);
+        let quoted = |suggestions: &[String], separator: &str| {
+            suggestions
+                .iter()
+                .map(|s| format!("'{}'", s))
+                .collect::<Vec<_>>()
+                .join(separator)
+        };
+
+        let mut message = if self.lang == "zh-CN" {
+            let list = typos
+                .iter()
+                .map(|(typo, suggestions)| {
+                    format!("'{}' 应该是 {}", typo, quoted(suggestions, "、"))
+                })
+                .collect::<Vec<_>>()
+                .join("，");
+            let messages = [
+                format!("{} '{}' 拼错了：{}", kind, name, list),
+                format!("'{}' 里有错别字：{}", name, list),
+                format!("'{}' 没过拼写检查：{}", name, list),
+            ];
+            messages[self.issues.len() % messages.len()].clone()
+        } else {
+            let list = typos
+                .iter()
+                .map(|(typo, suggestions)| {
+                    format!("'{}' should be {}", typo, quoted(suggestions, " or "))
+                })
+                .collect::<Vec<_>>()
+                .join(", ");
+            let messages = [
+                format!("{} '{}' is misspelled: {}", kind, name, list),
+                format!(
+                    "'{}' contains typos the dictionary disagrees with: {}",
+                    name, list
+                ),
+                format!("Typos in '{}': {}", name, list),
+            ];
+            messages[self.issues.len() % messages.len()].clone()
+        };
+        if fixed != name {
+            if self.lang == "zh-CN" {
+                message += &format!("；建议改成 '{}'", fixed);
+            } else {
+                message += &format!("; try '{}'", fixed);
+            }
+        }
+
+        // 局部变量改起来容易，条目名一旦公开就成了 API
+        let severity = if binding.kind.is_local() {
+            Severity::Mild
+        } else {
+            Severity::Spicy
+        };
+
+        CodeIssue {
+            file_path: self.file_path.clone(),
+            line: binding.line,
+            column: binding.column,
+            rule_name: "identifier-typo".to_string(),
+            message: message + &binding.uses_note(&self.lang),
+            severity,
+            roast_level: RoastLevel::Gentle,
+            context: CodeContext::Production,
+        }
+    }
+
+    fn check_bindings(&mut self, bindings: &[Binding]) {
+        for binding in bindings {
+            let typos = self.misspellings(&binding.name);
+            if !typos.is_empty() {
+                self.issues.push(self.create_issue(binding, &typos));
+            }
+        }
+    }
+}
+
+/// 按原单词的大小写写出替换词：`LENGHT` -> `LENGTH`，`Lenght` -> `Length`
+fn match_case(original: &str, word: &str) -> String {
+    if original.chars().all(|c| c.is_ascii_uppercase()) {
+        word.to_uppercase()
+    } else if original.starts_with(|c: char| c.is_ascii_uppercase()) {
+        let mut chars = word.chars();
+        chars
+            .next()
+            .map(|first| first.to_ascii_uppercase().to_string() + chars.as_str())
+            .unwrap_or_default()
+    } else {
+        word.to_string()
+    }
+}
+
+/// 带相邻交换的编辑距离（OSA），`lenght` 和 `length` 只差一步
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a = a.as_bytes();
+    let b = b.as_bytes();
+    let mut prev2 = vec![0; b.len() + 1];
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    let mut current = vec![0; b.len() + 1];
+
+    for i in 1..=a.len() {
+        current[0] = i;
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            current[j] = (prev[j] + 1)
+                .min(current[j - 1] + 1)
+                .min(prev[j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                current[j] = current[j].min(prev2[j - 2] + 1);
+            }
+        }
+        std::mem::swap(&mut prev2, &mut prev);
+        std::mem::swap(&mut prev, &mut current);
+    }
+
+    prev[b.len()]
+}
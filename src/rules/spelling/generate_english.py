@@ -0,0 +1,79 @@
+#!/usr/bin/env python3
+"""Regenerate english.txt from SCOWL (Spell Checker Oriented Word Lists).
+
+The archive's Copyright file is copied to SCOWL-LICENSE next to this script.
+
+SCOWL sorts words into size levels (10, 20, 35, 40, 50, 55, 60, ...), smaller
+levels holding more common words. The output keeps that order, so the first
+lines of english.txt are the most common words, which is what the spell
+checker relies on for its common-word set and for ranking suggestions.
+
+Usage:
+    python3 src/rules/spelling/generate_english.py > src/rules/spelling/english.txt
+    python3 src/rules/spelling/generate_english.py path/to/scowl-2020.12.07.tar.gz > ...
+"""
+
+import pathlib
+import sys
+import tarfile
+import urllib.request
+
+SCOWL_VERSION = "2020.12.07"
+SCOWL_URL = f"https://downloads.sourceforge.net/wordlist/scowl-{SCOWL_VERSION}.tar.gz"
+
+# 60 是 SCOWL 推荐给拼写检查器的大小，再往上就是生僻词了
+MAX_LEVEL = 60
+
+# 只要普通单词：不要专有名词（*-upper）、缩写、所有格（*-contractions）
+CATEGORIES = ["english-words", "american-words", "british-words"]
+
+
+def levels(archive):
+    """final/ 目录下要用的词表按大小级别分组：{级别: [成员]}"""
+    grouped = {}
+    for member in archive.getmembers():
+        parts = member.name.split("/")
+        if len(parts) != 3 or parts[1] != "final":
+            continue
+        category, _, level = parts[2].rpartition(".")
+        if category in CATEGORIES and level.isdigit() and int(level) <= MAX_LEVEL:
+            grouped.setdefault(int(level), []).append(member)
+    return grouped
+
+
+def main():
+    if len(sys.argv) > 1:
+        path = sys.argv[1]
+    else:
+        path, _ = urllib.request.urlretrieve(SCOWL_URL)
+    archive = tarfile.open(path)
+
+    # 许可证原文跟词表放在一起，SCOWL 要求随词表一起分发
+    notice = archive.extractfile(f"scowl-{SCOWL_VERSION}/Copyright").read()
+    (pathlib.Path(__file__).parent / "SCOWL-LICENSE").write_bytes(notice)
+
+    seen = set()
+    words = []
+    for level, members in sorted(levels(archive).items()):
+        level_words = set()
+        for member in members:
+            text = archive.extractfile(member).read().decode("iso-8859-1")
+            # 词典按小写 ASCII 单词查，带撇号、重音和大写字母的都跳过
+            level_words.update(
+                word
+                for word in map(str.strip, text.splitlines())
+                if word.isascii() and word.isalpha() and word.islower()
+            )
+        # 同一级别里没有词频，按字母排；低级别出现过的词不再重复
+        words.extend(sorted(level_words - seen))
+        seen |= level_words
+
+    print(f"# English words from SCOWL {SCOWL_VERSION} (size <= {MAX_LEVEL}), most common first")
+    print("# Copyright Kevin Atkinson and the SCOWL contributors, see SCOWL-LICENSE")
+    print("# Generated by generate_english.py, do not edit by hand")
+    for word in words:
+        print(word)
+
+
+if __name__ == "__main__":
+    main()
@@ -1,11 +1,18 @@
 use std::path::Path;
-use syn::{visit::Visit, ExprMacro, File};
-
-use crate::analyzer::{CodeIssue, RoastLevel, Severity};
-use crate::rules::Rule;
+use syn::{
+    visit::Visit, Expr, ExprLit, ExprMacro, ExprMethodCall, File, Item, Lit, Local, Macro, Pat,
+};
+
+use crate::analyzer::{CodeContext, CodeIssue, RoastLevel, Severity};
+use crate::rules::common::is_library_file;
+use crate::rules::macro_args::{parse_macro_args, visit_macro_args};
+use crate::rules::{ContextMap, ContextPolicy, Rule};
 use crate::utils::get_position;
 
-/// 检测到处都是 println! 调试语句
+/// 检测遗留的调试输出：print!/println!/eprint!/eprintln!/dbg! 和直接写 stdout/stderr
+///
+/// 库代码不该替调用者往标准输出写东西，每一处都报；二进制（main.rs、src/bin/）
+/// 输出是本职工作，只报看起来像调试的那些。dbg! 不管在哪都是忘了删的。
 pub struct PrintlnDebuggingRule;
 
 impl Rule for PrintlnDebuggingRule {
@@ -13,26 +20,54 @@ impl Rule for PrintlnDebuggingRule {
         "println-debugging"
     }
 
+    fn context_policy(&self, context: CodeContext) -> ContextPolicy {
+        match context {
+            // 示例和构建脚本本来就要输出（build.rs 靠 println!("cargo:...") 和 cargo 通信）
+            CodeContext::Test | CodeContext::Example | CodeContext::BuildScript => {
+                ContextPolicy::Ignore
+            }
+            CodeContext::Bench => ContextPolicy::Downgrade,
+            CodeContext::Production => ContextPolicy::Report,
+        }
+    }
+
     fn check(
         &self,
         file_path: &Path,
         syntax_tree: &File,
-        content: &str,
+        _content: &str,
         lang: &str,
     ) -> Vec<CodeIssue> {
-        let mut visitor = PrintlnDebuggingVisitor::new(file_path.to_path_buf(), lang);
+        let binary = is_binary_target(file_path, syntax_tree);
+        let mut visitor = PrintlnDebuggingVisitor::new(file_path.to_path_buf(), lang, binary);
         visitor.visit_file(syntax_tree);
 
-        // 同时检查内容中的 println! 数量
-        let println_count = content.matches("println!").count();
-        if println_count > 5 {
-            visitor.add_excessive_println_issue(println_count);
+        // 库代码里的输出太多再加一条文件级的问题，测试代码里的不算
+        if !binary {
+            let contexts = ContextMap::new(file_path, syntax_tree);
+            let output_count = visitor
+                .output_lines
+                .iter()
+                .filter(|&&line| !contexts.context_at(line).is_test_code())
+                .count();
+            if output_count > 5 {
+                visitor.add_excessive_println_issue(output_count);
+            }
         }
 
         visitor.issues
     }
 }
 
+/// main.rs、src/bin/ 下的文件、纯二进制 crate 的模块，以及顶层有 `fn main` 的文件都算二进制
+fn is_binary_target(file_path: &Path, syntax_tree: &File) -> bool {
+    !is_library_file(file_path)
+        || syntax_tree
+            .items
+            .iter()
+            .any(|item| matches!(item, Item::Fn(item_fn) if item_fn.sig.ident == "main"))
+}
+
 /// 检测随意使用 panic! 和 unwrap()
 pub struct PanicAbuseRule;
 
@@ -41,6 +76,17 @@ impl Rule for PanicAbuseRule {
         "panic-abuse"
     }
 
+    fn context_policy(&self, context: CodeContext) -> ContextPolicy {
+        match context {
+            // 测试就是靠 panic 来失败的
+            CodeContext::Test => ContextPolicy::Ignore,
+            CodeContext::Example | CodeContext::Bench | CodeContext::BuildScript => {
+                ContextPolicy::Downgrade
+            }
+            CodeContext::Production => ContextPolicy::Report,
+        }
+    }
+
     fn check(
         &self,
         file_path: &Path,
@@ -51,9 +97,16 @@ impl Rule for PanicAbuseRule {
         let mut visitor = PanicAbuseVisitor::new(file_path.to_path_buf(), lang);
         visitor.visit_file(syntax_tree);
 
-        // 检查内容中的 panic! 和 unwrap 使用
-        let panic_count = content.matches("panic!").count();
-        let unwrap_count = content.matches(".unwrap()").count();
+        // 检查内容中的 panic! 和 unwrap 使用，测试代码里的不算
+        let contexts = ContextMap::new(file_path, syntax_tree);
+        let count = |pattern: &str| -> usize {
+            contexts
+                .non_test_lines(content)
+                .map(|line| line.matches(pattern).count())
+                .sum()
+        };
+        let panic_count = count("panic!");
+        let unwrap_count = count(".unwrap()");
 
         if panic_count > 2 {
             visitor.add_excessive_panic_issue(panic_count);
@@ -66,115 +119,73 @@ impl Rule for PanicAbuseRule {
     }
 }
 
-/// 检测过多的 TODO 注释
-pub struct TodoCommentRule;
+// ============================================================================
+// Visitor 实现
+// ============================================================================
 
-impl Rule for TodoCommentRule {
-    fn name(&self) -> &'static str {
-        "todo-comment"
-    }
+/// 调试输出的种类
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum OutputKind {
+    /// print! / println!
+    Stdout,
+    /// eprint! / eprintln!
+    Stderr,
+    Dbg,
+}
 
-    fn check(
-        &self,
-        file_path: &Path,
-        _syntax_tree: &File,
-        content: &str,
-        lang: &str,
-    ) -> Vec<CodeIssue> {
-        let mut issues = Vec::new();
-
-        // 检查各种 TODO 模式
-        let todo_patterns = [
-            "TODO",
-            "FIXME",
-            "XXX",
-            "HACK",
-            "BUG",
-            "NOTE",
-            "todo!",
-            "unimplemented!",
-            "unreachable!",
-        ];
-
-        let mut total_todos = 0;
-        for pattern in &todo_patterns {
-            total_todos += content.matches(pattern).count();
+impl OutputKind {
+    fn from_macro(name: &str) -> Option<Self> {
+        match name {
+            "print" | "println" => Some(OutputKind::Stdout),
+            "eprint" | "eprintln" => Some(OutputKind::Stderr),
+            "dbg" => Some(OutputKind::Dbg),
+            _ => None,
         }
+    }
 
-        if total_todos > 5 {
-            let messages = if lang == "zh-CN" {
-                vec![
-                    format!("发现 {} 个 TODO/FIXME，这是代码还是购物清单？", total_todos),
-                    format!("{} 个未完成项目？你是在写代码还是在记日记？", total_todos),
-                    format!("TODO 比实际代码还多，建议改名叫 'TODO Hunter'",),
-                    format!("{} 个 TODO，看来这个项目还在'施工中'", total_todos),
-                    format!("这么多 TODO，是不是该考虑换个职业了？",),
-                ]
-            } else {
-                vec![
-                    format!(
-                        "Found {} TODOs/FIXMEs - is this code or a shopping list?",
-                        total_todos
-                    ),
-                    format!(
-                        "{} unfinished items? Are you coding or journaling?",
-                        total_todos
-                    ),
-                    format!("More TODOs than actual code, consider renaming to 'TODO Hunter'"),
-                    format!(
-                        "{} TODOs - looks like this project is still 'under construction'",
-                        total_todos
-                    ),
-                    format!("So many TODOs, maybe consider a career change?"),
-                ]
-            };
-
-            let severity = if total_todos > 10 {
-                Severity::Spicy
-            } else {
-                Severity::Mild
-            };
-
-            issues.push(CodeIssue {
-                file_path: file_path.to_path_buf(),
-                line: 1,
-                column: 1,
-                rule_name: "todo-comment".to_string(),
-                message: messages[total_todos % messages.len()].clone(),
-                severity,
-                roast_level: RoastLevel::Sarcastic,
-            });
+    fn stream(&self) -> &'static str {
+        match self {
+            OutputKind::Stderr => "stderr",
+            _ => "stdout",
         }
+    }
 
-        issues
+    /// 对应的 log/tracing 写法
+    fn replacement(&self) -> &'static str {
+        match self {
+            OutputKind::Stderr => "log::warn! / tracing::warn!",
+            _ => "log::debug! / tracing::debug!",
+        }
     }
 }
 
-// ============================================================================
-// Visitor 实现
-// ============================================================================
-
 struct PrintlnDebuggingVisitor {
     file_path: std::path::PathBuf,
     issues: Vec<CodeIssue>,
     lang: String,
-    println_count: usize,
+    binary: bool,
+    /// 每一处输出的行号，包括二进制里没报的正常输出
+    output_lines: Vec<usize>,
+    /// 绑定了 stdout()/stderr() 的局部变量，`let mut out = io::stdout();`
+    stream_bindings: Vec<(String, OutputKind)>,
 }
 
 impl PrintlnDebuggingVisitor {
-    fn new(file_path: std::path::PathBuf, lang: &str) -> Self {
+    fn new(file_path: std::path::PathBuf, lang: &str, binary: bool) -> Self {
         Self {
             file_path,
             issues: Vec::new(),
             lang: lang.to_string(),
-            println_count: 0,
+            binary,
+            output_lines: Vec::new(),
+            stream_bindings: Vec::new(),
         }
     }
 
     fn add_excessive_println_issue(&mut self, count: usize) {
         let messages = if self.lang == "zh-CN" {
             vec![
-                format!("{} 个 println! 调试？你是在开演唱会吗？", count),
+                format!("{} 处调试输出？你是在开演唱会吗？", count),
                 format!("这么多 println!，控制台都要被刷屏了",),
                 format!("{} 个打印语句，建议学学 debugger 的使用", count),
                 format!("println! 用得比我说话还频繁",),
@@ -182,7 +193,10 @@ impl PrintlnDebuggingVisitor {
             ]
         } else {
             vec![
-                format!("{} println! statements? Are you hosting a concert?", count),
+                format!(
+                    "{} debug output statements? Are you hosting a concert?",
+                    count
+                ),
                 format!("So many println!s, the console is crying"),
                 format!("{} print statements - time to learn about debuggers", count),
                 format!("You use println! more than I use excuses"),
@@ -198,47 +212,182 @@ impl PrintlnDebuggingVisitor {
             message: messages[count % messages.len()].clone(),
             severity: Severity::Spicy,
             roast_level: RoastLevel::Savage,
+            context: CodeContext::Production,
+        });
+    }
+
+    /// 记录一处输出；二进制里的正常输出只计数不报告
+    fn record(
+        &mut self,
+        (line, column): (usize, usize),
+        kind: OutputKind,
+        name: &str,
+        debug_like: bool,
+    ) {
+        self.output_lines.push(line);
+
+        let replacement = kind.replacement();
+        let (messages, severity, roast_level) = if kind == OutputKind::Dbg {
+            let messages = if self.lang == "zh-CN" {
+                vec![
+                    "dbg! 忘了删，它会把文件名、行号和值一起甩到 stderr 上".to_string(),
+                    "dbg! 是临时调试用的，能提交上来说明没人看过这段 diff".to_string(),
+                    "这个 dbg! 一上线，用户的终端就成了你的调试器".to_string(),
+                ]
+            } else {
+                vec![
+                    "Leftover dbg! - it dumps file, line and value straight to stderr".to_string(),
+                    "dbg! is for five-minute debugging, committing it means nobody read this diff"
+                        .to_string(),
+                    "Ship this dbg! and your users' terminals become your debugger".to_string(),
+                ]
+            };
+            (messages, Severity::Nuclear, RoastLevel::Savage)
+        } else if !self.binary {
+            let messages = if self.lang == "zh-CN" {
+                vec![
+                    format!("库代码里的 {name} 直接写到调用者的终端上，换成 {replacement}，让调用者决定要不要看"),
+                    format!("{name} 出现在库里，用你这个库的程序没法关掉它，用 {replacement}"),
+                    format!("库不该自己往标准输出写东西，{name} 换成 {replacement} 吧"),
+                ]
+            } else {
+                vec![
+                    format!("{name} in library code writes to the caller's terminal, use {replacement} and let them decide"),
+                    format!("{name} in a library can't be switched off by the programs using it, use {replacement}"),
+                    format!("Libraries shouldn't print on their own, replace {name} with {replacement}"),
+                ]
+            };
+            (messages, Severity::Spicy, RoastLevel::Sarcastic)
+        } else if debug_like {
+            let messages = if self.lang == "zh-CN" {
+                vec![
+                    format!("这个 {name} 看起来是调试用的，换成 {replacement}，需要时再用 RUST_LOG 打开"),
+                    format!("{name} 打印 {{:?}}，用户不需要看到你的内部结构，用 {replacement}"),
+                    format!("这个 {name} 是临时的，对吧？对吧？真要留着就用 {replacement}"),
+                ]
+            } else {
+                vec![
+                    format!("This {name} looks like debugging, use {replacement} and turn it on with RUST_LOG when needed"),
+                    format!("{name} with {{:?}} - users don't need your internals, use {replacement}"),
+                    format!("This {name} is temporary, right? Right? If it stays, make it {replacement}"),
+                ]
+            };
+            (messages, Severity::Mild, RoastLevel::Gentle)
+        } else {
+            return;
+        };
+
+        self.issues.push(CodeIssue {
+            file_path: self.file_path.clone(),
+            line,
+            column,
+            rule_name: "println-debugging".to_string(),
+            message: messages[self.issues.len() % messages.len()].clone(),
+            severity,
+            roast_level,
+            context: CodeContext::Production,
         });
     }
+
+    /// `stdout()`、`io::stderr().lock()`，或者绑定了它们的变量，返回写的是哪个流
+    fn stream_of(&self, expr: &Expr) -> Option<OutputKind> {
+        match expr {
+            Expr::Call(call) => match call.func.as_ref() {
+                Expr::Path(func) => match func.path.segments.last() {
+                    Some(segment) if segment.ident == "stdout" => Some(OutputKind::Stdout),
+                    Some(segment) if segment.ident == "stderr" => Some(OutputKind::Stderr),
+                    _ => None,
+                },
+                _ => None,
+            },
+            Expr::MethodCall(call) if call.method == "lock" => self.stream_of(&call.receiver),
+            Expr::Reference(reference) => self.stream_of(&reference.expr),
+            Expr::Paren(paren) => self.stream_of(&paren.expr),
+            Expr::Path(path) => {
+                let ident = path.path.get_ident()?;
+                self.stream_bindings
+                    .iter()
+                    .rev()
+                    .find(|(name, _)| ident == name)
+                    .map(|(_, kind)| *kind)
+            }
+            _ => None,
+        }
+    }
+}
+
+/// 格式字符串像调试输出：用了 {:?}，或者以 debug、here、reached 之类开头
+fn looks_like_debug(format: &str) -> bool {
+    let lower = format.trim_start().to_lowercase();
+    format.contains("?}")
+        || [
+            "debug",
+            "dbg",
+            "here",
+            "got here",
+            "reached",
+            "checkpoint",
+            "xxx",
+            "!!!",
+            ">>>",
+        ]
+        .iter()
+        .any(|prefix| lower.starts_with(prefix))
+}
+
+fn format_string(args: &[Expr]) -> Option<String> {
+    args.iter().find_map(|arg| match arg {
+        Expr::Lit(ExprLit {
+            lit: Lit::Str(format),
+            ..
+        }) => Some(format.value()),
+        _ => None,
+    })
 }
 
 impl<'ast> Visit<'ast> for PrintlnDebuggingVisitor {
-    fn visit_expr_macro(&mut self, expr_macro: &'ast ExprMacro) {
-        if let Some(ident) = expr_macro.mac.path.get_ident() {
-            if ident == "println" {
-                self.println_count += 1;
+    fn visit_local(&mut self, local: &'ast Local) {
+        if let (Pat::Ident(binding), Some(init)) = (&local.pat, &local.init) {
+            if let Some(kind) = self.stream_of(&init.expr) {
+                self.stream_bindings.push((binding.ident.to_string(), kind));
+            }
+        }
+        syn::visit::visit_local(self, local);
+    }
 
-                let messages = if self.lang == "zh-CN" {
-                    vec![
-                        "又一个 println! 调试，专业！",
-                        "println! 调试大法好，但是...",
-                        "看到这个 println!，我想起了我的学生时代",
-                        "println! 调试：简单粗暴，但不优雅",
-                        "这个 println! 是临时的，对吧？对吧？",
-                    ]
-                } else {
-                    vec![
-                        "Another println! debug - so professional!",
-                        "println! debugging strikes again",
-                        "This println! brings back student memories",
-                        "println! debugging: simple, crude, but effective",
-                        "This println! is temporary, right? Right?",
-                    ]
-                };
+    fn visit_macro(&mut self, mac: &'ast Macro) {
+        let name = mac
+            .path
+            .segments
+            .last()
+            .map(|s| s.ident.to_string())
+            .unwrap_or_default();
+        let args = parse_macro_args(mac);
+
+        if let Some(kind) = OutputKind::from_macro(&name) {
+            let debug_like = format_string(&args).is_some_and(|format| looks_like_debug(&format));
+            self.record(get_position(mac), kind, &format!("{name}!"), debug_like);
+        } else if matches!(name.as_str(), "write" | "writeln") {
+            if let Some(kind) = args.first().and_then(|target| self.stream_of(target)) {
+                let debug_like =
+                    format_string(&args[1..]).is_some_and(|format| looks_like_debug(&format));
+                let label = format!("{name}!({}(), ..)", kind.stream());
+                self.record(get_position(mac), kind, &label, debug_like);
+            }
+        }
 
-                let (line, column) = get_position(expr_macro);
-                self.issues.push(CodeIssue {
-                    file_path: self.file_path.clone(),
-                    line,
-                    column,
-                    rule_name: "println-debugging".to_string(),
-                    message: messages[self.println_count % messages.len()].to_string(),
-                    severity: Severity::Mild,
-                    roast_level: RoastLevel::Gentle,
-                });
+        visit_macro_args(self, mac);
+    }
+
+    fn visit_expr_method_call(&mut self, call: &'ast ExprMethodCall) {
+        let method = call.method.to_string();
+        if matches!(method.as_str(), "write" | "write_all" | "write_fmt") {
+            if let Some(kind) = self.stream_of(&call.receiver) {
+                let label = format!("{}().{method}(..)", kind.stream());
+                self.record(get_position(&call.method), kind, &label, false);
             }
         }
-        syn::visit::visit_expr_macro(self, expr_macro);
+        syn::visit::visit_expr_method_call(self, call);
     }
 }
 
@@ -286,6 +435,7 @@ impl PanicAbuseVisitor {
             message: messages[count % messages.len()].clone(),
             severity: Severity::Nuclear,
             roast_level: RoastLevel::Savage,
+            context: CodeContext::Production,
         });
     }
 
@@ -314,11 +464,16 @@ impl PanicAbuseVisitor {
             message: messages[count % messages.len()].clone(),
             severity: Severity::Spicy,
             roast_level: RoastLevel::Sarcastic,
+            context: CodeContext::Production,
         });
     }
 }
 
 impl<'ast> Visit<'ast> for PanicAbuseVisitor {
+    fn visit_macro(&mut self, mac: &'ast Macro) {
+        visit_macro_args(self, mac);
+    }
+
     fn visit_expr_macro(&mut self, expr_macro: &'ast ExprMacro) {
         if let Some(ident) = expr_macro.mac.path.get_ident() {
             if ident == "panic" {
@@ -347,6 +502,7 @@ impl<'ast> Visit<'ast> for PanicAbuseVisitor {
                     message: messages[self.issues.len() % messages.len()].to_string(),
                     severity: Severity::Spicy,
                     roast_level: RoastLevel::Sarcastic,
+                    context: CodeContext::Production,
                 });
             }
         }
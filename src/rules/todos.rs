@@ -0,0 +1,658 @@
+use regex::Regex;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use std::time::{SystemTime, UNIX_EPOCH};
+use syn::{visit::Visit, Expr, ExprLit, File, Lit, Macro};
+
+use crate::analyzer::{CodeContext, CodeIssue, RoastLevel, Severity};
+use crate::rules::macro_args::{parse_macro_args, visit_macro_args};
+use crate::rules::{ContextPolicy, Rule};
+use crate::utils::get_position;
+
+/// 逐条检查 TODO/FIXME/HACK 注释和 todo!()/unimplemented!()：没有工单、过了期限的重点点名
+pub struct TodoCommentRule;
+
+impl Rule for TodoCommentRule {
+    fn name(&self) -> &'static str {
+        "todo-comment"
+    }
+
+    fn context_policy(&self, context: CodeContext) -> ContextPolicy {
+        match context {
+            CodeContext::Production | CodeContext::BuildScript => ContextPolicy::Report,
+            _ => ContextPolicy::Downgrade,
+        }
+    }
+
+    fn check(
+        &self,
+        file_path: &Path,
+        syntax_tree: &File,
+        content: &str,
+        lang: &str,
+    ) -> Vec<CodeIssue> {
+        let inventory = TodoInventory::collect(file_path, syntax_tree, content);
+        let today = TodoDate::today();
+        let mut issues = Vec::new();
+
+        for item in &inventory.items {
+            let (message, severity) = item_message(item, today, lang, issues.len());
+            let roast_level = match severity {
+                Severity::Mild => RoastLevel::Gentle,
+                _ => RoastLevel::Sarcastic,
+            };
+
+            issues.push(CodeIssue {
+                file_path: file_path.to_path_buf(),
+                line: item.line,
+                column: item.column,
+                rule_name: "todo-comment".to_string(),
+                message,
+                severity,
+                roast_level,
+                context: CodeContext::Production,
+            });
+        }
+
+        issues
+    }
+}
+
+/// 待办事项的形式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TodoKind {
+    Todo,
+    Fixme,
+    Hack,
+    TodoMacro,
+    Unimplemented,
+}
+
+impl TodoKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            TodoKind::Todo => "TODO",
+            TodoKind::Fixme => "FIXME",
+            TodoKind::Hack => "HACK",
+            TodoKind::TodoMacro => "todo!()",
+            TodoKind::Unimplemented => "unimplemented!()",
+        }
+    }
+
+    /// 宏在运行时会直接 panic，注释只是欠账
+    pub fn is_macro(&self) -> bool {
+        matches!(self, TodoKind::TodoMacro | TodoKind::Unimplemented)
+    }
+}
+
+/// 写在 TODO 里的日期，当作期限
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct TodoDate {
+    pub year: u32,
+    pub month: u32,
+    pub day: u32,
+}
+
+impl TodoDate {
+    /// 今天的日期（UTC）
+    pub fn today() -> Self {
+        let seconds = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_secs())
+            .unwrap_or_default();
+        Self::from_days(seconds / 86_400)
+    }
+
+    /// 从 1970-01-01 起的天数换算成公历日期
+    fn from_days(days: u64) -> Self {
+        // 以 0000-03-01 为起点，每 400 年 146097 天
+        let days = days + 719_468;
+        let era = days / 146_097;
+        let day_of_era = days % 146_097;
+        let year_of_era =
+            (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let shifted_month = (5 * day_of_year + 2) / 153;
+        let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+        let month = if shifted_month < 10 {
+            shifted_month + 3
+        } else {
+            shifted_month - 9
+        };
+        let year = year_of_era + era * 400 + u64::from(month <= 2);
+
+        Self {
+            year: year as u32,
+            month: month as u32,
+            day: day as u32,
+        }
+    }
+
+    fn parse(text: &str) -> Option<Self> {
+        let captures = date_pattern().captures(text)?;
+        let field = |index: usize| captures[index].parse::<u32>().ok();
+        let date = Self {
+            year: field(1)?,
+            month: field(2)?,
+            day: field(3)?,
+        };
+        ((1..=12).contains(&date.month) && (1..=31).contains(&date.day)).then_some(date)
+    }
+}
+
+impl fmt::Display for TodoDate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+/// 一条待办事项
+#[derive(Debug, Clone)]
+pub struct TodoItem {
+    pub line: usize,
+    pub column: usize,
+    pub kind: TodoKind,
+    /// `TODO(alice)` 里的负责人
+    pub owner: Option<String>,
+    /// 工单引用：`#123`、`owner/repo#123`、`PROJ-42` 或 issue 链接
+    pub ticket: Option<String>,
+    /// 期限：`TODO(alice, 2024-06-01)` 或正文里的日期
+    pub due: Option<TodoDate>,
+    /// 标记后面的说明，宏取第一个字符串参数
+    pub text: String,
+}
+
+impl TodoItem {
+    pub fn is_tracked(&self) -> bool {
+        self.ticket.is_some()
+    }
+
+    pub fn is_overdue(&self, today: TodoDate) -> bool {
+        self.due.is_some_and(|due| due < today)
+    }
+
+    /// 从标记后的括号内容和正文里找负责人、工单和日期
+    fn annotate(&mut self, tag: Option<&str>) {
+        for token in tag
+            .unwrap_or_default()
+            .split(|c: char| c == ',' || c.is_whitespace())
+            .filter(|token| !token.is_empty())
+        {
+            if let Some(ticket) = find_ticket(token) {
+                self.ticket.get_or_insert(ticket);
+            } else if let Some(date) = TodoDate::parse(token) {
+                self.due.get_or_insert(date);
+            } else if self.owner.is_none() {
+                self.owner = Some(token.trim_start_matches('@').to_string());
+            }
+        }
+
+        if self.ticket.is_none() {
+            self.ticket = find_ticket(&self.text);
+        }
+        if self.due.is_none() {
+            self.due = TodoDate::parse(&self.text);
+        }
+    }
+}
+
+/// 一个文件里的所有待办事项，给 --todos 清单和规则共用
+#[derive(Debug, Clone)]
+pub struct TodoInventory {
+    pub file_path: PathBuf,
+    pub items: Vec<TodoItem>,
+}
+
+impl TodoInventory {
+    pub fn collect(file_path: &Path, syntax_tree: &File, content: &str) -> Self {
+        let mut items = comment_items(content);
+
+        let mut collector = MacroCollector { items: Vec::new() };
+        collector.visit_file(syntax_tree);
+        items.extend(collector.items);
+
+        items.sort_by_key(|item| (item.line, item.column));
+        Self {
+            file_path: file_path.to_path_buf(),
+            items,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// 没有工单引用的注释数量，宏不算
+    pub fn untracked(&self) -> usize {
+        self.items
+            .iter()
+            .filter(|item| !item.kind.is_macro() && !item.is_tracked())
+            .count()
+    }
+
+    pub fn overdue(&self, today: TodoDate) -> usize {
+        self.items
+            .iter()
+            .filter(|item| item.is_overdue(today))
+            .count()
+    }
+}
+
+fn item_message(item: &TodoItem, today: TodoDate, lang: &str, seed: usize) -> (String, Severity) {
+    let label = match &item.owner {
+        Some(owner) => format!("{}({})", item.kind.label(), owner),
+        None => item.kind.label().to_string(),
+    };
+    let ticket = item.ticket.as_deref().unwrap_or("-");
+
+    let (messages, severity) = match item.due {
+        _ if item.kind.is_macro() => (
+            if lang == "zh-CN" {
+                vec![
+                    format!("{label} 一执行就 panic，别让它混进发布版本"),
+                    format!("{label} 还留在代码里，调用方只能祈祷不走到这条路径"),
+                ]
+            } else {
+                vec![
+                    format!("{label} panics the moment this path runs, don't ship it"),
+                    format!("{label} is still here, callers can only pray they never reach it"),
+                ]
+            },
+            Severity::Spicy,
+        ),
+        Some(due) if due < today => (
+            if lang == "zh-CN" {
+                vec![
+                    format!("{label} 的期限是 {due}，已经过期了还躺在这里"),
+                    format!("{label} 说好 {due} 之前处理，日历翻过去了，代码没动"),
+                ]
+            } else {
+                vec![
+                    format!("{label} was due {due} and is still lying here"),
+                    format!("{label} promised to be gone by {due}, the calendar moved on, the code didn't"),
+                ]
+            },
+            Severity::Spicy,
+        ),
+        _ if !item.is_tracked() => {
+            (
+                if lang == "zh-CN" {
+                    vec![
+                        format!("{label} 没有关联工单，没人排期的 TODO 会永远留在这里"),
+                        format!("{label} 没写工单号，这是待办事项还是许愿池？"),
+                    ]
+                } else {
+                    vec![
+                    format!("{label} has no ticket, nobody will ever schedule it"),
+                    format!("{label} without a ticket reference - is this a task or a wishing well?"),
+                ]
+                },
+                Severity::Spicy,
+            )
+        }
+        _ => {
+            (
+                if lang == "zh-CN" {
+                    vec![
+                        format!("{label} 已经记在 {ticket}，记得真的去做"),
+                        format!("{label} 跟着 {ticket}，账记下了，总要还的"),
+                    ]
+                } else {
+                    vec![
+                    format!("{label} is tracked in {ticket}, now actually do it"),
+                    format!("{label} filed as {ticket} - the debt is recorded, it still has to be paid"),
+                ]
+                },
+                Severity::Mild,
+            )
+        }
+    };
+
+    (messages[seed % messages.len()].clone(), severity)
+}
+
+fn marker_pattern() -> &'static Regex {
+    static PATTERN: OnceLock<Regex> = OnceLock::new();
+    PATTERN.get_or_init(|| {
+        Regex::new(r"\b(TODO|FIXME|HACK)\b(?:\(([^)]*)\))?(:)?").expect("valid marker pattern")
+    })
+}
+
+fn ticket_pattern() -> &'static Regex {
+    static PATTERN: OnceLock<Regex> = OnceLock::new();
+    PATTERN.get_or_init(|| {
+        Regex::new(
+            r"https?://\S*(?:issues|pull|browse|tickets?|bugs?)\S*|(?:\b[\w.-]+/[\w.-]+)?#\d+\b|\b([A-Z][A-Z0-9]+)-\d+\b",
+        )
+        .expect("valid ticket pattern")
+    })
+}
+
+/// 行首的 `WORD:` 或 `WORD(...):` 标签，比如 `NOTE:`、`SAFETY:`、`DEBUG(bob):`
+fn tag_pattern() -> &'static Regex {
+    static PATTERN: OnceLock<Regex> = OnceLock::new();
+    PATTERN
+        .get_or_init(|| Regex::new(r"^[A-Z][A-Z0-9_-]*(?:\([^)]*\))?:").expect("valid tag pattern"))
+}
+
+fn date_pattern() -> &'static Regex {
+    static PATTERN: OnceLock<Regex> = OnceLock::new();
+    PATTERN.get_or_init(|| Regex::new(r"\b(\d{4})-(\d{2})-(\d{2})\b").expect("valid date pattern"))
+}
+
+/// 长得像工单号但其实是标准名的前缀，比如 UTF-8、SHA-256
+const NOT_TICKET_PREFIXES: &[&str] = &["UTF", "SHA", "MD", "AES", "ISO", "RFC", "HTTP", "TLS"];
+
+fn find_ticket(text: &str) -> Option<String> {
+    ticket_pattern()
+        .captures_iter(text)
+        .find(|captures| {
+            captures
+                .get(1)
+                .is_none_or(|prefix| !NOT_TICKET_PREFIXES.contains(&prefix.as_str()))
+        })
+        .map(|captures| {
+            captures[0]
+                .trim_end_matches([')', '.', ',', ';'])
+                .to_string()
+        })
+}
+
+/// 注释里的 TODO/FIXME/HACK，下面紧接着、缩进更深的注释行算作同一条的续写
+fn comment_items(content: &str) -> Vec<TodoItem> {
+    let comments = comment_lines(content);
+    let mut items = Vec::new();
+
+    for (index, comment) in comments.iter().enumerate() {
+        let Some(captures) = marker_pattern().captures(&comment.text) else {
+            continue;
+        };
+        let marker = captures.get(0).expect("whole match");
+        let prefix = &comment.text[..marker.start()];
+        // "the TODO list" 这样的散文不算，标记要在开头或者带着括号、冒号
+        let leading = prefix
+            .trim_start_matches(|c: char| c.is_whitespace() || matches!(c, '/' | '!' | '*'))
+            .is_empty();
+        if !leading && captures.get(2).is_none() && captures.get(3).is_none() {
+            continue;
+        }
+
+        let kind = match &captures[1] {
+            "TODO" => TodoKind::Todo,
+            "FIXME" => TodoKind::Fixme,
+            _ => TodoKind::Hack,
+        };
+
+        // 续写行要比标记所在行缩进得更深，`NOTE:`、`DEBUG:` 这种另起标签的行不算
+        let indent = comment_indent(&comment.text);
+        let mut text = comment.text[marker.end()..].trim().to_string();
+        for (next_line, next) in (comment.line + 1..).zip(&comments[index + 1..]) {
+            let continuation = next.text.trim_start_matches(['/', '!', '*']).trim();
+            if next.line != next_line
+                || !next.own_line
+                || continuation.is_empty()
+                || comment_indent(&next.text) <= indent
+                || marker_pattern().is_match(continuation)
+                || tag_pattern().is_match(continuation)
+            {
+                break;
+            }
+            text.push(' ');
+            text.push_str(continuation);
+        }
+
+        let mut item = TodoItem {
+            line: comment.line,
+            column: comment.column + prefix.chars().count(),
+            kind,
+            owner: None,
+            ticket: None,
+            due: None,
+            text,
+        };
+        item.annotate(captures.get(2).map(|tag| tag.as_str()));
+        items.push(item);
+    }
+
+    items
+}
+
+/// 注释标记（`//` 之后的 `/`、`!`、`*`）后面的空白宽度
+fn comment_indent(text: &str) -> usize {
+    text.trim_start_matches(['/', '!', '*'])
+        .chars()
+        .take_while(|c| c.is_whitespace())
+        .count()
+}
+
+/// 一行注释内容：行号、内容起始列（都从 1 开始）、是否独占一行
+struct CommentLine {
+    line: usize,
+    column: usize,
+    text: String,
+    own_line: bool,
+}
+
+/// 扫描源码里的 `//` 和 `/* */` 注释，跳过字符串、字符和原始字符串字面量，
+/// 这样 `"TODO"` 和 `DEBUG` 这种标识符都不会被误认。块注释按行拆开。
+fn comment_lines(content: &str) -> Vec<CommentLine> {
+    let mut lexer = Lexer {
+        chars: content.chars().collect(),
+        pos: 0,
+        line: 1,
+        line_start: 0,
+        comments: Vec::new(),
+    };
+    lexer.run();
+    lexer.comments
+}
+
+struct Lexer {
+    chars: Vec<char>,
+    pos: usize,
+    line: usize,
+    line_start: usize,
+    comments: Vec<CommentLine>,
+}
+
+impl Lexer {
+    fn peek(&self, offset: usize) -> Option<char> {
+        self.chars.get(self.pos + offset).copied()
+    }
+
+    fn bump(&mut self) {
+        if self.peek(0) == Some('\n') {
+            self.line += 1;
+            self.line_start = self.pos + 1;
+        }
+        self.pos += 1;
+    }
+
+    fn column(&self) -> usize {
+        self.pos - self.line_start + 1
+    }
+
+    fn at_line_start(&self) -> bool {
+        self.chars[self.line_start..self.pos]
+            .iter()
+            .all(|c| c.is_whitespace())
+    }
+
+    fn push_comment(&mut self, start: usize, column: usize, own_line: bool) {
+        self.comments.push(CommentLine {
+            line: self.line,
+            column,
+            text: self.chars[start..self.pos].iter().collect(),
+            own_line,
+        });
+    }
+
+    fn run(&mut self) {
+        while let Some(c) = self.peek(0) {
+            match (c, self.peek(1)) {
+                ('/', Some('/')) => self.line_comment(),
+                ('/', Some('*')) => self.block_comment(),
+                ('"', _) => {
+                    self.bump();
+                    self.string();
+                }
+                ('\'', _) => self.char_or_lifetime(),
+                _ if c.is_alphanumeric() || c == '_' => self.word(),
+                _ => self.bump(),
+            }
+        }
+    }
+
+    fn line_comment(&mut self) {
+        let own_line = self.at_line_start();
+        self.pos += 2;
+        let (start, column) = (self.pos, self.column());
+        while self.peek(0).is_some_and(|c| c != '\n') {
+            self.bump();
+        }
+        self.push_comment(start, column, own_line);
+    }
+
+    fn block_comment(&mut self) {
+        let own_line = self.at_line_start();
+        self.pos += 2;
+        let (mut start, mut column, mut own_line) = (self.pos, self.column(), own_line);
+        let mut depth = 1;
+
+        while let Some(c) = self.peek(0) {
+            match (c, self.peek(1)) {
+                ('/', Some('*')) => {
+                    depth += 1;
+                    self.pos += 2;
+                }
+                ('*', Some('/')) => {
+                    depth -= 1;
+                    if depth == 0 {
+                        self.push_comment(start, column, own_line);
+                        self.pos += 2;
+                        return;
+                    }
+                    self.pos += 2;
+                }
+                ('\n', _) => {
+                    self.push_comment(start, column, own_line);
+                    self.bump();
+                    (start, column, own_line) = (self.pos, 1, true);
+                }
+                _ => self.bump(),
+            }
+        }
+        self.push_comment(start, column, own_line);
+    }
+
+    /// 普通字符串，开头的引号已经跳过
+    fn string(&mut self) {
+        while let Some(c) = self.peek(0) {
+            self.bump();
+            match c {
+                '\\' => self.bump(),
+                '"' => return,
+                _ => {}
+            }
+        }
+    }
+
+    /// 标识符和数字整个跳过；`r"..."`、`br#"..."#` 这类原始字符串在这里处理
+    fn word(&mut self) {
+        let start = self.pos;
+        while self
+            .peek(0)
+            .is_some_and(|c| c.is_alphanumeric() || c == '_')
+        {
+            self.bump();
+        }
+        let word: String = self.chars[start..self.pos].iter().collect();
+        if !matches!(word.as_str(), "r" | "br" | "cr") {
+            return;
+        }
+
+        let mut hashes = 0;
+        while self.peek(hashes) == Some('#') {
+            hashes += 1;
+        }
+        // `r#ident` 是原始标识符
+        if self.peek(hashes) != Some('"') {
+            return;
+        }
+        for _ in 0..=hashes {
+            self.bump();
+        }
+        while let Some(c) = self.peek(0) {
+            self.bump();
+            if c == '"' && (0..hashes).all(|offset| self.peek(offset) == Some('#')) {
+                for _ in 0..hashes {
+                    self.bump();
+                }
+                return;
+            }
+        }
+    }
+
+    /// `'a'`、`'\n'` 是字符，`'a` 是生命周期
+    fn char_or_lifetime(&mut self) {
+        self.bump();
+        match (self.peek(0), self.peek(1)) {
+            (Some('\\'), _) => {
+                self.bump();
+                self.bump();
+                while self.peek(0).is_some_and(|c| c != '\'' && c != '\n') {
+                    self.bump();
+                }
+                self.bump();
+            }
+            (Some(_), Some('\'')) => {
+                self.bump();
+                self.bump();
+            }
+            _ => {}
+        }
+    }
+}
+
+/// 找 todo!() 和 unimplemented!()，宏参数里嵌套的也算
+struct MacroCollector {
+    items: Vec<TodoItem>,
+}
+
+impl<'ast> Visit<'ast> for MacroCollector {
+    fn visit_macro(&mut self, mac: &'ast Macro) {
+        let kind = match mac.path.segments.last().map(|s| s.ident.to_string()) {
+            Some(name) if name == "todo" => Some(TodoKind::TodoMacro),
+            Some(name) if name == "unimplemented" => Some(TodoKind::Unimplemented),
+            _ => None,
+        };
+
+        if let Some(kind) = kind {
+            let text = parse_macro_args(mac)
+                .into_iter()
+                .find_map(|arg| match arg {
+                    Expr::Lit(ExprLit {
+                        lit: Lit::Str(message),
+                        ..
+                    }) => Some(message.value()),
+                    _ => None,
+                })
+                .unwrap_or_default();
+            let (line, column) = get_position(mac);
+            let mut item = TodoItem {
+                line,
+                column,
+                kind,
+                owner: None,
+                ticket: None,
+                due: None,
+                text,
+            };
+            item.annotate(None);
+            self.items.push(item);
+        }
+
+        visit_macro_args(self, mac);
+    }
+}
@@ -0,0 +1,493 @@
+use std::collections::HashSet;
+use std::path::Path;
+
+use syn::{
+    visit::Visit, Field, File, GenericArgument, ImplItem, ItemImpl, ItemStruct, PathArguments, Type,
+};
+
+use crate::analyzer::{CodeContext, CodeIssue, Severity};
+use crate::rules::common::{derived_traits, rule_issue, type_name, type_to_string};
+use crate::rules::{ContextPolicy, Rule};
+use crate::utils::get_position;
+
+/// 字段多到什么都往里塞的结构体
+pub struct GodStructRule;
+
+/// 一堆 Option 字段，其实是几种状态的枚举
+pub struct OptionSoupRule;
+
+/// 同一组字段在多个结构体里反复出现
+pub struct DataClumpRule;
+
+/// ID、邮箱、URL 全用 String 表示
+pub struct PrimitiveObsessionRule;
+
+/// 方法多到翻不完的 impl 块
+pub struct HugeImplRule;
+
+/// 测试里的辅助结构体不讲究设计
+fn design_context_policy(context: CodeContext) -> ContextPolicy {
+    match context {
+        CodeContext::Test => ContextPolicy::Ignore,
+        CodeContext::Example | CodeContext::Bench => ContextPolicy::Downgrade,
+        CodeContext::Production | CodeContext::BuildScript => ContextPolicy::Report,
+    }
+}
+
+impl Rule for GodStructRule {
+    fn name(&self) -> &'static str {
+        "god-struct"
+    }
+
+    fn context_policy(&self, context: CodeContext) -> ContextPolicy {
+        design_context_policy(context)
+    }
+
+    fn check(
+        &self,
+        file_path: &Path,
+        syntax_tree: &File,
+        _content: &str,
+        lang: &str,
+    ) -> Vec<CodeIssue> {
+        TypeSurface::collect(syntax_tree).god_structs(file_path, lang)
+    }
+}
+
+impl Rule for OptionSoupRule {
+    fn name(&self) -> &'static str {
+        "option-soup"
+    }
+
+    fn context_policy(&self, context: CodeContext) -> ContextPolicy {
+        design_context_policy(context)
+    }
+
+    fn check(
+        &self,
+        file_path: &Path,
+        syntax_tree: &File,
+        _content: &str,
+        lang: &str,
+    ) -> Vec<CodeIssue> {
+        TypeSurface::collect(syntax_tree).option_soup(file_path, lang)
+    }
+}
+
+impl Rule for DataClumpRule {
+    fn name(&self) -> &'static str {
+        "data-clump"
+    }
+
+    fn context_policy(&self, context: CodeContext) -> ContextPolicy {
+        design_context_policy(context)
+    }
+
+    fn check(
+        &self,
+        file_path: &Path,
+        syntax_tree: &File,
+        _content: &str,
+        lang: &str,
+    ) -> Vec<CodeIssue> {
+        TypeSurface::collect(syntax_tree).data_clumps(file_path, lang)
+    }
+}
+
+impl Rule for PrimitiveObsessionRule {
+    fn name(&self) -> &'static str {
+        "primitive-obsession"
+    }
+
+    fn context_policy(&self, context: CodeContext) -> ContextPolicy {
+        design_context_policy(context)
+    }
+
+    fn check(
+        &self,
+        file_path: &Path,
+        syntax_tree: &File,
+        _content: &str,
+        lang: &str,
+    ) -> Vec<CodeIssue> {
+        TypeSurface::collect(syntax_tree).primitive_obsession(file_path, lang)
+    }
+}
+
+impl Rule for HugeImplRule {
+    fn name(&self) -> &'static str {
+        "huge-impl"
+    }
+
+    fn context_policy(&self, context: CodeContext) -> ContextPolicy {
+        design_context_policy(context)
+    }
+
+    fn check(
+        &self,
+        file_path: &Path,
+        syntax_tree: &File,
+        _content: &str,
+        lang: &str,
+    ) -> Vec<CodeIssue> {
+        TypeSurface::collect(syntax_tree).huge_impls(file_path, lang)
+    }
+}
+
+// ============================================================================
+// 收集文件里的类型定义
+// ============================================================================
+
+/// 超过这么多字段就是上帝结构体
+const GOD_STRUCT_FIELDS: usize = 12;
+/// 超过这么多字段直接升级成 Spicy
+const GOD_STRUCT_SPICY_FIELDS: usize = 20;
+
+/// Option 字段达到这个数，状态组合已经没人数得清了
+const OPTION_SOUP_FIELDS: usize = 4;
+
+/// 两个结构体共享这么多个同名同类型字段，就是一团数据泥
+const DATA_CLUMP_FIELDS: usize = 3;
+
+/// 一个结构体里有这么多个"其实是 ID/邮箱/URL"的 String
+const PRIMITIVE_OBSESSION_FIELDS: usize = 2;
+
+/// 字段名最后一个词说明它其实是个领域类型
+const DOMAIN_WORDS: &[&str] = &[
+    "id", "uuid", "email", "mail", "url", "uri", "href", "phone", "ip", "ipv4", "ipv6",
+];
+
+/// 命令行参数结构体天生就是一长串可选的开关
+const CLI_DERIVES: &[&str] = &["Parser", "Args"];
+
+/// 一个 impl 块里超过这么多方法就太大了
+const HUGE_IMPL_FNS: usize = 20;
+/// 超过这么多方法直接升级成 Spicy
+const HUGE_IMPL_SPICY_FNS: usize = 40;
+
+#[derive(Default)]
+struct TypeSurface<'ast> {
+    structs: Vec<&'ast ItemStruct>,
+    /// 固有 impl 块，trait impl 的方法数由 trait 决定
+    impls: Vec<&'ast ItemImpl>,
+}
+
+impl<'ast> TypeSurface<'ast> {
+    fn collect(syntax_tree: &'ast File) -> Self {
+        let mut surface = Self::default();
+        surface.visit_file(syntax_tree);
+        surface
+    }
+
+    fn god_structs(&self, file_path: &Path, lang: &str) -> Vec<CodeIssue> {
+        let mut issues = Vec::new();
+
+        for item_struct in self.structs.iter().filter(|s| !is_cli_args(s)) {
+            let count = item_struct.fields.len();
+            if count <= GOD_STRUCT_FIELDS {
+                continue;
+            }
+            let name = &item_struct.ident;
+
+            let messages = if lang == "zh-CN" {
+                vec![
+                    format!(
+                        "结构体 {name} 有 {count} 个字段，它是不是把整个程序的状态都装进去了？"
+                    ),
+                    format!("{name} 的 {count} 个字段里至少藏着两三个该独立出来的类型"),
+                    format!("上帝结构体 {name}：{count} 个字段，改一个要看懂全部"),
+                ]
+            } else {
+                vec![
+                    format!("struct {name} has {count} fields - is it holding the whole program's state?"),
+                    format!("somewhere in the {count} fields of {name} hide two or three types waiting to be extracted"),
+                    format!("god struct {name}: {count} fields, and you need to understand all of them to change one"),
+                ]
+            };
+
+            let severity = if count > GOD_STRUCT_SPICY_FIELDS {
+                Severity::Spicy
+            } else {
+                Severity::Mild
+            };
+            issues.push(rule_issue(
+                file_path,
+                get_position(name),
+                "god-struct",
+                messages[issues.len() % messages.len()].clone(),
+                severity,
+            ));
+        }
+
+        issues
+    }
+
+    fn option_soup(&self, file_path: &Path, lang: &str) -> Vec<CodeIssue> {
+        let mut issues = Vec::new();
+
+        for item_struct in self.structs.iter().filter(|s| !is_cli_args(s)) {
+            let options: Vec<String> = item_struct
+                .fields
+                .iter()
+                .filter(|field| is_option(&field.ty))
+                .map(field_name)
+                .collect();
+            if options.len() < OPTION_SOUP_FIELDS {
+                continue;
+            }
+            let name = &item_struct.ident;
+            let count = options.len();
+            let fields = options.join(", ");
+
+            let messages = if lang == "zh-CN" {
+                vec![
+                    format!("{name} 有 {count} 个 Option 字段（{fields}），2^{count} 种组合里哪些是合法的？用枚举把状态写出来"),
+                    format!("{name} 是 Option 汤：{fields} 都可能是 None，每次使用都得猜它现在处于什么状态"),
+                    format!("{count} 个 Option 字段说明 {name} 其实是好几种东西，拆成枚举的变体吧"),
+                ]
+            } else {
+                vec![
+                    format!("{name} has {count} Option fields ({fields}) - which of the 2^{count} combinations are valid? Spell the states out as an enum"),
+                    format!("{name} is Option soup: {fields} can all be None, every use has to guess which state it's in"),
+                    format!("{count} Option fields say {name} is really several things - make them enum variants"),
+                ]
+            };
+
+            issues.push(rule_issue(
+                file_path,
+                get_position(name),
+                "option-soup",
+                messages[issues.len() % messages.len()].clone(),
+                Severity::Mild,
+            ));
+        }
+
+        issues
+    }
+
+    fn data_clumps(&self, file_path: &Path, lang: &str) -> Vec<CodeIssue> {
+        let mut issues = Vec::new();
+        let signatures: Vec<Vec<(String, String)>> = self
+            .structs
+            .iter()
+            .map(|item_struct| {
+                item_struct
+                    .fields
+                    .iter()
+                    .filter(|field| field.ident.is_some())
+                    .map(|field| (field_name(field), type_to_string(&field.ty)))
+                    .collect()
+            })
+            .collect();
+
+        for (index, item_struct) in self.structs.iter().enumerate() {
+            // 每个结构体只和它前面第一个撞上的结构体比
+            let clump = (0..index).find_map(|earlier| {
+                let earlier_fields: HashSet<&(String, String)> =
+                    signatures[earlier].iter().collect();
+                let shared: Vec<&str> = signatures[index]
+                    .iter()
+                    .filter(|field| earlier_fields.contains(field))
+                    .map(|(name, _)| name.as_str())
+                    .collect();
+                (shared.len() >= DATA_CLUMP_FIELDS).then(|| (self.structs[earlier], shared))
+            });
+            let Some((other, shared)) = clump else {
+                continue;
+            };
+            let name = &item_struct.ident;
+            let other = &other.ident;
+            let fields = shared.join(", ");
+
+            let messages = if lang == "zh-CN" {
+                vec![
+                    format!("{name} 和 {other} 都有 {fields} 这组字段，它们总是一起出现，提取成一个类型吧"),
+                    format!("数据泥团：{fields} 在 {other} 和 {name} 里各抄了一遍"),
+                    format!("{fields} 总是成群结队地出现，{name} 和 {other} 该共享一个结构体"),
+                ]
+            } else {
+                vec![
+                    format!("{name} and {other} both carry {fields} - fields that always travel together deserve their own type"),
+                    format!("data clump: {fields} is copied into both {other} and {name}"),
+                    format!("{fields} always show up as a group, {name} and {other} should share a struct"),
+                ]
+            };
+
+            issues.push(rule_issue(
+                file_path,
+                get_position(name),
+                "data-clump",
+                messages[issues.len() % messages.len()].clone(),
+                Severity::Mild,
+            ));
+        }
+
+        issues
+    }
+
+    fn primitive_obsession(&self, file_path: &Path, lang: &str) -> Vec<CodeIssue> {
+        let mut issues = Vec::new();
+
+        for item_struct in &self.structs {
+            let domain_fields: Vec<String> = item_struct
+                .fields
+                .iter()
+                .filter(|field| is_string(&field.ty) && names_domain_value(&field_name(field)))
+                .map(field_name)
+                .collect();
+            if domain_fields.len() < PRIMITIVE_OBSESSION_FIELDS {
+                continue;
+            }
+            let name = &item_struct.ident;
+            let fields = domain_fields.join(", ");
+            let newtypes = domain_fields
+                .iter()
+                .map(|field| newtype_name(field))
+                .collect::<Vec<_>>()
+                .join(", ");
+
+            let messages = if lang == "zh-CN" {
+                vec![
+                    format!("{name} 的 {fields} 全是 String，编译器分不清用户 ID 和邮箱，试试 {newtypes} 这样的 newtype"),
+                    format!("基本类型偏执：{name} 把 {fields} 都当普通字符串，传反了也能编译通过"),
+                    format!("{fields} 都是 String？给它们各自一个类型（{newtypes}），校验只用写一次"),
+                ]
+            } else {
+                vec![
+                    format!("{fields} in {name} are all String - the compiler can't tell an ID from an email, try newtypes like {newtypes}"),
+                    format!("primitive obsession: {name} treats {fields} as plain strings, swap them and it still compiles"),
+                    format!("{fields} are all String? Give each its own type ({newtypes}) and validate once"),
+                ]
+            };
+
+            issues.push(rule_issue(
+                file_path,
+                get_position(name),
+                "primitive-obsession",
+                messages[issues.len() % messages.len()].clone(),
+                Severity::Mild,
+            ));
+        }
+
+        issues
+    }
+
+    fn huge_impls(&self, file_path: &Path, lang: &str) -> Vec<CodeIssue> {
+        let mut issues = Vec::new();
+
+        for item_impl in &self.impls {
+            let count = item_impl
+                .items
+                .iter()
+                .filter(|item| matches!(item, ImplItem::Fn(_)))
+                .count();
+            if count <= HUGE_IMPL_FNS {
+                continue;
+            }
+            let name = type_to_string(&item_impl.self_ty);
+
+            let messages = if lang == "zh-CN" {
+                vec![
+                    format!("impl {name} 里有 {count} 个方法，这个类型管的事太多了"),
+                    format!("{name} 的 impl 块有 {count} 个方法，翻到底需要一杯咖啡"),
+                    format!(
+                        "巨型 impl：{name} 的 {count} 个方法可以按职责拆到几个 trait 或者新类型里"
+                    ),
+                ]
+            } else {
+                vec![
+                    format!("impl {name} has {count} methods - this type is in charge of too much"),
+                    format!("the impl block of {name} has {count} methods, scrolling to the end takes a coffee"),
+                    format!("huge impl: the {count} methods of {name} could be split by responsibility into traits or new types"),
+                ]
+            };
+
+            let severity = if count > HUGE_IMPL_SPICY_FNS {
+                Severity::Spicy
+            } else {
+                Severity::Mild
+            };
+            issues.push(rule_issue(
+                file_path,
+                get_position(&item_impl.self_ty),
+                "huge-impl",
+                messages[issues.len() % messages.len()].clone(),
+                severity,
+            ));
+        }
+
+        issues
+    }
+}
+
+impl<'ast> Visit<'ast> for TypeSurface<'ast> {
+    fn visit_item_struct(&mut self, item_struct: &'ast ItemStruct) {
+        self.structs.push(item_struct);
+        syn::visit::visit_item_struct(self, item_struct);
+    }
+
+    fn visit_item_impl(&mut self, item_impl: &'ast ItemImpl) {
+        if item_impl.trait_.is_none() {
+            self.impls.push(item_impl);
+        }
+        syn::visit::visit_item_impl(self, item_impl);
+    }
+}
+
+fn is_cli_args(item_struct: &ItemStruct) -> bool {
+    derived_traits(&item_struct.attrs)
+        .iter()
+        .any(|name| CLI_DERIVES.contains(&name.as_str()))
+}
+
+/// 元组结构体的字段用下标当名字
+fn field_name(field: &Field) -> String {
+    field
+        .ident
+        .as_ref()
+        .map(|ident| ident.to_string())
+        .unwrap_or_default()
+}
+
+fn last_segment_is(ty: &Type, name: &str) -> bool {
+    type_name(ty).is_some_and(|ty_name| ty_name == name)
+}
+
+fn is_string(ty: &Type) -> bool {
+    last_segment_is(ty, "String")
+}
+
+fn is_option(ty: &Type) -> bool {
+    let Type::Path(type_path) = ty else {
+        return false;
+    };
+    let Some(segment) = type_path.path.segments.last() else {
+        return false;
+    };
+    segment.ident == "Option"
+        && matches!(&segment.arguments, PathArguments::AngleBracketed(args)
+            if matches!(args.args.first(), Some(GenericArgument::Type(_))))
+}
+
+/// `user_id` / `email` / `homepage_url` 这类名字
+fn names_domain_value(field: &str) -> bool {
+    field
+        .rsplit('_')
+        .next()
+        .is_some_and(|word| DOMAIN_WORDS.contains(&word.to_lowercase().as_str()))
+}
+
+/// `user_id` -> `UserId`
+fn newtype_name(field: &str) -> String {
+    field
+        .split('_')
+        .filter(|word| !word.is_empty())
+        .map(|word| {
+            let mut chars = word.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect(),
+                None => String::new(),
+            }
+        })
+        .collect()
+}
@@ -0,0 +1,425 @@
+use std::path::{Path, PathBuf};
+use syn::{
+    visit::Visit, Attribute, Expr, ExprCall, ExprLit, ExprUnsafe, File, ImplItemFn, ItemFn,
+    ItemImpl, ItemStatic, ItemTrait, Lit, Macro, Meta, StaticMutability, Stmt, TraitItemFn,
+};
+
+use crate::analyzer::{CodeContext, CodeIssue, RoastLevel, Severity};
+use crate::rules::macro_args::visit_macro_args;
+use crate::rules::{ContextPolicy, Rule};
+use crate::utils::get_position;
+
+/// 审计 unsafe 代码：缺少 SAFETY 注释 / # Safety 文档，以及 transmute、from_raw_parts、static mut
+pub struct UnsafeAbuseRule;
+
+impl Rule for UnsafeAbuseRule {
+    fn name(&self) -> &'static str {
+        "unsafe-abuse"
+    }
+
+    fn context_policy(&self, context: CodeContext) -> ContextPolicy {
+        // 测试里的 unsafe 一样可能是 UB，只是影响面小一些
+        match context {
+            CodeContext::Production | CodeContext::BuildScript => ContextPolicy::Report,
+            _ => ContextPolicy::Downgrade,
+        }
+    }
+
+    fn check(
+        &self,
+        file_path: &Path,
+        syntax_tree: &File,
+        content: &str,
+        lang: &str,
+    ) -> Vec<CodeIssue> {
+        let inventory = UnsafeInventory::collect(file_path, syntax_tree, content);
+        let mut issues = Vec::new();
+
+        for site in &inventory.sites {
+            let Some((message, severity)) = site_message(site, lang, issues.len()) else {
+                continue;
+            };
+            let roast_level = match severity {
+                Severity::Nuclear => RoastLevel::Savage,
+                _ => RoastLevel::Sarcastic,
+            };
+
+            issues.push(CodeIssue {
+                file_path: file_path.to_path_buf(),
+                line: site.line,
+                column: site.column,
+                rule_name: "unsafe-abuse".to_string(),
+                message,
+                severity,
+                roast_level,
+                context: CodeContext::Production,
+            });
+        }
+
+        issues
+    }
+}
+
+/// unsafe 出现的形式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnsafeKind {
+    Block,
+    Fn,
+    Impl,
+    Trait,
+    StaticMut,
+    Transmute,
+    FromRawParts,
+}
+
+impl UnsafeKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            UnsafeKind::Block => "unsafe block",
+            UnsafeKind::Fn => "unsafe fn",
+            UnsafeKind::Impl => "unsafe impl",
+            UnsafeKind::Trait => "unsafe trait",
+            UnsafeKind::StaticMut => "static mut",
+            UnsafeKind::Transmute => "transmute",
+            UnsafeKind::FromRawParts => "from_raw_parts",
+        }
+    }
+}
+
+/// 一处 unsafe 代码
+#[derive(Debug, Clone)]
+pub struct UnsafeSite {
+    pub line: usize,
+    pub column: usize,
+    pub kind: UnsafeKind,
+    /// 所在的函数，或者被 impl 的类型、static 的名字
+    pub item: Option<String>,
+    /// 块和 impl 是否有 `// SAFETY:` 注释，unsafe fn 是否有 `# Safety` 文档；
+    /// 其他种类不适用，为 None
+    pub documented: Option<bool>,
+}
+
+/// 一个文件里所有 unsafe 代码的清单，供安全审查使用
+#[derive(Debug, Clone)]
+pub struct UnsafeInventory {
+    pub file_path: PathBuf,
+    pub sites: Vec<UnsafeSite>,
+}
+
+impl UnsafeInventory {
+    pub fn collect(file_path: &Path, syntax_tree: &File, content: &str) -> Self {
+        let mut collector = InventoryCollector {
+            lines: content.lines().collect(),
+            sites: Vec::new(),
+            current_item: None,
+            stmt_lines: Vec::new(),
+        };
+        collector.visit_file(syntax_tree);
+
+        let mut sites = collector.sites;
+        sites.sort_by_key(|site| (site.line, site.column));
+        Self {
+            file_path: file_path.to_path_buf(),
+            sites,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.sites.is_empty()
+    }
+
+    pub fn count(&self, kind: UnsafeKind) -> usize {
+        self.sites.iter().filter(|site| site.kind == kind).count()
+    }
+
+    /// 缺少 SAFETY 注释或 # Safety 文档的数量
+    pub fn undocumented(&self) -> usize {
+        self.sites
+            .iter()
+            .filter(|site| site.documented == Some(false))
+            .count()
+    }
+}
+
+fn site_message(site: &UnsafeSite, lang: &str, seed: usize) -> Option<(String, Severity)> {
+    let item = site
+        .item
+        .as_deref()
+        .map(|name| format!(" ({name})"))
+        .unwrap_or_default();
+
+    let (messages, severity) = match (site.kind, site.documented) {
+        (UnsafeKind::Block, Some(false)) => (
+            if lang == "zh-CN" {
+                vec![
+                    format!("unsafe 块{item}前面没有 // SAFETY: 注释，审查的人只能猜你为什么觉得它是安全的"),
+                    format!("这个 unsafe 块{item}没写 SAFETY 说明，等于在雷区里拆掉了警示牌"),
+                ]
+            } else {
+                vec![
+                    format!("unsafe block{item} has no // SAFETY: comment, reviewers have to guess why it's sound"),
+                    format!("undocumented unsafe block{item} - you just removed the warning sign from the minefield"),
+                ]
+            },
+            Severity::Spicy,
+        ),
+        (UnsafeKind::Impl, Some(false)) => (
+            if lang == "zh-CN" {
+                vec![
+                    format!("unsafe impl{item} 没有 // SAFETY: 注释，说明一下为什么这个类型满足 trait 的约定"),
+                    format!("unsafe impl{item} 不写理由，Send/Sync 的承诺全凭信仰"),
+                ]
+            } else {
+                vec![
+                    format!("unsafe impl{item} has no // SAFETY: comment explaining why the type upholds the trait's contract"),
+                    format!("unsafe impl{item} without a justification - Send/Sync promises held up by faith alone"),
+                ]
+            },
+            Severity::Spicy,
+        ),
+        (UnsafeKind::Fn, Some(false)) => (
+            if lang == "zh-CN" {
+                vec![
+                    format!("unsafe fn{item} 没有 # Safety 文档，调用者不知道要满足什么前提"),
+                    format!("unsafe fn{item} 不写 # Safety，调用者只能读源码猜约束条件"),
+                ]
+            } else {
+                vec![
+                    format!("unsafe fn{item} has no # Safety doc section, callers don't know what they must uphold"),
+                    format!("unsafe fn{item} without # Safety docs - callers get to reverse-engineer the preconditions"),
+                ]
+            },
+            Severity::Spicy,
+        ),
+        (UnsafeKind::Transmute, _) => (
+            if lang == "zh-CN" {
+                vec![
+                    format!("transmute{item}：把类型系统关掉再重新解释内存，先看看 from_ne_bytes、as 转换或 bytemuck 能不能替代"),
+                    format!("transmute{item} 是 unsafe 里最锋利的那把刀，布局一变就是未定义行为"),
+                ]
+            } else {
+                vec![
+                    format!("transmute{item}: switching off the type system to reinterpret memory - try from_ne_bytes, `as` or bytemuck first"),
+                    format!("transmute{item} is the sharpest knife in the unsafe drawer, one layout change and it's UB"),
+                ]
+            },
+            Severity::Nuclear,
+        ),
+        (UnsafeKind::FromRawParts, _) => (
+            if lang == "zh-CN" {
+                vec![
+                    format!("from_raw_parts{item}：指针、长度、对齐、生命周期，四个条件错一个就是未定义行为"),
+                    format!("from_raw_parts{item} 把一个裸指针当成了切片，确认长度和所有权都对吗？"),
+                ]
+            } else {
+                vec![
+                    format!("from_raw_parts{item}: pointer, length, alignment, lifetime - get one wrong and it's UB"),
+                    format!("from_raw_parts{item} turns a raw pointer into a slice, are the length and ownership really right?"),
+                ]
+            },
+            Severity::Spicy,
+        ),
+        (UnsafeKind::StaticMut, _) => (
+            if lang == "zh-CN" {
+                vec![
+                    format!("static mut{item}：全局可变状态加上数据竞争，换成 Atomic*、Mutex 或 OnceLock"),
+                    format!("static mut{item} 的每一次访问都是 unsafe，Rust 2024 已经不让你拿它的引用了"),
+                ]
+            } else {
+                vec![
+                    format!("static mut{item}: global mutable state plus data races - use an Atomic*, Mutex or OnceLock"),
+                    format!("static mut{item} makes every access unsafe, Rust 2024 won't even let you take a reference to it"),
+                ]
+            },
+            Severity::Nuclear,
+        ),
+        _ => return None,
+    };
+
+    Some((messages[seed % messages.len()].clone(), severity))
+}
+
+struct InventoryCollector<'a> {
+    lines: Vec<&'a str>,
+    sites: Vec<UnsafeSite>,
+    current_item: Option<String>,
+    /// 外层语句的起始行，`let x = unsafe { .. };` 的 SAFETY 注释写在语句前面
+    stmt_lines: Vec<usize>,
+}
+
+impl InventoryCollector<'_> {
+    fn record(
+        &mut self,
+        (line, column): (usize, usize),
+        kind: UnsafeKind,
+        item: Option<String>,
+        documented: Option<bool>,
+    ) {
+        self.sites.push(UnsafeSite {
+            line,
+            column,
+            kind,
+            item: item.or_else(|| self.current_item.clone()),
+            documented,
+        });
+    }
+
+    /// 往上跳过注释和属性，找 `// SAFETY:`
+    ///
+    /// 以 `*` 开头的行只有在 `/* */` 块里面才算注释，`*p = 1;` 是代码
+    fn has_safety_comment(&self, line: usize) -> bool {
+        let mut index = line.saturating_sub(1);
+        let mut in_block = false;
+        while index > 0 {
+            index -= 1;
+            let Some(text) = self.lines.get(index).map(|l| l.trim()) else {
+                return false;
+            };
+            let is_comment = if in_block {
+                in_block = !text.contains("/*");
+                true
+            } else if text.starts_with("//") || text.starts_with("/*") {
+                true
+            } else if text.ends_with("*/") {
+                // 块注释的最后一行，往上继续都在块里
+                in_block = true;
+                true
+            } else {
+                false
+            };
+            if is_comment {
+                if text.contains("SAFETY:") {
+                    return true;
+                }
+            } else if !text.starts_with("#[") {
+                return false;
+            }
+        }
+        false
+    }
+
+    fn with_item<F: FnOnce(&mut Self)>(&mut self, name: String, f: F) {
+        let outer = self.current_item.replace(name);
+        f(self);
+        self.current_item = outer;
+    }
+
+    fn record_unsafe_fn(&mut self, sig: &syn::Signature, attrs: &[Attribute]) {
+        if let Some(unsafety) = &sig.unsafety {
+            self.record(
+                get_position(unsafety),
+                UnsafeKind::Fn,
+                Some(sig.ident.to_string()),
+                Some(has_safety_doc(attrs)),
+            );
+        }
+    }
+}
+
+impl<'ast> Visit<'ast> for InventoryCollector<'_> {
+    fn visit_stmt(&mut self, stmt: &'ast Stmt) {
+        self.stmt_lines.push(get_position(stmt).0);
+        syn::visit::visit_stmt(self, stmt);
+        self.stmt_lines.pop();
+    }
+
+    fn visit_expr_unsafe(&mut self, unsafe_expr: &'ast ExprUnsafe) {
+        let position = get_position(&unsafe_expr.unsafe_token);
+        let documented = self.has_safety_comment(position.0)
+            || self
+                .stmt_lines
+                .last()
+                .is_some_and(|&line| line != position.0 && self.has_safety_comment(line));
+        self.record(position, UnsafeKind::Block, None, Some(documented));
+        syn::visit::visit_expr_unsafe(self, unsafe_expr);
+    }
+
+    fn visit_expr_call(&mut self, call: &'ast ExprCall) {
+        if let Expr::Path(func) = call.func.as_ref() {
+            let name = func.path.segments.last().map(|s| s.ident.to_string());
+            let kind = match name.as_deref() {
+                Some("transmute" | "transmute_copy") => Some(UnsafeKind::Transmute),
+                Some("from_raw_parts" | "from_raw_parts_mut") => Some(UnsafeKind::FromRawParts),
+                _ => None,
+            };
+            if let Some(kind) = kind {
+                self.record(get_position(call), kind, None, None);
+            }
+        }
+        syn::visit::visit_expr_call(self, call);
+    }
+
+    fn visit_item_fn(&mut self, item_fn: &'ast ItemFn) {
+        self.record_unsafe_fn(&item_fn.sig, &item_fn.attrs);
+        let name = item_fn.sig.ident.to_string();
+        self.with_item(name, |v| syn::visit::visit_item_fn(v, item_fn));
+    }
+
+    fn visit_impl_item_fn(&mut self, impl_fn: &'ast ImplItemFn) {
+        self.record_unsafe_fn(&impl_fn.sig, &impl_fn.attrs);
+        let name = impl_fn.sig.ident.to_string();
+        self.with_item(name, |v| syn::visit::visit_impl_item_fn(v, impl_fn));
+    }
+
+    fn visit_trait_item_fn(&mut self, trait_fn: &'ast TraitItemFn) {
+        self.record_unsafe_fn(&trait_fn.sig, &trait_fn.attrs);
+        let name = trait_fn.sig.ident.to_string();
+        self.with_item(name, |v| syn::visit::visit_trait_item_fn(v, trait_fn));
+    }
+
+    fn visit_item_impl(&mut self, item_impl: &'ast ItemImpl) {
+        if let Some(unsafety) = &item_impl.unsafety {
+            let position = get_position(unsafety);
+            let self_ty = &item_impl.self_ty;
+            let name = match &item_impl.trait_ {
+                Some((_, path, _)) => quote::quote!(#path for #self_ty).to_string(),
+                None => quote::quote!(#self_ty).to_string(),
+            };
+            let documented = self.has_safety_comment(position.0);
+            self.record(position, UnsafeKind::Impl, Some(name), Some(documented));
+        }
+        syn::visit::visit_item_impl(self, item_impl);
+    }
+
+    fn visit_item_trait(&mut self, item_trait: &'ast ItemTrait) {
+        if let Some(unsafety) = &item_trait.unsafety {
+            self.record(
+                get_position(unsafety),
+                UnsafeKind::Trait,
+                Some(item_trait.ident.to_string()),
+                None,
+            );
+        }
+        syn::visit::visit_item_trait(self, item_trait);
+    }
+
+    fn visit_item_static(&mut self, item_static: &'ast ItemStatic) {
+        if matches!(item_static.mutability, StaticMutability::Mut(_)) {
+            self.record(
+                get_position(&item_static.static_token),
+                UnsafeKind::StaticMut,
+                Some(item_static.ident.to_string()),
+                None,
+            );
+        }
+        syn::visit::visit_item_static(self, item_static);
+    }
+
+    fn visit_macro(&mut self, mac: &'ast Macro) {
+        visit_macro_args(self, mac);
+    }
+}
+
+/// 文档注释里有没有 `# Safety` 小节
+fn has_safety_doc(attrs: &[Attribute]) -> bool {
+    attrs.iter().any(|attr| {
+        let Meta::NameValue(meta) = &attr.meta else {
+            return false;
+        };
+        if !meta.path.is_ident("doc") {
+            return false;
+        }
+        matches!(&meta.value, Expr::Lit(ExprLit { lit: Lit::Str(doc), .. })
+            if doc.value().trim_start().starts_with("# Safety"))
+    })
+}
@@ -18,6 +18,8 @@ pub struct CodeQualityScore {
     pub issue_density: f64,
     pub severity_distribution: SeverityDistribution,
     pub quality_level: QualityLevel,
+    /// score of test and bench code, None when it has no issues
+    pub test_score: Option<f64>,
 }
 
 #[derive(Debug, Clone)]
@@ -89,6 +91,9 @@ impl CodeScorer {
         // Basic code quality issues
         rule_weights.insert("terrible-naming".to_string(), 0.2);
         rule_weights.insert("single-letter-variable".to_string(), 1.5);
+        rule_weights.insert("naming-convention".to_string(), 0.5);
+        rule_weights.insert("pinyin-naming".to_string(), 0.5);
+        rule_weights.insert("identifier-typo".to_string(), 0.5);
 
         // Complexity issues
         rule_weights.insert("deep-nesting".to_string(), 0.3);
@@ -100,6 +105,33 @@ impl CodeScorer {
         rule_weights.insert("unwrap-abuse".to_string(), 0.4); // high weight, because it may cause panic
         rule_weights.insert("unnecessary-clone".to_string(), 0.3);
 
+        // Error handling issues
+        rule_weights.insert("stringly-error".to_string(), 0.4);
+        rule_weights.insert("boxed-error".to_string(), 0.3);
+        rule_weights.insert("swallowed-error".to_string(), 0.5); // swallowed errors hide production bugs
+        rule_weights.insert("incomplete-error-type".to_string(), 0.3);
+
+        // Public API hygiene
+        rule_weights.insert("owned-ref-param".to_string(), 0.3);
+        rule_weights.insert("bool-param-soup".to_string(), 0.3);
+        rule_weights.insert("leaky-struct".to_string(), 0.4); // public fields bypass the constructor's checks
+        rule_weights.insert("missing-non-exhaustive".to_string(), 0.2);
+        rule_weights.insert("missing-debug".to_string(), 0.2);
+        rule_weights.insert("leaky-impl-trait".to_string(), 0.2);
+
+        // Type design
+        rule_weights.insert("god-struct".to_string(), 0.4);
+        rule_weights.insert("option-soup".to_string(), 0.3);
+        rule_weights.insert("data-clump".to_string(), 0.3);
+        rule_weights.insert("primitive-obsession".to_string(), 0.2);
+        rule_weights.insert("huge-impl".to_string(), 0.3);
+
+        // Documentation debt
+        rule_weights.insert("missing-docs".to_string(), 0.2);
+        rule_weights.insert("parrot-docs".to_string(), 0.2);
+        rule_weights.insert("broken-doc-example".to_string(), 0.4); // doctests that can't even parse
+        rule_weights.insert("missing-doc-sections".to_string(), 0.2);
+
         // Advanced Rust features abuse
         rule_weights.insert("complex-closure".to_string(), 0.3);
         rule_weights.insert("lifetime-abuse".to_string(), 0.35);
@@ -108,7 +140,8 @@ impl CodeScorer {
 
         // Rust features abuse
         rule_weights.insert("channel-abuse".to_string(), 0.4);
-        rule_weights.insert("async-abuse".to_string(), 0.4);
+        rule_weights.insert("async-abuse".to_string(), 0.5); // blocking the executor stalls every task
+        rule_weights.insert("lock-abuse".to_string(), 0.3);
         rule_weights.insert("dyn-trait-abuse".to_string(), 0.4);
         rule_weights.insert("unsafe-abuse".to_string(), 0.5); // highest weight, because it's a safety issue
         rule_weights.insert("ffi-abuse".to_string(), 0.6); // high weight, because it's a safety issue
@@ -130,13 +163,46 @@ impl CodeScorer {
         }
     }
 
-    /// calculate code quality score using normalized category-based approach
+    /// calculate code quality score using normalized category-based approach,
+    /// treating every line as production code
     pub fn calculate_score(
         &self,
         issues: &[CodeIssue],
         file_count: usize,
         total_lines: usize,
     ) -> CodeQualityScore {
+        self.calculate_score_with_test_lines(issues, file_count, total_lines, 0)
+    }
+
+    /// calculate code quality score, `test_lines` of the `total_lines` being test and bench code
+    pub fn calculate_score_with_test_lines(
+        &self,
+        issues: &[CodeIssue],
+        file_count: usize,
+        total_lines: usize,
+        test_lines: usize,
+    ) -> CodeQualityScore {
+        // test code is scored on its own so it doesn't drown the production score,
+        // and each side is measured against its own line count
+        let production_lines = total_lines.saturating_sub(test_lines);
+        let (test_issues, issues): (Vec<CodeIssue>, Vec<CodeIssue>) = issues
+            .iter()
+            .cloned()
+            .partition(|issue| issue.context.is_test_code());
+        let test_score = if test_issues.is_empty() {
+            None
+        } else {
+            let test_categories = self.calculate_normalized_category_scores(
+                &test_issues,
+                if test_lines > 0 {
+                    test_lines
+                } else {
+                    total_lines
+                },
+            );
+            Some(self.calculate_weighted_final_score(&test_categories))
+        };
+
         if issues.is_empty() {
             return CodeQualityScore {
                 total_score: 100.0, // Perfect score when no issues
@@ -150,20 +216,21 @@ impl CodeScorer {
                     mild: 0,
                 },
                 quality_level: QualityLevel::Excellent,
+                test_score,
             };
         }
 
         // calculate severity distribution
-        let severity_distribution = self.calculate_severity_distribution(issues);
+        let severity_distribution = self.calculate_severity_distribution(&issues);
 
         // calculate category scores (0-100 for each category)
-        let category_scores = self.calculate_normalized_category_scores(issues, total_lines);
+        let category_scores = self.calculate_normalized_category_scores(&issues, production_lines);
 
         // calculate weighted final score
         let total_score = self.calculate_weighted_final_score(&category_scores);
 
-        let issue_density = if total_lines > 0 {
-            issues.len() as f64 / total_lines as f64 * 1000.0 // issues per 1000 lines
+        let issue_density = if production_lines > 0 {
+            issues.len() as f64 / production_lines as f64 * 1000.0 // issues per 1000 lines
         } else {
             0.0
         };
@@ -176,6 +243,7 @@ impl CodeScorer {
             issue_density,
             severity_distribution,
             quality_level: QualityLevel::from_score(total_score),
+            test_score,
         }
     }
 
@@ -276,13 +344,32 @@ impl CodeScorer {
 
         // define issue categories
         let categories = [
-            ("naming", vec!["terrible-naming", "single-letter-variable"]),
+            (
+                "naming",
+                vec![
+                    "terrible-naming",
+                    "single-letter-variable",
+                    "naming-convention",
+                    "pinyin-naming",
+                    "identifier-typo",
+                ],
+            ),
             (
                 "complexity",
                 vec!["deep-nesting", "long-function", "cyclomatic-complexity"],
             ),
             ("duplication", vec!["code-duplication"]),
-            ("rust-basics", vec!["unwrap-abuse", "unnecessary-clone"]),
+            (
+                "rust-basics",
+                vec![
+                    "unwrap-abuse",
+                    "unnecessary-clone",
+                    "stringly-error",
+                    "boxed-error",
+                    "swallowed-error",
+                    "incomplete-error-type",
+                ],
+            ),
             (
                 "advanced-rust",
                 vec![
@@ -297,6 +384,7 @@ impl CodeScorer {
                 vec![
                     "channel-abuse",
                     "async-abuse",
+                    "lock-abuse",
                     "dyn-trait-abuse",
                     "unsafe-abuse",
                     "ffi-abuse",
@@ -311,6 +399,26 @@ impl CodeScorer {
                     "reference-abuse",
                     "box-abuse",
                     "slice-abuse",
+                    "owned-ref-param",
+                    "bool-param-soup",
+                    "leaky-struct",
+                    "missing-non-exhaustive",
+                    "missing-debug",
+                    "leaky-impl-trait",
+                    "god-struct",
+                    "option-soup",
+                    "data-clump",
+                    "primitive-obsession",
+                    "huge-impl",
+                ],
+            ),
+            (
+                "documentation",
+                vec![
+                    "missing-docs",
+                    "parrot-docs",
+                    "broken-doc-example",
+                    "missing-doc-sections",
                 ],
             ),
         ];
@@ -348,13 +456,32 @@ impl CodeScorer {
 
         // Define categories with weights and thresholds
         let categories = [
-            ("naming", vec!["terrible-naming", "single-letter-variable"]),
+            (
+                "naming",
+                vec![
+                    "terrible-naming",
+                    "single-letter-variable",
+                    "naming-convention",
+                    "pinyin-naming",
+                    "identifier-typo",
+                ],
+            ),
             (
                 "complexity",
                 vec!["deep-nesting", "long-function", "cyclomatic-complexity"],
             ),
             ("duplication", vec!["code-duplication"]),
-            ("rust-basics", vec!["unwrap-abuse", "unnecessary-clone"]),
+            (
+                "rust-basics",
+                vec![
+                    "unwrap-abuse",
+                    "unnecessary-clone",
+                    "stringly-error",
+                    "boxed-error",
+                    "swallowed-error",
+                    "incomplete-error-type",
+                ],
+            ),
             (
                 "advanced-rust",
                 vec![
@@ -369,6 +496,7 @@ impl CodeScorer {
                 vec![
                     "channel-abuse",
                     "async-abuse",
+                    "lock-abuse",
                     "dyn-trait-abuse",
                     "unsafe-abuse",
                     "ffi-abuse",
@@ -383,6 +511,26 @@ impl CodeScorer {
                     "reference-abuse",
                     "box-abuse",
                     "slice-abuse",
+                    "owned-ref-param",
+                    "bool-param-soup",
+                    "leaky-struct",
+                    "missing-non-exhaustive",
+                    "missing-debug",
+                    "leaky-impl-trait",
+                    "god-struct",
+                    "option-soup",
+                    "data-clump",
+                    "primitive-obsession",
+                    "huge-impl",
+                ],
+            ),
+            (
+                "documentation",
+                vec![
+                    "missing-docs",
+                    "parrot-docs",
+                    "broken-doc-example",
+                    "missing-doc-sections",
                 ],
             ),
         ];
@@ -432,6 +580,7 @@ impl CodeScorer {
                 "advanced-rust" => (0.0, 0.5, 2.0, 4.0), // Advanced features should be used carefully
                 "rust-features" => (0.0, 0.5, 1.5, 3.0), // Special features should be rare
                 "structure" => (0.0, 1.0, 3.0, 6.0),     // Structure issues
+                "documentation" => (0.0, 2.0, 5.0, 10.0), // Every undocumented pub item counts
                 _ => (0.0, 1.0, 3.0, 6.0),               // Default thresholds
             };
 
@@ -459,13 +608,14 @@ impl CodeScorer {
     fn calculate_weighted_final_score(&self, category_scores: &HashMap<String, f64>) -> f64 {
         // Category weights (should sum to 1.0)
         let weights = [
-            ("naming", 0.25),        // 25% - Very important
+            ("naming", 0.20),        // 20% - Very important
             ("complexity", 0.20),    // 20% - Very important
             ("duplication", 0.15),   // 15% - Important
             ("rust-basics", 0.15),   // 15% - Important
             ("advanced-rust", 0.10), // 10% - Moderate
             ("rust-features", 0.10), // 10% - Moderate
             ("structure", 0.05),     // 5% - Less critical
+            ("documentation", 0.05), // 5% - Less critical
         ];
 
         let mut weighted_sum = 0.0;
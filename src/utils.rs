@@ -1,21 +1,47 @@
 use syn::spanned::Spanned;
 
-/// 从 syn AST 节点获取行号
-/// 注意：由于 proc_macro2::Span 的限制，我们只能返回默认值
-pub fn get_line_number<T: Spanned>(_node: &T) -> usize {
-    // proc_macro2::Span 在非 proc-macro 上下文中不提供位置信息
-    // 这是一个已知限制，我们返回默认值
-    1
+/// 从 syn AST 节点获取行号（从 1 开始）
+/// 依赖 proc-macro2 的 span-locations 特性
+pub fn get_line_number<T: Spanned>(node: &T) -> usize {
+    node.span().start().line.max(1)
 }
 
-/// 从 syn AST 节点获取列号
-pub fn get_column_number<T: Spanned>(_node: &T) -> usize {
-    // 同样的限制，返回默认值
-    1
+/// 从 syn AST 节点获取列号（从 1 开始）
+pub fn get_column_number<T: Spanned>(node: &T) -> usize {
+    node.span().start().column + 1
 }
 
 /// 从 syn AST 节点获取位置信息 (行号, 列号)
-/// 注意：在当前实现中，这将返回 (1, 1) 作为默认值
 pub fn get_position<T: Spanned>(node: &T) -> (usize, usize) {
     (get_line_number(node), get_column_number(node))
 }
+
+/// 从源码中取出 AST 节点对应的原文，多余的空白压成一个空格
+/// 用于在问题描述里给出改写建议
+pub fn get_source_text<T: Spanned>(content: &str, node: &T) -> Option<String> {
+    let span = node.span();
+    let (start, end) = (span.start(), span.end());
+    let lines: Vec<&str> = content.lines().collect();
+    if start.line == 0 || start.line > end.line || end.line > lines.len() {
+        return None;
+    }
+
+    let mut text = String::new();
+    for line_no in start.line..=end.line {
+        let line = lines[line_no - 1];
+        let from = if line_no == start.line {
+            start.column
+        } else {
+            0
+        };
+        let to = if line_no == end.line {
+            end.column
+        } else {
+            line.chars().count()
+        };
+        text.extend(line.chars().skip(from).take(to.saturating_sub(from)));
+        text.push(' ');
+    }
+
+    Some(text.split_whitespace().collect::<Vec<_>>().join(" "))
+}
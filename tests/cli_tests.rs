@@ -303,3 +303,66 @@ fn test_cli_invalid_rust_file() {
     // Should handle invalid Rust files gracefully
     assert!(output.status.success());
 }
+
+#[test]
+fn test_cli_unsafe_audit() {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    let file_path = temp_dir.path().join("ffi.rs");
+
+    let code = r#"
+static mut COUNTER: u32 = 0;
+
+fn bump() {
+    unsafe { COUNTER += 1 };
+}
+"#;
+    fs::write(&file_path, code).expect("Failed to write test file");
+
+    let output = Command::new("cargo")
+        .args([
+            "run",
+            "--",
+            "--unsafe-audit",
+            "--markdown",
+            file_path.to_str().unwrap(),
+        ])
+        .output()
+        .expect("Failed to execute command");
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("# Unsafe Inventory"));
+    assert!(stdout.contains("| 2 | static mut | COUNTER | - |"));
+    assert!(stdout.contains("| 5 | unsafe block | bump | ❌ |"));
+}
+
+#[test]
+fn test_cli_todos() {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    let file_path = temp_dir.path().join("todo.rs");
+
+    let code = r#"
+// TODO(alice): retry on timeout, see #12
+fn fetch() {
+    todo!()
+}
+"#;
+    fs::write(&file_path, code).expect("Failed to write test file");
+
+    let output = Command::new("cargo")
+        .args([
+            "run",
+            "--",
+            "--todos",
+            "--markdown",
+            file_path.to_str().unwrap(),
+        ])
+        .output()
+        .expect("Failed to execute command");
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("# TODO Inventory"));
+    assert!(stdout.contains("| 2 | TODO | alice | #12 | - | retry on timeout, see #12 |"));
+    assert!(stdout.contains("| 4 | todo!() | - | - | - |  |"));
+}
@@ -300,7 +300,7 @@ fn test_severity_and_roast_level_coverage() {
 
 #[test]
 fn test_code_issue_debug_and_clone() {
-    use garbage_code_hunter::{CodeIssue, RoastLevel, Severity};
+    use garbage_code_hunter::{CodeContext, CodeIssue, RoastLevel, Severity};
     use std::path::PathBuf;
 
     let issue = CodeIssue {
@@ -311,6 +311,7 @@ fn test_code_issue_debug_and_clone() {
         message: "Test message".to_string(),
         severity: Severity::Spicy,
         roast_level: RoastLevel::Sarcastic,
+        context: CodeContext::Production,
     };
 
     // Test Debug trait
@@ -323,3 +324,32 @@ fn test_code_issue_debug_and_clone() {
     assert_eq!(issue.file_path, cloned_issue.file_path);
     assert_eq!(issue.message, cloned_issue.message);
 }
+
+#[test]
+fn test_score_density_ignores_test_lines() {
+    use garbage_code_hunter::{CodeContext, CodeIssue, CodeScorer, RoastLevel, Severity};
+    use std::path::PathBuf;
+
+    let production = CodeIssue {
+        file_path: PathBuf::from("src/lib.rs"),
+        line: 3,
+        column: 1,
+        rule_name: "unwrap-abuse".to_string(),
+        message: "unwrap".to_string(),
+        severity: Severity::Spicy,
+        roast_level: RoastLevel::Sarcastic,
+        context: CodeContext::Production,
+    };
+    let test = production.clone().with_context(CodeContext::Test);
+    assert_eq!(production.context, CodeContext::Production);
+
+    let scorer = CodeScorer::new();
+    let issues = vec![production, test];
+    let all_production = scorer.calculate_score(&issues, 1, 1000);
+    let half_tests = scorer.calculate_score_with_test_lines(&issues, 1, 1000, 500);
+
+    // One production issue per 1000 lines vs per 500 production lines
+    assert_eq!(all_production.issue_density, 1.0);
+    assert_eq!(half_tests.issue_density, 2.0);
+    assert!(half_tests.test_score.is_some());
+}
@@ -1,4 +1,7 @@
-use garbage_code_hunter::{CodeAnalyzer, RoastLevel, Severity};
+use garbage_code_hunter::rules::todos::{TodoDate, TodoKind};
+use garbage_code_hunter::rules::unsafe_audit::UnsafeKind;
+use garbage_code_hunter::rules::RuleConfig;
+use garbage_code_hunter::{CodeAnalyzer, CodeContext, RoastLevel, Severity};
 use std::fs;
 use tempfile::TempDir;
 
@@ -111,6 +114,85 @@ fn main() {
     );
 }
 
+#[test]
+fn test_unwrap_reported_at_each_location() {
+    let code = r#"
+pub fn load(path: &str) -> Result<String, std::io::Error> {
+    let text = std::fs::read_to_string(path).unwrap();
+    let first = text.lines().next().expect("empty file");
+    Ok(first.to_string())
+}
+"#;
+
+    let (_temp_dir, file_path) = create_temp_rust_file(code);
+    let analyzer = CodeAnalyzer::new(&[], "en-US");
+    let issues = analyzer.analyze_file(&file_path);
+
+    let unwrap_issues: Vec<_> = issues
+        .iter()
+        .filter(|issue| issue.rule_name == "unwrap-abuse")
+        .collect();
+
+    assert_eq!(unwrap_issues.len(), 2, "Should report unwrap and expect");
+    assert_eq!(unwrap_issues[0].line, 3);
+    assert_eq!(unwrap_issues[1].line, 4);
+    assert!(
+        unwrap_issues
+            .iter()
+            .all(|issue| issue.message.contains('?')),
+        "Should suggest ? inside a function returning Result"
+    );
+}
+
+#[test]
+fn test_unwrap_context_aware_severity() {
+    let code = r#"
+pub fn library(value: Option<i32>) -> i32 {
+    value.unwrap()
+}
+
+pub fn guarded(value: Option<i32>) -> i32 {
+    if value.is_none() {
+        return 0;
+    }
+    value.unwrap()
+}
+
+fn main() {
+    Some(1).unwrap();
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn works() {
+        Some(1).unwrap();
+    }
+}
+"#;
+
+    let (_temp_dir, file_path) = create_temp_rust_file(code);
+    let analyzer = CodeAnalyzer::new(&[], "en-US");
+    let issues = analyzer.analyze_file(&file_path);
+
+    let unwrap_issues: Vec<_> = issues
+        .iter()
+        .filter(|issue| issue.rule_name == "unwrap-abuse")
+        .collect();
+
+    let severity_at = |line: usize| {
+        unwrap_issues
+            .iter()
+            .find(|issue| issue.line == line)
+            .map(|issue| issue.severity.clone())
+    };
+
+    assert_eq!(severity_at(3), Some(Severity::Spicy));
+    assert_eq!(severity_at(10), Some(Severity::Mild), "guarded unwrap");
+    assert_eq!(severity_at(14), Some(Severity::Mild), "unwrap in main");
+    assert_eq!(severity_at(21), None, "unwrap in test code is ignored");
+}
+
 #[test]
 fn test_unnecessary_clone_detection() {
     let code = r#"
@@ -138,6 +220,1753 @@ fn main() {
     assert!(!clone_issues.is_empty(), "Should detect unnecessary clones");
 }
 
+#[test]
+fn test_clone_semantic_patterns() {
+    let code = r#"
+use std::rc::Rc;
+
+fn consume(items: Vec<u8>) {}
+
+fn main() {
+    let name = String::from("hello");
+    let copy = name.clone();
+    println!("{} {}", copy, copy.len());
+
+    let items = vec![1u8, 2, 3];
+    consume(items.clone());
+
+    let kept = vec![1u8];
+    consume(kept.clone());
+    println!("{:?}", kept);
+
+    let shared = Rc::new(3);
+    let other = shared.clone();
+    let doubled = name.to_string().clone();
+
+    let mut log = vec![1u8];
+    let snapshot = log.clone();
+    log.push(2);
+    println!("{}", snapshot.len());
+
+    let n = 5;
+    let m = n.clone();
+}
+
+fn own(v: &Vec<i32>, s: &String, t: &str) -> (Vec<i32>, String, &str) {
+    (v.clone(), s.clone(), t.clone())
+}
+"#;
+
+    let (_temp_dir, file_path) = create_temp_rust_file(code);
+    let analyzer = CodeAnalyzer::new(&[], "en-US");
+    let issues = analyzer.analyze_file(&file_path);
+
+    let clone_issue_at = |line: usize| {
+        issues
+            .iter()
+            .find(|issue| issue.rule_name == "unnecessary-clone" && issue.line == line)
+    };
+
+    let borrowed = clone_issue_at(8).expect("clone that is only borrowed");
+    assert!(borrowed.message.contains("'copy'"));
+    assert!(clone_issue_at(12)
+        .expect("clone before last use")
+        .message
+        .contains("'items'"));
+    assert!(
+        clone_issue_at(15).is_none(),
+        "kept is still used afterwards"
+    );
+    assert!(clone_issue_at(19)
+        .expect("Rc clone")
+        .message
+        .contains("Rc::clone(&shared)"));
+    assert!(clone_issue_at(20).is_some(), "clone of a fresh String");
+    assert!(
+        clone_issue_at(23).is_none(),
+        "snapshot taken before the original is changed"
+    );
+    let copy_literal = clone_issue_at(28).expect("clone of a Copy literal");
+    assert!(copy_literal.message.contains("is Copy"));
+    assert!(!copy_literal.message.contains("reference"));
+    // Auto-deref makes clone() on &Vec<T> and &String copy the whole value, only &str copies the reference
+    let references: Vec<_> = issues
+        .iter()
+        .filter(|issue| issue.rule_name == "unnecessary-clone" && issue.line == 32)
+        .collect();
+    assert_eq!(references.len(), 1);
+    assert!(references[0].message.contains("'t' is already a reference"));
+}
+
+#[test]
+fn test_test_code_uses_rule_context_policy() {
+    let code = r#"
+pub fn production(input: i32) -> i32 {
+    if input > 4242 {
+        println!("too big")
+    }
+    input * 1337
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn big_input() {
+        assert_eq!(production(9001), 9001 * 1337);
+        println!("checking {}", production(42))
+    }
+}
+"#;
+
+    let (_temp_dir, file_path) = create_temp_rust_file(code);
+    let analyzer = CodeAnalyzer::new(&[], "en-US");
+    let issues = analyzer.analyze_file(&file_path);
+
+    let reported = |rule: &str, line: usize| {
+        issues
+            .iter()
+            .any(|issue| issue.rule_name == rule && issue.line == line)
+    };
+
+    assert!(reported("println-debugging", 4));
+    assert!(reported("magic-number", 6));
+    assert!(!reported("magic-number", 15), "expected values in tests");
+    assert!(!reported("println-debugging", 16));
+    assert!(issues
+        .iter()
+        .filter(|issue| issue.line >= 10)
+        .all(|issue| issue.context == CodeContext::Test));
+}
+
+#[test]
+fn test_build_script_may_print() {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    let file_path = temp_dir.path().join("build.rs");
+    fs::write(
+        &file_path,
+        "fn main() {\n    println!(\"cargo:rerun-if-changed=build.rs\")\n}\n",
+    )
+    .expect("Failed to write build script");
+
+    let analyzer = CodeAnalyzer::new(&[], "en-US");
+    let issues = analyzer.analyze_file(&file_path);
+
+    assert!(issues
+        .iter()
+        .all(|issue| issue.rule_name != "println-debugging"));
+}
+
+#[test]
+fn test_debug_output_in_libraries_and_binaries() {
+    let library = r#"
+use std::io::Write;
+
+pub fn run(x: u32) -> u32 {
+    println!("value {}", x);
+    let y = dbg!(x + 1);
+    let mut err = std::io::stderr().lock();
+    writeln!(err, "retrying").unwrap();
+    if x > 1 { print!("x") }
+    y
+}
+"#;
+    let (_temp_dir, file_path) = create_temp_rust_file(library);
+    let analyzer = CodeAnalyzer::new(&[], "en-US");
+    let issues: Vec<_> = analyzer
+        .analyze_file(&file_path)
+        .into_iter()
+        .filter(|issue| issue.rule_name == "println-debugging")
+        .collect();
+
+    let lines: Vec<usize> = issues.iter().map(|issue| issue.line).collect();
+    assert_eq!(lines, vec![5, 6, 8, 9]);
+    assert_eq!(issues[1].severity, Severity::Nuclear);
+    assert!(issues[0].message.contains("tracing::debug!"));
+    assert!(issues[2].message.contains("tracing::warn!"));
+
+    // Normal binary output is fine, only debug-looking output and dbg! are reported
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    let file_path = temp_dir.path().join("main.rs");
+    let binary = r#"
+fn main() {
+    let files = 3;
+    println!("Processed {} files", files);
+    println!("{:?}", files);
+    eprintln!("error: {}", files);
+    dbg!(files);
+}
+"#;
+    fs::write(&file_path, binary).expect("Failed to write test file");
+    let issues: Vec<_> = analyzer
+        .analyze_file(&file_path)
+        .into_iter()
+        .filter(|issue| issue.rule_name == "println-debugging")
+        .collect();
+
+    let lines: Vec<usize> = issues.iter().map(|issue| issue.line).collect();
+    assert_eq!(lines, vec![5, 7]);
+    assert_eq!(issues[0].severity, Severity::Mild);
+    assert_eq!(issues[1].severity, Severity::Nuclear);
+}
+
+#[test]
+fn test_library_code_is_decided_per_crate() {
+    let printing = r#"
+pub fn report(files: usize) {
+    println!("Processed {} files", files);
+}
+"#;
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    let write = |relative: &str, content: &str| {
+        let path = temp_dir.path().join(relative);
+        fs::create_dir_all(path.parent().unwrap()).expect("Failed to create crate directory");
+        fs::write(&path, content).expect("Failed to write crate file");
+        path
+    };
+    let analyzer = CodeAnalyzer::new(&[], "en-US");
+    let prints = |path: &std::path::Path| {
+        analyzer
+            .analyze_file(path)
+            .iter()
+            .filter(|issue| issue.rule_name == "println-debugging")
+            .count()
+    };
+
+    // A module of a binary-only crate is not library code
+    write("app/Cargo.toml", "[package]\nname = \"app\"\n");
+    write("app/src/main.rs", "mod report;\nfn main() {}\n");
+    let module = write("app/src/report.rs", printing);
+    assert_eq!(prints(&module), 0);
+
+    // A crate with lib.rs is a library even when it sits inside a directory called bin,
+    // only its own src/bin and the modules main.rs declares itself hold binary code
+    write("bin/tools/Cargo.toml", "[package]\nname = \"tools\"\n");
+    write("bin/tools/src/lib.rs", "pub mod report;\n");
+    write("bin/tools/src/main.rs", "mod cli;\nfn main() {}\n");
+    let module = write("bin/tools/src/report.rs", printing);
+    assert_eq!(prints(&module), 1);
+    let module = write("bin/tools/src/cli.rs", printing);
+    assert_eq!(prints(&module), 0);
+    let binary = write("bin/tools/src/bin/cli.rs", printing);
+    assert_eq!(prints(&binary), 0);
+}
+
+#[test]
+fn test_rules_look_inside_macro_arguments() {
+    let code = r#"
+pub fn build(input: Option<u32>) -> Vec<u32> {
+    let items = vec![input.unwrap(), 4096];
+    assert!(items.len() > 1, "got {}", items.first().unwrap());
+    items
+}
+"#;
+
+    let (_temp_dir, file_path) = create_temp_rust_file(code);
+    let analyzer = CodeAnalyzer::new(&[], "en-US");
+    let issues = analyzer.analyze_file(&file_path);
+
+    let reported = |rule: &str, line: usize| {
+        issues
+            .iter()
+            .any(|issue| issue.rule_name == rule && issue.line == line)
+    };
+
+    assert!(reported("unwrap-abuse", 3), "unwrap inside vec![]");
+    assert!(reported("magic-number", 3), "literal inside vec![]");
+    assert!(reported("unwrap-abuse", 4), "unwrap inside assert!");
+}
+
+#[test]
+fn test_macro_rules_pitfalls() {
+    let code = r#"
+#[macro_export]
+macro_rules! square {
+    ($x:expr) => {
+        $x * $x
+    };
+}
+
+macro_rules! forever {
+    ($($t:tt)*) => { forever!($($t)*) };
+}
+"#;
+
+    let (_temp_dir, file_path) = create_temp_rust_file(code);
+    let analyzer = CodeAnalyzer::new(&[], "en-US");
+    let issues = analyzer.analyze_file(&file_path);
+
+    let macro_issues: Vec<_> = issues
+        .iter()
+        .filter(|issue| issue.rule_name == "macro-abuse")
+        .collect();
+
+    assert!(macro_issues
+        .iter()
+        .any(|issue| issue.line == 4 && issue.message.contains("$x")));
+    assert!(macro_issues
+        .iter()
+        .any(|issue| issue.line == 9 && issue.severity == Severity::Nuclear));
+}
+
+#[test]
+fn test_error_handling_rules() {
+    let code = r#"
+#[derive(Debug)]
+pub enum ConfigError {
+    Missing,
+}
+
+pub fn parse(input: &str) -> Result<u32, String> {
+    let _ = std::fs::remove_file("parse.lock");
+    std::fs::write("parse.log", input).ok();
+    input.parse::<u32>().map_err(|_| "bad number".to_string())
+}
+
+pub fn load() -> Result<(), Box<dyn std::error::Error>> {
+    let guard = vec![1];
+    let _ = guard.len();
+    let _ = guard.iter().count();
+    Ok(())
+}
+"#;
+
+    let (_temp_dir, file_path) = create_temp_rust_file(code);
+    let analyzer = CodeAnalyzer::new(&[], "en-US");
+    let issues = analyzer.analyze_file(&file_path);
+
+    let lines_of = |rule: &str| -> Vec<usize> {
+        issues
+            .iter()
+            .filter(|issue| issue.rule_name == rule)
+            .map(|issue| issue.line)
+            .collect()
+    };
+
+    assert_eq!(lines_of("incomplete-error-type"), vec![3]);
+    assert_eq!(lines_of("stringly-error"), vec![7]);
+    // Infallible calls like len() and count() have no error to swallow
+    assert_eq!(lines_of("swallowed-error"), vec![8, 9, 10]);
+    assert_eq!(lines_of("boxed-error"), vec![13]);
+}
+
+#[test]
+fn test_concurrency_rules() {
+    let code = r#"
+use std::sync::{mpsc, Arc, Mutex};
+
+pub async fn refresh(state: Arc<Mutex<Vec<u8>>>) {
+    let guard = state.lock().unwrap();
+    std::thread::sleep(std::time::Duration::from_millis(10));
+    fetch().await;
+    drop(guard);
+}
+
+pub async fn offload() {
+    tokio::task::spawn_blocking(|| std::fs::read("data.bin")).await;
+}
+
+async fn fetch() {}
+
+pub fn fan_out(n: usize) {
+    for _ in 0..n {
+        let (tx, _rx) = mpsc::channel::<u8>();
+        let (_bounded_tx, _bounded_rx) = tokio::sync::mpsc::channel::<u8>(8);
+        drop(tx);
+    }
+}
+
+pub fn shared() {
+    let s: Arc<Mutex<Vec<i32>>> = Arc::new(Mutex::new(Vec::new()));
+    s.lock().unwrap().push(1);
+}
+"#;
+
+    let (_temp_dir, file_path) = create_temp_rust_file(code);
+    let analyzer = CodeAnalyzer::new(&[], "en-US");
+    let issues = analyzer.analyze_file(&file_path);
+
+    let lines_of = |rule: &str| -> Vec<usize> {
+        let mut lines: Vec<usize> = issues
+            .iter()
+            .filter(|issue| issue.rule_name == rule)
+            .map(|issue| issue.line)
+            .collect();
+        lines.sort();
+        lines
+    };
+
+    // Guards held across await and thread::sleep; std::fs inside spawn_blocking is fine
+    assert_eq!(lines_of("async-abuse"), vec![5, 6]);
+    // Only std's argument-less channel() is unbounded
+    assert_eq!(lines_of("channel-abuse"), vec![19]);
+    // One finding per let, and .lock().unwrap() belongs to lock-abuse alone
+    assert_eq!(lines_of("lock-abuse"), vec![4, 5, 26, 27]);
+    assert!(lines_of("unwrap-abuse").is_empty());
+
+    let guard_issue = issues
+        .iter()
+        .find(|issue| issue.rule_name == "async-abuse" && issue.line == 5)
+        .unwrap();
+    assert_eq!(guard_issue.severity, Severity::Nuclear);
+    assert!(guard_issue.message.contains("line 7"));
+}
+
+#[test]
+fn test_unsafe_audit() {
+    let code = r#"
+static mut COUNTER: u32 = 0;
+
+/// Reads one byte.
+///
+/// # Safety
+///
+/// `ptr` must be valid for reads.
+pub unsafe fn read(ptr: *const u8) -> u8 {
+    // SAFETY: the caller upholds the contract above
+    unsafe { *ptr }
+}
+
+pub unsafe fn view(ptr: *const u8, len: usize) -> &'static [u8] {
+    std::slice::from_raw_parts(ptr, len)
+}
+
+struct Handle(*mut u8);
+
+// SAFETY: Handle is only used behind a mutex
+unsafe impl Send for Handle {}
+unsafe impl Sync for Handle {}
+
+fn bits(x: u32) -> f32 {
+    unsafe { std::mem::transmute::<u32, f32>(x) }
+}
+
+fn store(p: *mut u8, r: &mut u8) {
+    /*
+     * SAFETY: p is valid for writes
+     */
+    unsafe { p.write(1) };
+    // SAFETY: p is still valid
+    *r = 3;
+    unsafe { p.write(4) };
+}
+"#;
+
+    let (_temp_dir, file_path) = create_temp_rust_file(code);
+    let analyzer = CodeAnalyzer::new(&[], "en-US");
+    let issues = analyzer.analyze_file(&file_path);
+
+    let mut lines: Vec<usize> = issues
+        .iter()
+        .filter(|issue| issue.rule_name == "unsafe-abuse")
+        .map(|issue| issue.line)
+        .collect();
+    lines.sort();
+    // static mut, fn without # Safety, from_raw_parts, impl without SAFETY, block without SAFETY + transmute,
+    // and a block whose SAFETY comment belongs to the statement above it
+    assert_eq!(lines, vec![2, 14, 15, 22, 25, 25, 35]);
+
+    let inventory = analyzer.unsafe_inventory(&file_path);
+    assert_eq!(inventory.len(), 1);
+    assert_eq!(inventory[0].sites.len(), 11);
+    assert_eq!(inventory[0].count(UnsafeKind::Block), 4);
+    assert_eq!(inventory[0].count(UnsafeKind::Impl), 2);
+    assert_eq!(inventory[0].undocumented(), 4);
+}
+
+#[test]
+fn test_todo_tracking() {
+    let code = r#"
+// TODO(alice): split the parser, see #42
+// TODO: handle unicode
+//   tracked in PROJ-17
+fn parse(input: &str) -> usize {
+    let DEBUG = "TODO: not a comment";
+    let r = r"// FIXME not a comment either";
+    // FIXME(bob, 2020-01-01): remove after the migration
+    // HACK: UTF-8 only
+    /* the TODO list below is prose */
+    // TODO(carol): fix this
+    // DEBUG: not a todo
+    // TODO: check the length
+    // again before parsing
+    //   NOTE: see #9
+    if input.is_empty() {
+        unimplemented!("empty input (#7)")
+    }
+    todo!()
+}
+"#;
+
+    let (_temp_dir, file_path) = create_temp_rust_file(code);
+    let analyzer = CodeAnalyzer::new(&[], "en-US");
+    let issues: Vec<_> = analyzer
+        .analyze_file(&file_path)
+        .into_iter()
+        .filter(|issue| issue.rule_name == "todo-comment")
+        .collect();
+
+    let lines: Vec<usize> = issues.iter().map(|issue| issue.line).collect();
+    assert_eq!(lines, vec![2, 3, 8, 9, 11, 13, 17, 19]);
+    // TODOs with a ticket are reminders, stale and ticketless ones are called out, the macros panic
+    let severities: Vec<&Severity> = issues.iter().map(|issue| &issue.severity).collect();
+    assert_eq!(
+        severities,
+        vec![
+            &Severity::Mild,
+            &Severity::Mild,
+            &Severity::Spicy,
+            &Severity::Spicy,
+            &Severity::Spicy,
+            &Severity::Spicy,
+            &Severity::Spicy,
+            &Severity::Spicy
+        ]
+    );
+    assert!(issues[2].message.contains("2020-01-01"));
+
+    let inventory = analyzer.todo_inventory(&file_path);
+    assert_eq!(inventory.len(), 1);
+    let items = &inventory[0].items;
+    assert_eq!(items[0].owner.as_deref(), Some("alice"));
+    assert_eq!(items[0].ticket.as_deref(), Some("#42"));
+    assert_eq!(items[1].ticket.as_deref(), Some("PROJ-17"));
+    assert_eq!(items[2].kind, TodoKind::Fixme);
+    assert_eq!(items[2].owner.as_deref(), Some("bob"));
+    assert_eq!(
+        items[2].due,
+        Some(TodoDate {
+            year: 2020,
+            month: 1,
+            day: 1
+        })
+    );
+    // UTF-8 is not a ticket number
+    assert_eq!(items[3].ticket, None);
+    // other tags and unindented comment lines don't continue a TODO
+    assert_eq!(items[4].text, "fix this");
+    assert_eq!(items[5].text, "check the length");
+    assert_eq!(items[5].ticket, None);
+    assert_eq!(items[6].kind, TodoKind::Unimplemented);
+    assert_eq!(items[6].ticket.as_deref(), Some("#7"));
+    assert_eq!(items[7].kind, TodoKind::TodoMacro);
+    assert_eq!(inventory[0].untracked(), 4);
+    assert_eq!(inventory[0].overdue(TodoDate::today()), 1);
+}
+
+#[test]
+fn test_public_api_hygiene_rules() {
+    let code = r#"
+pub struct Percent {
+    pub value: u8,
+}
+
+impl Percent {
+    pub fn new(value: u8) -> Option<Self> {
+        if value > 100 {
+            return None;
+        }
+        Some(Self { value })
+    }
+}
+
+#[derive(Debug)]
+pub enum ParseError {
+    Empty,
+}
+
+pub fn render(name: &String, out: &mut Vec<u8>) {}
+
+pub fn configure(verbose: bool, color: bool, strict: bool) {}
+
+fn internal(verbose: bool, color: bool, strict: bool, name: &String) {}
+
+pub fn numbers() -> impl Iterator<Item = u8> {
+    0..3
+}
+
+pub fn handle() -> impl Clone {
+    3
+}
+"#;
+
+    let (temp_dir, file_path) = create_temp_rust_file(code);
+    let analyzer = CodeAnalyzer::new(&[], "en-US");
+    let issues = analyzer.analyze_file(&file_path);
+
+    let lines_of = |rule: &str| -> Vec<usize> {
+        issues
+            .iter()
+            .filter(|issue| issue.rule_name == rule)
+            .map(|issue| issue.line)
+            .collect()
+    };
+
+    assert_eq!(lines_of("leaky-struct"), vec![2]);
+    assert_eq!(lines_of("missing-debug"), vec![2]);
+    assert_eq!(lines_of("missing-non-exhaustive"), vec![16]);
+    // Private fns and &mut Vec are fine
+    assert_eq!(lines_of("owned-ref-param"), vec![20]);
+    assert_eq!(lines_of("bool-param-soup"), vec![22]);
+    // Returning an iterator is idiomatic
+    assert_eq!(lines_of("leaky-impl-trait"), vec![30]);
+
+    // A binary has no public API to keep tidy
+    let main_path = temp_dir.path().join("main.rs");
+    fs::write(&main_path, code).expect("Failed to write test file");
+    let binary_issues = analyzer.analyze_file(&main_path);
+    assert!(!binary_issues.iter().any(|issue| [
+        "leaky-struct",
+        "missing-debug",
+        "missing-non-exhaustive",
+        "owned-ref-param",
+        "bool-param-soup",
+        "leaky-impl-trait",
+    ]
+    .contains(&issue.rule_name.as_str())));
+}
+
+#[test]
+fn test_documentation_rules() {
+    let code = r#"
+pub struct Config {
+    name: String,
+}
+
+impl Config {
+    /// Gets the name.
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    /// Loads a config from disk, creating the file when missing.
+    pub fn load(path: &str) -> Result<Config, std::io::Error> {
+        if path.is_empty() {
+            panic!("empty path");
+        }
+        Ok(Config { name: path.into() })
+    }
+
+    /// Builds a config with the given display name.
+    ///
+    /// ```
+    /// let cfg = Config::build("x"
+    /// ```
+    ///
+    /// ```text
+    /// not rust (
+    /// ```
+    pub fn build(name: &str) -> Self {
+        Config { name: name.into() }
+    }
+
+    /// Parses `raw` into a config.
+    ///
+    /// # Errors
+    ///
+    /// Fails when `raw` is empty.
+    ///
+    /// ```
+    /// # use demo::Config;
+    /// let cfg = Config::parse("x")?;
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn parse(raw: &str) -> Result<Config, std::io::Error> {
+        Ok(Config { name: raw.into() })
+    }
+}
+
+fn undocumented_private() {}
+"#;
+
+    let (_temp_dir, file_path) = create_temp_rust_file(code);
+    let analyzer = CodeAnalyzer::new(&[], "en-US");
+    let issues = analyzer.analyze_file(&file_path);
+
+    let lines_of = |rule: &str| -> Vec<usize> {
+        issues
+            .iter()
+            .filter(|issue| issue.rule_name == rule)
+            .map(|issue| issue.line)
+            .collect()
+    };
+
+    // Private fns need no docs
+    assert_eq!(lines_of("missing-docs"), vec![2]);
+    assert_eq!(lines_of("parrot-docs"), vec![8]);
+    // text code blocks and examples with hidden lines are fine
+    assert_eq!(lines_of("broken-doc-example"), vec![22]);
+    assert_eq!(lines_of("missing-doc-sections"), vec![13]);
+
+    let sections = issues
+        .iter()
+        .find(|issue| issue.rule_name == "missing-doc-sections")
+        .unwrap();
+    assert!(sections.message.contains("# Errors / # Panics"));
+}
+
+#[test]
+fn test_type_design_rules() {
+    let code = r#"
+struct Everything {
+    f0: u8,
+    f1: u8,
+    f2: u8,
+    f3: u8,
+    f4: u8,
+    f5: u8,
+    f6: u8,
+    f7: u8,
+    f8: u8,
+    f9: u8,
+    f10: u8,
+    f11: u8,
+    f12: u8,
+}
+
+struct Job {
+    started_at: Option<u64>,
+    finished_at: Option<u64>,
+    error: Option<String>,
+    output: Option<Vec<u8>>,
+}
+
+struct Order {
+    street: String,
+    city: String,
+    zip: String,
+    total: u64,
+}
+
+struct Customer {
+    name: String,
+    street: String,
+    city: String,
+    zip: String,
+}
+
+struct User {
+    user_id: String,
+    email: String,
+    avatar_url: String,
+    nickname: String,
+}
+
+impl Everything {
+    fn m0(&self) {}
+    fn m1(&self) {}
+    fn m2(&self) {}
+    fn m3(&self) {}
+    fn m4(&self) {}
+    fn m5(&self) {}
+    fn m6(&self) {}
+    fn m7(&self) {}
+    fn m8(&self) {}
+    fn m9(&self) {}
+    fn m10(&self) {}
+    fn m11(&self) {}
+    fn m12(&self) {}
+    fn m13(&self) {}
+    fn m14(&self) {}
+    fn m15(&self) {}
+    fn m16(&self) {}
+    fn m17(&self) {}
+    fn m18(&self) {}
+    fn m19(&self) {}
+    fn m20(&self) {}
+}
+
+#[derive(Parser)]
+struct Args {
+    flag_0: u8,
+    flag_1: u8,
+    flag_2: u8,
+    flag_3: u8,
+    flag_4: u8,
+    flag_5: u8,
+    flag_6: u8,
+    flag_7: u8,
+    flag_8: u8,
+    flag_9: u8,
+    flag_10: u8,
+    flag_11: u8,
+    flag_12: u8,
+}
+"#;
+
+    let (_temp_dir, file_path) = create_temp_rust_file(code);
+    let analyzer = CodeAnalyzer::new(&[], "en-US");
+    let issues = analyzer.analyze_file(&file_path);
+
+    let lines_of = |rule: &str| -> Vec<usize> {
+        issues
+            .iter()
+            .filter(|issue| issue.rule_name == rule)
+            .map(|issue| issue.line)
+            .collect()
+    };
+
+    // A clap args struct is not a god struct
+    assert_eq!(lines_of("god-struct"), vec![2]);
+    assert_eq!(lines_of("option-soup"), vec![18]);
+    assert_eq!(lines_of("data-clump"), vec![32]);
+    assert_eq!(lines_of("primitive-obsession"), vec![39]);
+    assert_eq!(lines_of("huge-impl"), vec![46]);
+
+    let obsession = issues
+        .iter()
+        .find(|issue| issue.rule_name == "primitive-obsession")
+        .unwrap();
+    assert!(obsession.message.contains("UserId"));
+}
+
+#[test]
+fn test_string_allocation_waste() {
+    let code = r#"
+pub struct User {
+    pub name: String,
+}
+
+fn greet(name: &String) -> String {
+    format!("{}", name)
+}
+
+pub fn shout(msg: String) -> usize {
+    println!("{}", msg);
+    msg.trim().len()
+}
+
+pub fn keep(msg: String) -> User {
+    User { name: msg }
+}
+
+fn copy(s: String) -> String {
+    let t = s.to_string();
+    t + s.as_str().to_string().as_str()
+}
+
+fn build(items: &[u32]) -> String {
+    let mut out = String::new();
+    let mut csv = String::new();
+    let mut total = 0;
+    for item in items {
+        out = out + &item.to_string() + ",";
+        csv = format!("{}{},", csv, item);
+        total = total + item;
+        out.push_str(&format!("[{}]", item));
+    }
+    out + &csv + &total.to_string()
+}
+"#;
+
+    let (_temp_dir, file_path) = create_temp_rust_file(code);
+    let analyzer = CodeAnalyzer::new(&[], "en-US");
+    let issues: Vec<_> = analyzer
+        .analyze_file(&file_path)
+        .into_iter()
+        .filter(|issue| issue.rule_name == "string-abuse")
+        .collect();
+    let lines: Vec<usize> = issues.iter().map(|issue| issue.line).collect();
+
+    // String fields, params that take ownership, numeric sums and a plain to_string() are fine
+    assert_eq!(lines, vec![6, 7, 10, 20, 21, 29, 30, 32]);
+    assert!(issues[1].message.contains("name.clone()"));
+    assert_eq!(
+        issues[2].severity,
+        Severity::Spicy,
+        "public read-only String param"
+    );
+    assert!(issues[4].message.contains("as_str()"));
+    assert_eq!(
+        issues[6].severity,
+        Severity::Spicy,
+        "format! rebuild is quadratic"
+    );
+    assert!(issues[7].message.contains("write!(out"));
+}
+
+#[test]
+fn test_vec_allocation_waste() {
+    let code = r#"
+const W: usize = 8;
+
+fn sum(values: &Vec<i32>) -> i32 {
+    values.iter().sum()
+}
+
+fn build() -> Vec<i32> {
+    let mut v = Vec::new();
+    v.push(1);
+    v.push(2);
+    v
+}
+
+fn squares(n: usize) -> Vec<usize> {
+    let mut out = Vec::new();
+    for i in 0..n {
+        let sq = i * i;
+        out.push(sq);
+    }
+    out
+}
+
+fn check(items: &[i32], list: Vec<i32>) -> usize {
+    let evens = items.iter().filter(|x| **x > 0).collect::<Vec<_>>().len();
+    if list.len() == 0 {
+        return evens;
+    }
+    let grid = vec![vec![0u8; W]; 4];
+    items.iter().filter(|x| list.contains(x)).count() + grid[0].len()
+}
+
+pub fn public(values: &Vec<i32>) -> usize {
+    let mut grid = vec![vec![0u8; 3]; 3];
+    grid.push(vec![1]);
+    let mut odd = Vec::new();
+    for v in values {
+        if *v % 2 == 1 {
+            continue;
+        }
+        odd.push(*v);
+        odd.push(*v);
+    }
+    for v in values {
+        let local = vec![1, 2];
+        if local.contains(v) {}
+    }
+    odd.len() + grid.len()
+}
+"#;
+
+    let (_temp_dir, file_path) = create_temp_rust_file(code);
+    let analyzer = CodeAnalyzer::new(&[], "en-US");
+    let issues: Vec<_> = analyzer
+        .analyze_file(&file_path)
+        .into_iter()
+        .filter(|issue| issue.rule_name == "vec-abuse")
+        .collect();
+    let mut lines: Vec<usize> = issues.iter().map(|issue| issue.line).collect();
+    lines.sort_unstable();
+
+    // &Vec in a pub fn, resized matrices, loops that don't push every iteration and Vecs created inside loops are fine
+    assert_eq!(lines, vec![4, 9, 16, 25, 26, 29, 30]);
+    let message = |line: usize| {
+        issues
+            .iter()
+            .find(|issue| issue.line == line)
+            .map(|issue| issue.message.clone())
+            .unwrap_or_default()
+    };
+    assert!(message(4).contains("&[i32]"));
+    assert!(message(9).contains("vec![1, 2]"));
+    assert!(message(16).contains("Vec::with_capacity(n)"));
+    assert!(message(26).contains("is_empty()"));
+    assert!(message(29).contains("[[0u8; W]; 4]"));
+    assert!(issues
+        .iter()
+        .any(|issue| issue.line == 30 && issue.severity == Severity::Spicy));
+}
+
+#[test]
+fn test_redundant_lifetimes() {
+    let code = r#"
+struct Pair<'a, 'b> {
+    left: &'a str,
+    right: &'b str,
+}
+
+struct Tied<'a, 'b> {
+    slot: &'a mut Vec<&'b str>,
+}
+
+fn first<'a>(s: &'a str) -> &'a str {
+    &s[..1]
+}
+
+fn longest<'a>(x: &'a str, y: &'a str) -> &'a str {
+    if x.len() > y.len() { x } else { y }
+}
+
+fn show<'a>(x: &'a str) -> usize {
+    x.len()
+}
+
+fn make<'a>() -> &'a str {
+    "hi"
+}
+
+fn debug<T: std::fmt::Debug + 'static>(value: T) -> String {
+    format!("{value:?}")
+}
+
+fn spawn_it<T: Send + 'static>(value: T) {
+    std::thread::spawn(move || drop(value));
+}
+
+struct Parser<'a> {
+    input: &'a str,
+}
+
+impl<'a> Parser<'a> {
+    fn next(&'a mut self) -> Option<&'a str> {
+        None
+    }
+
+    fn peek(&self) -> &'a str {
+        self.input
+    }
+}
+
+fn shorten<'a>(v: &mut Vec<&'a str>, s: &'a str) {
+    v.push(s);
+}
+
+struct Registry {
+    items: Vec<Box<dyn std::any::Any>>,
+}
+
+fn store<T: 'static>(registry: &mut Registry, t: T) {
+    registry.items.push(Box::new(t));
+}
+
+impl Registry {
+    fn add<T: 'static>(&mut self, t: T) {
+        store(self, t)
+    }
+}
+
+fn count<T: 'static>(items: &[T]) -> usize {
+    let spawned_threads = items.len();
+    spawned_threads
+}
+"#;
+
+    let (_temp_dir, file_path) = create_temp_rust_file(code);
+    let analyzer = CodeAnalyzer::new(&[], "en-US");
+    let issues: Vec<_> = analyzer
+        .analyze_file(&file_path)
+        .into_iter()
+        .filter(|issue| issue.rule_name == "lifetime-abuse")
+        .collect();
+    let lines: Vec<usize> = issues.iter().map(|issue| issue.line).collect();
+
+    // Lifetimes that really tie a param to the return value, invariant ones behind &mut and 'static next to Send are fine
+    // 'static is also fine when the value is handed to another call, and names like spawned_threads don't count as spawn
+    assert_eq!(lines, vec![2, 11, 19, 23, 27, 40, 67]);
+    assert!(issues[0].message.contains("'a"));
+    assert!(issues[2].message.contains("show()"));
+    assert_eq!(issues[3].severity, Severity::Spicy, "return-only lifetime");
+    assert!(issues[4].message.contains("T: 'static"));
+    assert_eq!(issues[5].severity, Severity::Spicy, "&'a mut self");
+}
+
+#[test]
+fn test_generic_abuse() {
+    let head = r#"
+use std::fmt::Display;
+use std::hash::Hash;
+use std::path::Path;
+
+fn show<T: Display>(value: T) -> String {
+    value.to_string()
+}
+
+fn show_all<T>(values: &[T]) -> usize
+where
+    T: Display,
+{
+    values.len()
+}
+
+fn pick<T: Clone>(values: &[T]) -> T {
+    values[0].clone()
+}
+
+fn nothing<T: Default>() -> usize {
+    1
+}
+
+fn ignored<T: Display>(_value: T) -> usize {
+    2
+}
+
+fn pair<T: Display>(a: T, b: T) -> String {
+    format!("{a}{b}")
+}
+
+fn parse<T: std::str::FromStr>(text: &str) -> Option<T> {
+    text.parse::<T>().ok()
+}
+
+struct Cache<K: Hash + Eq, V> {
+    items: Vec<(K, V)>,
+}
+
+struct Summed<I: Iterator> {
+    last: Option<I::Item>,
+}
+
+struct Guard<T: Display> {
+    value: T,
+}
+
+impl<T: Display> Drop for Guard<T> {
+    fn drop(&mut self) {
+        println!("{}", self.value);
+    }
+}
+
+fn a<T: Clone + Send + Sync>(x: T, y: T) -> (T, T) { (x, y) }
+fn b<T: Send + Sync + Clone>(x: T, y: T) -> (T, T) { (x, y) }
+fn c<T>(x: T, y: T) -> (T, T) where T: Clone + Send + Sync { (x, y) }
+
+fn read_config<P: AsRef<Path>>(path: P) -> usize {
+    let path = path.as_ref();
+    let mut total = 0;
+"#;
+    let code = format!(
+        "{head}{}    total\n}}\n",
+        "    total += path.to_str().map_or(0, |s| s.len());\n".repeat(30)
+    );
+
+    let (_temp_dir, file_path) = create_temp_rust_file(&code);
+    let analyzer = CodeAnalyzer::new(&[], "en-US");
+    let mut issues: Vec<_> = analyzer
+        .analyze_file(&file_path)
+        .into_iter()
+        .filter(|issue| issue.rule_name == "generic-abuse")
+        .collect();
+    issues.sort_by_key(|issue| issue.line);
+    let lines: Vec<usize> = issues.iter().map(|issue| issue.line).collect();
+
+    // Params used several times or in the return type, bounds needed by Drop or by an associated type are fine
+    assert_eq!(lines, vec![6, 10, 21, 25, 37, 55, 59]);
+    assert!(issues[0].message.contains("impl Display"));
+    assert_eq!(issues[2].severity, Severity::Spicy, "unused type parameter");
+    assert!(issues[4].message.contains("Cache"));
+    assert!(issues[5].message.contains('3'));
+    assert!(issues[6].message.contains("read_config"));
+}
+
+#[test]
+fn test_match_rewrites() {
+    let code = r#"
+enum Kind { A(u8), B, C, D }
+
+fn rewrites(opt: Option<u8>, res: Result<u8, String>, flag: bool) -> Result<u8, String> {
+    let a = match opt {
+        Some(v) => Some(v + 1),
+        None => None,
+    };
+    let b = match opt {
+        Some(v) => v,
+        None => 0,
+    };
+    let c = match res {
+        Ok(v) => v,
+        Err(e) => return Err(e),
+    };
+    match flag {
+        true => println!("yes"),
+        false => println!("no"),
+    }
+    match opt {
+        Some(v) => println!("{v}"),
+        None => {}
+    }
+    let Something = 1;
+    let d = match Something {
+        1 => "one",
+        _ => "many",
+    };
+    Ok(c)
+}
+
+fn label(kind: &Kind) -> &str {
+    match kind {
+        Kind::A(_) => "a",
+        Kind::B => "b",
+        _ => "other",
+    }
+}
+"#;
+
+    let (_temp_dir, file_path) = create_temp_rust_file(code);
+    let analyzer = CodeAnalyzer::new(&[], "en-US");
+    let issues = analyzer.analyze_file(&file_path);
+
+    let messages_of = |rule: &str| -> Vec<(usize, String)> {
+        issues
+            .iter()
+            .filter(|issue| issue.rule_name == rule)
+            .map(|issue| (issue.line, issue.message.clone()))
+            .collect()
+    };
+
+    let matches = messages_of("match-abuse");
+    // Identifiers containing Some and plain numeric matches are fine
+    let lines: Vec<usize> = matches.iter().map(|(line, _)| *line).collect();
+    assert_eq!(lines, vec![5, 9, 13, 17, 21]);
+    assert!(matches[0].1.contains("opt.map(|v| v + 1)"));
+    assert!(matches[1].1.contains("opt.unwrap_or(0)"));
+    assert!(matches[2].1.contains("res?"));
+    assert!(matches[3].1.contains("if flag { .. } else { .. }"));
+    assert!(matches[4].1.contains("if let Some(v) = opt { .. }"));
+
+    let hidden = messages_of("pattern-matching-abuse");
+    assert_eq!(hidden.len(), 1);
+    assert_eq!(hidden[0].0, 37);
+    assert!(hidden[0].1.contains("Kind::C | Kind::D"));
+
+    // Same-named enums in different modules can't be told apart by name
+    let shadowed = r#"
+mod shapes {
+    pub enum Kind { Circle, Square, Line }
+
+    pub fn name(kind: &Kind) -> &str {
+        match kind {
+            Kind::Circle => "circle",
+            Kind::Square => "square",
+            _ => "other",
+        }
+    }
+}
+
+enum Kind { A, B, C }
+"#;
+    let (_shadowed_dir, shadowed_path) = create_temp_rust_file(shadowed);
+    assert!(!analyzer
+        .analyze_file(&shadowed_path)
+        .iter()
+        .any(|issue| issue.rule_name == "pattern-matching-abuse"));
+}
+
+#[test]
+fn test_iterator_rewrites() {
+    let code = r#"
+fn loops(v: Vec<i32>, w: Vec<i32>, target: i32) -> Option<usize> {
+    let mut out = Vec::new();
+    for x in &v {
+        if *x > 0 {
+            out.push(x * 2);
+        }
+    }
+    let mut total = 0;
+    for x in &v {
+        total += x;
+    }
+    for (i, x) in v.iter().enumerate() {
+        if *x == target {
+            return Some(i);
+        }
+    }
+    for i in 0..v.len() {
+        println!("{}", v[i]);
+    }
+    let mut pairs = Vec::new();
+    for a in &v {
+        for b in &w {
+            pairs.push((a, b));
+        }
+    }
+    for x in &v {
+        println!("{x}");
+    }
+    None
+}
+
+fn checks(mut v: Vec<i32>) -> Result<(), String> {
+    for i in 0..v.len() {
+        v[i] *= 2;
+    }
+    for i in 0..v.len() {
+        v[i] = 0;
+    }
+    for x in &v {
+        if *x < 0 {
+            return Err("negative".to_string());
+        }
+    }
+    Ok(())
+}
+
+fn all_small(v: &[i32]) -> bool {
+    for x in v {
+        if *x > 100 {
+            return false;
+        }
+    }
+    true
+}
+
+fn stats(v: &[u64], seed: Vec<u64>) -> (u64, Vec<u64>, usize) {
+    let mut total: u64 = 0;
+    for x in v {
+        total += x;
+    }
+    let mut out = seed;
+    for x in v {
+        out.push(*x);
+    }
+    let mut big = 3;
+    for x in v {
+        if *x > 9 {
+            big += 1;
+        }
+    }
+    (total, out, big)
+}
+"#;
+
+    let (_temp_dir, file_path) = create_temp_rust_file(code);
+    let analyzer = CodeAnalyzer::new(&[], "en-US");
+    let issues = analyzer.analyze_file(&file_path);
+
+    let loops: Vec<(usize, String)> = issues
+        .iter()
+        .filter(|issue| issue.rule_name == "iterator-abuse")
+        .map(|issue| (issue.line, issue.message.clone()))
+        .collect();
+
+    // Plain for loops are no longer reported, nested loops only at the outer one
+    let lines: Vec<usize> = loops.iter().map(|(line, _)| *line).collect();
+    assert_eq!(lines, vec![4, 10, 13, 18, 22, 34, 37, 49, 59, 63, 67]);
+    assert!(loops[0]
+        .1
+        .contains("v.iter().filter(|x| *x > 0).map(|x| x * 2).collect()"));
+    assert!(loops[1].1.contains("v.iter().sum()"));
+    assert!(loops[2].1.contains("v.iter().position(|x| *x == target)"));
+    assert!(loops[3].1.contains("for item in v.iter()"));
+    assert!(loops[4]
+        .1
+        .contains("flat_map(|a| w.iter().map(move |b| (a, b)))"));
+    // In-place updates iterate mutably, an early Err is not a search
+    assert!(loops[5].1.contains("for item in v.iter_mut()"));
+    assert!(loops[6].1.contains("for item in v.iter_mut()"));
+    assert!(loops[7].1.contains("v.into_iter().all(|x| *x <= 100)"));
+    // Only a freshly initialised accumulator becomes a new let, otherwise the old value is kept
+    assert!(loops[1].1.contains("let total = v.iter().sum();"));
+    assert!(loops[8].1.contains("let total: u64 = v.into_iter().sum();"));
+    assert!(loops[9].1.contains("out.extend(v.into_iter().copied());"));
+    assert!(loops[10]
+        .1
+        .contains("big += v.into_iter().filter(|x| *x > 9).count();"));
+}
+
+#[test]
+fn test_magic_number_context() {
+    let code = r#"
+const BUFFER_SIZE: usize = 4096;
+static TABLE: [u8; 300] = [0; 300];
+
+pub enum Status {
+    Teapot = 418,
+}
+
+pub fn poll(flags: u32, buf: &[u8]) -> u32 {
+    let masked = flags & 0xFF00;
+    let head = &buf[..4];
+    let retry_limit = 7;
+    if flags > retry_limit * 7 {
+        return 7;
+    }
+    masked + head.len() as u32 + 60 + 1024 + 4242
+}
+
+#[test]
+fn poll_works() {
+    assert_eq!(super::poll(3, &[]), 9999);
+}
+"#;
+
+    let (temp_dir, file_path) = create_temp_rust_file(code);
+    fs::write(
+        temp_dir.path().join("other.rs"),
+        "pub fn f(x: u32) -> u32 { x + 4242 }",
+    )
+    .expect("Failed to write second file");
+
+    let analyzer = CodeAnalyzer::new(&[], "en-US");
+    let issues: Vec<_> = analyzer
+        .analyze_path(temp_dir.path())
+        .into_iter()
+        .filter(|issue| issue.rule_name == "magic-number")
+        .collect();
+    let at = |file: &std::path::Path, line: usize| {
+        issues
+            .iter()
+            .filter(|issue| issue.file_path == file && issue.line == line)
+            .count()
+    };
+
+    // Consts, array lengths, discriminants, bit masks, range bounds, common values and tests are fine
+    for line in [2, 3, 6, 10, 11, 21] {
+        assert_eq!(at(&file_path, line), 0, "line {line} is not magic");
+    }
+    assert_eq!(at(&file_path, 12), 1, "7 repeated in one file");
+    assert!(issues
+        .iter()
+        .any(|issue| issue.line == 12 && issue.message.contains("RETRY_LIMIT")));
+    assert_eq!(at(&file_path, 13), 0, "folded into the repeated finding");
+    let cross_file: Vec<_> = issues
+        .iter()
+        .filter(|issue| issue.message.contains("4242"))
+        .collect();
+    assert_eq!(cross_file.len(), 2);
+    assert!(cross_file
+        .iter()
+        .all(|issue| issue.severity == Severity::Spicy && issue.message.contains("2 files")));
+
+    let relaxed = CodeAnalyzer::new(&[], "en-US").with_config(RuleConfig {
+        allowed_numbers: vec![7, 4242],
+        ..RuleConfig::default()
+    });
+    assert!(relaxed
+        .analyze_path(temp_dir.path())
+        .iter()
+        .all(|issue| issue.rule_name != "magic-number"));
+}
+
+#[test]
+fn test_commented_code_trial_parsing() {
+    let code = r#"
+/// ```
+/// let x = compute();
+/// println!("{}", x);
+/// ```
+pub fn compute() -> u32 {
+    // This computes the answer (see above), and then
+    // returns it to the caller.
+    // +-------+     +-------+
+    // | input | --> | proc  |
+    // +-------+     +-------+
+    // let old = legacy();
+    // if old > 3 {
+    //     return old;
+    // }
+    3
+}
+"#;
+
+    let (_temp_dir, file_path) = create_temp_rust_file(code);
+    let analyzer = CodeAnalyzer::new(&[], "en-US");
+    let issues: Vec<_> = analyzer
+        .analyze_file(&file_path)
+        .into_iter()
+        .filter(|issue| issue.rule_name == "commented-code")
+        .collect();
+
+    // Doc examples, prose and ASCII art are fine, only the 4 lines that parse
+    assert_eq!(issues.len(), 1);
+    assert_eq!((issues[0].line, issues[0].column), (12, 5));
+    assert!(issues[0].message.contains("4 lines"));
+    assert!(issues[0].message.contains("(lines 12-15)"));
+}
+
+#[test]
+fn test_commented_code_long_blocks_stay_fast() {
+    let prose =
+        "// Permission is hereby granted (free of charge) to any person obtaining a copy,\n"
+            .repeat(400);
+    let code_block = "//     total += values[index] * 2;\n".repeat(120);
+    let code = format!("fn main() {{\n{prose}{code_block}}}\n");
+
+    let (_temp_dir, file_path) = create_temp_rust_file(&code);
+    let analyzer = CodeAnalyzer::new(&[], "en-US");
+    let started = std::time::Instant::now();
+    let issues: Vec<_> = analyzer
+        .analyze_file(&file_path)
+        .into_iter()
+        .filter(|issue| issue.rule_name == "commented-code")
+        .collect();
+
+    assert!(started.elapsed() < std::time::Duration::from_secs(10));
+    // The long block is found in pieces and reported once
+    assert_eq!(issues.len(), 1);
+    assert!(issues[0].message.contains("(lines 402-521)"));
+}
+
+#[test]
+fn test_dead_code_detection() {
+    let code = r#"
+const USED: u32 = 1;
+const UNUSED: u32 = 2;
+#[allow(dead_code)]
+fn forgotten() {}
+
+pub fn run(flag: bool) -> u32 {
+    if false {
+        return USED;
+    }
+    if flag {
+        return 3;
+        let x = 1;
+        x
+    } else {
+        loop {
+            for i in 0..3 {
+                if i == 2 {
+                    break;
+                }
+            }
+        }
+        println!("never");
+    }
+}
+"#;
+
+    let (_temp_dir, file_path) = create_temp_rust_file(code);
+    let analyzer = CodeAnalyzer::new(&[], "en-US");
+    let issues = analyzer.analyze_file(&file_path);
+    let dead_lines: Vec<usize> = issues
+        .iter()
+        .filter(|issue| issue.rule_name == "dead-code")
+        .map(|issue| issue.line)
+        .collect();
+
+    assert!(dead_lines.contains(&3), "unused private const");
+    assert!(dead_lines.contains(&4), "allow(dead_code) hiding it");
+    assert!(dead_lines.contains(&5), "unused private fn");
+    assert!(dead_lines.contains(&8), "if false");
+    assert!(dead_lines.contains(&13), "statements after return");
+    assert!(dead_lines.contains(&23), "statements after breakless loop");
+    assert!(!dead_lines.contains(&2), "USED is referenced");
+    assert!(!dead_lines.contains(&19), "break inside nested for loop");
+}
+
+#[test]
+fn test_naming_reports_declarations_once() {
+    let code = r#"
+use external::Response;
+
+pub fn run(resp: Response) -> usize {
+    let data = resp.data.len();
+    let Response { info, .. } = resp;
+    data + data + info + resp.temp
+}
+"#;
+
+    let (_temp_dir, file_path) = create_temp_rust_file(code);
+    let analyzer = CodeAnalyzer::new(&[], "en-US");
+    let issues: Vec<_> = analyzer
+        .analyze_file(&file_path)
+        .into_iter()
+        .filter(|issue| issue.rule_name == "meaningless-naming")
+        .collect();
+
+    // Only the data declared here, data/info/temp fields of external types are fine
+    assert_eq!(issues.len(), 1);
+    assert_eq!((issues[0].line, issues[0].column), (5, 9));
+    assert!(issues[0].message.ends_with("(used 2 times)"));
+}
+
+#[test]
+fn test_binding_use_counts_are_per_binding() {
+    let code = r#"
+pub struct Holder {
+    pub data: u8,
+}
+
+fn other() -> u8 {
+    let data = 1;
+    let data = data + data;
+    data
+}
+"#;
+
+    let (_temp_dir, file_path) = create_temp_rust_file(code);
+    let analyzer = CodeAnalyzer::new(&[], "en-US");
+    let issues: Vec<_> = analyzer
+        .analyze_file(&file_path)
+        .into_iter()
+        .filter(|issue| issue.rule_name == "meaningless-naming")
+        .collect();
+    let message_at = |line: usize| {
+        issues
+            .iter()
+            .find(|issue| issue.line == line)
+            .map(|issue| issue.message.clone())
+            .unwrap_or_default()
+    };
+
+    // The field is never read, the local of the same name in other() doesn't count for it
+    assert!(message_at(3).ends_with("(used 0 times)"));
+    // Each shadowed let gets its own uses
+    assert!(message_at(7).ends_with("(used 2 times)"));
+    assert!(message_at(8).ends_with("(used once)"));
+}
+
+#[test]
+fn test_naming_conventions() {
+    let code = r#"
+pub struct HTTPServer {
+    name: String,
+}
+const maxRetries: u32 = 3;
+fn parseInput<const N: usize, T>(value: T) -> T {
+    value
+}
+impl HTTPServer {
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+    pub fn get_mut(&mut self) -> &mut String {
+        &mut self.name
+    }
+    pub fn as_owned(&self) -> String {
+        self.name.clone()
+    }
+    pub fn into_name(self) -> String {
+        self.name
+    }
+}
+#[derive(Clone, Copy)]
+pub struct Handle(u32);
+impl Handle {
+    pub fn as_raw(self) -> u32 {
+        self.0
+    }
+}
+pub struct Buffer(Vec<u8>);
+impl Buffer {
+    pub fn as_bytes(self) -> Vec<u8> {
+        self.0
+    }
+}
+"#;
+
+    let (_temp_dir, file_path) = create_temp_rust_file(code);
+    let analyzer = CodeAnalyzer::new(&[], "en-US");
+    let issues: Vec<_> = analyzer
+        .analyze_file(&file_path)
+        .into_iter()
+        .filter(|issue| issue.rule_name == "naming-convention")
+        .collect();
+    let message_at = |line: usize| {
+        issues
+            .iter()
+            .find(|issue| issue.line == line)
+            .map(|issue| issue.message.clone())
+            .unwrap_or_default()
+    };
+
+    assert!(message_at(2).contains("`HttpServer`"));
+    assert!(message_at(5).contains("`MAX_RETRIES`"));
+    assert!(message_at(6).contains("`parse_input`"));
+    assert!(message_at(10).contains("`name`"));
+    assert!(message_at(16).contains("`to_owned`"));
+    assert!(message_at(32).contains("`into_bytes`"));
+    // get_mut, an into_ that consumes self, const generic N and as_ by value on a Copy type
+    // all follow the conventions
+    assert_eq!(issues.len(), 6);
+}
+
+#[test]
+fn test_pinyin_naming() {
+    let code = r#"
+struct Yonghu {
+    mima: String,
+}
+fn denglu(yonghu: &Yonghu, pending_change: &str) -> bool {
+    let get_yonghu_list = vec![yonghu];
+    let 用户名 = "admin";
+    let yonghuMing = 用户名;
+    get_yonghu_list.len() > 1 && yonghu.mima == pending_change && yonghuMing.is_empty()
+}
+"#;
+
+    let (_temp_dir, file_path) = create_temp_rust_file(code);
+    let analyzer = CodeAnalyzer::new(&[], "en-US");
+    let issues: Vec<_> = analyzer
+        .analyze_file(&file_path)
+        .into_iter()
+        .filter(|issue| issue.rule_name == "pinyin-naming")
+        .collect();
+    let message_at = |line: usize, column: usize| {
+        issues
+            .iter()
+            .find(|issue| issue.line == line && issue.column == column)
+            .map(|issue| issue.message.clone())
+            .unwrap_or_default()
+    };
+
+    assert!(message_at(2, 8).contains("'User'"));
+    assert!(message_at(3, 5).contains("'password'"));
+    assert!(message_at(5, 4).contains("'login'"));
+    assert!(message_at(6, 9).contains("'get_user_list'"));
+    assert!(message_at(7, 9).contains("'username'"));
+    // camelCase splits yonghuMing, but yong-hu-ming is still all pinyin, not mixed
+    assert!(message_at(8, 9).contains("'username'"));
+    assert!(!message_at(8, 9).contains("mix"));
+    // Mixed pinyin/English and Chinese-character identifiers are worse than plain pinyin
+    let spicy = issues
+        .iter()
+        .filter(|issue| issue.severity == Severity::Spicy)
+        .count();
+    assert_eq!(spicy, 2);
+    // pending_change is English that only looks like pinyin
+    assert_eq!(issues.len(), 7);
+}
+
+#[test]
+fn test_identifier_typos() {
+    let code = r#"
+pub struct RecieveBuffer {
+    lenght: usize,
+}
+pub fn send_to_adress(adress: &str, parsed_filenames: Vec<String>) -> usize {
+    let seperator = ",";
+    let acme_widget = adress.len() + seperator.len();
+    acme_widget + parsed_filenames.len()
+}
+const DEFUALT_TIMOUT: u64 = 30;
+"#;
+
+    let (_temp_dir, file_path) = create_temp_rust_file(code);
+    let typos = |analyzer: CodeAnalyzer| -> Vec<_> {
+        analyzer
+            .analyze_file(&file_path)
+            .into_iter()
+            .filter(|issue| issue.rule_name == "identifier-typo")
+            .collect()
+    };
+    let issues = typos(CodeAnalyzer::new(&[], "en-US"));
+    let messages_at = |line: usize| -> Vec<String> {
+        issues
+            .iter()
+            .filter(|issue| issue.line == line)
+            .map(|issue| issue.message.clone())
+            .collect()
+    };
+
+    assert!(messages_at(2)[0].contains("'ReceiveBuffer'"));
+    assert!(messages_at(3)[0].contains("'length'"));
+    assert!(messages_at(5)
+        .iter()
+        .any(|message| message.contains("'send_to_address'")));
+    assert!(messages_at(6)[0].contains("'separator'"));
+    // Two typos in one name give one issue that lists both and fixes the whole name
+    let constant = messages_at(10);
+    assert_eq!(constant.len(), 1);
+    assert!(constant[0].contains("'defualt'"));
+    assert!(constant[0].contains("'timout'"));
+    assert!(constant[0].contains("'DEFAULT_TIMEOUT'"));
+    // Item names are worse than locals
+    let struct_issue = issues.iter().find(|issue| issue.line == 2).unwrap();
+    assert_eq!(struct_issue.severity, Severity::Spicy);
+    let local_issue = issues.iter().find(|issue| issue.line == 6).unwrap();
+    assert_eq!(local_issue.severity, Severity::Mild);
+    // Inflections, compounds and proper nouns with no close word are fine
+    assert_eq!(issues.len(), 6);
+
+    let with_vocabulary = typos(CodeAnalyzer::new(&[], "en-US").with_config(RuleConfig {
+        custom_words: vec!["Lenght".to_string(), "seperator".to_string()],
+        ..RuleConfig::default()
+    }));
+    assert_eq!(with_vocabulary.len(), 4);
+}
+
 #[test]
 fn test_deep_nesting_detection() {
     let code = r#"
@@ -1,4 +1,4 @@
-use garbage_code_hunter::{CodeAnalyzer, CodeIssue, Reporter, RoastLevel, Severity};
+use garbage_code_hunter::{CodeAnalyzer, CodeContext, CodeIssue, Reporter, RoastLevel, Severity};
 use std::fs;
 use std::path::PathBuf;
 use tempfile::TempDir;
@@ -13,6 +13,7 @@ fn create_test_issues() -> Vec<CodeIssue> {
             message: "Test terrible naming message".to_string(),
             severity: Severity::Spicy,
             roast_level: RoastLevel::Sarcastic,
+            context: CodeContext::Production,
         },
         CodeIssue {
             file_path: PathBuf::from("test1.rs"),
@@ -22,6 +23,7 @@ fn create_test_issues() -> Vec<CodeIssue> {
             message: "Test unwrap abuse message".to_string(),
             severity: Severity::Nuclear,
             roast_level: RoastLevel::Savage,
+            context: CodeContext::Production,
         },
         CodeIssue {
             file_path: PathBuf::from("test2.rs"),
@@ -31,6 +33,7 @@ fn create_test_issues() -> Vec<CodeIssue> {
             message: "Test single letter message".to_string(),
             severity: Severity::Mild,
             roast_level: RoastLevel::Gentle,
+            context: CodeContext::Production,
         },
     ]
 }
@@ -167,6 +170,7 @@ fn test_reporter_limited_issues_per_file() {
             message: format!("Issue {i}"),
             severity: Severity::Spicy,
             roast_level: RoastLevel::Sarcastic,
+            context: CodeContext::Production,
         });
     }
 
@@ -218,38 +222,39 @@ fn main() {
 
 #[test]
 fn test_reporter_with_different_severities() {
-    let mut issues = vec![];
-
     // Create issues with all severity levels
-    issues.push(CodeIssue {
-        file_path: PathBuf::from("test.rs"),
-        line: 1,
-        column: 1,
-        rule_name: "terrible-naming".to_string(),
-        message: "Nuclear issue".to_string(),
-        severity: Severity::Nuclear,
-        roast_level: RoastLevel::Savage,
-    });
-
-    issues.push(CodeIssue {
-        file_path: PathBuf::from("test.rs"),
-        line: 2,
-        column: 1,
-        rule_name: "unwrap-abuse".to_string(),
-        message: "Spicy issue".to_string(),
-        severity: Severity::Spicy,
-        roast_level: RoastLevel::Sarcastic,
-    });
-
-    issues.push(CodeIssue {
-        file_path: PathBuf::from("test.rs"),
-        line: 3,
-        column: 1,
-        rule_name: "single-letter-variable".to_string(),
-        message: "Mild issue".to_string(),
-        severity: Severity::Mild,
-        roast_level: RoastLevel::Gentle,
-    });
+    let issues = vec![
+        CodeIssue {
+            file_path: PathBuf::from("test.rs"),
+            line: 1,
+            column: 1,
+            rule_name: "terrible-naming".to_string(),
+            message: "Nuclear issue".to_string(),
+            severity: Severity::Nuclear,
+            roast_level: RoastLevel::Savage,
+            context: CodeContext::Production,
+        },
+        CodeIssue {
+            file_path: PathBuf::from("test.rs"),
+            line: 2,
+            column: 1,
+            rule_name: "unwrap-abuse".to_string(),
+            message: "Spicy issue".to_string(),
+            severity: Severity::Spicy,
+            roast_level: RoastLevel::Sarcastic,
+            context: CodeContext::Production,
+        },
+        CodeIssue {
+            file_path: PathBuf::from("test.rs"),
+            line: 3,
+            column: 1,
+            rule_name: "single-letter-variable".to_string(),
+            message: "Mild issue".to_string(),
+            severity: Severity::Mild,
+            roast_level: RoastLevel::Gentle,
+            context: CodeContext::Production,
+        },
+    ];
 
     let reporter = Reporter::new(false, false, true, 5, 5, false, false, "en-US");
     reporter.report(issues);
@@ -215,3 +215,49 @@ fn main() {
         // Note: The exact count might vary based on implementation details
     }
 }
+
+#[cfg(test)]
+mod context_tests {
+    use garbage_code_hunter::rules::ContextMap;
+    use garbage_code_hunter::CodeContext;
+    use std::path::Path;
+    use syn::parse_file;
+
+    #[test]
+    fn test_context_from_path() {
+        let cases = [
+            ("src/lib.rs", CodeContext::Production),
+            ("tests/integration.rs", CodeContext::Test),
+            ("src/parser/tests.rs", CodeContext::Test),
+            ("benches/speed.rs", CodeContext::Bench),
+            ("examples/demo.rs", CodeContext::Example),
+            ("build.rs", CodeContext::BuildScript),
+        ];
+
+        for (path, expected) in cases {
+            assert_eq!(CodeContext::from_path(Path::new(path)), expected, "{path}");
+        }
+    }
+
+    #[test]
+    fn test_context_map_finds_test_regions() {
+        let code = r#"
+fn production() {}
+
+#[test]
+fn standalone() {}
+
+#[cfg(test)]
+mod tests {
+    fn helper() {}
+}
+"#;
+
+        let syntax_tree = parse_file(code).expect("Failed to parse code");
+        let contexts = ContextMap::new(Path::new("src/lib.rs"), &syntax_tree);
+
+        assert_eq!(contexts.context_at(2), CodeContext::Production);
+        assert_eq!(contexts.context_at(5), CodeContext::Test);
+        assert_eq!(contexts.context_at(9), CodeContext::Test);
+    }
+}